@@ -0,0 +1,582 @@
+//! Parsers behind response renderers whose work is too heavy to do in the webview.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+
+use crate::types::{CsvTable, ProtoField, ProtoNode, ProtoValue};
+
+/// Rows returned per `parse_csv_body` call when no limit is given.
+const DEFAULT_CSV_PAGE_ROWS: u32 = 500;
+
+/// Delimiters tried, in order, when the caller doesn't pick one.
+const CSV_DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// Guess the delimiter of a CSV body by looking for one that appears the same
+/// (non-zero) number of times on each of the first few lines. Quoted fields are
+/// not considered, so this is only a heuristic for renderer detection.
+pub fn sniff_csv_delimiter(body: &[u8]) -> Option<u8> {
+    let text = String::from_utf8_lossy(&body[..body.len().min(4096)]);
+    let lines: Vec<&str> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(5)
+        .collect();
+    if lines.len() < 2 {
+        return None;
+    }
+    CSV_DELIMITERS.into_iter().find(|&delimiter| {
+        let count = |line: &str| line.bytes().filter(|&b| b == delimiter).count();
+        let first = count(lines[0]);
+        first > 0 && lines.iter().all(|line| count(line) == first)
+    })
+}
+
+fn parse_csv(
+    body: &[u8],
+    delimiter: u8,
+    has_header: bool,
+    offset: u32,
+    limit: u32,
+) -> Result<CsvTable, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .flexible(true)
+        .from_reader(body);
+
+    let headers = if has_header {
+        reader
+            .headers()
+            .map_err(|e| format!("Invalid CSV header: {}", e))?
+            .iter()
+            .map(str::to_string)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut rows = Vec::new();
+    let mut total_rows: u32 = 0;
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+        if total_rows >= offset && rows.len() < limit as usize {
+            rows.push(record.iter().map(str::to_string).collect());
+        }
+        total_rows += 1;
+    }
+
+    Ok(CsvTable {
+        headers,
+        rows,
+        truncated: total_rows > offset.saturating_add(limit),
+        total_rows,
+    })
+}
+
+/// Parse a CSV response body into a table, one page of rows at a time.
+///
+/// `delimiter` is a single character (`,`, `;`, `\t`, ...) and is sniffed from the
+/// body when omitted. `offset`/`limit` select which data rows are returned.
+#[tauri::command]
+#[specta::specta]
+pub async fn parse_csv_body(
+    body_base64: String,
+    delimiter: Option<String>,
+    has_header: bool,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<CsvTable, String> {
+    tokio::task::spawn_blocking(move || {
+        let body = BASE64
+            .decode(body_base64)
+            .map_err(|e| format!("Invalid base64 body: {}", e))?;
+        let delimiter = match delimiter.as_deref() {
+            Some("\\t") => b'\t',
+            Some(d) if d.len() == 1 => d.as_bytes()[0],
+            Some(d) => return Err(format!("Delimiter must be a single character, got '{}'", d)),
+            None => sniff_csv_delimiter(&body).unwrap_or(b','),
+        };
+        parse_csv(
+            &body,
+            delimiter,
+            has_header,
+            offset.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_CSV_PAGE_ROWS),
+        )
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+/// Conservative YAML check for bodies served without a YAML content type: a
+/// `---` document start, or top-level lines that are all `key:` entries.
+pub fn sniff_yaml(body: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(&body[..body.len().min(4096)]);
+    let mut lines = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.trim_start().starts_with('#'))
+        .take(10)
+        .peekable();
+    match lines.peek() {
+        Some(first) if *first == "---" || first.starts_with("--- ") => return true,
+        None => return false,
+        _ => {}
+    }
+    let is_key_line = |line: &str| {
+        line.split_once(':').is_some_and(|(key, rest)| {
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+                && (rest.is_empty() || rest.starts_with(' '))
+        })
+    };
+    let top_level: Vec<&str> = lines
+        .filter(|line| !line.starts_with([' ', '\t']))
+        .collect();
+    top_level.len() >= 2 && top_level.iter().all(|line| is_key_line(line))
+}
+
+/// YAML keys can be any scalar; JSON object keys must be strings.
+fn yaml_key_to_string(key: serde_yaml::Value) -> String {
+    match key {
+        serde_yaml::Value::String(s) => s,
+        serde_yaml::Value::Null => "null".to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        other => serde_yaml::to_string(&other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+pub(crate) fn yaml_to_json_value(value: serde_yaml::Value) -> serde_json::Value {
+    match value {
+        serde_yaml::Value::Null => serde_json::Value::Null,
+        serde_yaml::Value::Bool(b) => serde_json::Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                serde_json::Value::from(i)
+            } else if let Some(u) = n.as_u64() {
+                serde_json::Value::from(u)
+            } else {
+                n.as_f64()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(serde_json::Value::Number)
+                    // NaN and infinities have no JSON form.
+                    .unwrap_or_else(|| serde_json::Value::String(n.to_string()))
+            }
+        }
+        serde_yaml::Value::String(s) => serde_json::Value::String(s),
+        serde_yaml::Value::Sequence(items) => {
+            serde_json::Value::Array(items.into_iter().map(yaml_to_json_value).collect())
+        }
+        serde_yaml::Value::Mapping(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (yaml_key_to_string(k), yaml_to_json_value(v)))
+                .collect(),
+        ),
+        // Custom tags (`!Ref foo`) carry no JSON meaning; keep the tagged value.
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json_value(tagged.value),
+    }
+}
+
+fn yaml_error(e: serde_yaml::Error) -> String {
+    match e.location() {
+        Some(loc) => format!(
+            "YAML error at line {}, column {}: {}",
+            loc.line(),
+            loc.column(),
+            e
+        ),
+        None => format!("YAML error: {}", e),
+    }
+}
+
+/// Convert a YAML response body to JSON so the JSON tree view can show it.
+///
+/// Anchors, aliases and `<<` merge keys are resolved. A body with several
+/// documents becomes an array with one element per document.
+#[tauri::command]
+#[specta::specta]
+pub async fn yaml_to_json(body_base64: String) -> Result<serde_json::Value, String> {
+    tokio::task::spawn_blocking(move || {
+        let body = BASE64
+            .decode(body_base64)
+            .map_err(|e| format!("Invalid base64 body: {}", e))?;
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_slice(&body) {
+            let mut value = serde_yaml::Value::deserialize(document).map_err(yaml_error)?;
+            value.apply_merge().map_err(yaml_error)?;
+            documents.push(yaml_to_json_value(value));
+        }
+        Ok(match documents.len() {
+            0 => serde_json::Value::Null,
+            1 => documents.remove(0),
+            _ => serde_json::Value::Array(documents),
+        })
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+/// Markdown check for `text/plain` bodies: the first non-blank line is an ATX heading.
+pub fn sniff_markdown(body: &[u8]) -> bool {
+    let text = String::from_utf8_lossy(&body[..body.len().min(1024)]);
+    text.lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| {
+            let hashes = line.len() - line.trim_start_matches('#').len();
+            (1..=6).contains(&hashes) && line[hashes..].starts_with(' ')
+        })
+}
+
+/// Render a Markdown response body to HTML for the HtmlPreview renderer.
+///
+/// The output is sanitized: scripts, iframes, event handler attributes and
+/// `javascript:` links are stripped, whether they came from raw HTML in the
+/// Markdown or from link targets.
+#[tauri::command]
+#[specta::specta]
+pub async fn render_markdown(body_base64: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        let body = BASE64
+            .decode(body_base64)
+            .map_err(|e| format!("Invalid base64 body: {}", e))?;
+        let text = String::from_utf8_lossy(&body);
+
+        let options = pulldown_cmark::Options::ENABLE_TABLES
+            | pulldown_cmark::Options::ENABLE_STRIKETHROUGH
+            | pulldown_cmark::Options::ENABLE_TASKLISTS
+            | pulldown_cmark::Options::ENABLE_FOOTNOTES;
+        let parser = pulldown_cmark::Parser::new_ext(&text, options);
+        let mut html = String::with_capacity(text.len() * 3 / 2);
+        pulldown_cmark::html::push_html(&mut html, parser);
+
+        Ok(ammonia::clean(&html))
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+/// MessagePack binary and ext payloads have no JSON form; they are shown as an
+/// object carrying the base64 data and its decoded length.
+fn msgpack_bytes_to_json(bytes: &[u8], ext_type: Option<i8>) -> serde_json::Value {
+    let mut object = serde_json::Map::new();
+    if let Some(ext_type) = ext_type {
+        object.insert("$ext".to_string(), ext_type.into());
+    }
+    object.insert("$base64".to_string(), BASE64.encode(bytes).into());
+    object.insert("$length".to_string(), bytes.len().into());
+    serde_json::Value::Object(object)
+}
+
+fn msgpack_to_json_value(value: rmpv::Value) -> serde_json::Value {
+    match value {
+        rmpv::Value::Nil => serde_json::Value::Null,
+        rmpv::Value::Boolean(b) => serde_json::Value::Bool(b),
+        rmpv::Value::Integer(i) => match (i.as_i64(), i.as_u64()) {
+            (Some(i), _) => i.into(),
+            (None, Some(u)) => u.into(),
+            (None, None) => serde_json::Value::Null,
+        },
+        rmpv::Value::F32(f) => serde_json::Number::from_f64(f as f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| f.to_string().into()),
+        rmpv::Value::F64(f) => serde_json::Number::from_f64(f)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|| f.to_string().into()),
+        rmpv::Value::String(s) => match s.into_str() {
+            Some(s) => s.into(),
+            None => serde_json::Value::Null,
+        },
+        rmpv::Value::Binary(bytes) => msgpack_bytes_to_json(&bytes, None),
+        rmpv::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(msgpack_to_json_value).collect())
+        }
+        rmpv::Value::Map(entries) => serde_json::Value::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| {
+                    // JSON object keys must be strings.
+                    let key = match msgpack_to_json_value(k) {
+                        serde_json::Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, msgpack_to_json_value(v))
+                })
+                .collect(),
+        ),
+        rmpv::Value::Ext(ext_type, bytes) => msgpack_bytes_to_json(&bytes, Some(ext_type)),
+    }
+}
+
+/// Decode a MessagePack response body to JSON for the JSON tree view.
+///
+/// Map keys that aren't strings are stringified, and binary/ext values become
+/// `{"$base64", "$length"}` objects (plus `"$ext"` with the ext type).
+#[tauri::command]
+#[specta::specta]
+pub async fn decode_msgpack(body_base64: String) -> Result<serde_json::Value, String> {
+    tokio::task::spawn_blocking(move || {
+        let body = BASE64
+            .decode(body_base64)
+            .map_err(|e| format!("Invalid base64 body: {}", e))?;
+        let mut deserializer = rmp_serde::Deserializer::new(std::io::Cursor::new(&body));
+        let value = rmpv::Value::deserialize(&mut deserializer).map_err(|e| {
+            format!(
+                "MessagePack error at byte {}: {}",
+                deserializer.position(),
+                e
+            )
+        })?;
+        Ok(msgpack_to_json_value(value))
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+/// Length-delimited fields nested deeper than this are not tried as messages.
+const MAX_PROTO_DEPTH: usize = 32;
+
+/// A decode failure and the byte offset it happened at.
+type ProtoError = (usize, String);
+
+enum WireItem {
+    Field(ProtoField),
+    EndGroup(u32),
+}
+
+fn read_proto_varint(buf: &[u8], pos: &mut usize) -> Result<u64, ProtoError> {
+    let start = *pos;
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *buf
+            .get(*pos)
+            .ok_or_else(|| (start, "truncated varint".to_string()))?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err((start, "varint longer than 10 bytes".to_string()))
+}
+
+fn take_proto_bytes<'a>(
+    buf: &'a [u8],
+    pos: &mut usize,
+    len: usize,
+) -> Result<&'a [u8], ProtoError> {
+    let end = pos
+        .checked_add(len)
+        .filter(|end| *end <= buf.len())
+        .ok_or_else(|| {
+            (
+                *pos,
+                format!("needs {} bytes but only {} remain", len, buf.len() - *pos),
+            )
+        })?;
+    let bytes = &buf[*pos..end];
+    *pos = end;
+    Ok(bytes)
+}
+
+/// Length-delimited fields are ambiguous without a schema. Like
+/// `protoc --decode_raw`, try a message first, then a UTF-8 string, then bytes.
+fn decode_length_delimited(bytes: &[u8], depth: usize) -> ProtoValue {
+    if !bytes.is_empty() && depth < MAX_PROTO_DEPTH {
+        let node = decode_proto_message(bytes, depth + 1);
+        if node.error.is_none() {
+            return ProtoValue::Message(node);
+        }
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(s) => ProtoValue::String(s.to_string()),
+        Err(_) => ProtoValue::Bytes {
+            base64: BASE64.encode(bytes),
+        },
+    }
+}
+
+fn decode_proto_field(buf: &[u8], pos: &mut usize, depth: usize) -> Result<WireItem, ProtoError> {
+    let offset = *pos;
+    let tag = read_proto_varint(buf, pos)?;
+    let number = u32::try_from(tag >> 3)
+        .ok()
+        .filter(|n| (1..=0x1fff_ffff).contains(n))
+        .ok_or_else(|| (offset, format!("invalid field number {}", tag >> 3)))?;
+
+    let value = match tag & 7 {
+        0 => {
+            let v = read_proto_varint(buf, pos)?;
+            let zigzag = (v >> 1) as i64 ^ -((v & 1) as i64);
+            ProtoValue::Varint {
+                value: v.to_string(),
+                zigzag: zigzag.to_string(),
+            }
+        }
+        1 => {
+            let bytes = take_proto_bytes(buf, pos, 8)?;
+            let v = u64::from_le_bytes(bytes.try_into().unwrap());
+            ProtoValue::Fixed64 {
+                value: v.to_string(),
+                double: f64::from_bits(v),
+            }
+        }
+        2 => {
+            let len_offset = *pos;
+            let len = read_proto_varint(buf, pos)?;
+            let len = usize::try_from(len)
+                .map_err(|_| (len_offset, format!("length {} is too large", len)))?;
+            decode_length_delimited(take_proto_bytes(buf, pos, len)?, depth)
+        }
+        3 => {
+            if depth >= MAX_PROTO_DEPTH {
+                return Err((offset, "groups nested too deeply".to_string()));
+            }
+            let start = *pos;
+            let mut fields = Vec::new();
+            decode_proto_fields(buf, pos, depth + 1, Some(number), &mut fields)?;
+            ProtoValue::Group(ProtoNode {
+                fields,
+                decoded_bytes: (*pos - start) as u32,
+                error: None,
+            })
+        }
+        4 => return Ok(WireItem::EndGroup(number)),
+        5 => {
+            let bytes = take_proto_bytes(buf, pos, 4)?;
+            let v = u32::from_le_bytes(bytes.try_into().unwrap());
+            ProtoValue::Fixed32 {
+                value: v,
+                float: f32::from_bits(v),
+            }
+        }
+        wire_type => return Err((offset, format!("unknown wire type {}", wire_type))),
+    };
+
+    Ok(WireItem::Field(ProtoField {
+        number,
+        offset: offset as u32,
+        value,
+    }))
+}
+
+/// Decode fields until the end of `buf`, or until the end tag of `group`.
+/// On error `pos` is left at the start of the field that failed.
+fn decode_proto_fields(
+    buf: &[u8],
+    pos: &mut usize,
+    depth: usize,
+    group: Option<u32>,
+    fields: &mut Vec<ProtoField>,
+) -> Result<(), ProtoError> {
+    loop {
+        if *pos >= buf.len() {
+            return match group {
+                Some(number) => Err((*pos, format!("group {} has no end tag", number))),
+                None => Ok(()),
+            };
+        }
+        let start = *pos;
+        match decode_proto_field(buf, pos, depth) {
+            Ok(WireItem::Field(field)) => fields.push(field),
+            Ok(WireItem::EndGroup(number)) if group == Some(number) => return Ok(()),
+            Ok(WireItem::EndGroup(number)) => {
+                *pos = start;
+                return Err((start, format!("unexpected end of group {}", number)));
+            }
+            Err(e) => {
+                *pos = start;
+                return Err(e);
+            }
+        }
+    }
+}
+
+fn decode_proto_message(buf: &[u8], depth: usize) -> ProtoNode {
+    let mut pos = 0;
+    let mut fields = Vec::new();
+    let error = decode_proto_fields(buf, &mut pos, depth, None, &mut fields)
+        .err()
+        .map(|(offset, message)| format!("{} at byte {}", message, offset));
+    ProtoNode {
+        fields,
+        decoded_bytes: pos as u32,
+        error,
+    }
+}
+
+/// Walk a protobuf body's wire format without a schema, like
+/// `protoc --decode_raw`. Malformed input still returns the fields decoded
+/// before the error, with `error` set.
+#[tauri::command]
+#[specta::specta]
+pub async fn decode_protobuf_raw(body_base64: String) -> Result<ProtoNode, String> {
+    tokio::task::spawn_blocking(move || {
+        let body = BASE64
+            .decode(body_base64)
+            .map_err(|e| format!("Invalid base64 body: {}", e))?;
+        Ok(decode_proto_message(&body, 0))
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmpv::Value;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn msgpack_round_trips_through_decode_command() {
+        let value = Value::Map(vec![
+            (Value::from("id"), Value::from(42)),
+            (Value::from("name"), Value::from("mandy")),
+            (Value::from("ratio"), Value::F64(0.5)),
+            (Value::from("active"), Value::Boolean(true)),
+            (Value::from("missing"), Value::Nil),
+            (
+                Value::from("tags"),
+                Value::Array(vec![Value::from("a"), Value::from("b")]),
+            ),
+            (
+                Value::from("nested"),
+                Value::Map(vec![(Value::from(7), Value::from(u64::MAX))]),
+            ),
+            (Value::from("blob"), Value::Binary(vec![1, 2, 3])),
+            (Value::from("stamp"), Value::Ext(-1, vec![0, 0, 0, 1])),
+        ]);
+        let mut encoded = Vec::new();
+        rmpv::encode::write_value(&mut encoded, &value).unwrap();
+
+        let decoded = decode_msgpack(BASE64.encode(&encoded)).await.unwrap();
+        assert_eq!(
+            decoded,
+            json!({
+                "id": 42,
+                "name": "mandy",
+                "ratio": 0.5,
+                "active": true,
+                "missing": null,
+                "tags": ["a", "b"],
+                "nested": { "7": u64::MAX },
+                "blob": { "$base64": "AQID", "$length": 3 },
+                "stamp": { "$ext": -1, "$base64": "AAAAAQ==", "$length": 4 },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn truncated_msgpack_reports_the_error_offset() {
+        let mut encoded = Vec::new();
+        rmpv::encode::write_value(&mut encoded, &Value::from("truncated")).unwrap();
+        encoded.truncate(4);
+
+        let err = decode_msgpack(BASE64.encode(&encoded)).await.unwrap_err();
+        assert!(err.starts_with("MessagePack error"), "{err}");
+    }
+}
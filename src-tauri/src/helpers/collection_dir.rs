@@ -0,0 +1,541 @@
+//! Collections stored as a directory tree, for keeping them in version
+//! control: `_collection.json` at the root, a directory with a `_folder.json`
+//! per folder, and one `<name>.json` file per request. Files are
+//! pretty-printed with sorted keys and only rewritten when their content
+//! changes, so a diff shows just the requests that did. Ids are kept in the
+//! files, so renaming a request renames its file but not its id.
+//!
+//! The manifests list every file and directory the app wrote, and only those
+//! are read back or removed; anything else in the tree is left alone.
+//!
+//! Linked directories are polled for changes made outside the app, which are
+//! reported with `collection-dir-changed`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::Utc;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::collections::{
+    get_collection_tree, linked_collections, store_collection, write_atomic,
+};
+use crate::types::{ApiRequest, Collection, CollectionDirChangedEvent, CollectionNode};
+
+/// Event carrying `CollectionDirChangedEvent` payloads.
+pub const COLLECTION_DIR_CHANGED_EVENT: &str = "collection-dir-changed";
+
+const COLLECTION_MANIFEST: &str = "_collection.json";
+const FOLDER_MANIFEST: &str = "_folder.json";
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Linked directories by collection id, with the hash of their files as
+/// last written or read by the app.
+static WATCHED: OnceLock<DashMap<String, (PathBuf, String)>> = OnceLock::new();
+static POLLING: AtomicBool = AtomicBool::new(false);
+
+fn watched() -> &'static DashMap<String, (PathBuf, String)> {
+    WATCHED.get_or_init(DashMap::new)
+}
+
+/// `_collection.json`. `items` and a folder's `items` list the file and
+/// directory names of its children in order. With `share_safe`, requests
+/// are written without settings tied to the local TLS library.
+#[derive(Serialize, Deserialize)]
+struct CollectionManifest {
+    id: String,
+    name: String,
+    created_at: String,
+    items: Vec<String>,
+    #[serde(default)]
+    share_safe: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FolderManifest {
+    #[serde(default)]
+    id: String,
+    name: String,
+    #[serde(default)]
+    items: Vec<String>,
+}
+
+/// Just the `items` of either manifest.
+#[derive(Deserialize)]
+struct ListedItems {
+    #[serde(default)]
+    items: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RequestFile {
+    #[serde(default)]
+    id: String,
+    name: String,
+    request: ApiRequest,
+}
+
+fn check_scope(app: &AppHandle, path: &str) -> Result<(), String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    Ok(())
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect::<Map<String, Value>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// Pretty JSON with sorted keys and a trailing newline.
+fn to_file_json(value: &impl Serialize) -> Result<Vec<u8>, String> {
+    let value = sort_keys(serde_json::to_value(value).map_err(|e| e.to_string())?);
+    let mut json = serde_json::to_vec_pretty(&value).map_err(|e| e.to_string())?;
+    json.push(b'\n');
+    Ok(json)
+}
+
+/// A file name stem from an item's name: lowercase letters, digits and
+/// dashes. Never starts with `_`, which manifests use.
+fn slug(name: &str, fallback: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        fallback.to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// `stem` plus `suffix`, numbered from `-2` when the name is taken. Names
+/// are compared without case for case-insensitive file systems.
+fn unique_name(taken: &mut HashSet<String>, stem: &str, suffix: &str) -> String {
+    let mut name = format!("{}{}", stem, suffix);
+    let mut n = 2;
+    while !taken.insert(name.to_lowercase()) {
+        name = format!("{}-{}{}", stem, n, suffix);
+        n += 1;
+    }
+    name
+}
+
+fn write_if_changed(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if std::fs::read(path).is_ok_and(|existing| existing == bytes) {
+        return Ok(());
+    }
+    write_atomic(path, bytes)
+}
+
+/// Whether a manifest entry names a child of its directory, not a manifest or
+/// a path elsewhere.
+fn is_item_name(name: &str) -> bool {
+    !name.starts_with(['_', '.']) && !name.contains(['/', '\\']) && !name.is_empty()
+}
+
+/// The children the manifest at `path` lists, from the app's last write.
+fn listed_items(path: &Path) -> Vec<String> {
+    read_json::<ListedItems>(path)
+        .map(|listed| listed.items)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| is_item_name(name))
+        .collect()
+}
+
+/// Write `items` into `dir`, returning their names in order. Children that
+/// `dir`'s manifest listed but that are no longer there are removed; other
+/// files in `dir` are left alone and their names aren't reused.
+fn write_items(
+    dir: &Path,
+    manifest: &str,
+    items: &[CollectionNode],
+    share_safe: bool,
+) -> Result<Vec<String>, String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let listed = listed_items(&dir.join(manifest));
+    let listed_lower: HashSet<String> = listed.iter().map(|name| name.to_lowercase()).collect();
+    let mut taken: HashSet<String> = std::fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .map(|name| name.to_lowercase())
+        .filter(|name| !listed_lower.contains(name))
+        .collect();
+    let mut names = Vec::new();
+    for node in items {
+        match node {
+            CollectionNode::Request { id, name, request } => {
+                let file_name = unique_name(&mut taken, &slug(name, "request"), ".json");
+                let mut file = RequestFile {
+                    id: id.clone(),
+                    name: name.clone(),
+                    request: (**request).clone(),
+                };
+                if share_safe {
+                    file.request.clear_local_tls_settings();
+                }
+                write_if_changed(&dir.join(&file_name), &to_file_json(&file)?)?;
+                names.push(file_name);
+            }
+            CollectionNode::Folder { id, name, items } => {
+                let dir_name = unique_name(&mut taken, &slug(name, "folder"), "");
+                let folder_dir = dir.join(&dir_name);
+                let manifest = FolderManifest {
+                    id: id.clone(),
+                    name: name.clone(),
+                    items: write_items(&folder_dir, FOLDER_MANIFEST, items, share_safe)?,
+                };
+                write_if_changed(&folder_dir.join(FOLDER_MANIFEST), &to_file_json(&manifest)?)?;
+                names.push(dir_name);
+            }
+        }
+    }
+    remove_stale(dir, &listed, &names)?;
+    Ok(names)
+}
+
+/// Remove the request files and folder directories in `listed` that aren't
+/// in `keep`.
+fn remove_stale(dir: &Path, listed: &[String], keep: &[String]) -> Result<(), String> {
+    let keep: HashSet<String> = keep.iter().map(|name| name.to_lowercase()).collect();
+    for name in listed {
+        if keep.contains(&name.to_lowercase()) {
+            continue;
+        }
+        let path = dir.join(name);
+        if path.is_dir() {
+            if path.join(FOLDER_MANIFEST).is_file() {
+                std::fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+            }
+        } else if name.ends_with(".json") && path.is_file() {
+            std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// `id`, or a new one when it's missing or already used.
+fn fresh_id(ids: &mut HashSet<String>, id: String) -> String {
+    if !id.is_empty() && ids.insert(id.clone()) {
+        return id;
+    }
+    let id = uuid::Uuid::new_v4().to_string();
+    ids.insert(id.clone());
+    id
+}
+
+/// Children of `dir` that its manifest lists, in order; other files are
+/// ignored, and listed ones that are gone are skipped. Missing or repeated
+/// ids are replaced.
+fn read_items(
+    dir: &Path,
+    listed: &[String],
+    ids: &mut HashSet<String>,
+) -> Result<Vec<CollectionNode>, String> {
+    let mut items = Vec::new();
+    for name in listed.iter().filter(|name| is_item_name(name)) {
+        let path = dir.join(name);
+        if path.join(FOLDER_MANIFEST).is_file() {
+            let manifest: FolderManifest = read_json(&path.join(FOLDER_MANIFEST))?;
+            items.push(CollectionNode::Folder {
+                id: fresh_id(ids, manifest.id),
+                name: manifest.name,
+                items: read_items(&path, &manifest.items, ids)?,
+            });
+        } else if name.ends_with(".json") && path.is_file() {
+            let file: RequestFile = read_json(&path)?;
+            items.push(CollectionNode::Request {
+                id: fresh_id(ids, file.id),
+                name: file.name,
+                request: Box::new(file.request),
+            });
+        }
+    }
+    Ok(items)
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// A hash of every file under `dir` and its path, skipping dot-files.
+fn fingerprint(dir: &Path) -> String {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            if path.is_dir() {
+                walk(root, &path, files);
+            } else if let Ok(bytes) = std::fs::read(&path) {
+                let relative = path.strip_prefix(root).unwrap_or(&path);
+                files.push((relative.to_string_lossy().into_owned(), bytes));
+            }
+        }
+    }
+    let mut files = Vec::new();
+    walk(dir, dir, &mut files);
+    files.sort();
+    let mut hasher = Sha256::new();
+    for (path, bytes) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(&bytes);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Watch `dir` for changes from here on, polling in the background.
+fn watch(app: &AppHandle, collection_id: &str, dir: &Path) {
+    watched().insert(
+        collection_id.to_string(),
+        (dir.to_path_buf(), fingerprint(dir)),
+    );
+    if POLLING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let changed = tokio::task::spawn_blocking(changed_dirs).await;
+            for event in changed.unwrap_or_default() {
+                let _ = app.emit(COLLECTION_DIR_CHANGED_EVENT, event);
+            }
+        }
+    });
+}
+
+/// Linked directories whose files changed since the last poll.
+fn changed_dirs() -> Vec<CollectionDirChangedEvent> {
+    let mut changed = Vec::new();
+    for mut entry in watched().iter_mut() {
+        let current = fingerprint(&entry.0);
+        if current != entry.1 {
+            entry.1 = current;
+            changed.push(CollectionDirChangedEvent {
+                collection_id: entry.key().clone(),
+                path: entry.0.to_string_lossy().into_owned(),
+            });
+        }
+    }
+    changed
+}
+
+pub(crate) fn unwatch(collection_id: &str) {
+    watched().remove(collection_id);
+}
+
+/// Start watching every collection with a `source_dir`, as done at startup.
+pub(crate) fn watch_linked_collections(app: &AppHandle) {
+    for collection in linked_collections(app) {
+        if let Some(ref dir) = collection.source_dir {
+            watch(app, &collection.id, Path::new(dir));
+        }
+    }
+}
+
+/// Write `collection` into its directory, without reporting the change as
+/// an outside edit. Keeps the directory's `share_safe` setting.
+pub(crate) fn write_collection_dir(
+    app: &AppHandle,
+    collection: &Collection,
+    dir: &Path,
+) -> Result<(), String> {
+    let share_safe = read_json::<CollectionManifest>(&dir.join(COLLECTION_MANIFEST))
+        .is_ok_and(|manifest| manifest.share_safe);
+    write_dir(collection, dir, share_safe)?;
+    watch(app, &collection.id, dir);
+    Ok(())
+}
+
+fn write_dir(collection: &Collection, dir: &Path, share_safe: bool) -> Result<(), String> {
+    let manifest = CollectionManifest {
+        id: collection.id.clone(),
+        name: collection.name.clone(),
+        created_at: collection.created_at.clone(),
+        items: write_items(dir, COLLECTION_MANIFEST, &collection.items, share_safe)?,
+        share_safe,
+    };
+    write_if_changed(&dir.join(COLLECTION_MANIFEST), &to_file_json(&manifest)?)
+}
+
+/// Store a saved collection in `path` as a directory tree, and keep writing
+/// its changes there. `path` must be empty or hold this same collection.
+/// With `share_safe`, cipher lists are left out of the request files, now
+/// and on later saves.
+#[tauri::command]
+#[specta::specta]
+pub fn export_collection_to_dir(
+    app: AppHandle,
+    collection_id: String,
+    path: String,
+    share_safe: bool,
+) -> Result<(), String> {
+    check_scope(&app, &path)?;
+    let dir = PathBuf::from(&path);
+    let manifest_path = dir.join(COLLECTION_MANIFEST);
+    if manifest_path.is_file() {
+        let manifest: CollectionManifest = read_json(&manifest_path)?;
+        if manifest.id != collection_id {
+            return Err(format!("'{}' holds a different collection", path));
+        }
+    } else if std::fs::read_dir(&dir).is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(format!("'{}' is not empty", path));
+    }
+    let mut collection = get_collection_tree(app.clone(), collection_id)?;
+    write_dir(&collection, &dir, share_safe)?;
+    watch(&app, &collection.id, &dir);
+    collection.source_dir = Some(path);
+    store_collection(&app, &collection)
+}
+
+/// Load a collection from a directory tree, replacing the saved copy with
+/// the same id, and keep it linked. Also used to reload after
+/// `collection-dir-changed`.
+#[tauri::command]
+#[specta::specta]
+pub fn open_collection_from_dir(app: AppHandle, path: String) -> Result<Collection, String> {
+    check_scope(&app, &path)?;
+    let dir = PathBuf::from(&path);
+    let manifest: CollectionManifest = read_json(&dir.join(COLLECTION_MANIFEST))?;
+    uuid::Uuid::parse_str(&manifest.id)
+        .map_err(|_| format!("Invalid collection id '{}'", manifest.id))?;
+    let mut ids = HashSet::new();
+    let collection = Collection {
+        items: read_items(&dir, &manifest.items, &mut ids)?,
+        id: manifest.id,
+        name: manifest.name,
+        created_at: manifest.created_at,
+        updated_at: Utc::now().to_rfc3339(),
+        source_dir: Some(path),
+    };
+    store_collection(&app, &collection)?;
+    watch(&app, &collection.id, &dir);
+    Ok(collection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection_with(request: ApiRequest) -> Collection {
+        Collection {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: "Shared".to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            updated_at: Utc::now().to_rfc3339(),
+            items: vec![CollectionNode::Folder {
+                id: "folder".to_string(),
+                name: "Folder".to_string(),
+                items: vec![CollectionNode::Request {
+                    id: "request".to_string(),
+                    name: "Get user".to_string(),
+                    request: Box::new(request),
+                }],
+            }],
+            source_dir: None,
+        }
+    }
+
+    #[test]
+    fn share_safe_dirs_leave_out_cipher_lists() {
+        let request = ApiRequest {
+            url: "https://example.com/user".to_string(),
+            tls_ciphers: Some("ECDHE-RSA-AES128-GCM-SHA256".to_string()),
+            tls13_ciphers: Some("TLS_AES_128_GCM_SHA256".to_string()),
+            ..Default::default()
+        };
+        let collection = collection_with(request);
+        for share_safe in [false, true] {
+            let dir = std::env::temp_dir().join(format!("mandy-dir-{}", uuid::Uuid::new_v4()));
+            write_dir(&collection, &dir, share_safe).unwrap();
+            let manifest: CollectionManifest = read_json(&dir.join(COLLECTION_MANIFEST)).unwrap();
+            assert_eq!(manifest.share_safe, share_safe);
+            let file: RequestFile = read_json(&dir.join("folder/get-user.json")).unwrap();
+            std::fs::remove_dir_all(&dir).unwrap();
+            assert_eq!(file.request.url, "https://example.com/user");
+            assert_eq!(file.request.tls_ciphers.is_none(), share_safe);
+            assert_eq!(file.request.tls13_ciphers.is_none(), share_safe);
+        }
+    }
+
+    #[test]
+    fn only_files_the_app_wrote_are_read_or_removed() {
+        let dir = std::env::temp_dir().join(format!("mandy-dir-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("package.json"), "{\"private\": true}").unwrap();
+        std::fs::write(dir.join("docs/notes.json"), "[]").unwrap();
+
+        let mut collection = collection_with(ApiRequest::default());
+        collection.items.push(CollectionNode::Request {
+            id: "package".to_string(),
+            name: "Package".to_string(),
+            request: Box::default(),
+        });
+        write_dir(&collection, &dir, false).unwrap();
+        assert!(dir.join("package-2.json").is_file());
+
+        let CollectionNode::Folder { ref mut items, .. } = collection.items[0] else {
+            unreachable!();
+        };
+        let CollectionNode::Request { ref mut name, .. } = items[0] else {
+            unreachable!();
+        };
+        *name = "Fetch user".to_string();
+        write_dir(&collection, &dir, false).unwrap();
+
+        assert!(!dir.join("folder/get-user.json").exists());
+        assert!(dir.join("folder/fetch-user.json").is_file());
+        assert!(dir.join("docs/notes.json").is_file());
+        assert!(dir.join("package.json").is_file());
+
+        let manifest: CollectionManifest = read_json(&dir.join(COLLECTION_MANIFEST)).unwrap();
+        let items = read_items(&dir, &manifest.items, &mut HashSet::new()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(items.len(), 2);
+        let CollectionNode::Folder { ref items, .. } = items[0] else {
+            panic!("expected the folder first");
+        };
+        assert_eq!(items.len(), 1);
+    }
+}
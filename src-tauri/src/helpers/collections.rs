@@ -0,0 +1,362 @@
+//! Saved collections, one `collections/<id>.json` file each in the workspace
+//! dir. Files are replaced by rename so a crash mid-write leaves the old
+//! version, and every read-modify-write holds `STORE_LOCK` so windows saving
+//! at the same time don't lose each other's changes.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use tauri::AppHandle;
+
+use crate::helpers::collection_dir::{unwatch, write_collection_dir};
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::{ApiRequest, Collection, CollectionNode, CollectionSummary};
+
+/// Serializes read-modify-write cycles on collection files.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn collections_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("collections"))
+}
+
+/// Ids become file names, so only UUIDs are accepted.
+fn collection_path(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    uuid::Uuid::parse_str(id).map_err(|_| format!("Invalid collection id '{}'", id))?;
+    Ok(collections_dir(app)?.join(format!("{}.json", id)))
+}
+
+/// Write `bytes` to a temporary file beside `path`, flush it to disk, then
+/// rename it over `path`, so a crash leaves either the old or the new file.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let written = std::fs::File::create(&tmp).and_then(|mut file| {
+        file.write_all(bytes)?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.to_string());
+    }
+    std::fs::rename(&tmp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        e.to_string()
+    })
+}
+
+fn load(app: &AppHandle, id: &str) -> Result<Collection, String> {
+    match std::fs::read(collection_path(app, id)?) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(format!("Collection '{}' not found", id))
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Save a collection, and write it to its `source_dir` when it has one.
+fn save(app: &AppHandle, collection: &Collection) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(collection).map_err(|e| e.to_string())?;
+    write_atomic(&collection_path(app, &collection.id)?, &json)?;
+    if let Some(ref dir) = collection.source_dir {
+        write_collection_dir(app, collection, Path::new(dir))?;
+    }
+    Ok(())
+}
+
+/// Save a whole collection, replacing any with the same id.
+pub(crate) fn store_collection(app: &AppHandle, collection: &Collection) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    save(app, collection)
+}
+
+/// Saved collections stored in a directory as well. Files that can't be read
+/// are skipped.
+pub(crate) fn linked_collections(app: &AppHandle) -> Vec<Collection> {
+    let Ok(dir) = collections_dir(app) else {
+        return Vec::new();
+    };
+    let Ok(_guard) = STORE_LOCK.lock() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let bytes = std::fs::read(entry.ok()?.path()).ok()?;
+            serde_json::from_slice::<Collection>(&bytes).ok()
+        })
+        .filter(|collection| collection.source_dir.is_some())
+        .collect()
+}
+
+/// Load a collection, change it, and save it if `change` succeeds.
+fn modify<T>(
+    app: &AppHandle,
+    id: &str,
+    change: impl FnOnce(&mut Collection) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut collection = load(app, id)?;
+    let result = change(&mut collection)?;
+    collection.updated_at = Utc::now().to_rfc3339();
+    save(app, &collection)?;
+    Ok(result)
+}
+
+fn node_id(node: &CollectionNode) -> &str {
+    match node {
+        CollectionNode::Folder { id, .. } | CollectionNode::Request { id, .. } => id,
+    }
+}
+
+fn request_count(items: &[CollectionNode]) -> u32 {
+    items
+        .iter()
+        .map(|node| match node {
+            CollectionNode::Folder { items, .. } => request_count(items),
+            CollectionNode::Request { .. } => 1,
+        })
+        .sum()
+}
+
+fn find_mut<'a>(items: &'a mut [CollectionNode], id: &str) -> Option<&'a mut CollectionNode> {
+    for node in items {
+        if node_id(node) == id {
+            return Some(node);
+        }
+        if let CollectionNode::Folder { items, .. } = node {
+            if let Some(found) = find_mut(items, id) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Remove the item with `id` from wherever it is in the tree.
+fn take(items: &mut Vec<CollectionNode>, id: &str) -> Option<CollectionNode> {
+    if let Some(index) = items.iter().position(|node| node_id(node) == id) {
+        return Some(items.remove(index));
+    }
+    items.iter_mut().find_map(|node| match node {
+        CollectionNode::Folder { items, .. } => take(items, id),
+        CollectionNode::Request { .. } => None,
+    })
+}
+
+/// The items of folder `folder_id`, or the top level when it's `None`.
+fn children_mut<'a>(
+    collection: &'a mut Collection,
+    folder_id: Option<&str>,
+) -> Result<&'a mut Vec<CollectionNode>, String> {
+    let Some(folder_id) = folder_id else {
+        return Ok(&mut collection.items);
+    };
+    match find_mut(&mut collection.items, folder_id) {
+        Some(CollectionNode::Folder { items, .. }) => Ok(items),
+        Some(CollectionNode::Request { .. }) => {
+            Err(format!("'{}' is a request, not a folder", folder_id))
+        }
+        None => Err(format!("Folder '{}' not found", folder_id)),
+    }
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Create an empty collection.
+#[tauri::command]
+#[specta::specta]
+pub fn create_collection(app: AppHandle, name: String) -> Result<Collection, String> {
+    let now = Utc::now().to_rfc3339();
+    let collection = Collection {
+        id: new_id(),
+        name,
+        items: Vec::new(),
+        created_at: now.clone(),
+        updated_at: now,
+        source_dir: None,
+    };
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    save(&app, &collection)?;
+    Ok(collection)
+}
+
+/// Every saved collection, by name. Files that can't be read are skipped.
+#[tauri::command]
+#[specta::specta]
+pub fn list_collections(app: AppHandle) -> Result<Vec<CollectionSummary>, String> {
+    let dir = collections_dir(&app)?;
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut summaries: Vec<CollectionSummary> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                return None;
+            }
+            let bytes = std::fs::read(path).ok()?;
+            let collection: Collection = serde_json::from_slice(&bytes).ok()?;
+            Some(CollectionSummary {
+                request_count: request_count(&collection.items),
+                id: collection.id,
+                name: collection.name,
+                updated_at: collection.updated_at,
+            })
+        })
+        .collect();
+    summaries.sort_by_key(|summary| summary.name.to_lowercase());
+    Ok(summaries)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_collection_tree(app: AppHandle, collection_id: String) -> Result<Collection, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    load(&app, &collection_id)
+}
+
+/// Returns `false` when there was no such collection. A `source_dir` is
+/// left as it is.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_collection(app: AppHandle, collection_id: String) -> Result<bool, String> {
+    let path = collection_path(&app, &collection_id)?;
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    unwatch(&collection_id);
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Add an empty folder inside `parent_id`, or at the top level. Returns its id.
+#[tauri::command]
+#[specta::specta]
+pub fn create_folder(
+    app: AppHandle,
+    collection_id: String,
+    parent_id: Option<String>,
+    name: String,
+) -> Result<String, String> {
+    modify(&app, &collection_id, |collection| {
+        let id = new_id();
+        children_mut(collection, parent_id.as_deref())?.push(CollectionNode::Folder {
+            id: id.clone(),
+            name,
+            items: Vec::new(),
+        });
+        Ok(id)
+    })
+}
+
+/// Add a request at the end of `folder_id`, or at the top level. Returns its id.
+#[tauri::command]
+#[specta::specta]
+pub fn save_request(
+    app: AppHandle,
+    collection_id: String,
+    folder_id: Option<String>,
+    name: String,
+    request: ApiRequest,
+) -> Result<String, String> {
+    modify(&app, &collection_id, |collection| {
+        let id = new_id();
+        children_mut(collection, folder_id.as_deref())?.push(CollectionNode::Request {
+            id: id.clone(),
+            name,
+            request: Box::new(request),
+        });
+        Ok(id)
+    })
+}
+
+/// Replace a saved request, and rename it when `name` is set.
+#[tauri::command]
+#[specta::specta]
+pub fn update_request(
+    app: AppHandle,
+    collection_id: String,
+    request_id: String,
+    name: Option<String>,
+    request: ApiRequest,
+) -> Result<(), String> {
+    modify(&app, &collection_id, |collection| {
+        match find_mut(&mut collection.items, &request_id) {
+            Some(CollectionNode::Request {
+                name: saved_name,
+                request: saved,
+                ..
+            }) => {
+                **saved = request;
+                if let Some(name) = name {
+                    *saved_name = name;
+                }
+                Ok(())
+            }
+            Some(CollectionNode::Folder { .. }) => {
+                Err(format!("'{}' is a folder, not a request", request_id))
+            }
+            None => Err(format!("Request '{}' not found", request_id)),
+        }
+    })
+}
+
+/// Move a request or folder into `target_folder_id` (the top level when
+/// `None`) at `index`, or at the end. A folder can't move into itself.
+#[tauri::command]
+#[specta::specta]
+pub fn move_item(
+    app: AppHandle,
+    collection_id: String,
+    item_id: String,
+    target_folder_id: Option<String>,
+    index: Option<u32>,
+) -> Result<(), String> {
+    modify(&app, &collection_id, |collection| {
+        if let Some(target) = target_folder_id.as_deref() {
+            if let Some(node @ CollectionNode::Folder { .. }) =
+                find_mut(&mut collection.items, &item_id)
+            {
+                if find_mut(std::slice::from_mut(node), target).is_some() {
+                    return Err("A folder can't be moved into itself".to_string());
+                }
+            }
+        }
+        let node = take(&mut collection.items, &item_id)
+            .ok_or_else(|| format!("Item '{}' not found", item_id))?;
+        let items = children_mut(collection, target_folder_id.as_deref())?;
+        let index = index.map_or(items.len(), |index| (index as usize).min(items.len()));
+        items.insert(index, node);
+        Ok(())
+    })
+}
+
+/// Delete a request, or a folder with everything in it. Returns `false` when
+/// there was no such item.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_item(app: AppHandle, collection_id: String, item_id: String) -> Result<bool, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut collection = load(&app, &collection_id)?;
+    if take(&mut collection.items, &item_id).is_none() {
+        return Ok(false);
+    }
+    collection.updated_at = Utc::now().to_rfc3339();
+    save(&app, &collection)?;
+    Ok(true)
+}
@@ -0,0 +1,521 @@
+//! Cookie storage for REST requests. A `CookieJar` replays cookies set by
+//! redirect hops within one request, and the per-session jars keyed by
+//! `ApiRequest::session_id` carry cookies from one request to the next.
+
+use chrono::{NaiveDate, Utc};
+use dashmap::DashMap;
+use std::sync::OnceLock;
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+use url::{Host, Url};
+
+use crate::types::{Cookie, CookieImport};
+
+static SESSION_JARS: OnceLock<DashMap<String, CookieJar>> = OnceLock::new();
+
+fn session_jars() -> &'static DashMap<String, CookieJar> {
+    SESSION_JARS.get_or_init(DashMap::new)
+}
+
+/// Bytes that separate date tokens in a cookie date (RFC 6265 §5.1.1).
+fn is_date_delimiter(b: u8) -> bool {
+    matches!(b, 0x09 | 0x20..=0x2F | 0x3B..=0x40 | 0x5B..=0x60 | 0x7B..=0x7E)
+}
+
+/// Value of a token's leading digits when there are `min..=max` of them.
+fn leading_number(token: &str, min: usize, max: usize) -> Option<u32> {
+    let digits = token.bytes().take_while(u8::is_ascii_digit).count();
+    if (min..=max).contains(&digits) {
+        token[..digits].parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_cookie_time(token: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = token.splitn(3, ':');
+    let hour = parts.next()?;
+    let minute = parts.next()?;
+    let second = parts.next()?;
+    // Only the seconds field may carry trailing non-digits.
+    Some((
+        leading_number(hour, hour.len(), 2)?,
+        leading_number(minute, minute.len(), 2)?,
+        leading_number(second, 1, 2)?,
+    ))
+}
+
+/// Parse a cookie `Expires` value to unix seconds with the RFC 6265 §5.1.1
+/// algorithm, which covers RFC 1123, RFC 850 and asctime dates alike and maps
+/// two-digit years 70-99 to 19xx and 00-69 to 20xx.
+pub fn parse_cookie_date(value: &str) -> Option<i64> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+
+    let mut time = None;
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+    for token in value.split(|c: char| c.is_ascii() && is_date_delimiter(c as u8)) {
+        if token.is_empty() {
+            continue;
+        }
+        if time.is_none() {
+            if let Some(t) = parse_cookie_time(token) {
+                time = Some(t);
+                continue;
+            }
+        }
+        if day.is_none() {
+            if let Some(d) = leading_number(token, 1, 2) {
+                day = Some(d);
+                continue;
+            }
+        }
+        if month.is_none() {
+            let prefix = token.get(..3).map(str::to_ascii_lowercase);
+            if let Some(m) = MONTHS.iter().position(|m| Some(*m) == prefix.as_deref()) {
+                month = Some(m as u32 + 1);
+                continue;
+            }
+        }
+        if year.is_none() {
+            if let Some(y) = leading_number(token, 2, 4) {
+                year = Some(y);
+            }
+        }
+    }
+
+    let (hour, minute, second) = time?;
+    let year = match year? {
+        y @ 70..=99 => y + 1900,
+        y @ 0..=69 => y + 2000,
+        y => y,
+    };
+    if year < 1601 || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let date = NaiveDate::from_ymd_opt(year as i32, month?, day?)?;
+    let at = date.and_hms_opt(hour, minute, second)?;
+    Some(at.and_utc().timestamp())
+}
+
+fn is_expired(cookie: &Cookie) -> bool {
+    cookie
+        .expires_at
+        .or_else(|| cookie.expires.as_deref().and_then(parse_cookie_date))
+        .is_some_and(|at| at <= Utc::now().timestamp())
+}
+
+/// RFC 6265 §5.1.3: the host is the domain or a subdomain of it. IP addresses
+/// only match exactly.
+fn domain_matches(url: &Url, domain: &str) -> bool {
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+    host == domain
+        || (matches!(url.host(), Some(Host::Domain(_)))
+            && host.ends_with(&domain)
+            && host[..host.len() - domain.len()].ends_with('.'))
+}
+
+/// RFC 6265 §5.1.4: the cookie path is the request path or a `/`-bounded prefix of it.
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Why `cookie` must not be sent to `url`, or `None` when it applies. `set_by` is
+/// the host a cookie without `Domain` is tied to; `None` means the request's own
+/// host. A missing `Path` applies to every path.
+pub fn cookie_mismatch(cookie: &Cookie, set_by: Option<&str>, url: &Url) -> Option<String> {
+    let host = url.host_str().unwrap_or_default();
+    match (cookie.domain.as_deref(), set_by) {
+        (Some(domain), _) if !domain_matches(url, domain) => {
+            return Some(format!("domain {} does not match {}", domain, host));
+        }
+        (None, Some(set_by)) if !host.eq_ignore_ascii_case(set_by) => {
+            return Some(format!("host-only cookie for {}", set_by));
+        }
+        _ => {}
+    }
+    if let Some(path) = cookie.path.as_deref().filter(|p| p.starts_with('/')) {
+        if !path_matches(url.path(), path) {
+            return Some(format!("path {} does not match {}", path, url.path()));
+        }
+    }
+    if cookie.secure == Some(true) && url.scheme() != "https" {
+        return Some("Secure cookie on a non-HTTPS URL".to_string());
+    }
+    if is_expired(cookie) {
+        return Some("expired".to_string());
+    }
+    None
+}
+
+#[derive(Default, Clone)]
+pub struct CookieJar {
+    /// `(host that set it, cookie)`; cookies without a `Domain` stay on that host.
+    cookies: Vec<(String, Cookie)>,
+}
+
+impl CookieJar {
+    /// Add or replace cookies set by `host`. An already-expired cookie only
+    /// removes the one it replaces, which is how servers delete cookies.
+    pub fn store(&mut self, host: &str, cookies: &[Cookie]) {
+        for cookie in cookies {
+            self.cookies.retain(|(set_by, existing)| {
+                !(existing.name == cookie.name
+                    && existing.domain == cookie.domain
+                    && (cookie.domain.is_some() || set_by == host))
+            });
+            if !is_expired(cookie) {
+                self.cookies.push((host.to_string(), cookie.clone()));
+            }
+        }
+    }
+
+    /// Unexpired cookies that apply to `url` by domain, path and `Secure`.
+    pub fn matching<'a>(&'a self, url: &'a Url) -> impl Iterator<Item = &'a Cookie> {
+        self.cookies
+            .iter()
+            .filter(move |(set_by, cookie)| cookie_mismatch(cookie, Some(set_by), url).is_none())
+            .map(|(_, cookie)| cookie)
+    }
+
+    /// Drop cookies whose name is in `names`, e.g. ones the request sets explicitly.
+    pub fn without_names(mut self, names: &[&str]) -> Self {
+        self.cookies
+            .retain(|(_, cookie)| !names.contains(&cookie.name.as_str()));
+        self
+    }
+}
+
+/// A snapshot of a session's jar, empty when the session has no cookies yet.
+pub fn session_jar(session_id: &str) -> CookieJar {
+    session_jars()
+        .get(session_id)
+        .map(|jar| jar.value().clone())
+        .unwrap_or_default()
+}
+
+/// Empty every session's jar, as when switching workspaces.
+pub(crate) fn clear_all_session_jars() {
+    session_jars().clear();
+}
+
+/// Store cookies a response from `host` set in a session's jar.
+pub fn store_session_cookies(session_id: &str, host: &str, cookies: &[Cookie]) {
+    if cookies.is_empty() {
+        return;
+    }
+    session_jars()
+        .entry(session_id.to_string())
+        .or_default()
+        .store(host, cookies);
+}
+
+/// Unexpired cookies in a session's jar. Host-only cookies report the host that
+/// set them as their `domain`.
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_cookies(session_id: String) -> Result<Vec<Cookie>, String> {
+    let Some(jar) = session_jars().get(&session_id) else {
+        return Ok(Vec::new());
+    };
+    Ok(jar
+        .cookies
+        .iter()
+        .filter(|(_, cookie)| !is_expired(cookie))
+        .map(|(set_by, cookie)| Cookie {
+            domain: cookie.domain.clone().or_else(|| Some(set_by.clone())),
+            ..cookie.clone()
+        })
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn clear_session_cookies(session_id: String) -> Result<(), String> {
+    session_jars().remove(&session_id);
+    Ok(())
+}
+
+/// Remove one cookie from a session's jar. `domain` is matched against the
+/// cookie's `Domain` attribute, or the setting host for host-only cookies.
+/// Returns whether a cookie was removed.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_cookie(session_id: String, name: String, domain: String) -> Result<bool, String> {
+    let Some(mut jar) = session_jars().get_mut(&session_id) else {
+        return Ok(false);
+    };
+    let domain = domain.trim_start_matches('.');
+    let before = jar.cookies.len();
+    jar.cookies.retain(|(set_by, cookie)| {
+        let cookie_domain = cookie.domain.as_deref().unwrap_or(set_by);
+        !(cookie.name == name
+            && cookie_domain
+                .trim_start_matches('.')
+                .eq_ignore_ascii_case(domain))
+    });
+    Ok(jar.cookies.len() != before)
+}
+
+/// One line of a Netscape cookies.txt file: domain, include-subdomains flag,
+/// path, secure flag, expiry (unix seconds, 0 for a session cookie), name and
+/// value, tab-separated. Returns the cookie and the host it belongs to.
+fn parse_cookies_txt_line(line: &str) -> Option<(String, Cookie)> {
+    // curl and browsers mark HttpOnly cookies by prefixing the domain.
+    let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+        Some(rest) => (rest, true),
+        None => (line, false),
+    };
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [domain, include_subdomains, path, secure, expiry, name, value] = fields[..] else {
+        return None;
+    };
+    let flag = |s: &str| match s.to_ascii_uppercase().as_str() {
+        "TRUE" => Some(true),
+        "FALSE" => Some(false),
+        _ => None,
+    };
+    let include_subdomains = flag(include_subdomains)?;
+    let secure = flag(secure)?;
+    let expiry: i64 = expiry.trim().parse().ok()?;
+    if domain.is_empty() || name.is_empty() {
+        return None;
+    }
+
+    let host = domain.trim_start_matches('.').to_string();
+    let expires_at = (expiry > 0).then_some(expiry);
+    let cookie = Cookie {
+        name: name.to_string(),
+        value: value.to_string(),
+        // Without the subdomain flag the cookie is host-only.
+        domain: include_subdomains.then(|| domain.to_string()),
+        path: Some(path.to_string()).filter(|p| !p.is_empty()),
+        expires: None,
+        expires_at,
+        is_expired: expires_at.map(|at| at <= Utc::now().timestamp()),
+        http_only: Some(http_only),
+        secure: Some(secure),
+        max_age: None,
+        same_site: None,
+    };
+    Some((host, cookie))
+}
+
+/// Load a Netscape-format cookies.txt (as exported by browsers and curl) into a
+/// session's jar. Malformed lines are skipped and counted; already-expired
+/// cookies are returned but not stored.
+#[tauri::command]
+#[specta::specta]
+pub fn import_cookies_txt(
+    app: AppHandle,
+    path: String,
+    session_id: String,
+) -> Result<CookieImport, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+
+    let mut jar = session_jars().entry(session_id).or_default();
+    Ok(read_cookies_txt(&content, &mut jar))
+}
+
+/// Store every valid line of a cookies.txt file in `jar`, returning the cookies
+/// read (with their host filled in as the domain) and the count of bad lines.
+fn read_cookies_txt(content: &str, jar: &mut CookieJar) -> CookieImport {
+    let mut cookies = Vec::new();
+    let mut ignored_lines = 0;
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        let is_comment = line.starts_with('#') && !line.starts_with("#HttpOnly_");
+        if line.trim().is_empty() || is_comment {
+            continue;
+        }
+        match parse_cookies_txt_line(line) {
+            Some((host, cookie)) => {
+                jar.store(&host, std::slice::from_ref(&cookie));
+                cookies.push(Cookie {
+                    domain: cookie.domain.clone().or(Some(host)),
+                    ..cookie
+                });
+            }
+            None => ignored_lines += 1,
+        }
+    }
+
+    CookieImport {
+        cookies,
+        ignored_lines,
+    }
+}
+
+/// Write a session's unexpired cookies as a Netscape-format cookies.txt that
+/// curl (`-b`) and browser extensions can read. Returns how many were written.
+#[tauri::command]
+#[specta::specta]
+pub fn export_cookies_txt(app: AppHandle, session_id: String, path: String) -> Result<u32, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+
+    let (out, written) = write_cookies_txt(&session_jar(&session_id));
+    std::fs::write(&path, out).map_err(|e| format!("Failed to write '{}': {}", path, e))?;
+    Ok(written)
+}
+
+/// A jar's unexpired cookies in Netscape cookies.txt format, and how many there are.
+fn write_cookies_txt(jar: &CookieJar) -> (String, u32) {
+    let mut out = String::from("# Netscape HTTP Cookie File\n");
+    let mut written = 0;
+    for (set_by, cookie) in jar.cookies.iter().filter(|(_, c)| !is_expired(c)) {
+        // Domain cookies are written with a leading dot and the subdomain flag;
+        // host-only ones under the exact host that set them.
+        let (domain, include_subdomains) = match cookie.domain.as_deref() {
+            Some(domain) => (format!(".{}", domain.trim_start_matches('.')), "TRUE"),
+            None => (set_by.clone(), "FALSE"),
+        };
+        let prefix = if cookie.http_only == Some(true) {
+            "#HttpOnly_"
+        } else {
+            ""
+        };
+        let secure = if cookie.secure == Some(true) {
+            "TRUE"
+        } else {
+            "FALSE"
+        };
+        let expiry = cookie
+            .expires_at
+            .or_else(|| cookie.expires.as_deref().and_then(parse_cookie_date))
+            .unwrap_or(0);
+        out.push_str(&format!(
+            "{}{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            prefix,
+            domain,
+            include_subdomains,
+            cookie.path.as_deref().unwrap_or("/"),
+            secure,
+            expiry,
+            cookie.name,
+            cookie.value,
+        ));
+        written += 1;
+    }
+    (out, written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sun, 06 Nov 1994 08:49:37 GMT, the example date in RFC 7231.
+    const EXAMPLE: i64 = 784_111_777;
+
+    #[test]
+    fn rfc1123_date_is_parsed() {
+        assert_eq!(
+            parse_cookie_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(EXAMPLE)
+        );
+    }
+
+    #[test]
+    fn rfc850_date_is_parsed() {
+        assert_eq!(
+            parse_cookie_date("Sunday, 06-Nov-94 08:49:37 GMT"),
+            Some(EXAMPLE)
+        );
+    }
+
+    #[test]
+    fn asctime_date_is_parsed() {
+        assert_eq!(parse_cookie_date("Sun Nov  6 08:49:37 1994"), Some(EXAMPLE));
+    }
+
+    #[test]
+    fn two_digit_years_split_at_seventy() {
+        let year_of = |value: &str| {
+            chrono::DateTime::from_timestamp(parse_cookie_date(value).unwrap(), 0)
+                .unwrap()
+                .format("%Y")
+                .to_string()
+        };
+        assert_eq!(year_of("Thu, 01-Jan-70 00:00:00 GMT"), "1970");
+        assert_eq!(year_of("Fri, 31-Dec-99 23:59:59 GMT"), "1999");
+        assert_eq!(year_of("Sat, 01-Jan-00 00:00:00 GMT"), "2000");
+        assert_eq!(year_of("Sun, 31-Dec-69 23:59:59 GMT"), "2069");
+    }
+
+    #[test]
+    fn invalid_dates_are_rejected() {
+        assert_eq!(parse_cookie_date("Sun, 06 Nov 1994"), None);
+        assert_eq!(parse_cookie_date("Sun, 31 Feb 1994 08:49:37 GMT"), None);
+        assert_eq!(parse_cookie_date("Sun, 06 Nov 1994 24:00:00 GMT"), None);
+        assert_eq!(parse_cookie_date("Mon, 01 Jan 1600 00:00:00 GMT"), None);
+        assert_eq!(parse_cookie_date("tomorrow"), None);
+    }
+
+    const COOKIES_TXT: &str = include_str!("testdata/cookies.txt");
+
+    #[test]
+    fn cookies_txt_fixture_is_imported() {
+        let mut jar = CookieJar::default();
+        let import = read_cookies_txt(COOKIES_TXT, &mut jar);
+        assert_eq!(import.ignored_lines, 0);
+        assert_eq!(import.cookies.len(), 4);
+
+        let sid = &import.cookies[0];
+        assert_eq!(sid.domain.as_deref(), Some(".example.com"));
+        assert_eq!(sid.secure, Some(true));
+        assert_eq!(sid.http_only, Some(false));
+        assert_eq!(sid.expires_at, Some(4_102_444_800));
+        assert_eq!(sid.is_expired, Some(false));
+
+        let token = &import.cookies[1];
+        assert_eq!(token.domain.as_deref(), Some("api.example.com"));
+        assert_eq!(token.path.as_deref(), Some("/v1"));
+        assert_eq!(token.value, "x=y");
+        assert_eq!(token.http_only, Some(true));
+        assert_eq!(token.secure, Some(false));
+
+        let session = &import.cookies[2];
+        assert_eq!(session.http_only, Some(true));
+        assert_eq!(session.expires_at, None);
+        assert_eq!(session.is_expired, None);
+    }
+
+    #[test]
+    fn cookies_txt_fixture_round_trips() {
+        let mut jar = CookieJar::default();
+        read_cookies_txt(COOKIES_TXT, &mut jar);
+        let (exported, written) = write_cookies_txt(&jar);
+        assert_eq!(written, 4);
+        assert_eq!(exported, COOKIES_TXT);
+    }
+
+    #[test]
+    fn expired_and_malformed_cookies_txt_lines_are_not_stored() {
+        let content = "# comment\n\
+            .example.com\tTRUE\t/\tFALSE\t1\told\tgone\n\
+            example.com\tMAYBE\t/\tFALSE\t0\tbad\tflag\n\
+            too\tfew\tfields\n";
+        let mut jar = CookieJar::default();
+        let import = read_cookies_txt(content, &mut jar);
+        assert_eq!(import.ignored_lines, 2);
+        assert_eq!(import.cookies.len(), 1);
+        assert_eq!(import.cookies[0].is_expired, Some(true));
+        assert_eq!(write_cookies_txt(&jar).1, 0);
+    }
+}
@@ -0,0 +1,2034 @@
+//! `curl` command lines, as found in API docs and browser "Copy as cURL", read
+//! into an `ApiRequest`, and requests written back out as one.
+
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+use crate::helpers::jwt::sign_jwt;
+use crate::helpers::rest::{
+    build_url_with_params, graphql_envelope, jsonrpc_body, method_from_string,
+    method_to_curl_string, mime_from_extension, soap_envelope, soap_headers,
+};
+use crate::types::{
+    ApiKeyLocation, ApiRequest, AuthType, BodyType, ClientCert, Cookie, CurlExportOptions,
+    CurlImport, HttpProtocol, IpVersion, Methods, MultipartField, MultipartValue, NetrcMode,
+    ProxyAuthScheme, ProxyConfig, ProxyKind, ResolveOverride,
+};
+
+/// Long names of the short options that are understood.
+fn long_option(short: char) -> Option<&'static str> {
+    Some(match short {
+        'X' => "request",
+        'H' => "header",
+        'd' => "data",
+        'F' => "form",
+        'u' => "user",
+        'b' => "cookie",
+        'x' => "proxy",
+        'U' => "proxy-user",
+        'k' => "insecure",
+        'L' => "location",
+        'm' => "max-time",
+        'A' => "user-agent",
+        'e' => "referer",
+        'G' => "get",
+        'I' => "head",
+        'T' => "upload-file",
+        'E' => "cert",
+        'n' => "netrc",
+        '4' => "ipv4",
+        '6' => "ipv6",
+        'o' => "output",
+        'O' => "remote-name",
+        'w' => "write-out",
+        'D' => "dump-header",
+        'c' => "cookie-jar",
+        's' => "silent",
+        'S' => "show-error",
+        'v' => "verbose",
+        'i' => "include",
+        'f' => "fail",
+        'g' => "globoff",
+        'N' => "no-buffer",
+        'r' => "range",
+        'K' => "config",
+        'C' => "continue-at",
+        'z' => "time-cond",
+        'y' => "speed-time",
+        'Y' => "speed-limit",
+        '#' => "progress-bar",
+        _ => return None,
+    })
+}
+
+/// Options whose value is the next argument.
+const VALUE_OPTIONS: &[&str] = &[
+    "request",
+    "header",
+    "data",
+    "data-ascii",
+    "data-raw",
+    "data-binary",
+    "data-urlencode",
+    "json",
+    "form",
+    "form-string",
+    "user",
+    "oauth2-bearer",
+    "delegation",
+    "cookie",
+    "proxy",
+    "proxy-user",
+    "noproxy",
+    "socks4",
+    "socks5",
+    "socks5-hostname",
+    "max-redirs",
+    "connect-timeout",
+    "max-time",
+    "user-agent",
+    "referer",
+    "upload-file",
+    "url",
+    "cert",
+    "cert-type",
+    "key",
+    "pass",
+    "cacert",
+    "pinnedpubkey",
+    "ciphers",
+    "tls13-ciphers",
+    "netrc-file",
+    "resolve",
+    "unix-socket",
+    "interface",
+    // Read so their values aren't taken for the URL, but not carried over.
+    "output",
+    "write-out",
+    "dump-header",
+    "cookie-jar",
+    "config",
+    "range",
+    "retry",
+    "retry-delay",
+    "retry-max-time",
+    "limit-rate",
+    "connect-to",
+    "continue-at",
+    "time-cond",
+    "speed-limit",
+    "speed-time",
+    "trace",
+    "trace-ascii",
+    "stderr",
+    "proto",
+    "proto-redir",
+    "capath",
+    "proxy-cacert",
+    "max-filesize",
+    "keepalive-time",
+    "expect100-timeout",
+    "local-port",
+    "request-target",
+    "aws-sigv4",
+];
+
+/// Options that don't change the request: output handling, and `--compressed`
+/// since every supported encoding is advertised anyway.
+const OUTPUT_OPTIONS: &[&str] = &[
+    "output",
+    "remote-name",
+    "write-out",
+    "dump-header",
+    "silent",
+    "show-error",
+    "verbose",
+    "include",
+    "fail",
+    "fail-with-body",
+    "globoff",
+    "no-buffer",
+    "progress-bar",
+    "no-progress-meter",
+    "compressed",
+    "stderr",
+];
+
+/// Splits a command line like a POSIX shell: single and double quotes,
+/// `$'...'` strings, backslash escapes, line continuations and comments.
+fn tokenize(command: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    // Separate from `token.is_empty()`, so `''` is still an argument.
+    let mut in_token = false;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') => {
+                    chars.next_if_eq(&'\n');
+                }
+                Some(escaped) => {
+                    token.push(escaped);
+                    in_token = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => token.push(c),
+                        None => return Err("Unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => token.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                token.push('\\');
+                                token.push(c);
+                            }
+                            None => return Err("Unterminated double quote".to_string()),
+                        },
+                        Some(c) => token.push(c),
+                        None => return Err("Unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_token = true;
+                ansi_c_string(&mut chars, &mut token)?;
+            }
+            '#' if !in_token => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if std::mem::take(&mut in_token) {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => {
+                token.push(c);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// The rest of a `$'...'` string, which Chrome uses for bodies with quotes
+/// or newlines in them.
+fn ansi_c_string(chars: &mut Peekable<Chars>, token: &mut String) -> Result<(), String> {
+    loop {
+        let c = match chars.next() {
+            Some('\'') => return Ok(()),
+            Some('\\') => match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('r') => '\r',
+                Some('a') => '\x07',
+                Some('b') => '\x08',
+                Some('f') => '\x0c',
+                Some('v') => '\x0b',
+                Some('e' | 'E') => '\x1b',
+                Some(kind @ ('x' | 'u' | 'U')) => {
+                    let digits = match kind {
+                        'x' => 2,
+                        'u' => 4,
+                        _ => 8,
+                    };
+                    match hex_escape(chars, digits) {
+                        Some(c) => c,
+                        None => {
+                            token.push('\\');
+                            kind
+                        }
+                    }
+                }
+                Some(c) => c,
+                None => return Err("Unterminated $'...' string".to_string()),
+            },
+            Some(c) => c,
+            None => return Err("Unterminated $'...' string".to_string()),
+        };
+        token.push(c);
+    }
+}
+
+fn hex_escape(chars: &mut Peekable<Chars>, max_digits: u32) -> Option<char> {
+    let mut value = 0u32;
+    let mut digits = 0;
+    while digits < max_digits {
+        let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) else {
+            break;
+        };
+        value = value * 16 + digit;
+        digits += 1;
+        chars.next();
+    }
+    if digits == 0 {
+        return None;
+    }
+    char::from_u32(value)
+}
+
+/// Percent-encodes everything but unreserved characters, like `curl_easy_escape`.
+fn url_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    escaped
+}
+
+/// Seconds as curl takes them (decimals allowed), in milliseconds.
+fn parse_seconds(option: &str, value: &str) -> Result<u32, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(|secs| (secs * 1000.0).min(u32::MAX as f64) as u32)
+        .ok_or_else(|| format!("Invalid --{} value '{}'", option, value))
+}
+
+/// `user:password`, with the password empty when there's no colon.
+fn split_credentials(value: &str) -> (String, String) {
+    match value.split_once(':') {
+        Some((user, password)) => (user.to_string(), password.to_string()),
+        None => (value.to_string(), String::new()),
+    }
+}
+
+/// Splits `path:password` without mistaking a Windows drive letter for the separator.
+fn split_cert_password(value: &str) -> (String, Option<String>) {
+    let skip = match value.as_bytes() {
+        [drive, b':', b'\\' | b'/', ..] if drive.is_ascii_alphabetic() => 2,
+        _ => 0,
+    };
+    match value[skip..].find(':') {
+        Some(at) => (
+            value[..skip + at].to_string(),
+            Some(value[skip + at + 1..].to_string()),
+        ),
+        None => (value.to_string(), None),
+    }
+}
+
+fn proxy_kind(url: &str) -> ProxyKind {
+    let scheme = url
+        .split_once("://")
+        .map(|(scheme, _)| scheme.to_ascii_lowercase());
+    match scheme.as_deref() {
+        Some("https") => ProxyKind::Https,
+        Some("socks4" | "socks4a") => ProxyKind::Socks4,
+        Some("socks5") => ProxyKind::Socks5,
+        Some("socks5h") => ProxyKind::Socks5Hostname,
+        _ => ProxyKind::Http,
+    }
+}
+
+enum DataPart {
+    Text(String),
+    /// `@path`; curl strips newlines from the file unless sent as binary.
+    File {
+        path: String,
+        binary: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum HttpAuthScheme {
+    Basic,
+    Digest,
+    Ntlm,
+    Negotiate,
+}
+
+/// Splits a `-F` file spec on `;`, except inside double quotes, which curl
+/// uses (with backslash escapes) for paths and filenames containing `;` or `,`.
+fn split_form_attributes(spec: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut quoted = false;
+    let mut chars = spec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' if quoted => part.extend(chars.next()),
+            ';' if !quoted => parts.push(std::mem::take(&mut part)),
+            c => part.push(c),
+        }
+    }
+    parts.push(part);
+    parts
+}
+
+/// What the options add up to, turned into the request once all are read.
+struct CurlOptions {
+    request: ApiRequest,
+    warnings: Vec<String>,
+    url: Option<String>,
+    method: Option<Methods>,
+    headers: Vec<String>,
+    data: Vec<DataPart>,
+    form: Vec<MultipartField>,
+    upload_file: Option<String>,
+    user: Option<String>,
+    auth_scheme: HttpAuthScheme,
+    bearer: Option<String>,
+    delegate: bool,
+    cert_type: Option<String>,
+    get: bool,
+    head: bool,
+}
+
+impl CurlOptions {
+    fn new() -> Self {
+        Self {
+            request: ApiRequest {
+                // curl only follows redirects with -L.
+                follow_redirects: Some(false),
+                ..Default::default()
+            },
+            warnings: Vec::new(),
+            url: None,
+            method: None,
+            headers: Vec::new(),
+            data: Vec::new(),
+            form: Vec::new(),
+            upload_file: None,
+            user: None,
+            auth_scheme: HttpAuthScheme::Basic,
+            bearer: None,
+            delegate: false,
+            cert_type: None,
+            get: false,
+            head: false,
+        }
+    }
+
+    fn set_url(&mut self, url: String) {
+        if self.url.is_some() {
+            self.warnings
+                .push(format!("Only the first URL is used; '{}' was ignored", url));
+        } else {
+            self.url = Some(url);
+        }
+    }
+
+    fn proxy(&mut self) -> &mut ProxyConfig {
+        self.request.proxy.get_or_insert_with(|| ProxyConfig {
+            url: String::new(),
+            username: None,
+            password: None,
+            proxy_type: ProxyKind::Http,
+            no_proxy: None,
+            auth_scheme: None,
+        })
+    }
+
+    fn apply(&mut self, name: &str, value: Option<String>) -> Result<(), String> {
+        let value = value.unwrap_or_default();
+        match name {
+            "request" => self.method = Some(method_from_string(&value)),
+            "header" => self.headers.push(value),
+            "user-agent" => self.headers.push(format!("User-Agent: {}", value)),
+            "referer" => self.headers.push(format!("Referer: {}", value)),
+            "data" | "data-ascii" | "data-binary" => match value.strip_prefix('@') {
+                Some(path) => self.data.push(DataPart::File {
+                    path: path.to_string(),
+                    binary: name == "data-binary",
+                }),
+                None => self.data.push(DataPart::Text(value)),
+            },
+            "data-raw" => self.data.push(DataPart::Text(value)),
+            "data-urlencode" => {
+                // `content`, `=content`, `name=content`, `@file` or `name@file`.
+                let (prefix, content) = match value.find(['=', '@']) {
+                    Some(at) if value[at..].starts_with('@') => {
+                        self.warnings.push(format!(
+                            "--data-urlencode '{}' reads a file and was left out",
+                            value
+                        ));
+                        return Ok(());
+                    }
+                    Some(at) => (&value[..at], &value[at + 1..]),
+                    None => ("", value.as_str()),
+                };
+                let part = if prefix.is_empty() {
+                    url_escape(content)
+                } else {
+                    format!("{}={}", prefix, url_escape(content))
+                };
+                self.data.push(DataPart::Text(part));
+            }
+            "json" => {
+                self.apply("data-binary", Some(value))?;
+                self.headers
+                    .push("Content-Type: application/json".to_string());
+                self.headers.push("Accept: application/json".to_string());
+            }
+            "form" | "form-string" => self.add_form_field(&value, name == "form-string"),
+            "upload-file" => self.upload_file = Some(value),
+            "get" => self.get = true,
+            "head" => self.head = true,
+            "user" => self.user = Some(value),
+            "basic" => self.auth_scheme = HttpAuthScheme::Basic,
+            "digest" => self.auth_scheme = HttpAuthScheme::Digest,
+            "ntlm" => self.auth_scheme = HttpAuthScheme::Ntlm,
+            "negotiate" => self.auth_scheme = HttpAuthScheme::Negotiate,
+            "oauth2-bearer" => self.bearer = Some(value),
+            "delegation" => self.delegate = value != "none",
+            "cookie" => self.add_cookies(&value),
+            "proxy" => {
+                let kind = proxy_kind(&value);
+                let proxy = self.proxy();
+                proxy.proxy_type = kind;
+                proxy.url = value;
+            }
+            "socks4" | "socks5" | "socks5-hostname" => {
+                let proxy = self.proxy();
+                proxy.proxy_type = match name {
+                    "socks4" => ProxyKind::Socks4,
+                    "socks5" => ProxyKind::Socks5,
+                    _ => ProxyKind::Socks5Hostname,
+                };
+                proxy.url = value;
+            }
+            "proxy-user" => {
+                let (user, password) = split_credentials(&value);
+                let proxy = self.proxy();
+                proxy.username = Some(user);
+                proxy.password = Some(password);
+            }
+            "noproxy" => self.proxy().no_proxy = Some(value),
+            "proxy-basic" => self.proxy().auth_scheme = Some(ProxyAuthScheme::Basic),
+            "proxy-digest" => self.proxy().auth_scheme = Some(ProxyAuthScheme::Digest),
+            "proxy-ntlm" => self.proxy().auth_scheme = Some(ProxyAuthScheme::Ntlm),
+            "proxy-negotiate" => self.proxy().auth_scheme = Some(ProxyAuthScheme::Negotiate),
+            "proxy-anyauth" => self.proxy().auth_scheme = Some(ProxyAuthScheme::Any),
+            "insecure" => self.request.verify_ssl = Some(false),
+            "location" => self.request.follow_redirects = Some(true),
+            "max-redirs" => {
+                let max = value
+                    .trim()
+                    .parse::<i64>()
+                    .map_err(|_| format!("Invalid --max-redirs value '{}'", value))?;
+                // -1 is curl's "no limit".
+                self.request.max_redirects = Some(if max < 0 {
+                    u32::MAX
+                } else {
+                    max.min(u32::MAX as i64) as u32
+                });
+            }
+            "connect-timeout" => {
+                self.request.connect_timeout_ms = Some(parse_seconds(name, &value)?)
+            }
+            "max-time" => self.request.timeout_ms = Some(parse_seconds(name, &value)?),
+            "url" => self.set_url(value),
+            "cert" => {
+                let (cert_path, key_password) = split_cert_password(&value);
+                self.request.client_cert = Some(ClientCert::Pem {
+                    cert_path,
+                    key_path: None,
+                    key_password,
+                });
+            }
+            "cert-type" => self.cert_type = Some(value.to_ascii_uppercase()),
+            "key" => match self.request.client_cert {
+                Some(ClientCert::Pem {
+                    ref mut key_path, ..
+                }) => *key_path = Some(value),
+                _ => self
+                    .warnings
+                    .push("--key without --cert was ignored".to_string()),
+            },
+            "pass" => match self.request.client_cert {
+                Some(ClientCert::Pem {
+                    ref mut key_password,
+                    ..
+                }) => *key_password = Some(value),
+                _ => self
+                    .warnings
+                    .push("--pass without --cert was ignored".to_string()),
+            },
+            "cacert" => self.request.ca_cert_path = Some(value),
+            "pinnedpubkey" => self.request.pinned_public_key = Some(value),
+            "ciphers" => self.request.tls_ciphers = Some(value),
+            "tls13-ciphers" => self.request.tls13_ciphers = Some(value),
+            "netrc" => self.request.use_netrc = Some(NetrcMode::Required),
+            "netrc-optional" => self.request.use_netrc = Some(NetrcMode::Optional),
+            "netrc-file" => {
+                self.request.netrc_file = Some(value);
+                self.request.use_netrc.get_or_insert(NetrcMode::Required);
+            }
+            "resolve" => self.add_resolve(&value),
+            "unix-socket" => self.request.unix_socket = Some(value),
+            "interface" => self.request.local_interface = Some(value),
+            "ipv4" => self.request.ip_version = Some(IpVersion::V4Only),
+            "ipv6" => self.request.ip_version = Some(IpVersion::V6Only),
+            "http1.1" => self.request.protocol = Some(HttpProtocol::Http1_1),
+            "http2" => self.request.protocol = Some(HttpProtocol::Http2),
+            "http2-prior-knowledge" => {
+                self.request.protocol = Some(HttpProtocol::Http2PriorKnowledge)
+            }
+            "http3" | "http3-only" => self.request.protocol = Some(HttpProtocol::Quic),
+            _ if OUTPUT_OPTIONS.contains(&name) => {}
+            _ if VALUE_OPTIONS.contains(&name) => self.warnings.push(format!(
+                "Unsupported option --{} '{}' was ignored",
+                name, value
+            )),
+            _ => self
+                .warnings
+                .push(format!("Unsupported option --{} was ignored", name)),
+        }
+        Ok(())
+    }
+
+    /// `name=value`, `name=@path` or `name=<path`, with optional `;type=` and
+    /// `;filename=` attributes on files.
+    fn add_form_field(&mut self, field: &str, literal: bool) {
+        let Some((name, value)) = field.split_once('=') else {
+            self.warnings.push(format!(
+                "Form field '{}' has no value and was ignored",
+                field
+            ));
+            return;
+        };
+        let name = name.to_string();
+        if literal {
+            self.form.push(MultipartField {
+                name,
+                value: MultipartValue::Text(value.to_string()),
+            });
+            return;
+        }
+        if let Some(spec) = value.strip_prefix('@') {
+            let mut attributes = split_form_attributes(spec).into_iter();
+            let path = attributes.next().unwrap_or_default();
+            let mut filename = None;
+            let mut content_type = None;
+            for attribute in attributes {
+                match attribute.split_once('=') {
+                    Some(("type", ty)) => content_type = Some(ty.to_string()),
+                    Some(("filename", file)) => filename = Some(file.to_string()),
+                    _ => {}
+                }
+            }
+            self.form.push(MultipartField {
+                name,
+                value: MultipartValue::FilePath {
+                    path,
+                    filename,
+                    content_type,
+                },
+            });
+        } else if value.starts_with('<') {
+            self.warnings.push(format!(
+                "Form field '{}' reads its value from a file and was left out",
+                name
+            ));
+        } else {
+            // A `;type=` on a text field only sets its part's Content-Type.
+            let text = value.split(";type=").next().unwrap_or_default();
+            self.form.push(MultipartField {
+                name,
+                value: MultipartValue::Text(text.to_string()),
+            });
+        }
+    }
+
+    fn add_cookies(&mut self, value: &str) {
+        if !value.contains('=') {
+            self.warnings
+                .push(format!("Cookie file '{}' was not imported", value));
+            return;
+        }
+        for pair in value.split(';') {
+            let Some((name, value)) = pair.split_once('=') else {
+                continue;
+            };
+            self.request.cookies.push(Cookie {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+                domain: None,
+                path: None,
+                expires: None,
+                expires_at: None,
+                is_expired: None,
+                http_only: None,
+                secure: None,
+                max_age: None,
+                same_site: None,
+            });
+        }
+    }
+
+    /// `host:port:address`, where the address may be a bracketed IPv6 one.
+    fn add_resolve(&mut self, value: &str) {
+        let parsed = value.split_once(':').and_then(|(host, rest)| {
+            let (port, address) = rest.split_once(':')?;
+            let address = address.trim_start_matches('[').trim_end_matches(']');
+            Some(ResolveOverride {
+                host: host.to_string(),
+                port: port.parse().ok()?,
+                address: address.split(',').next()?.to_string(),
+            })
+        });
+        match parsed {
+            Some(entry) => self.request.resolve_overrides.push(entry),
+            None => self
+                .warnings
+                .push(format!("Invalid --resolve entry '{}' was ignored", value)),
+        }
+    }
+
+    /// Moves `Authorization` headers curl would send as-is into `auth` when
+    /// they're Bearer or Basic credentials.
+    fn header_auth(&mut self, value: &str) -> bool {
+        let (scheme, credentials) = value.split_once(' ').unwrap_or((value, ""));
+        let credentials = credentials.trim();
+        if scheme.eq_ignore_ascii_case("bearer") && !credentials.is_empty() {
+            self.request.auth = AuthType::Bearer {
+                token: credentials.to_string(),
+            };
+            return true;
+        }
+        if scheme.eq_ignore_ascii_case("basic") {
+            let decoded = BASE64
+                .decode(credentials)
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok());
+            if let Some((username, password)) = decoded.as_deref().and_then(|d| d.split_once(':')) {
+                self.request.auth = AuthType::Basic {
+                    username: username.to_string(),
+                    password: password.to_string(),
+                };
+                return true;
+            }
+        }
+        false
+    }
+
+    fn finish(mut self) -> Result<CurlImport, String> {
+        let url = self
+            .url
+            .take()
+            .ok_or_else(|| "No URL found in the curl command".to_string())?;
+        // curl assumes http:// for a bare host.
+        self.request.url = if url.contains("://") {
+            url
+        } else {
+            format!("http://{}", url)
+        };
+
+        let mut has_content_type = false;
+        for header in std::mem::take(&mut self.headers) {
+            let (name, value) = match header.split_once(':') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                // `Name;` sends the header with an empty value.
+                None => match header.trim().strip_suffix(';') {
+                    Some(name) => (name, ""),
+                    None => {
+                        self.warnings
+                            .push(format!("Invalid header '{}' was ignored", header));
+                        continue;
+                    }
+                },
+            };
+            // `Name:` with nothing after it only stops curl sending that header.
+            if value.is_empty() && !header.trim_end().ends_with(';') {
+                continue;
+            }
+            if name.eq_ignore_ascii_case("authorization") && self.header_auth(value) {
+                continue;
+            }
+            has_content_type |= name.eq_ignore_ascii_case("content-type");
+            self.request
+                .headers
+                .push((name.to_string(), value.to_string()));
+        }
+
+        if let Some(user) = self.user.take() {
+            let (username, password) = split_credentials(&user);
+            if !user.contains(':') {
+                self.warnings.push(format!(
+                    "No password was given for '{}'; curl would have prompted for one",
+                    username
+                ));
+            }
+            self.request.auth = match self.auth_scheme {
+                HttpAuthScheme::Ntlm => {
+                    let (domain, username) = match username.split_once('\\') {
+                        Some((domain, user)) => (Some(domain.to_string()), user.to_string()),
+                        None => (None, username),
+                    };
+                    AuthType::Ntlm {
+                        username,
+                        password,
+                        domain,
+                    }
+                }
+                HttpAuthScheme::Negotiate => AuthType::Negotiate {
+                    username: Some(username).filter(|u| !u.is_empty()),
+                    password: Some(password).filter(|p| !p.is_empty()),
+                    delegate: self.delegate,
+                },
+                scheme => {
+                    if scheme == HttpAuthScheme::Digest {
+                        self.warnings
+                            .push("Digest auth isn't supported; Basic is used".to_string());
+                    }
+                    AuthType::Basic { username, password }
+                }
+            };
+        } else if self.auth_scheme == HttpAuthScheme::Negotiate {
+            self.request.auth = AuthType::Negotiate {
+                username: None,
+                password: None,
+                delegate: self.delegate,
+            };
+        }
+        if let Some(token) = self.bearer.take() {
+            self.request.auth = AuthType::Bearer { token };
+        }
+
+        if let Some(ClientCert::Pem {
+            cert_path,
+            key_password,
+            ..
+        }) = self.request.client_cert.clone()
+        {
+            if matches!(self.cert_type.as_deref(), Some("P12" | "PKCS12")) {
+                self.request.client_cert = Some(ClientCert::Pkcs12 {
+                    path: cert_path,
+                    password: key_password,
+                });
+            }
+        }
+        if self
+            .request
+            .proxy
+            .as_ref()
+            .is_some_and(|proxy| proxy.url.is_empty())
+        {
+            self.warnings
+                .push("Proxy settings without --proxy were ignored".to_string());
+            self.request.proxy = None;
+        }
+
+        let default_method = self.apply_body(has_content_type);
+        self.request.method = self.method.take().unwrap_or(default_method);
+        Ok(CurlImport {
+            request: self.request,
+            warnings: self.warnings,
+        })
+    }
+
+    /// Sets the body (or, with -G, the query) and returns the method curl
+    /// would use for it when none is given.
+    fn apply_body(&mut self, has_content_type: bool) -> Methods {
+        let mut text = Vec::new();
+        let mut file = None;
+        for part in std::mem::take(&mut self.data) {
+            match part {
+                DataPart::Text(part) => text.push(part),
+                DataPart::File { path, binary } => {
+                    if file.is_some() || self.get {
+                        self.warnings
+                            .push(format!("Data file '{}' was left out", path));
+                        continue;
+                    }
+                    if !binary {
+                        self.warnings.push(format!(
+                            "'{}' is sent as-is; curl -d would strip its newlines",
+                            path
+                        ));
+                    }
+                    file = Some(path);
+                }
+            }
+        }
+        if file.is_some() && !text.is_empty() {
+            self.warnings
+                .push("Inline data next to a data file was left out".to_string());
+        }
+
+        let form_type =
+            (!has_content_type).then(|| "application/x-www-form-urlencoded".to_string());
+        if self.get {
+            let query = text.join("&");
+            self.request.query_params.extend(
+                url::form_urlencoded::parse(query.as_bytes())
+                    .map(|(key, value)| (key.into_owned(), value.into_owned())),
+            );
+            return if self.head {
+                Methods::HEAD
+            } else {
+                Methods::GET
+            };
+        }
+        if !self.form.is_empty() {
+            if file.is_some() || !text.is_empty() {
+                self.warnings
+                    .push("-d data can't be combined with -F and was left out".to_string());
+            }
+            self.request.body = BodyType::Multipart {
+                fields: std::mem::take(&mut self.form),
+            };
+            return Methods::POST;
+        }
+        if let Some(path) = file {
+            self.request.body = BodyType::RawFile {
+                path,
+                content_type: form_type,
+            };
+            return Methods::POST;
+        }
+        if !text.is_empty() {
+            self.request.body = BodyType::Raw {
+                content: text.join("&"),
+                content_type: form_type,
+            };
+            return Methods::POST;
+        }
+        if let Some(path) = self.upload_file.take() {
+            self.request.body = BodyType::BinaryFile {
+                path,
+                content_type: None,
+            };
+            return Methods::PUT;
+        }
+        if self.head {
+            Methods::HEAD
+        } else {
+            Methods::GET
+        }
+    }
+}
+
+pub(crate) fn parse_command(command: &str) -> Result<CurlImport, String> {
+    let mut args = tokenize(command)?.into_iter().peekable();
+    // A leading `$ ` prompt, then the program name (possibly a path or curl.exe).
+    args.next_if(|arg| arg == "$");
+    if args.peek().is_some_and(|arg| {
+        let program = arg.rsplit(['/', '\\']).next().unwrap_or(arg);
+        program.eq_ignore_ascii_case("curl") || program.eq_ignore_ascii_case("curl.exe")
+    }) {
+        args.next();
+    }
+
+    let mut options = CurlOptions::new();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            options.set_url(arg);
+        } else if let Some(name) = arg.strip_prefix("--") {
+            let value = if VALUE_OPTIONS.contains(&name) {
+                Some(
+                    args.next()
+                        .ok_or_else(|| format!("Option --{} needs a value", name))?,
+                )
+            } else {
+                None
+            };
+            options.apply(name, value)?;
+        } else {
+            // Short options can be bundled (`-sSL`) and take their value from
+            // the rest of the argument (`-XPOST`) or the next one.
+            for (at, flag) in arg[1..].char_indices() {
+                let Some(name) = long_option(flag) else {
+                    options
+                        .warnings
+                        .push(format!("Unsupported option -{} was ignored", flag));
+                    continue;
+                };
+                if !VALUE_OPTIONS.contains(&name) {
+                    options.apply(name, None)?;
+                    continue;
+                }
+                let rest = &arg[1 + at + flag.len_utf8()..];
+                let value = if rest.is_empty() {
+                    args.next()
+                        .ok_or_else(|| format!("Option -{} needs a value", flag))?
+                } else {
+                    rest.to_string()
+                };
+                options.apply(name, Some(value))?;
+                break;
+            }
+        }
+    }
+    options.finish()
+}
+
+/// Read a `curl` command line into a request. Options that can't be carried
+/// over are reported in `warnings` instead of failing the import.
+#[tauri::command]
+#[specta::specta]
+pub fn parse_curl(command: String) -> Result<CurlImport, String> {
+    parse_command(&command)
+}
+
+/// Leaves plain words bare and single-quotes everything else, closing the
+/// quote around each embedded `'`.
+fn shell_quote(text: &str) -> String {
+    let plain = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if plain {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', r"'\''"))
+    }
+}
+
+/// Milliseconds as the seconds curl's timeout options take.
+fn seconds(ms: u32) -> String {
+    (ms as f64 / 1000.0).to_string()
+}
+
+/// A `-F` file path, double-quoted when `;` or `,` would end it early.
+fn form_path(path: &str) -> String {
+    if path.contains([';', ',', '"']) {
+        format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        path.to_string()
+    }
+}
+
+struct CurlWriter<'a> {
+    options: &'a CurlExportOptions,
+    args: Vec<String>,
+}
+
+impl CurlWriter<'_> {
+    fn flag(&mut self, short: Option<char>, long: &str) {
+        let flag = match short {
+            Some(short) if !self.options.long_flags => format!("-{}", short),
+            _ => format!("--{}", long),
+        };
+        self.args.push(flag);
+    }
+
+    fn option(&mut self, short: Option<char>, long: &str, value: &str) {
+        self.flag(short, long);
+        if let Some(flag) = self.args.last_mut() {
+            flag.push(' ');
+            flag.push_str(&shell_quote(value));
+        }
+    }
+
+    fn header(&mut self, name: &str, value: &str) {
+        self.option(Some('H'), "header", &format!("{}: {}", name, value));
+    }
+
+    fn secret<'s>(&self, value: &'s str, placeholder: &'s str) -> &'s str {
+        if self.options.redact_auth {
+            placeholder
+        } else {
+            value
+        }
+    }
+
+    fn finish(self) -> String {
+        let separator = if self.options.multiline {
+            " \\\n  "
+        } else {
+            " "
+        };
+        std::iter::once("curl".to_string())
+            .chain(self.args)
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+}
+
+fn write_auth(out: &mut CurlWriter, auth: &AuthType) -> Result<(), String> {
+    match auth {
+        AuthType::None | AuthType::ApiKey { .. } => {}
+        AuthType::Basic { username, password } => {
+            let password = out.secret(password, "<password>");
+            out.option(Some('u'), "user", &format!("{}:{}", username, password));
+        }
+        AuthType::Bearer { token }
+        | AuthType::OAuth2 {
+            access_token: token,
+        } => {
+            let token = out.secret(token, "<token>");
+            out.header("Authorization", &format!("Bearer {}", token));
+        }
+        AuthType::Jwt {
+            algorithm,
+            secret_or_key_pem,
+            claims_json,
+            header_overrides,
+            expires_in_secs,
+        } => {
+            let token = if out.options.redact_auth {
+                "<jwt>".to_string()
+            } else {
+                sign_jwt(
+                    algorithm,
+                    secret_or_key_pem,
+                    claims_json,
+                    header_overrides.as_deref(),
+                    *expires_in_secs,
+                )?
+            };
+            out.header("Authorization", &format!("Bearer {}", token));
+        }
+        AuthType::Ntlm {
+            username,
+            password,
+            domain,
+        } => {
+            out.flag(None, "ntlm");
+            let user = match domain {
+                Some(domain) => format!("{}\\{}", domain, username),
+                None => username.clone(),
+            };
+            let password = out.secret(password, "<password>");
+            out.option(Some('u'), "user", &format!("{}:{}", user, password));
+        }
+        AuthType::Negotiate {
+            username,
+            password,
+            delegate,
+        } => {
+            out.flag(None, "negotiate");
+            // An empty user makes curl use the ticket cache.
+            let password = out.secret(password.as_deref().unwrap_or_default(), "<password>");
+            let user = username.as_deref().unwrap_or_default();
+            out.option(Some('u'), "user", &format!("{}:{}", user, password));
+            if *delegate {
+                out.option(None, "delegation", "always");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `a=<value>; b=<value>`: the cookie names in a `Cookie` header without their values.
+fn redact_cookies(header: &str) -> String {
+    header
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) => format!("{}=<value>", name.trim()),
+            None => "<value>".to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn write_body(out: &mut CurlWriter, req: &ApiRequest) -> Result<(), String> {
+    let has_content_type = req
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+    let json_type = |out: &mut CurlWriter| {
+        if !has_content_type {
+            out.header("Content-Type", "application/json");
+        }
+    };
+    match &req.body {
+        BodyType::None => {}
+        BodyType::Raw {
+            content,
+            content_type,
+        } => {
+            if let Some(content_type) = content_type.as_ref().filter(|_| !has_content_type) {
+                out.header("Content-Type", content_type);
+            }
+            out.option(None, "data-raw", content);
+        }
+        BodyType::FormUrlEncoded { fields } => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort();
+            for (name, value) in fields {
+                out.option(
+                    None,
+                    "data-urlencode",
+                    &format!("{}={}", url_escape(name), value),
+                );
+            }
+        }
+        BodyType::Multipart { fields } => {
+            for field in fields {
+                match &field.value {
+                    // -F would read `@`, `<` and `;type=` in the value.
+                    MultipartValue::Text(text) => {
+                        let value = format!("{}={}", field.name, text);
+                        if text.starts_with(['@', '<']) || text.contains(';') {
+                            out.option(None, "form-string", &value);
+                        } else {
+                            out.option(Some('F'), "form", &value);
+                        }
+                    }
+                    MultipartValue::File {
+                        filename,
+                        content_type,
+                        ..
+                    } => {
+                        // The contents aren't on disk; the part names the file to supply.
+                        let mut value = format!("{}=@{}", field.name, form_path(filename));
+                        if let Some(content_type) = content_type {
+                            value.push_str(&format!(";type={}", content_type));
+                        }
+                        out.option(Some('F'), "form", &value);
+                    }
+                    MultipartValue::FilePath {
+                        path,
+                        filename,
+                        content_type,
+                    } => {
+                        let mut value = format!("{}=@{}", field.name, form_path(path));
+                        if let Some(filename) = filename {
+                            value.push_str(&format!(";filename={}", form_path(filename)));
+                        }
+                        if let Some(content_type) = content_type {
+                            value.push_str(&format!(";type={}", content_type));
+                        }
+                        out.option(Some('F'), "form", &value);
+                    }
+                }
+            }
+        }
+        BodyType::Binary { data, filename } => {
+            if !has_content_type {
+                out.header("Content-Type", "application/octet-stream");
+            }
+            match std::str::from_utf8(data) {
+                Ok(text) => out.option(None, "data-raw", text),
+                Err(_) => {
+                    let file = filename.as_deref().unwrap_or("body.bin");
+                    out.option(None, "data-binary", &format!("@{}", file));
+                }
+            }
+        }
+        BodyType::RawFile { path, content_type } => {
+            let content_type = content_type
+                .as_deref()
+                .unwrap_or("application/octet-stream");
+            if !has_content_type {
+                out.header("Content-Type", content_type);
+            }
+            out.option(None, "data-binary", &format!("@{}", path));
+        }
+        BodyType::BinaryFile { path, content_type } => {
+            let content_type = content_type
+                .as_deref()
+                .unwrap_or_else(|| mime_from_extension(Path::new(path)));
+            if !has_content_type {
+                out.header("Content-Type", content_type);
+            }
+            out.option(None, "data-binary", &format!("@{}", path));
+        }
+        BodyType::GraphQL {
+            query,
+            variables,
+            operation_name,
+        } => {
+            let envelope =
+                graphql_envelope(query, variables.as_deref(), operation_name.as_deref())?;
+            json_type(out);
+            out.option(None, "data-raw", &envelope);
+        }
+        BodyType::Soap {
+            action,
+            envelope_body,
+            soap_version,
+        } => {
+            let envelope = soap_envelope(envelope_body, *soap_version)?;
+            let (content_type, soap_action) = soap_headers(action, *soap_version);
+            let has_soap_action = req
+                .headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("soapaction"));
+            if let Some(soap_action) = soap_action.filter(|_| !has_soap_action) {
+                out.header("SOAPAction", &soap_action);
+            }
+            if !has_content_type {
+                out.header("Content-Type", &content_type);
+            }
+            out.option(None, "data-raw", &envelope);
+        }
+        BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } => {
+            let envelope = jsonrpc_body(&req.body)?;
+            let envelope = serde_json::to_string(&envelope).map_err(|e| e.to_string())?;
+            json_type(out);
+            out.option(None, "data-raw", &envelope);
+        }
+    }
+    Ok(())
+}
+
+fn write_connection(out: &mut CurlWriter, req: &ApiRequest) {
+    if req.verify_ssl == Some(false) {
+        out.flag(Some('k'), "insecure");
+    }
+    if req.follow_redirects.unwrap_or(true) {
+        out.flag(Some('L'), "location");
+        if let Some(max) = req.max_redirects {
+            out.option(None, "max-redirs", &max.to_string());
+        }
+    }
+    if let Some(timeout) = req.connect_timeout_ms.filter(|&t| t > 0) {
+        out.option(None, "connect-timeout", &seconds(timeout));
+    }
+    if let Some(timeout) = req.timeout_ms.filter(|&t| t > 0) {
+        out.option(Some('m'), "max-time", &seconds(timeout));
+    }
+    match req.protocol {
+        Some(HttpProtocol::Http1_1) => out.flag(None, "http1.1"),
+        Some(HttpProtocol::Http2) => out.flag(None, "http2"),
+        Some(HttpProtocol::Http2PriorKnowledge) => out.flag(None, "http2-prior-knowledge"),
+        Some(HttpProtocol::Quic) => out.flag(None, "http3"),
+        Some(HttpProtocol::Tcp | HttpProtocol::Auto) | None => {}
+    }
+    match req.ip_version {
+        Some(IpVersion::V4Only) => out.flag(Some('4'), "ipv4"),
+        Some(IpVersion::V6Only) => out.flag(Some('6'), "ipv6"),
+        Some(IpVersion::Any) | None => {}
+    }
+
+    if let Some(ref proxy) = req.proxy {
+        let scheme = match proxy.proxy_type {
+            ProxyKind::Http => "http",
+            ProxyKind::Https => "https",
+            ProxyKind::Socks4 => "socks4",
+            ProxyKind::Socks5 => "socks5",
+            ProxyKind::Socks5Hostname => "socks5h",
+        };
+        let url = if proxy.url.contains("://") {
+            proxy.url.clone()
+        } else {
+            format!("{}://{}", scheme, proxy.url)
+        };
+        out.option(Some('x'), "proxy", &url);
+        if let Some(ref username) = proxy.username {
+            let password = out.secret(proxy.password.as_deref().unwrap_or_default(), "<password>");
+            out.option(
+                Some('U'),
+                "proxy-user",
+                &format!("{}:{}", username, password),
+            );
+        }
+        match proxy.auth_scheme {
+            Some(ProxyAuthScheme::Digest) => out.flag(None, "proxy-digest"),
+            Some(ProxyAuthScheme::Ntlm) => out.flag(None, "proxy-ntlm"),
+            Some(ProxyAuthScheme::Negotiate) => out.flag(None, "proxy-negotiate"),
+            Some(ProxyAuthScheme::Any) => out.flag(None, "proxy-anyauth"),
+            Some(ProxyAuthScheme::Basic) | None => {}
+        }
+        if let Some(ref no_proxy) = proxy.no_proxy {
+            out.option(None, "noproxy", no_proxy);
+        }
+    }
+
+    if let Some(ref socket) = req.unix_socket {
+        out.option(None, "unix-socket", socket);
+    }
+    for entry in &req.resolve_overrides {
+        let address = if entry.address.contains(':') {
+            format!("[{}]", entry.address)
+        } else {
+            entry.address.clone()
+        };
+        out.option(
+            None,
+            "resolve",
+            &format!("{}:{}:{}", entry.host, entry.port, address),
+        );
+    }
+    if let Some(ref interface) = req.local_interface {
+        out.option(None, "interface", interface);
+    }
+    if let Some(ref path) = req.ca_cert_path {
+        out.option(None, "cacert", path);
+    }
+    match req.client_cert {
+        Some(ClientCert::Pem {
+            ref cert_path,
+            ref key_path,
+            ref key_password,
+        }) => {
+            out.option(Some('E'), "cert", cert_path);
+            if let Some(key_path) = key_path {
+                out.option(None, "key", key_path);
+            }
+            if let Some(password) = key_password {
+                let password = out.secret(password, "<password>");
+                out.option(None, "pass", password);
+            }
+        }
+        Some(ClientCert::Pkcs12 {
+            ref path,
+            ref password,
+        }) => {
+            out.option(Some('E'), "cert", path);
+            out.option(None, "cert-type", "P12");
+            if let Some(password) = password {
+                let password = out.secret(password, "<password>");
+                out.option(None, "pass", password);
+            }
+        }
+        None => {}
+    }
+    if let Some(ref pins) = req.pinned_public_key {
+        out.option(None, "pinnedpubkey", pins);
+    }
+    if let Some(ref ciphers) = req.tls_ciphers {
+        out.option(None, "ciphers", ciphers);
+    }
+    if let Some(ref ciphers) = req.tls13_ciphers {
+        out.option(None, "tls13-ciphers", ciphers);
+    }
+    match req.use_netrc {
+        Some(NetrcMode::Required) => out.flag(Some('n'), "netrc"),
+        Some(NetrcMode::Optional) => out.flag(None, "netrc-optional"),
+        Some(NetrcMode::Ignored) | None => {}
+    }
+    if let Some(ref file) = req.netrc_file {
+        out.option(None, "netrc-file", file);
+    }
+}
+
+/// Write a request as a `curl` command line. Files referenced by the body are
+/// passed as `@path`; in-memory file parts name their file instead.
+#[tauri::command]
+#[specta::specta]
+pub fn to_curl(mut req: ApiRequest, options: CurlExportOptions) -> Result<String, String> {
+    if options.share_safe {
+        req.clear_local_tls_settings();
+    }
+    let mut out = CurlWriter {
+        options: &options,
+        args: Vec::new(),
+    };
+
+    // Implied by the body flags for POST, and -X HEAD would wait for a body.
+    let has_body = !matches!(req.body, BodyType::None);
+    match req.method {
+        Methods::HEAD => out.flag(Some('I'), "head"),
+        Methods::GET if !has_body => {}
+        Methods::POST if has_body => {}
+        ref method => out.option(Some('X'), "request", method_to_curl_string(method)),
+    }
+
+    let api_key_query = match req.auth {
+        AuthType::ApiKey {
+            ref key,
+            ref value,
+            add_to: ApiKeyLocation::Query,
+        } => Some((key.as_str(), out.secret(value, "<api-key>"))),
+        _ => None,
+    };
+    let url = if req.query_params.is_empty() && api_key_query.is_none() {
+        req.url.clone()
+    } else {
+        build_url_with_params(&req.url, &req.query_params, api_key_query)?
+    };
+    out.args.push(shell_quote(&url));
+
+    for (name, value) in &req.headers {
+        let value = if !out.options.redact_auth {
+            value.clone()
+        } else if name.eq_ignore_ascii_case("authorization")
+            || name.eq_ignore_ascii_case("proxy-authorization")
+        {
+            match value.trim().split_once(' ') {
+                Some((scheme, _)) => format!("{} <token>", scheme),
+                None => "<token>".to_string(),
+            }
+        } else if name.eq_ignore_ascii_case("cookie") {
+            redact_cookies(value)
+        } else {
+            value.clone()
+        };
+        out.header(name, &value);
+    }
+    write_auth(&mut out, &req.auth)?;
+    if let AuthType::ApiKey {
+        ref key,
+        ref value,
+        add_to: ApiKeyLocation::Header,
+    } = req.auth
+    {
+        let value = out.secret(value, "<api-key>");
+        out.header(key, value);
+    }
+    if req.disable_expect_continue.unwrap_or(false) {
+        out.option(Some('H'), "header", "Expect:");
+    }
+
+    let mut cookies: Vec<String> = req
+        .cookies
+        .iter()
+        .map(|cookie| format!("{}={}", cookie.name, out.secret(&cookie.value, "<value>")))
+        .collect();
+    if let AuthType::ApiKey {
+        ref key,
+        ref value,
+        add_to: ApiKeyLocation::Cookie,
+    } = req.auth
+    {
+        cookies.push(format!("{}={}", key, out.secret(value, "<api-key>")));
+    }
+    if !cookies.is_empty() {
+        out.option(Some('b'), "cookie", &cookies.join("; "));
+    }
+
+    write_body(&mut out, &req)?;
+    write_connection(&mut out, &req);
+    Ok(out.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn parse(command: &str) -> CurlImport {
+        parse_command(command).unwrap()
+    }
+
+    fn to_json<T: serde::Serialize>(value: &T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn chrome_copy_as_curl_get() {
+        let import = parse(
+            r#"curl 'https://api.github.com/repos/rust-lang/rust/issues?state=open' \
+  -H 'accept: application/vnd.github+json' \
+  -H 'authorization: Bearer ghp_abc123' \
+  -H 'user-agent: Mozilla/5.0 (X11; Linux x86_64)' \
+  --compressed"#,
+        );
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "GET");
+        assert_eq!(
+            req.url,
+            "https://api.github.com/repos/rust-lang/rust/issues?state=open"
+        );
+        assert_eq!(
+            to_json(&req.headers),
+            json!([
+                ["accept", "application/vnd.github+json"],
+                ["user-agent", "Mozilla/5.0 (X11; Linux x86_64)"],
+            ])
+        );
+        assert_eq!(
+            to_json(&req.auth),
+            json!({ "Bearer": { "token": "ghp_abc123" } })
+        );
+        assert_eq!(req.follow_redirects, Some(false));
+        assert!(import.warnings.is_empty());
+    }
+
+    #[test]
+    fn chrome_copy_as_curl_post_with_ansi_c_quoting() {
+        let import = parse(
+            r#"curl 'https://example.com/api/notes' \
+  -H 'content-type: application/json' \
+  -b 'session=abc; theme=dark' \
+  --data-raw $'{"text":"it\'s\\nmultiline"}'"#,
+        );
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "POST");
+        assert_eq!(
+            to_json(&req.body),
+            json!({ "Raw": {
+                "content": "{\"text\":\"it's\\nmultiline\"}",
+                "content_type": null,
+            } })
+        );
+        assert_eq!(
+            to_json(&req.headers),
+            json!([["content-type", "application/json"]])
+        );
+        let cookies: Vec<_> = req
+            .cookies
+            .iter()
+            .map(|c| (c.name.as_str(), c.value.as_str()))
+            .collect();
+        assert_eq!(cookies, [("session", "abc"), ("theme", "dark")]);
+    }
+
+    #[test]
+    fn stripe_docs_form_post_with_api_key_user() {
+        let import = parse(
+            r#"curl https://api.stripe.com/v1/charges \
+  -u sk_test_4eC39HqLyjWDarjtT1zdp7dc: \
+  -d amount=2000 \
+  -d currency=usd \
+  -d "description=My First Test Charge""#,
+        );
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "POST");
+        assert_eq!(
+            to_json(&req.auth),
+            json!({ "Basic": { "username": "sk_test_4eC39HqLyjWDarjtT1zdp7dc", "password": "" } })
+        );
+        assert_eq!(
+            to_json(&req.body),
+            json!({ "Raw": {
+                "content": "amount=2000&currency=usd&description=My First Test Charge",
+                "content_type": "application/x-www-form-urlencoded",
+            } })
+        );
+        assert!(import.warnings.is_empty());
+    }
+
+    #[test]
+    fn github_docs_explicit_method_and_redirects() {
+        let import = parse(
+            r#"curl -L \
+  -X POST \
+  -H "Accept: application/vnd.github+json" \
+  -H "Authorization: Bearer <YOUR-TOKEN>" \
+  -H "X-GitHub-Api-Version: 2022-11-28" \
+  https://api.github.com/repos/OWNER/REPO/issues \
+  -d '{"title":"Found a bug","labels":["bug"]}'"#,
+        );
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "POST");
+        assert_eq!(req.follow_redirects, Some(true));
+        assert_eq!(
+            to_json(&req.auth),
+            json!({ "Bearer": { "token": "<YOUR-TOKEN>" } })
+        );
+        assert_eq!(req.headers.len(), 2);
+        assert_eq!(
+            to_json(&req.body)["Raw"]["content"],
+            "{\"title\":\"Found a bug\",\"labels\":[\"bug\"]}"
+        );
+    }
+
+    #[test]
+    fn multipart_upload_with_file_type() {
+        let import = parse(
+            r#"curl -F "file=@photos/cat.jpg;type=image/jpeg" -F 'caption=hello' https://upload.example.com/v1/files"#,
+        );
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "POST");
+        assert_eq!(
+            to_json(&req.body),
+            json!({ "Multipart": { "fields": [
+                { "name": "file", "value": { "FilePath": {
+                    "path": "photos/cat.jpg",
+                    "filename": null,
+                    "content_type": "image/jpeg",
+                } } },
+                { "name": "caption", "value": { "Text": "hello" } },
+            ] } })
+        );
+    }
+
+    #[test]
+    fn get_with_urlencoded_data_becomes_query_params() {
+        let import = parse(
+            r#"curl -G https://api.example.com/search --data-urlencode "q=hello world" -d limit=5"#,
+        );
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "GET");
+        assert_eq!(
+            to_json(&req.query_params),
+            json!([["q", "hello world"], ["limit", "5"]])
+        );
+        assert_eq!(to_json(&req.body), json!("None"));
+    }
+
+    #[test]
+    fn bundled_short_flags_and_attached_values() {
+        let import = parse("curl -sSLk -XDELETE -m 30 https://example.com/items/1");
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "DELETE");
+        assert_eq!(req.follow_redirects, Some(true));
+        assert_eq!(req.verify_ssl, Some(false));
+        assert_eq!(req.timeout_ms, Some(30_000));
+        assert!(import.warnings.is_empty());
+    }
+
+    #[test]
+    fn json_option_sets_body_and_headers() {
+        let import = parse(r#"$ curl --json '{"name":"mandy"}' localhost:8080/users"#);
+        let req = import.request;
+        assert_eq!(method_to_curl_string(&req.method), "POST");
+        assert_eq!(req.url, "http://localhost:8080/users");
+        assert_eq!(
+            to_json(&req.headers),
+            json!([
+                ["Content-Type", "application/json"],
+                ["Accept", "application/json"],
+            ])
+        );
+        assert_eq!(to_json(&req.body)["Raw"]["content"], "{\"name\":\"mandy\"}");
+    }
+
+    #[test]
+    fn windows_curl_exe_with_basic_auth_header() {
+        let import = parse(
+            r#"curl.exe -H "Authorization: Basic dXNlcjpwYXNz" https://intranet.example.com/"#,
+        );
+        assert_eq!(
+            to_json(&import.request.auth),
+            json!({ "Basic": { "username": "user", "password": "pass" } })
+        );
+        assert!(import.request.headers.is_empty());
+    }
+
+    #[test]
+    fn unsupported_options_become_warnings() {
+        let import = parse("curl --retry 3 --tcp-fastopen https://example.com/");
+        assert_eq!(import.request.url, "https://example.com/");
+        assert_eq!(import.warnings.len(), 2);
+    }
+
+    #[test]
+    fn command_without_url_is_an_error() {
+        assert!(parse_command("curl -H 'Accept: */*'").is_err());
+        assert!(parse_command("curl -H").is_err());
+    }
+
+    /// `req` written by `to_curl` in every layout and read back by `parse_curl`.
+    fn round_trips(req: &ApiRequest) -> Vec<ApiRequest> {
+        [(false, false), (true, false), (false, true), (true, true)]
+            .into_iter()
+            .map(|(multiline, long_flags)| {
+                let options = CurlExportOptions {
+                    multiline,
+                    long_flags,
+                    redact_auth: false,
+                    share_safe: false,
+                };
+                let command = to_curl(req.clone(), options).unwrap();
+                let import = parse_curl(command.clone()).unwrap();
+                assert!(
+                    import.warnings.is_empty(),
+                    "{command}: {:?}",
+                    import.warnings
+                );
+                import.request
+            })
+            .collect()
+    }
+
+    #[test]
+    fn json_request_survives_export_and_import() {
+        let req = ApiRequest {
+            method: Methods::PATCH,
+            url: "https://api.example.com/users/42".to_string(),
+            query_params: vec![
+                ("fields".to_string(), "name,email".to_string()),
+                ("tag".to_string(), "a b".to_string()),
+                ("tag".to_string(), "c&d".to_string()),
+            ],
+            headers: vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("X-Trace".to_string(), "it's \"quoted\"".to_string()),
+            ],
+            body: BodyType::Raw {
+                content: "{\"name\": \"O'Brien\",\n \"age\": 7}".to_string(),
+                content_type: Some("application/json".to_string()),
+            },
+            auth: AuthType::Bearer {
+                token: "tok$en".to_string(),
+            },
+            timeout_ms: Some(2_500),
+            connect_timeout_ms: Some(1_000),
+            max_redirects: Some(3),
+            verify_ssl: Some(false),
+            protocol: Some(HttpProtocol::Http1_1),
+            ..Default::default()
+        };
+        let url = build_url_with_params(&req.url, &req.query_params, None).unwrap();
+
+        for back in round_trips(&req) {
+            assert_eq!(method_to_curl_string(&back.method), "PATCH");
+            // The query is folded into the URL, which sends the same request line.
+            assert_eq!(back.url, url);
+            assert!(back.query_params.is_empty());
+            // The body's content type comes back as the header it is sent as.
+            let mut headers = req.headers.clone();
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            assert_eq!(back.headers, headers);
+            assert_eq!(
+                to_json(&back.body),
+                json!({ "Raw": {
+                    "content": "{\"name\": \"O'Brien\",\n \"age\": 7}",
+                    "content_type": null,
+                } })
+            );
+            assert_eq!(to_json(&back.auth), to_json(&req.auth));
+            assert_eq!(back.timeout_ms, req.timeout_ms);
+            assert_eq!(back.connect_timeout_ms, req.connect_timeout_ms);
+            assert_eq!(back.follow_redirects, Some(true));
+            assert_eq!(back.max_redirects, Some(3));
+            assert_eq!(back.verify_ssl, Some(false));
+            assert!(matches!(back.protocol, Some(HttpProtocol::Http1_1)));
+        }
+    }
+
+    #[test]
+    fn multipart_and_basic_auth_survive_export_and_import() {
+        let req = ApiRequest {
+            method: Methods::POST,
+            url: "https://upload.example.com/files".to_string(),
+            body: BodyType::Multipart {
+                fields: vec![
+                    MultipartField {
+                        name: "doc".to_string(),
+                        value: MultipartValue::FilePath {
+                            path: "reports/q1; final.pdf".to_string(),
+                            filename: Some("q1.pdf".to_string()),
+                            content_type: Some("application/pdf".to_string()),
+                        },
+                    },
+                    MultipartField {
+                        name: "note".to_string(),
+                        value: MultipartValue::Text("@not-a-file; really".to_string()),
+                    },
+                ],
+            },
+            auth: AuthType::Basic {
+                username: "ana".to_string(),
+                password: "p:ss word".to_string(),
+            },
+            cookies: vec![Cookie {
+                name: "sid".to_string(),
+                value: "abc".to_string(),
+                domain: None,
+                path: None,
+                expires: None,
+                expires_at: None,
+                is_expired: None,
+                http_only: None,
+                secure: None,
+                max_age: None,
+                same_site: None,
+            }],
+            follow_redirects: Some(false),
+            ..Default::default()
+        };
+
+        for back in round_trips(&req) {
+            assert_eq!(method_to_curl_string(&back.method), "POST");
+            assert_eq!(to_json(&back.body), to_json(&req.body));
+            assert_eq!(to_json(&back.auth), to_json(&req.auth));
+            assert_eq!(to_json(&back.cookies), to_json(&req.cookies));
+            assert_eq!(back.follow_redirects, Some(false));
+        }
+    }
+
+    #[test]
+    fn form_fields_are_sent_unchanged_after_a_round_trip() {
+        let fields = [("q", "rust & curl"), ("page", "2"), ("emoji", "✓")];
+        let req = ApiRequest {
+            method: Methods::POST,
+            url: "https://example.com/search".to_string(),
+            body: BodyType::FormUrlEncoded {
+                fields: fields
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            },
+            ..Default::default()
+        };
+
+        for back in round_trips(&req) {
+            let BodyType::Raw {
+                content,
+                content_type,
+            } = &back.body
+            else {
+                panic!("expected a raw body");
+            };
+            assert_eq!(
+                content_type.as_deref(),
+                Some("application/x-www-form-urlencoded")
+            );
+            let mut sent: Vec<_> = url::form_urlencoded::parse(content.as_bytes())
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect();
+            sent.sort();
+            let mut expected: Vec<_> = fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            expected.sort();
+            assert_eq!(sent, expected);
+        }
+    }
+
+    #[test]
+    fn connection_settings_survive_export_and_import() {
+        let req = ApiRequest {
+            method: Methods::HEAD,
+            url: "http://localhost/health".to_string(),
+            proxy: Some(ProxyConfig {
+                url: "proxy.internal:3128".to_string(),
+                username: Some("svc".to_string()),
+                password: Some("secret".to_string()),
+                proxy_type: ProxyKind::Socks5Hostname,
+                no_proxy: Some("localhost,.corp".to_string()),
+                auth_scheme: None,
+            }),
+            unix_socket: Some("/run/app.sock".to_string()),
+            resolve_overrides: vec![ResolveOverride {
+                host: "api.test".to_string(),
+                port: 443,
+                address: "::1".to_string(),
+            }],
+            client_cert: Some(ClientCert::Pkcs12 {
+                path: "certs/me.p12".to_string(),
+                password: Some("pw".to_string()),
+            }),
+            ca_cert_path: Some("certs/ca.pem".to_string()),
+            ip_version: Some(IpVersion::V4Only),
+            use_netrc: Some(NetrcMode::Optional),
+            tls_ciphers: Some("ECDHE-RSA-AES128-GCM-SHA256".to_string()),
+            tls13_ciphers: Some("TLS_AES_128_GCM_SHA256".to_string()),
+            ..Default::default()
+        };
+
+        for back in round_trips(&req) {
+            assert_eq!(method_to_curl_string(&back.method), "HEAD");
+            let proxy = back.proxy.as_ref().unwrap();
+            assert_eq!(proxy.url, "socks5h://proxy.internal:3128");
+            assert!(matches!(proxy.proxy_type, ProxyKind::Socks5Hostname));
+            assert_eq!(proxy.username.as_deref(), Some("svc"));
+            assert_eq!(proxy.password.as_deref(), Some("secret"));
+            assert_eq!(proxy.no_proxy, req.proxy.as_ref().unwrap().no_proxy);
+            assert_eq!(back.unix_socket, req.unix_socket);
+            assert_eq!(
+                to_json(&back.resolve_overrides),
+                to_json(&req.resolve_overrides)
+            );
+            assert_eq!(to_json(&back.client_cert), to_json(&req.client_cert));
+            assert_eq!(back.ca_cert_path, req.ca_cert_path);
+            assert!(matches!(back.ip_version, Some(IpVersion::V4Only)));
+            assert!(matches!(back.use_netrc, Some(NetrcMode::Optional)));
+            assert_eq!(back.tls_ciphers, req.tls_ciphers);
+            assert_eq!(back.tls13_ciphers, req.tls13_ciphers);
+        }
+    }
+
+    #[test]
+    fn redacted_export_hides_header_credentials() {
+        let req = ApiRequest {
+            url: "https://example.com/".to_string(),
+            headers: vec![
+                ("Authorization".to_string(), "Bearer abc123".to_string()),
+                ("Proxy-Authorization".to_string(), "s3cr3t".to_string()),
+                ("Cookie".to_string(), "session=xyz; theme=dark".to_string()),
+                ("Accept".to_string(), "text/plain".to_string()),
+            ],
+            ..Default::default()
+        };
+        let options = CurlExportOptions {
+            redact_auth: true,
+            ..Default::default()
+        };
+        let command = to_curl(req, options).unwrap();
+        for secret in ["abc123", "s3cr3t", "xyz", "dark"] {
+            assert!(!command.contains(secret), "{command}");
+        }
+        assert!(
+            command.contains("Authorization: Bearer <token>"),
+            "{command}"
+        );
+        assert!(
+            command.contains("Proxy-Authorization: <token>"),
+            "{command}"
+        );
+        assert!(
+            command.contains("Cookie: session=<value>; theme=<value>"),
+            "{command}"
+        );
+        assert!(command.contains("Accept: text/plain"), "{command}");
+    }
+
+    #[test]
+    fn request_content_type_is_not_sent_twice() {
+        let bodies = [
+            BodyType::Raw {
+                content: "{}".to_string(),
+                content_type: Some("application/json".to_string()),
+            },
+            BodyType::Binary {
+                data: b"data".to_vec(),
+                filename: None,
+            },
+            BodyType::RawFile {
+                path: "body.txt".to_string(),
+                content_type: None,
+            },
+            BodyType::BinaryFile {
+                path: "image.png".to_string(),
+                content_type: None,
+            },
+        ];
+        for body in bodies {
+            let req = ApiRequest {
+                method: Methods::POST,
+                url: "https://example.com/".to_string(),
+                headers: vec![("content-type".to_string(), "text/x-custom".to_string())],
+                body,
+                ..Default::default()
+            };
+            let command = to_curl(req, CurlExportOptions::default()).unwrap();
+            assert_eq!(
+                command.to_lowercase().matches("content-type").count(),
+                1,
+                "{}",
+                command
+            );
+        }
+    }
+
+    #[test]
+    fn share_safe_export_leaves_out_cipher_lists() {
+        let req = ApiRequest {
+            url: "https://example.com/".to_string(),
+            tls_ciphers: Some("ECDHE-RSA-AES128-GCM-SHA256".to_string()),
+            tls13_ciphers: Some("TLS_AES_128_GCM_SHA256".to_string()),
+            ..Default::default()
+        };
+        let options = CurlExportOptions {
+            share_safe: true,
+            ..Default::default()
+        };
+        let command = to_curl(req, options).unwrap();
+        assert!(!command.contains("ciphers"), "{}", command);
+        assert!(!command.contains("TLS_AES"), "{}", command);
+    }
+}
@@ -0,0 +1,150 @@
+//! `.env` files read into an environment: `KEY=VALUE` lines, optionally
+//! prefixed with `export`, with single, double or backtick quoted values that
+//! may span lines. `${VAR}` references are kept as written.
+
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::environments::merge_into_environment;
+use crate::types::{DotenvImport, EnvironmentVariable};
+
+/// Words in a key that mark its value as a secret.
+const SECRET_WORDS: [&str; 4] = ["SECRET", "TOKEN", "PASSWORD", "KEY"];
+
+fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    SECRET_WORDS.iter().any(|word| key.contains(word))
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// A quoted value starting after the opening quote, and what follows the
+/// closing one. Only double quotes take escapes. `None` when unterminated.
+fn quoted(text: &str, quote: char) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((index, c)) = chars.next() {
+        if c == quote {
+            return Some((value, &text[index + c.len_utf8()..]));
+        }
+        if c == '\\' && quote == '"' {
+            match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                escaped @ ('"' | '\\' | '$') => value.push(escaped),
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            }
+        } else {
+            value.push(c);
+        }
+    }
+    None
+}
+
+/// An unquoted value ends at a ` #` comment and has its spaces trimmed.
+fn unquoted(text: &str) -> String {
+    let end = text
+        .char_indices()
+        .find(|&(index, c)| c == '#' && text[..index].ends_with([' ', '\t']))
+        .map_or(text.len(), |(index, _)| index);
+    text[..end].trim().to_string()
+}
+
+/// The file's assignments in order, and how many lines weren't one.
+fn parse_dotenv(content: &str) -> (Vec<(String, String)>, u32) {
+    let content = content.replace("\r\n", "\n");
+    let mut variables = Vec::new();
+    let mut ignored_lines = 0;
+    let mut rest = content.as_str();
+    while !rest.is_empty() {
+        let line = rest.trim_start_matches([' ', '\t']);
+        let (first, next) = line.split_once('\n').unwrap_or((line, ""));
+        rest = next;
+        if first.trim().is_empty() || first.starts_with('#') {
+            continue;
+        }
+        let line = line
+            .strip_prefix("export")
+            .filter(|line| line.starts_with([' ', '\t']))
+            .map_or(line, |line| line.trim_start_matches([' ', '\t']));
+        let Some(eq) = line
+            .find('=')
+            .filter(|&eq| eq < line.find('\n').unwrap_or(line.len()))
+        else {
+            ignored_lines += 1;
+            continue;
+        };
+        let key = line[..eq].trim();
+        if !is_key(key) {
+            ignored_lines += 1;
+            continue;
+        }
+
+        let value = line[eq + 1..].trim_start_matches([' ', '\t']);
+        match value.chars().next() {
+            Some(quote @ ('"' | '\'' | '`')) => match quoted(&value[1..], quote) {
+                Some((text, after)) => {
+                    // Anything after the closing quote, like a comment, is dropped.
+                    rest = after.split_once('\n').map_or("", |(_, next)| next);
+                    variables.push((key.to_string(), text));
+                }
+                None => ignored_lines += 1,
+            },
+            _ => {
+                let text = value.split_once('\n').map_or(value, |(text, _)| text);
+                variables.push((key.to_string(), unquoted(text)));
+            }
+        }
+    }
+    (variables, ignored_lines)
+}
+
+/// Read a `.env` file into the named environment, creating it when there is
+/// none. Keys naming a secret, token, password or key are marked secret and
+/// their values kept in the keychain; a key set twice in the file takes its
+/// last value.
+#[tauri::command]
+#[specta::specta]
+pub fn import_dotenv(
+    app: AppHandle,
+    path: String,
+    environment_name: String,
+) -> Result<DotenvImport, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let (parsed, ignored_lines) = parse_dotenv(&content);
+
+    let mut variables: Vec<EnvironmentVariable> = Vec::new();
+    for (key, value) in parsed {
+        match variables.iter_mut().find(|variable| variable.key == key) {
+            Some(variable) => variable.value = value,
+            None => variables.push(EnvironmentVariable {
+                is_secret: is_secret(&key),
+                key,
+                value,
+            }),
+        }
+    }
+    let imported = variables.len() as u32;
+    let overwritten = merge_into_environment(&app, &environment_name, variables)?;
+
+    Ok(DotenvImport {
+        imported,
+        overwritten,
+        ignored_lines,
+    })
+}
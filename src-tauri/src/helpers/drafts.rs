@@ -0,0 +1,161 @@
+//! Unsaved request drafts, one `drafts/<tab_id>.json` file per editor tab in
+//! the workspace dir, so tabs can be restored after a crash. `save_draft`
+//! only keeps the latest state in memory; it's written out at most every
+//! `FLUSH_DELAY`, and once more when the app exits.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::helpers::collections::write_atomic;
+use crate::helpers::settings::load_settings;
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::{ApiRequest, Draft};
+
+const FLUSH_DELAY: Duration = Duration::from_millis(500);
+
+/// Drafts waiting to be written, by file path.
+static PENDING: OnceLock<DashMap<PathBuf, Draft>> = OnceLock::new();
+static FLUSH_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+/// Held while pending drafts are written and while drafts are deleted, so a
+/// flush already under way can't bring back a deleted draft.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+fn pending() -> &'static DashMap<PathBuf, Draft> {
+    PENDING.get_or_init(DashMap::new)
+}
+
+fn drafts_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("drafts"))
+}
+
+/// Tab ids become file names, so only letters, digits, `-` and `_` are accepted.
+fn draft_path(app: &AppHandle, tab_id: &str) -> Result<PathBuf, String> {
+    let valid = !tab_id.is_empty()
+        && tab_id.len() <= 128
+        && tab_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+    if !valid {
+        return Err(format!("Invalid tab id '{}'", tab_id));
+    }
+    Ok(drafts_dir(app)?.join(format!("{}.json", tab_id)))
+}
+
+/// Write every pending draft now, as done before listing and on exit.
+pub(crate) fn flush_drafts() {
+    let Ok(_guard) = WRITE_LOCK.lock() else {
+        return;
+    };
+    let paths: Vec<PathBuf> = pending().iter().map(|entry| entry.key().clone()).collect();
+    for path in paths {
+        if let Some((path, draft)) = pending().remove(&path) {
+            if let Ok(json) = serde_json::to_vec(&draft) {
+                let _ = write_atomic(&path, &json);
+            }
+        }
+    }
+}
+
+/// Delete drafts older than the `draft_max_age_days` setting.
+fn prune_old(app: &AppHandle) -> Result<(), String> {
+    let Some(max_age_days) = load_settings(app)?.draft_max_age_days else {
+        return Ok(());
+    };
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(max_age_days));
+    let _guard = WRITE_LOCK.lock().map_err(|e| e.to_string())?;
+    let entries = match std::fs::read_dir(drafts_dir(app)?) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.to_string()),
+    };
+    for entry in entries.flatten() {
+        let modified = entry.metadata().and_then(|metadata| metadata.modified());
+        if modified.is_ok_and(|modified| DateTime::<Utc>::from(modified) < cutoff) {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Prune old drafts without waiting for it, as done at startup.
+pub(crate) fn prune_in_background(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let _ = tokio::task::spawn_blocking(move || prune_old(&app)).await;
+    });
+}
+
+/// Keep the current state of a tab. Cheap enough to call on every change:
+/// the draft is written to disk shortly after, replacing the previous one.
+#[tauri::command]
+#[specta::specta]
+pub fn save_draft(
+    app: AppHandle,
+    tab_id: String,
+    request: ApiRequest,
+    metadata: Option<Value>,
+) -> Result<(), String> {
+    let path = draft_path(&app, &tab_id)?;
+    let draft = Draft {
+        tab_id,
+        request,
+        metadata: metadata.unwrap_or(Value::Null),
+        saved_at: Utc::now().to_rfc3339(),
+    };
+    pending().insert(path, draft);
+    if !FLUSH_SCHEDULED.swap(true, Ordering::SeqCst) {
+        tauri::async_runtime::spawn(async {
+            tokio::time::sleep(FLUSH_DELAY).await;
+            FLUSH_SCHEDULED.store(false, Ordering::SeqCst);
+            let _ = tokio::task::spawn_blocking(flush_drafts).await;
+        });
+    }
+    Ok(())
+}
+
+/// Drafts left in the active workspace, most recent first, after deleting
+/// ones past the `draft_max_age_days` setting. Unreadable files are skipped.
+#[tauri::command]
+#[specta::specta]
+pub fn list_drafts(app: AppHandle) -> Result<Vec<Draft>, String> {
+    flush_drafts();
+    prune_old(&app)?;
+    let entries = match std::fs::read_dir(drafts_dir(&app)?) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let mut drafts: Vec<Draft> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                return None;
+            }
+            serde_json::from_slice(&std::fs::read(path).ok()?).ok()
+        })
+        .collect();
+    drafts.sort_by(|a, b| b.saved_at.cmp(&a.saved_at));
+    Ok(drafts)
+}
+
+/// Forget a tab's draft, as when it's saved or closed. Returns `false` when
+/// it had none.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_draft(app: AppHandle, tab_id: String) -> Result<bool, String> {
+    let path = draft_path(&app, &tab_id)?;
+    let _guard = WRITE_LOCK.lock().map_err(|e| e.to_string())?;
+    let was_pending = pending().remove(&path).is_some();
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(was_pending),
+        Err(e) => Err(e.to_string()),
+    }
+}
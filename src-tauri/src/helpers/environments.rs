@@ -0,0 +1,509 @@
+//! Environments, kept in `environments.json` in the workspace dir along
+//! with which one is active. The global environment always exists; the
+//! others inherit its variables and override them by key.
+//!
+//! Secret variables are saved with an empty value; the value itself is kept
+//! in the OS keychain under the account `<workspace_id>/<env_id>/<key>`, or
+//! `<env_id>/<key>` in the default workspace. When the keychain
+//! can't be reached, saving or using a secret fails rather than falling back
+//! to the file.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::helpers::collections::write_atomic;
+use crate::helpers::workspaces::{active_workspace_id, workspace_dir, DEFAULT_WORKSPACE_ID};
+use crate::types::{Environment, EnvironmentVariable, ImportedEnvironment, ImportedVariable};
+
+pub(crate) const GLOBAL_ENVIRONMENT_ID: &str = "global";
+
+/// Shown in place of a secret's value in exports and previews.
+pub(crate) const SECRET_MASK: &str = "****";
+
+const KEYCHAIN_SERVICE: &str = "com.marban.mandy";
+
+/// Serializes read-modify-write cycles on the environments file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Default)]
+struct Store {
+    active_id: Option<String>,
+    environments: Vec<Environment>,
+}
+
+impl Store {
+    fn get(&self, id: &str) -> Result<&Environment, String> {
+        self.environments
+            .iter()
+            .find(|env| env.id == id)
+            .ok_or_else(|| format!("Environment '{}' not found", id))
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut Environment, String> {
+        self.environments
+            .iter_mut()
+            .find(|env| env.id == id)
+            .ok_or_else(|| format!("Environment '{}' not found", id))
+    }
+}
+
+/// The keychain account of a secret. Accounts in the default workspace have
+/// no prefix, as they did before workspaces.
+fn secret_account(workspace_id: &str, env_id: &str, key: &str) -> String {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        format!("{}/{}", env_id, key)
+    } else {
+        format!("{}/{}/{}", workspace_id, env_id, key)
+    }
+}
+
+fn keychain_entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+/// A secret's value from the keychain.
+pub(crate) fn read_secret(account: &str, key: &str) -> Result<String, String> {
+    match keychain_entry(account)?.get_password() {
+        Ok(value) => Ok(value),
+        Err(keyring::Error::NoEntry) => Err(format!("Secret '{}' is not in the keychain", key)),
+        Err(e) => Err(format!("Keychain unavailable: {}", e)),
+    }
+}
+
+pub(crate) fn write_secret(account: &str, value: &str) -> Result<(), String> {
+    keychain_entry(account)?
+        .set_password(value)
+        .map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+pub(crate) fn delete_secret(account: &str) -> Result<(), String> {
+    match keychain_entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Keychain unavailable: {}", e)),
+    }
+}
+
+/// Move secret values still in the store into the keychain, so they're never
+/// written to the file.
+fn move_secrets_to_keychain(store: &mut Store, workspace_id: &str) -> Result<(), String> {
+    for environment in &mut store.environments {
+        for variable in &mut environment.variables {
+            if variable.is_secret && !variable.value.is_empty() {
+                let account = secret_account(workspace_id, &environment.id, &variable.key);
+                write_secret(&account, &variable.value)?;
+                variable.value.clear();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("environments.json"))
+}
+
+fn load(app: &AppHandle) -> Result<Store, String> {
+    load_from(&store_path(app)?)
+}
+
+fn load_from(path: &Path) -> Result<Store, String> {
+    let mut store: Store = match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Store::default(),
+        Err(e) => return Err(e.to_string()),
+    };
+    if !store.environments.iter().any(|env| env.is_global) {
+        store.environments.insert(
+            0,
+            Environment {
+                id: GLOBAL_ENVIRONMENT_ID.to_string(),
+                name: "Global".to_string(),
+                variables: Vec::new(),
+                is_global: true,
+            },
+        );
+    }
+    Ok(store)
+}
+
+fn save(app: &AppHandle, store: &Store) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(store).map_err(|e| e.to_string())?;
+    write_atomic(&store_path(app)?, &json)
+}
+
+/// Load the store, change it, and save it if `change` succeeds.
+fn modify<T>(
+    app: &AppHandle,
+    change: impl FnOnce(&mut Store) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let workspace_id = active_workspace_id(app)?;
+    let mut store = load(app)?;
+    let result = change(&mut store)?;
+    move_secrets_to_keychain(&mut store, &workspace_id)?;
+    save(app, &store)?;
+    Ok(result)
+}
+
+/// `environment`'s variables over the global ones, each with the id of the
+/// environment it comes from.
+fn merged_variables<'a>(
+    store: &'a Store,
+    environment: &'a Environment,
+) -> Vec<(&'a str, &'a EnvironmentVariable)> {
+    let mut variables: Vec<(&str, &EnvironmentVariable)> = store
+        .environments
+        .iter()
+        .filter(|env| env.is_global && env.id != environment.id)
+        .flat_map(|env| env.variables.iter().map(|v| (env.id.as_str(), v)))
+        .collect();
+    for variable in &environment.variables {
+        let own = (environment.id.as_str(), variable);
+        match variables.iter_mut().find(|(_, v)| v.key == variable.key) {
+            Some(inherited) => *inherited = own,
+            None => variables.push(own),
+        }
+    }
+    variables
+}
+
+/// A variable's value as the resolver sees it. Secrets are only read from
+/// the keychain when a request uses them.
+pub(crate) enum VariableValue {
+    Plain(String),
+    Secret { key: String, account: String },
+}
+
+/// The id of the active environment, if one is set.
+pub(crate) fn active_environment_id(app: &AppHandle) -> Result<Option<String>, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    Ok(load(app)?.active_id)
+}
+
+/// The variables requests see in `env_id` (the active environment when
+/// `None`), global ones included. Only the global ones when nothing is active.
+pub(crate) fn environment_variables(
+    app: &AppHandle,
+    env_id: Option<&str>,
+) -> Result<HashMap<String, VariableValue>, String> {
+    let workspace_id = active_workspace_id(app)?;
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let store = load(app)?;
+    let id = env_id
+        .map(str::to_string)
+        .or_else(|| store.active_id.clone())
+        .unwrap_or_else(|| GLOBAL_ENVIRONMENT_ID.to_string());
+    let environment = store.get(&id)?;
+    Ok(merged_variables(&store, environment)
+        .into_iter()
+        .map(|(owner, variable)| {
+            // Values left in the file by older versions are used as they are
+            // until the environment is next saved.
+            let value = if variable.is_secret && variable.value.is_empty() {
+                VariableValue::Secret {
+                    key: variable.key.clone(),
+                    account: secret_account(&workspace_id, owner, &variable.key),
+                }
+            } else {
+                VariableValue::Plain(variable.value.clone())
+            };
+            (variable.key.clone(), value)
+        })
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn create_environment(app: AppHandle, name: String) -> Result<Environment, String> {
+    modify(&app, |store| {
+        let environment = Environment {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            variables: Vec::new(),
+            is_global: false,
+        };
+        store.environments.push(environment.clone());
+        Ok(environment)
+    })
+}
+
+/// Every environment, the global one first. Variables are each environment's
+/// own, without the inherited ones. Secret values are empty; see
+/// `reveal_secret`.
+#[tauri::command]
+#[specta::specta]
+pub fn list_environments(app: AppHandle) -> Result<Vec<Environment>, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    Ok(load(&app)?.environments)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_environment(app: AppHandle, env_id: String) -> Result<Environment, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    load(&app)?.get(&env_id).cloned()
+}
+
+/// The global environment can't be deleted. Deleting the active one leaves
+/// none active. Returns `false` when there was no such environment.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_environment(app: AppHandle, env_id: String) -> Result<bool, String> {
+    if env_id == GLOBAL_ENVIRONMENT_ID {
+        return Err("The global environment can't be deleted".to_string());
+    }
+    let workspace_id = active_workspace_id(&app)?;
+    modify(&app, |store| {
+        if let Ok(environment) = store.get(&env_id) {
+            for variable in environment.variables.iter().filter(|v| v.is_secret) {
+                delete_secret(&secret_account(&workspace_id, &env_id, &variable.key))?;
+            }
+        }
+        let before = store.environments.len();
+        store.environments.retain(|env| env.id != env_id);
+        if store.active_id.as_deref() == Some(env_id.as_str()) {
+            store.active_id = None;
+        }
+        Ok(store.environments.len() != before)
+    })
+}
+
+/// Set `variables` in the environment called `name`, creating it when there
+/// is none. A variable that was secret stays secret. Returns the keys the
+/// environment already had.
+pub(crate) fn merge_into_environment(
+    app: &AppHandle,
+    name: &str,
+    variables: Vec<EnvironmentVariable>,
+) -> Result<Vec<String>, String> {
+    let workspace_id = active_workspace_id(app)?;
+    modify(app, |store| {
+        let index = match store.environments.iter().position(|env| env.name == name) {
+            Some(index) => index,
+            None => {
+                store.environments.push(Environment {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    name: name.to_string(),
+                    variables: Vec::new(),
+                    is_global: false,
+                });
+                store.environments.len() - 1
+            }
+        };
+        let environment = &mut store.environments[index];
+        let mut overwritten = Vec::new();
+        for mut variable in variables {
+            let existing = environment
+                .variables
+                .iter()
+                .position(|v| v.key == variable.key);
+            if let Some(existing) = existing {
+                variable.is_secret |= environment.variables[existing].is_secret;
+            }
+            if variable.is_secret {
+                let account = secret_account(&workspace_id, &environment.id, &variable.key);
+                write_secret(&account, &variable.value)?;
+                variable.value.clear();
+            }
+            match existing {
+                Some(existing) => {
+                    overwritten.push(variable.key.clone());
+                    environment.variables[existing] = variable;
+                }
+                None => environment.variables.push(variable),
+            }
+        }
+        Ok(overwritten)
+    })
+}
+
+/// Copy an environment's own variables, secrets included, into a new one.
+#[tauri::command]
+#[specta::specta]
+pub fn duplicate_environment(
+    app: AppHandle,
+    env_id: String,
+    new_name: String,
+) -> Result<Environment, String> {
+    let workspace_id = active_workspace_id(&app)?;
+    modify(&app, |store| {
+        let mut variables = store.get(&env_id)?.variables.clone();
+        for variable in variables.iter_mut().filter(|v| v.is_secret) {
+            if variable.value.is_empty() {
+                let account = secret_account(&workspace_id, &env_id, &variable.key);
+                variable.value = read_secret(&account, &variable.key)?;
+            }
+        }
+        let environment = Environment {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: new_name,
+            variables,
+            is_global: false,
+        };
+        store.environments.push(environment.clone());
+        Ok(environment)
+    })
+}
+
+/// Add a variable, or replace the one with the same key. A secret's value
+/// goes to the keychain; one that stops being secret is removed from it.
+#[tauri::command]
+#[specta::specta]
+pub fn set_variable(
+    app: AppHandle,
+    env_id: String,
+    key: String,
+    value: String,
+    is_secret: bool,
+) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("Variable name is empty".to_string());
+    }
+    let account = secret_account(&active_workspace_id(&app)?, &env_id, &key);
+    modify(&app, |store| {
+        let variables = &mut store.get_mut(&env_id)?.variables;
+        let mut variable = EnvironmentVariable {
+            key,
+            value,
+            is_secret,
+        };
+        if is_secret {
+            write_secret(&account, &variable.value)?;
+            variable.value.clear();
+        }
+        match variables.iter_mut().find(|v| v.key == variable.key) {
+            Some(existing) => {
+                if existing.is_secret && !is_secret {
+                    delete_secret(&account)?;
+                }
+                *existing = variable;
+            }
+            None => variables.push(variable),
+        }
+        Ok(())
+    })
+}
+
+/// Add or replace a secret variable, keeping its value in the keychain.
+#[tauri::command]
+#[specta::specta]
+pub fn set_secret(
+    app: AppHandle,
+    env_id: String,
+    key: String,
+    value: String,
+) -> Result<(), String> {
+    set_variable(app, env_id, key, value, true)
+}
+
+/// The value of one of the environment's own secret variables.
+#[tauri::command]
+#[specta::specta]
+pub fn reveal_secret(app: AppHandle, env_id: String, key: String) -> Result<String, String> {
+    let variable = {
+        let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+        load(&app)?
+            .get(&env_id)?
+            .variables
+            .iter()
+            .find(|v| v.key == key)
+            .cloned()
+            .ok_or_else(|| format!("Variable '{}' not found", key))?
+    };
+    if !variable.is_secret {
+        return Err(format!("'{}' is not a secret", key));
+    }
+    if !variable.value.is_empty() {
+        return Ok(variable.value);
+    }
+    read_secret(
+        &secret_account(&active_workspace_id(&app)?, &env_id, &key),
+        &key,
+    )
+}
+
+/// Returns `false` when the environment had no such variable of its own.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_variable(app: AppHandle, env_id: String, key: String) -> Result<bool, String> {
+    let account = secret_account(&active_workspace_id(&app)?, &env_id, &key);
+    modify(&app, |store| {
+        let variables = &mut store.get_mut(&env_id)?.variables;
+        if variables.iter().any(|v| v.key == key && v.is_secret) {
+            delete_secret(&account)?;
+        }
+        let before = variables.len();
+        variables.retain(|v| v.key != key);
+        Ok(variables.len() != before)
+    })
+}
+
+/// Pick the environment requests resolve variables from, or none (only the
+/// global variables) with `None`.
+#[tauri::command]
+#[specta::specta]
+pub fn set_active_environment(app: AppHandle, env_id: Option<String>) -> Result<(), String> {
+    modify(&app, |store| {
+        if let Some(ref id) = env_id {
+            store.get(id)?;
+        }
+        store.active_id = env_id;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_active_environment(app: AppHandle) -> Result<Option<String>, String> {
+    active_environment_id(&app)
+}
+
+/// An environment with the global variables it inherits, in the shape
+/// exporters take. Set it as `CollectionExport::environment` to include it in
+/// an export, or leave that `None` to leave environments out. Secret values
+/// are replaced with `****`.
+#[tauri::command]
+#[specta::specta]
+pub fn export_environment(app: AppHandle, env_id: String) -> Result<ImportedEnvironment, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let store = load(&app)?;
+    let environment = store.get(&env_id)?;
+    Ok(ImportedEnvironment {
+        name: environment.name.clone(),
+        variables: merged_variables(&store, environment)
+            .into_iter()
+            .map(|(_, variable)| ImportedVariable {
+                key: variable.key.clone(),
+                value: if variable.is_secret {
+                    SECRET_MASK.to_string()
+                } else {
+                    variable.value.clone()
+                },
+                enabled: true,
+                secret: variable.is_secret,
+            })
+            .collect(),
+    })
+}
+
+/// Remove the keychain entries of every secret in the workspace at `dir`,
+/// before the workspace itself is deleted.
+pub(crate) fn delete_workspace_secrets(dir: &Path, workspace_id: &str) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let store = load_from(&dir.join("environments.json"))?;
+    for environment in &store.environments {
+        for variable in environment.variables.iter().filter(|v| v.is_secret) {
+            delete_secret(&secret_account(
+                workspace_id,
+                &environment.id,
+                &variable.key,
+            ))?;
+        }
+    }
+    Ok(())
+}
@@ -1,12 +1,13 @@
-use std::collections::HashMap;
-use std::io::{Cursor, Read};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde_json::Value;
 
 use crate::helpers::request_notify::{
     notify_request_completed_if_background, pick_display_name,
 };
-use crate::types::{GraphQLIntrospectRequest, GraphQLIntrospectResponse};
+use crate::helpers::rest::execute_curl_request;
+use crate::types::{
+    ApiRequest, AuthType, BodyType, GraphQLIntrospectRequest, GraphQLIntrospectResponse, Methods,
+};
 use tauri::AppHandle;
 use url::Url;
 
@@ -104,159 +105,138 @@ fragment TypeRef on __Type {
 }
 "#;
 
-fn build_curl_handle(
-    url: &str,
-    headers: &HashMap<String, String>,
-    body: &str,
-) -> Result<curl::easy::Easy, String> {
-    let mut easy = curl::easy::Easy::new();
-
-    easy.url(url).map_err(|e| format!("URL error: {e}"))?;
-    easy.post(true).map_err(|e| e.to_string())?;
-    easy.timeout(Duration::from_secs(30))
-        .map_err(|e| e.to_string())?;
-    easy.follow_location(true).map_err(|e| e.to_string())?;
-    easy.max_redirections(5).map_err(|e| e.to_string())?;
-    easy.ssl_verify_peer(true).map_err(|e| e.to_string())?;
-    easy.ssl_verify_host(true).map_err(|e| e.to_string())?;
-
-    // Use HTTP/2 with TLS fallback
-    easy.http_version(curl::easy::HttpVersion::V2TLS)
-        .map_err(|e| e.to_string())?;
-
-    let mut header_list = curl::easy::List::new();
-
-    // Default Content-Type
-    header_list
-        .append("Content-Type: application/json")
-        .map_err(|e| e.to_string())?;
-
-    // Caller-supplied headers (may override Content-Type)
-    for (k, v) in headers {
-        header_list
-            .append(&format!("{}: {}", k, v))
-            .map_err(|e| e.to_string())?;
+impl GraphQLIntrospectResponse {
+    fn failed(error: String) -> Self {
+        GraphQLIntrospectResponse {
+            schema_json: None,
+            error: Some(error),
+            introspection_disabled: false,
+            type_count: 0,
+            query_type: None,
+            mutation_type: None,
+            subscription_type: None,
+        }
     }
-
-    easy.http_headers(header_list)
-        .map_err(|e| e.to_string())?;
-
-    let body_bytes = body.as_bytes().to_vec();
-    easy.post_field_size(body_bytes.len() as u64)
-        .map_err(|e| e.to_string())?;
-
-    Ok(easy)
 }
 
-fn execute_introspection(
-    url: &str,
-    headers: &HashMap<String, String>,
-) -> Result<GraphQLIntrospectResponse, String> {
-    let body = serde_json::json!({ "query": INTROSPECTION_QUERY }).to_string();
-
-    let mut easy = build_curl_handle(url, headers, &body)?;
-
-    let mut response_body: Vec<u8> = Vec::new();
-    let body_bytes = body.into_bytes();
-    let cursor = Arc::new(Mutex::new(Cursor::new(body_bytes)));
-
-    {
-        let mut transfer = easy.transfer();
-
-        transfer
-            .read_function(|buf| {
-                let mut c = cursor.lock().unwrap();
-                let n = c.read(buf).unwrap_or(0);
-                Ok(n)
-            })
-            .map_err(|e| e.to_string())?;
+/// `message` of every entry in a GraphQL `errors` array.
+fn error_messages(json: &Value) -> Vec<String> {
+    json.get("errors")
+        .and_then(Value::as_array)
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(|e| e.get("message")?.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
-        transfer
-            .write_function(|data| {
-                response_body.extend_from_slice(data);
-                Ok(data.len())
-            })
-            .map_err(|e| e.to_string())?;
+/// Matches what graphql-js, Apollo and most other servers answer when
+/// introspection is turned off.
+fn is_introspection_disabled(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    (message.contains("introspection")
+        && ["disabled", "not allowed", "not permitted"]
+            .iter()
+            .any(|phrase| message.contains(phrase)))
+        || (message.contains("cannot query field") && message.contains("__schema"))
+}
 
-        transfer.perform().map_err(|e| {
-            let mut msg = String::new();
-            if e.is_couldnt_resolve_host() {
-                msg.push_str("Could not resolve host. ");
-            }
-            if e.is_couldnt_connect() {
-                msg.push_str("Could not connect. ");
-            }
-            if e.is_operation_timedout() {
-                msg.push_str("Request timed out. ");
-            }
-            if e.is_ssl_connect_error() {
-                msg.push_str("SSL connect error. ");
-            }
-            msg.push_str(&e.to_string());
-            msg
-        })?;
-    }
+fn root_type_name(schema: &Value, root: &str) -> Option<String> {
+    schema.get(root)?.get("name")?.as_str().map(str::to_string)
+}
 
-    let status = easy.response_code().unwrap_or(0) as u16;
+fn parse_introspection(status: u16, body: &[u8]) -> Result<GraphQLIntrospectResponse, String> {
+    let json = serde_json::from_slice::<Value>(body).ok();
+    let messages = json.as_ref().map(error_messages).unwrap_or_default();
 
-    if status == 0 {
+    // Checked before the status: some servers refuse introspection with a 400.
+    if let Some(message) = messages.iter().find(|m| is_introspection_disabled(m)) {
         return Ok(GraphQLIntrospectResponse {
-            schema_json: None,
-            error: Some("No response received from server".to_string()),
+            introspection_disabled: true,
+            ..GraphQLIntrospectResponse::failed(format!(
+                "Introspection is disabled on this server: {message}"
+            ))
         });
     }
 
-    if status < 200 || status >= 300 {
-        let body_str = String::from_utf8_lossy(&response_body).to_string();
-        return Ok(GraphQLIntrospectResponse {
-            schema_json: None,
-            error: Some(format!("HTTP {status}: {body_str}")),
-        });
+    if !(200..300).contains(&status) {
+        let body_str = String::from_utf8_lossy(body).to_string();
+        return Ok(GraphQLIntrospectResponse::failed(format!(
+            "HTTP {status}: {body_str}"
+        )));
     }
 
-    // Parse the response JSON
-    let json: serde_json::Value =
-        serde_json::from_slice(&response_body).map_err(|e| format!("Invalid JSON: {e}"))?;
-
-    // Check for GraphQL-level errors (errors present but no data)
-    if let Some(errors) = json.get("errors") {
-        if json.get("data").is_none() || json["data"].is_null() {
-            let msg = errors
-                .as_array()
-                .and_then(|arr| {
-                    let msgs: Vec<String> = arr
-                        .iter()
-                        .filter_map(|e| e.get("message")?.as_str().map(str::to_string))
-                        .collect();
-                    if msgs.is_empty() {
-                        None
-                    } else {
-                        Some(msgs.join("; "))
-                    }
-                })
-                .unwrap_or_else(|| errors.to_string());
-            return Ok(GraphQLIntrospectResponse {
-                schema_json: None,
-                error: Some(msg),
-            });
-        }
-    }
+    let json = json.ok_or_else(|| "Invalid JSON in introspection response".to_string())?;
 
     // Extract the `data` field which is the introspection result
-    let data = json
-        .get("data")
-        .cloned()
-        .ok_or_else(|| "Response missing 'data' field".to_string())?;
+    let data = match json.get("data") {
+        Some(data) if !data.is_null() => data.clone(),
+        _ if !messages.is_empty() => {
+            return Ok(GraphQLIntrospectResponse::failed(messages.join("; ")));
+        }
+        _ => return Err("Response missing 'data' field".to_string()),
+    };
+    let Some(schema) = data.get("__schema").filter(|s| !s.is_null()) else {
+        return Ok(GraphQLIntrospectResponse::failed(
+            "Response has no '__schema'".to_string(),
+        ));
+    };
 
+    let type_count = schema
+        .get("types")
+        .and_then(Value::as_array)
+        .map_or(0, |types| types.len() as u32);
     let schema_json =
         serde_json::to_string(&data).map_err(|e| format!("Failed to serialise schema: {e}"))?;
 
     Ok(GraphQLIntrospectResponse {
         schema_json: Some(schema_json),
         error: None,
+        introspection_disabled: false,
+        type_count,
+        query_type: root_type_name(schema, "queryType"),
+        mutation_type: root_type_name(schema, "mutationType"),
+        subscription_type: root_type_name(schema, "subscriptionType"),
     })
 }
 
+/// Sends the introspection query through the REST request path, so auth,
+/// proxy and TLS settings behave exactly as they do for `rest_request`.
+fn execute_introspection(
+    app: &AppHandle,
+    req: GraphQLIntrospectRequest,
+) -> Result<GraphQLIntrospectResponse, String> {
+    let api_req = ApiRequest {
+        method: Methods::POST,
+        url: req.url,
+        headers: req.headers.into_iter().collect(),
+        body: BodyType::GraphQL {
+            query: INTROSPECTION_QUERY.to_string(),
+            variables: None,
+            operation_name: Some("IntrospectionQuery".to_string()),
+        },
+        auth: req.auth.unwrap_or(AuthType::None),
+        timeout_ms: Some(req.timeout_ms.unwrap_or(30_000)),
+        max_redirects: Some(5),
+        proxy: req.proxy,
+        use_system_proxy: req.use_system_proxy,
+        verify_ssl: req.verify_ssl,
+        client_cert: req.client_cert,
+        ca_cert_path: req.ca_cert_path,
+        // Schemas can be large; keep the whole body in memory for parsing.
+        spill_threshold_bytes: Some(u32::MAX),
+        ..Default::default()
+    };
+
+    let response = execute_curl_request(app, api_req, None).map_err(|e| e.to_string())?;
+    let body = BASE64
+        .decode(&response.body_base64)
+        .map_err(|e| e.to_string())?;
+    parse_introspection(response.status, &body)
+}
+
 fn graphql_introspect_fallback(req: &GraphQLIntrospectRequest) -> String {
     let host = Url::parse(&req.url)
         .ok()
@@ -277,7 +257,8 @@ pub async fn graphql_introspect(
 ) -> Result<GraphQLIntrospectResponse, String> {
     let label = req.request_label.clone();
     let fallback = graphql_introspect_fallback(&req);
-    let result = tokio::task::spawn_blocking(move || execute_introspection(&req.url, &req.headers))
+    let app_for_transfer = app.clone();
+    let result = tokio::task::spawn_blocking(move || execute_introspection(&app_for_transfer, req))
         .await
         .map_err(|e| format!("Task error: {e}"))?;
     if result.is_ok() {
@@ -0,0 +1,748 @@
+//! gRPC calls described by server reflection, or by descriptors imported with
+//! `import_proto_files`. Request and response messages are transcoded between
+//! JSON and protobuf with `prost-reflect`, so no generated code is needed for
+//! the services being called.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use dashmap::DashMap;
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor, ServiceDescriptor,
+};
+use prost_types::FileDescriptorProto;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_fs::FsExt;
+use tokio::sync::{mpsc, oneshot};
+use tonic::client::Grpc;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder, ProstCodec};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{KeyAndValueRef, MetadataKey, MetadataMap, MetadataValue};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
+use tonic::{Code, Request, Status};
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::{ServerReflectionRequest, ServerReflectionResponse};
+
+use crate::helpers::proto_registry::load_registry;
+use crate::types::{
+    GrpcMethodInfo, GrpcServiceInfo, GrpcStreamClosedEvent, GrpcStreamMessageEvent, GrpcTlsOptions,
+    GrpcUnaryResponse,
+};
+
+/// Event carrying `GrpcStreamMessageEvent` payloads.
+pub const GRPC_STREAM_MESSAGE_EVENT: &str = "grpc-stream-message";
+
+/// Event carrying `GrpcStreamClosedEvent` payloads.
+pub const GRPC_STREAM_CLOSED_EVENT: &str = "grpc-stream-closed";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Received stream messages waiting to be emitted. When the frontend falls
+/// this far behind, further messages are counted as dropped instead.
+const STREAM_QUEUE_LIMIT: usize = 1024;
+
+static GRPC_STREAMS: OnceLock<DashMap<String, oneshot::Sender<()>>> = OnceLock::new();
+
+/// Cancel handles of the running server streams, keyed by stream id.
+fn grpc_streams() -> &'static DashMap<String, oneshot::Sender<()>> {
+    GRPC_STREAMS.get_or_init(DashMap::new)
+}
+
+fn now_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as f64
+}
+
+/// Both reflection versions use the same messages, so the v1 types are sent
+/// to whichever path the server implements.
+const REFLECTION_PATHS: [&str; 2] = [
+    "/grpc.reflection.v1.ServerReflection/ServerReflectionInfo",
+    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo",
+];
+
+/// Trailer entries that are reported as the status instead.
+const STATUS_TRAILERS: [&str; 3] = ["grpc-status", "grpc-message", "grpc-status-details-bin"];
+
+/// Encodes and decodes `DynamicMessage`s of a type only known at runtime.
+struct DynamicCodec(MessageDescriptor);
+
+struct DynamicEncoder;
+
+struct DynamicDecoder(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.0.clone())
+    }
+}
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: DynamicMessage, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("Failed to encode request: {}", e)))
+    }
+}
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<DynamicMessage>, Status> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::internal(format!("Failed to decode response: {}", e)))
+    }
+}
+
+/// Error text including the sources, since tonic's transport errors alone
+/// only say "transport error".
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut text = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        text.push_str(": ");
+        text.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    text
+}
+
+fn read_allowed(app: &AppHandle, path: &str) -> Result<Vec<u8>, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    std::fs::read(path).map_err(|e| format!("Failed to read '{}': {}", path, e))
+}
+
+fn tls_config(app: &AppHandle, tls: &GrpcTlsOptions) -> Result<ClientTlsConfig, String> {
+    let mut config = ClientTlsConfig::new();
+    config = match tls.ca_cert_path.as_deref() {
+        Some(path) => config.ca_certificate(Certificate::from_pem(read_allowed(app, path)?)),
+        None => config.with_native_roots(),
+    };
+    match (
+        tls.client_cert_path.as_deref(),
+        tls.client_key_path.as_deref(),
+    ) {
+        (Some(cert), Some(key)) => {
+            config = config.identity(Identity::from_pem(
+                read_allowed(app, cert)?,
+                read_allowed(app, key)?,
+            ));
+        }
+        (None, None) => {}
+        _ => return Err("A client certificate needs both a cert and a key path".to_string()),
+    }
+    if let Some(name) = tls.server_name.as_deref() {
+        config = config.domain_name(name);
+    }
+    Ok(config)
+}
+
+/// Connects to `endpoint`, given as `host:port` or with an `http(s)://` scheme.
+async fn connect(
+    app: &AppHandle,
+    endpoint: &str,
+    tls: Option<&GrpcTlsOptions>,
+) -> Result<Channel, String> {
+    let tls = tls.cloned().unwrap_or_default();
+    let trimmed = endpoint.trim().trim_end_matches('/');
+    let (scheme, authority) = match trimmed.split_once("://") {
+        Some((scheme, authority)) => (Some(scheme.to_ascii_lowercase()), authority),
+        None => (None, trimmed),
+    };
+    if let Some(scheme) = scheme.as_deref() {
+        if scheme != "http" && scheme != "https" {
+            return Err(format!(
+                "Unsupported gRPC endpoint scheme '{}': use http://, https:// or host:port",
+                scheme
+            ));
+        }
+    }
+    let plaintext = tls.plaintext.unwrap_or(scheme.as_deref() == Some("http"));
+    let uri = format!(
+        "{}://{}",
+        if plaintext { "http" } else { "https" },
+        authority
+    );
+    let mut builder = Endpoint::from_shared(uri)
+        .map_err(|e| format!("Invalid gRPC endpoint '{}': {}", endpoint, e))?
+        .connect_timeout(CONNECT_TIMEOUT);
+    if !plaintext {
+        builder = builder
+            .tls_config(tls_config(app, &tls)?)
+            .map_err(|e| error_chain(&e))?;
+    }
+    builder
+        .connect()
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", endpoint, error_chain(&e)))
+}
+
+/// Runs `call` with `timeout`, reporting a late call as `DEADLINE_EXCEEDED`.
+async fn with_deadline<T>(
+    timeout: Duration,
+    call: impl Future<Output = Result<T, Status>>,
+) -> Result<T, Status> {
+    match tokio::time::timeout(timeout, call).await {
+        Ok(result) => result,
+        Err(_) => Err(Status::deadline_exceeded(format!(
+            "No response within {} ms",
+            timeout.as_millis()
+        ))),
+    }
+}
+
+/// A server reflection session on one channel.
+struct Reflection {
+    channel: Channel,
+    path: &'static str,
+    timeout: Duration,
+}
+
+impl Reflection {
+    /// Finds the reflection version the server implements and lists its
+    /// services with it.
+    async fn start(
+        channel: Channel,
+        endpoint: &str,
+        timeout: Duration,
+    ) -> Result<(Self, Vec<String>), String> {
+        for path in REFLECTION_PATHS {
+            let reflection = Reflection {
+                channel: channel.clone(),
+                path,
+                timeout,
+            };
+            match reflection
+                .call(MessageRequest::ListServices(String::new()))
+                .await
+            {
+                Ok(MessageResponse::ListServicesResponse(list)) => {
+                    let services = list.service.into_iter().map(|s| s.name).collect();
+                    return Ok((reflection, services));
+                }
+                Ok(_) => return Err("Unexpected server reflection response".to_string()),
+                Err(status) if status.code() == Code::Unimplemented => continue,
+                Err(status) => {
+                    return Err(format!("Server reflection failed: {}", status.message()))
+                }
+            }
+        }
+        Err(format!(
+            "Server reflection is unavailable on {}: it implements neither \
+             grpc.reflection.v1 nor v1alpha. Import the service's .proto files and \
+             call with their registry id instead.",
+            endpoint
+        ))
+    }
+
+    async fn call(&self, request: MessageRequest) -> Result<MessageResponse, Status> {
+        with_deadline(self.timeout, async {
+            let mut grpc = Grpc::new(self.channel.clone());
+            grpc.ready()
+                .await
+                .map_err(|e| Status::unavailable(error_chain(&e)))?;
+            let requests = futures_util::stream::iter([ServerReflectionRequest {
+                host: String::new(),
+                message_request: Some(request),
+            }]);
+            let codec = ProstCodec::<ServerReflectionRequest, ServerReflectionResponse>::default();
+            let mut responses = grpc
+                .streaming(
+                    Request::new(requests),
+                    PathAndQuery::from_static(self.path),
+                    codec,
+                )
+                .await?
+                .into_inner();
+            match responses.message().await?.and_then(|r| r.message_response) {
+                Some(MessageResponse::ErrorResponse(error)) => Err(Status::new(
+                    Code::from_i32(error.error_code),
+                    error.error_message,
+                )),
+                Some(response) => Ok(response),
+                None => Err(Status::internal("Empty server reflection response")),
+            }
+        })
+        .await
+    }
+
+    /// Adds the file declaring `symbol` and everything it imports to `files`.
+    async fn fetch_files(
+        &self,
+        symbol: &str,
+        files: &mut HashMap<String, FileDescriptorProto>,
+    ) -> Result<(), String> {
+        let mut requested = HashSet::new();
+        let mut pending = vec![MessageRequest::FileContainingSymbol(symbol.to_string())];
+        while let Some(request) = pending.pop() {
+            let response = self.call(request).await.map_err(|status| {
+                format!(
+                    "Server reflection couldn't describe '{}': {}",
+                    symbol,
+                    status.message()
+                )
+            })?;
+            let MessageResponse::FileDescriptorResponse(response) = response else {
+                return Err("Unexpected server reflection response".to_string());
+            };
+            for bytes in response.file_descriptor_proto {
+                let file = FileDescriptorProto::decode(bytes.as_slice())
+                    .map_err(|e| format!("Invalid file descriptor from the server: {}", e))?;
+                files.entry(file.name().to_string()).or_insert(file);
+            }
+            // Servers usually send the imports along, but aren't required to.
+            let missing: Vec<String> = files
+                .values()
+                .flat_map(|file| file.dependency.iter())
+                .filter(|name| !files.contains_key(*name))
+                .cloned()
+                .collect();
+            for name in missing {
+                if requested.insert(name.clone()) {
+                    pending.push(MessageRequest::FileByFilename(name));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn build_pool(files: HashMap<String, FileDescriptorProto>) -> Result<DescriptorPool, String> {
+    let mut pool = DescriptorPool::new();
+    pool.add_file_descriptor_protos(files.into_values())
+        .map_err(|e| format!("Invalid descriptors from server reflection: {}", e))?;
+    Ok(pool)
+}
+
+fn find_method(
+    pool: &DescriptorPool,
+    service: &str,
+    method: &str,
+) -> Result<MethodDescriptor, String> {
+    let descriptor = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| format!("Service '{}' not found", service))?;
+    let found = descriptor.methods().find(|m| m.name() == method);
+    found.ok_or_else(|| format!("Method '{}' not found on '{}'", method, service))
+}
+
+pub(crate) fn service_info(service: &ServiceDescriptor) -> GrpcServiceInfo {
+    GrpcServiceInfo {
+        name: service.full_name().to_string(),
+        methods: service
+            .methods()
+            .map(|method| GrpcMethodInfo {
+                name: method.name().to_string(),
+                input_type: method.input().full_name().to_string(),
+                output_type: method.output().full_name().to_string(),
+                client_streaming: method.is_client_streaming(),
+                server_streaming: method.is_server_streaming(),
+            })
+            .collect(),
+    }
+}
+
+/// Connects to `endpoint` and looks `service`/`method` up in the imported
+/// registry `registry_id`, or through reflection without one.
+async fn resolve_method(
+    app: &AppHandle,
+    endpoint: &str,
+    tls_options: Option<&GrpcTlsOptions>,
+    registry_id: Option<&str>,
+    service: &str,
+    method: &str,
+    timeout: Duration,
+) -> Result<(Channel, MethodDescriptor), String> {
+    let channel = connect(app, endpoint, tls_options).await?;
+    let pool = match registry_id {
+        Some(id) => load_registry(app, id)?,
+        None => {
+            let (reflection, _) = Reflection::start(channel.clone(), endpoint, timeout).await?;
+            let mut files = HashMap::new();
+            reflection.fetch_files(service, &mut files).await?;
+            build_pool(files)?
+        }
+    };
+    let descriptor = find_method(&pool, service, method)?;
+    Ok((channel, descriptor))
+}
+
+/// The request message for a call to `descriptor`, and the path it's sent to.
+fn build_request(
+    descriptor: &MethodDescriptor,
+    request_json: &str,
+    metadata: &[(String, String)],
+) -> Result<(Request<DynamicMessage>, PathAndQuery), String> {
+    let mut request = Request::new(message_from_json(descriptor.input(), request_json)?);
+    *request.metadata_mut() = encode_metadata(metadata)?;
+    let path = format!(
+        "/{}/{}",
+        descriptor.parent_service().full_name(),
+        descriptor.name()
+    );
+    let path = PathAndQuery::try_from(path).map_err(|e| format!("Invalid method path: {}", e))?;
+    Ok((request, path))
+}
+
+fn encode_metadata(pairs: &[(String, String)]) -> Result<MetadataMap, String> {
+    let mut map = MetadataMap::new();
+    for (name, value) in pairs {
+        let name = name.trim().to_ascii_lowercase();
+        let invalid = |e: &dyn std::fmt::Display| format!("Invalid metadata '{}': {}", name, e);
+        // Binary (`-bin`) values are given base64-encoded, as they're shown.
+        if name.ends_with("-bin") {
+            let key = MetadataKey::from_bytes(name.as_bytes()).map_err(|e| invalid(&e))?;
+            let bytes = BASE64.decode(value.trim()).map_err(|e| invalid(&e))?;
+            map.append_bin(key, MetadataValue::from_bytes(&bytes));
+        } else {
+            let key = MetadataKey::from_bytes(name.as_bytes()).map_err(|e| invalid(&e))?;
+            let value = MetadataValue::try_from(value.as_str()).map_err(|e| invalid(&e))?;
+            map.append(key, value);
+        }
+    }
+    Ok(map)
+}
+
+fn metadata_pairs(map: &MetadataMap) -> Vec<(String, String)> {
+    map.iter()
+        .filter_map(|entry| match entry {
+            KeyAndValueRef::Ascii(key, value) => {
+                Some((key.to_string(), value.to_str().ok()?.to_string()))
+            }
+            KeyAndValueRef::Binary(key, value) => {
+                Some((key.to_string(), BASE64.encode(value.to_bytes().ok()?)))
+            }
+        })
+        .filter(|(name, _)| !STATUS_TRAILERS.contains(&name.as_str()))
+        .collect()
+}
+
+/// Canonical upper-case name of a status code.
+fn status_name(code: Code) -> &'static str {
+    match code {
+        Code::Ok => "OK",
+        Code::Cancelled => "CANCELLED",
+        Code::Unknown => "UNKNOWN",
+        Code::InvalidArgument => "INVALID_ARGUMENT",
+        Code::DeadlineExceeded => "DEADLINE_EXCEEDED",
+        Code::NotFound => "NOT_FOUND",
+        Code::AlreadyExists => "ALREADY_EXISTS",
+        Code::PermissionDenied => "PERMISSION_DENIED",
+        Code::ResourceExhausted => "RESOURCE_EXHAUSTED",
+        Code::FailedPrecondition => "FAILED_PRECONDITION",
+        Code::Aborted => "ABORTED",
+        Code::OutOfRange => "OUT_OF_RANGE",
+        Code::Unimplemented => "UNIMPLEMENTED",
+        Code::Internal => "INTERNAL",
+        Code::Unavailable => "UNAVAILABLE",
+        Code::DataLoss => "DATA_LOSS",
+        Code::Unauthenticated => "UNAUTHENTICATED",
+    }
+}
+
+fn message_from_json(descriptor: MessageDescriptor, json: &str) -> Result<DynamicMessage, String> {
+    let json = if json.trim().is_empty() { "{}" } else { json };
+    let name = descriptor.full_name().to_string();
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    DynamicMessage::deserialize(descriptor, &mut deserializer)
+        .and_then(|message| deserializer.end().map(|_| message))
+        .map_err(|e| format!("Request JSON doesn't match {}: {}", name, e))
+}
+
+fn message_to_json(message: &DynamicMessage) -> Result<String, String> {
+    let value = message
+        .serialize_with_options(serde_json::value::Serializer, &Default::default())
+        .map_err(|e| e.to_string())?;
+    serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+}
+
+/// List the services a server exposes through reflection, with their methods.
+#[tauri::command]
+#[specta::specta]
+pub async fn grpc_list_services(
+    app: AppHandle,
+    endpoint: String,
+    tls_options: Option<GrpcTlsOptions>,
+) -> Result<Vec<GrpcServiceInfo>, String> {
+    let channel = connect(&app, &endpoint, tls_options.as_ref()).await?;
+    let (reflection, names) = Reflection::start(channel, &endpoint, DEFAULT_TIMEOUT).await?;
+    let mut files = HashMap::new();
+    for name in &names {
+        reflection.fetch_files(name, &mut files).await?;
+    }
+    let pool = build_pool(files)?;
+
+    Ok(names
+        .iter()
+        .filter_map(|name| pool.get_service_by_name(name))
+        .map(|service| service_info(&service))
+        .collect())
+}
+
+/// Call a unary method. `request_json` uses the protobuf JSON mapping, and
+/// `metadata` is sent as request headers. `timeout_ms` (30 s by default) is
+/// sent to the server as the call's deadline.
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub async fn grpc_unary(
+    app: AppHandle,
+    endpoint: String,
+    service: String,
+    method: String,
+    request_json: String,
+    metadata: Vec<(String, String)>,
+    tls_options: Option<GrpcTlsOptions>,
+    registry_id: Option<String>,
+    timeout_ms: Option<u32>,
+) -> Result<GrpcUnaryResponse, String> {
+    let timeout = timeout_ms
+        .map(|ms| Duration::from_millis(ms as u64))
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let (channel, descriptor) = resolve_method(
+        &app,
+        &endpoint,
+        tls_options.as_ref(),
+        registry_id.as_deref(),
+        &service,
+        &method,
+        timeout,
+    )
+    .await?;
+    if descriptor.is_client_streaming() || descriptor.is_server_streaming() {
+        return Err(format!(
+            "'{}' is a streaming method; grpc_unary only calls unary methods",
+            descriptor.full_name()
+        ));
+    }
+
+    let (mut request, path) = build_request(&descriptor, &request_json, &metadata)?;
+    request.set_timeout(timeout);
+
+    let started = Instant::now();
+    let mut headers = Vec::new();
+    // The unary call is made as a server stream so headers and trailers
+    // arrive separately; tonic's unary helper merges them.
+    let outcome = with_deadline(timeout, async {
+        let mut grpc = Grpc::new(channel);
+        grpc.ready()
+            .await
+            .map_err(|e| Status::unavailable(error_chain(&e)))?;
+        let response = grpc
+            .server_streaming(request, path, DynamicCodec(descriptor.output()))
+            .await?;
+        headers = metadata_pairs(response.metadata());
+        let mut stream = response.into_inner();
+        let message = stream
+            .message()
+            .await?
+            .ok_or_else(|| Status::internal("The server sent no response message"))?;
+        let trailers = stream.trailers().await?.unwrap_or_default();
+        Ok((message, trailers))
+    })
+    .await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(match outcome {
+        Ok((message, trailers)) => GrpcUnaryResponse {
+            response_json: Some(message_to_json(&message)?),
+            status_code: 0,
+            status_name: status_name(Code::Ok).to_string(),
+            status_message: String::new(),
+            headers,
+            trailers: metadata_pairs(&trailers),
+            duration_ms,
+        },
+        Err(status) => GrpcUnaryResponse {
+            response_json: None,
+            status_code: status.code() as u32,
+            status_name: status_name(status.code()).to_string(),
+            status_message: status.message().to_string(),
+            headers,
+            trailers: metadata_pairs(status.metadata()),
+            duration_ms,
+        },
+    })
+}
+
+/// Runs a server-streaming call to completion, emitting each message, and
+/// returns the closed event. Messages are transcoded on a separate task so a
+/// slow frontend doesn't hold up reading the stream.
+async fn run_server_stream(
+    app: &AppHandle,
+    stream_id: &str,
+    channel: Channel,
+    descriptor: MethodDescriptor,
+    request: Request<DynamicMessage>,
+    path: PathAndQuery,
+    cancel: oneshot::Receiver<()>,
+) -> GrpcStreamClosedEvent {
+    let started = Instant::now();
+    let dropped = Arc::new(AtomicU32::new(0));
+    let (queue, mut pending) = mpsc::channel::<(u32, DynamicMessage)>(STREAM_QUEUE_LIMIT);
+    let emitter = {
+        let app = app.clone();
+        let stream_id = stream_id.to_string();
+        let dropped = Arc::clone(&dropped);
+        tokio::spawn(async move {
+            while let Some((index, message)) = pending.recv().await {
+                let Ok(data) = message_to_json(&message) else {
+                    dropped.fetch_add(1, Ordering::SeqCst);
+                    continue;
+                };
+                let _ = app.emit(
+                    GRPC_STREAM_MESSAGE_EVENT,
+                    GrpcStreamMessageEvent {
+                        stream_id: stream_id.clone(),
+                        index,
+                        data,
+                        dropped: dropped.load(Ordering::SeqCst),
+                        timestamp_ms: now_ms(),
+                    },
+                );
+            }
+        })
+    };
+
+    let mut headers = Vec::new();
+    let mut received = 0u32;
+    let call = async {
+        let mut grpc = Grpc::new(channel);
+        grpc.ready()
+            .await
+            .map_err(|e| Status::unavailable(error_chain(&e)))?;
+        let response = grpc
+            .server_streaming(request, path, DynamicCodec(descriptor.output()))
+            .await?;
+        headers = metadata_pairs(response.metadata());
+        let mut stream = response.into_inner();
+        while let Some(message) = stream.message().await? {
+            received += 1;
+            if queue.try_send((received, message)).is_err() {
+                dropped.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        Ok(stream.trailers().await?.unwrap_or_default())
+    };
+    let result = tokio::select! {
+        result = call => result,
+        _ = cancel => Err(Status::cancelled("Cancelled by the client")),
+    };
+    // Let the queued messages go out before the closed event.
+    drop(queue);
+    let _ = emitter.await;
+
+    let (status, trailers) = match result {
+        Ok(trailers) => (Status::new(Code::Ok, ""), metadata_pairs(&trailers)),
+        Err(status) => {
+            let trailers = metadata_pairs(status.metadata());
+            (status, trailers)
+        }
+    };
+    GrpcStreamClosedEvent {
+        stream_id: stream_id.to_string(),
+        status_code: status.code() as u32,
+        status_name: status_name(status.code()).to_string(),
+        status_message: status.message().to_string(),
+        headers,
+        trailers,
+        received,
+        dropped: dropped.load(Ordering::SeqCst),
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+    }
+}
+
+/// Start a server-streaming call and return its stream id. `stream_id` is
+/// used as the id when set, so listeners can be in place before any event
+/// arrives.
+///
+/// Each response message is emitted as `grpc-stream-message`; the final
+/// status and trailers follow as `grpc-stream-closed`. The call has no
+/// deadline and runs until the server ends it or `grpc_cancel`.
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments)]
+pub async fn grpc_server_stream(
+    app: AppHandle,
+    endpoint: String,
+    service: String,
+    method: String,
+    request_json: String,
+    metadata: Vec<(String, String)>,
+    tls_options: Option<GrpcTlsOptions>,
+    registry_id: Option<String>,
+    stream_id: Option<String>,
+) -> Result<String, String> {
+    let stream_id = stream_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    if grpc_streams().contains_key(&stream_id) {
+        return Err(format!("Stream '{}' is already open", stream_id));
+    }
+    let (channel, descriptor) = resolve_method(
+        &app,
+        &endpoint,
+        tls_options.as_ref(),
+        registry_id.as_deref(),
+        &service,
+        &method,
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+    if descriptor.is_client_streaming() || !descriptor.is_server_streaming() {
+        return Err(format!(
+            "'{}' is not a server-streaming method",
+            descriptor.full_name()
+        ));
+    }
+    let (request, path) = build_request(&descriptor, &request_json, &metadata)?;
+
+    let (cancel, cancelled) = oneshot::channel();
+    grpc_streams().insert(stream_id.clone(), cancel);
+    let id = stream_id.clone();
+    tokio::spawn(async move {
+        let closed =
+            run_server_stream(&app, &id, channel, descriptor, request, path, cancelled).await;
+        grpc_streams().remove(&id);
+        let _ = app.emit(GRPC_STREAM_CLOSED_EVENT, closed);
+    });
+    Ok(stream_id)
+}
+
+/// Cancel a call started by `grpc_server_stream`. Returns `false` when no
+/// stream with that id is running.
+#[tauri::command]
+#[specta::specta]
+pub fn grpc_cancel(stream_id: String) -> Result<bool, String> {
+    match grpc_streams().remove(&stream_id) {
+        Some((_, cancel)) => {
+            let _ = cancel.send(());
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,1047 @@
+//! HAR 1.2 archives, as exported by browser dev tools and debugging proxies,
+//! read into collections and written from history. Archives can run to
+//! thousands of entries, so they're read as a stream and converted one entry
+//! at a time.
+
+use std::fmt;
+use std::io::Read;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use base64::Engine;
+use chrono::{SecondsFormat, Utc};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserializer;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::postman::split_query;
+use crate::helpers::rest::{
+    build_url_with_params, graphql_envelope, jsonrpc_body, method_from_string,
+    method_to_curl_string, mime_from_extension, soap_envelope, soap_headers,
+    PROGRESS_EMIT_INTERVAL,
+};
+use crate::types::{
+    ApiKeyLocation, ApiRequest, ApiResponse, AuthType, BodyType, Cookie, HarEntry,
+    HarImportProgressEvent, ImportResult, ImportedItem, MultipartField, MultipartValue, TimingInfo,
+};
+
+/// Event carrying `HarImportProgressEvent` payloads while an archive is read.
+pub const HAR_IMPORT_PROGRESS_EVENT: &str = "har-import-progress";
+
+/// Counts the bytes read so far, for progress events.
+struct CountingReader<R> {
+    inner: R,
+    read: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn list<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// The request body from `postData`: `params` when present, else `text`,
+/// decoded when it's marked as base64.
+fn post_data(post_data: &Value) -> Result<BodyType, String> {
+    let mime_type = field(post_data, "mimeType");
+    let content_type = (!mime_type.is_empty()).then(|| mime_type.to_string());
+    let params: Vec<&Value> = list(post_data, "params").collect();
+
+    if !params.is_empty() && mime_type.starts_with("multipart/form-data") {
+        let fields = params
+            .into_iter()
+            .map(|param| {
+                let name = field(param, "name").to_string();
+                let value = field(param, "value");
+                let value = match param.get("fileName").and_then(Value::as_str) {
+                    Some(filename) => MultipartValue::File {
+                        data: value.as_bytes().to_vec(),
+                        filename: filename.to_string(),
+                        content_type: param
+                            .get("contentType")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                    },
+                    None => MultipartValue::Text(value.to_string()),
+                };
+                MultipartField { name, value }
+            })
+            .collect();
+        return Ok(BodyType::Multipart { fields });
+    }
+    if !params.is_empty() && post_data.get("text").is_none() {
+        let fields = params
+            .into_iter()
+            .map(|param| {
+                (
+                    field(param, "name").to_string(),
+                    field(param, "value").to_string(),
+                )
+            })
+            .collect();
+        return Ok(BodyType::FormUrlEncoded { fields });
+    }
+
+    let text = field(post_data, "text");
+    if field(post_data, "encoding") == "base64" {
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .map_err(|e| format!("postData isn't valid base64: {}", e))?;
+        return Ok(BodyType::Binary {
+            data,
+            filename: None,
+        });
+    }
+    // `text` is the body as sent, so a multipart one keeps its boundary.
+    Ok(BodyType::Raw {
+        content: text.to_string(),
+        content_type,
+    })
+}
+
+fn entry_request(entry: &Value) -> Result<(String, ApiRequest), String> {
+    let request = entry.get("request").ok_or("no request")?;
+    let url = field(request, "url");
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("'{}' isn't an HTTP URL", url));
+    }
+    let method = field(request, "method").to_uppercase();
+
+    let (base, mut query_params) = split_query(url);
+    if query_params.is_empty() {
+        query_params = list(request, "queryString")
+            .map(|param| {
+                (
+                    field(param, "name").to_string(),
+                    field(param, "value").to_string(),
+                )
+            })
+            .collect();
+    }
+    let cookies: Vec<Cookie> = list(request, "cookies")
+        .map(|cookie| Cookie {
+            name: field(cookie, "name").to_string(),
+            value: field(cookie, "value").to_string(),
+            domain: None,
+            path: None,
+            expires: None,
+            expires_at: None,
+            is_expired: None,
+            http_only: None,
+            secure: None,
+            max_age: None,
+            same_site: None,
+        })
+        .collect();
+    let mut headers: Vec<(String, String)> = list(request, "headers")
+        .map(|header| (field(header, "name"), field(header, "value")))
+        // HTTP/2 pseudo-headers and values curl works out itself. The
+        // browser's `Accept-Encoding` may name codings curl can't decode.
+        .filter(|(name, _)| match name.to_ascii_lowercase().as_str() {
+            "content-length" | "accept-encoding" => false,
+            "cookie" => cookies.is_empty(),
+            name => !name.starts_with(':'),
+        })
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let body = match request.get("postData") {
+        Some(data) => post_data(data)?,
+        None => BodyType::None,
+    };
+    // curl writes its own boundary, which the captured header wouldn't match.
+    if let BodyType::Multipart { .. } = body {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+    }
+
+    let path = url::Url::parse(&base)
+        .map(|parsed| parsed.path().to_string())
+        .unwrap_or_else(|_| base.clone());
+    let name = format!("{} {}", method, path);
+    let api_request = ApiRequest {
+        method: method_from_string(&method),
+        url: base,
+        headers,
+        query_params,
+        body,
+        cookies,
+        ..Default::default()
+    };
+    Ok((name, api_request))
+}
+
+struct Page {
+    id: String,
+    title: String,
+}
+
+/// Converts entries as the parser reaches them.
+struct Importer<'a> {
+    pages: Vec<Page>,
+    /// Each entry's `pageref` and request, in archive order.
+    entries: Vec<(Option<String>, ImportedItem)>,
+    creator: Option<String>,
+    found_log: bool,
+    /// Entries read so far, converted or not.
+    seen: u32,
+    report: Vec<String>,
+    progress: &'a mut dyn FnMut(u32),
+}
+
+impl Importer<'_> {
+    fn entry(&mut self, entry: Value) {
+        self.seen += 1;
+        match entry_request(&entry) {
+            Ok((name, request)) => {
+                let page = entry
+                    .get("pageref")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                self.entries.push((
+                    page,
+                    ImportedItem::Request {
+                        name,
+                        description: None,
+                        request: Box::new(request),
+                        inherit_auth: false,
+                        example_response: None,
+                    },
+                ));
+            }
+            Err(e) => self
+                .report
+                .push(format!("Entry {}: skipped, {}", self.seen, e)),
+        }
+        (self.progress)(self.seen);
+    }
+
+    /// Pages become folders in archive order; entries without one stay at the top level.
+    fn items(self) -> Vec<ImportedItem> {
+        let mut folders: Vec<(String, Vec<ImportedItem>)> = self
+            .pages
+            .iter()
+            .map(|page| (page.id.clone(), Vec::new()))
+            .collect();
+        let mut top_level = Vec::new();
+        for (page, item) in self.entries {
+            let Some(page) = page else {
+                top_level.push(item);
+                continue;
+            };
+            match folders.iter_mut().find(|(id, _)| *id == page) {
+                Some((_, items)) => items.push(item),
+                None => folders.push((page, vec![item])),
+            }
+        }
+        let mut items: Vec<ImportedItem> = folders
+            .into_iter()
+            .filter(|(_, items)| !items.is_empty())
+            .map(|(id, items)| {
+                let title = self
+                    .pages
+                    .iter()
+                    .find(|page| page.id == id)
+                    .map(|page| page.title.clone())
+                    .filter(|title| !title.is_empty());
+                ImportedItem::Folder {
+                    name: title.unwrap_or(id),
+                    description: None,
+                    items,
+                }
+            })
+            .collect();
+        items.extend(top_level);
+        items
+    }
+}
+
+/// The archive's top-level object.
+struct Document<'i, 'a>(&'i mut Importer<'a>);
+
+/// `log`, whose `entries` are converted while the parser walks them.
+struct Log<'i, 'a>(&'i mut Importer<'a>);
+
+struct Entries<'i, 'a>(&'i mut Importer<'a>);
+
+impl<'de> DeserializeSeed<'de> for Document<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Document<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a HAR object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "log" {
+                self.0.found_log = true;
+                map.next_value_seed(Log(&mut *self.0))?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Log<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Log<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a HAR log")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "entries" => map.next_value_seed(Entries(&mut *self.0))?,
+                "pages" => {
+                    let pages: Vec<Value> = map.next_value()?;
+                    self.0.pages = pages
+                        .iter()
+                        .map(|page| Page {
+                            id: field(page, "id").to_string(),
+                            title: field(page, "title").to_string(),
+                        })
+                        .collect();
+                }
+                "creator" => {
+                    let creator: Value = map.next_value()?;
+                    let name = [field(&creator, "name"), field(&creator, "version")].join(" ");
+                    self.0.creator = Some(name.trim().to_string()).filter(|n| !n.is_empty());
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Entries<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Entries<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of HAR entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(entry) = seq.next_element::<Value>()? {
+            self.0.entry(entry);
+        }
+        Ok(())
+    }
+}
+
+/// Reads the archive at `path`, passing progress to `on_progress` at most
+/// every `PROGRESS_EMIT_INTERVAL` and once more at the end.
+fn import_archive(
+    path: &str,
+    on_progress: &mut dyn FnMut(HarImportProgressEvent),
+) -> Result<ImportResult, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0) as f64;
+    let read = Arc::new(AtomicU64::new(0));
+    let reader = CountingReader {
+        inner: std::io::BufReader::new(file),
+        read: Arc::clone(&read),
+    };
+
+    let mut last_emit: Option<Instant> = None;
+    let mut emit = |entries: u32, force: bool| {
+        if force || last_emit.is_none_or(|t| t.elapsed() >= PROGRESS_EMIT_INTERVAL) {
+            last_emit = Some(Instant::now());
+            on_progress(HarImportProgressEvent {
+                path: path.to_string(),
+                entries,
+                read_bytes: read.load(Ordering::Relaxed) as f64,
+                total_bytes,
+            });
+        }
+    };
+    let mut progress = |entries| emit(entries, false);
+    let mut importer = Importer {
+        pages: Vec::new(),
+        entries: Vec::new(),
+        creator: None,
+        found_log: false,
+        seen: 0,
+        report: Vec::new(),
+        progress: &mut progress,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    Document(&mut importer)
+        .deserialize(&mut deserializer)
+        .and_then(|()| deserializer.end())
+        .map_err(|e| format!("Invalid HAR JSON: {}", e))?;
+    if !importer.found_log {
+        return Err("Not a HAR file: `log` is missing".to_string());
+    }
+
+    let name = Path::new(path)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "Imported HAR".to_string());
+    let description = importer
+        .creator
+        .take()
+        .map(|creator| format!("Captured with {}", creator));
+    let report = std::mem::take(&mut importer.report);
+    let entries = importer.seen;
+    let items = importer.items();
+    emit(entries, true);
+
+    Ok(ImportResult {
+        name,
+        description,
+        items,
+        auth: None,
+        environment: None,
+        report,
+    })
+}
+
+/// Read a HAR archive into a collection with one request per entry, in
+/// capture order and grouped into a folder per page. The file is read as a
+/// stream, with `har-import-progress` events as entries are converted.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_har(app: AppHandle, path: String) -> Result<ImportResult, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    tokio::task::spawn_blocking(move || {
+        import_archive(&path, &mut |event| {
+            let _ = app.emit(HAR_IMPORT_PROGRESS_EVENT, event);
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn name_value(name: &str, value: &str) -> Value {
+    json!({ "name": name, "value": value })
+}
+
+/// A size from `SizeInfo`, or `-1` (unknown) without a response to take it from.
+fn size(bytes: Option<u32>) -> Value {
+    bytes.map_or(json!(-1), |bytes| json!(bytes))
+}
+
+/// `postData` for a request body; `None` for no body.
+fn har_post_data(body: &BodyType) -> Result<Option<Value>, String> {
+    let text = |mime_type: &str, text: String| json!({ "mimeType": mime_type, "text": text });
+    Ok(Some(match body {
+        BodyType::None => return Ok(None),
+        BodyType::Raw {
+            content,
+            content_type,
+        } => text(
+            content_type.as_deref().unwrap_or("text/plain"),
+            content.clone(),
+        ),
+        BodyType::FormUrlEncoded { fields } => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort();
+            let encoded = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(fields.iter().copied())
+                .finish();
+            let mut data = text("application/x-www-form-urlencoded", encoded);
+            data["params"] = fields
+                .into_iter()
+                .map(|(name, value)| name_value(name, value))
+                .collect();
+            data
+        }
+        // The boundary curl picks isn't known here, so the parts are listed
+        // as params instead of the body text.
+        BodyType::Multipart { fields } => {
+            let params: Vec<Value> = fields
+                .iter()
+                .map(|field| match &field.value {
+                    MultipartValue::Text(value) => name_value(&field.name, value),
+                    MultipartValue::File {
+                        filename,
+                        content_type,
+                        ..
+                    } => json!({
+                        "name": field.name,
+                        "fileName": filename,
+                        "contentType": content_type.as_deref().unwrap_or("application/octet-stream"),
+                    }),
+                    MultipartValue::FilePath {
+                        path,
+                        filename,
+                        content_type,
+                    } => {
+                        let filename = filename.clone().unwrap_or_else(|| {
+                            Path::new(path)
+                                .file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_default()
+                        });
+                        let content_type = content_type
+                            .as_deref()
+                            .unwrap_or_else(|| mime_from_extension(Path::new(path)));
+                        json!({ "name": field.name, "fileName": filename, "contentType": content_type })
+                    }
+                })
+                .collect();
+            json!({ "mimeType": "multipart/form-data", "params": params })
+        }
+        BodyType::Binary { data, .. } => match std::str::from_utf8(data) {
+            Ok(utf8) => text("application/octet-stream", utf8.to_string()),
+            // Not part of HAR 1.2, but read by most tools that import HAR.
+            Err(_) => json!({
+                "mimeType": "application/octet-stream",
+                "text": base64::engine::general_purpose::STANDARD.encode(data),
+                "encoding": "base64",
+            }),
+        },
+        BodyType::RawFile { path, content_type } | BodyType::BinaryFile { path, content_type } => {
+            let mime_type = content_type
+                .as_deref()
+                .unwrap_or_else(|| mime_from_extension(Path::new(path)));
+            json!({ "mimeType": mime_type, "text": "", "comment": format!("Sent from {}", path) })
+        }
+        BodyType::GraphQL {
+            query,
+            variables,
+            operation_name,
+        } => text(
+            "application/json",
+            graphql_envelope(query, variables.as_deref(), operation_name.as_deref())?,
+        ),
+        BodyType::Soap {
+            action,
+            envelope_body,
+            soap_version,
+        } => text(
+            &soap_headers(action, *soap_version).0,
+            soap_envelope(envelope_body, *soap_version)?,
+        ),
+        BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } => text(
+            "application/json",
+            serde_json::to_string(&jsonrpc_body(body)?).map_err(|e| e.to_string())?,
+        ),
+    }))
+}
+
+fn har_request(req: &ApiRequest, response: Option<&ApiResponse>) -> Result<Value, String> {
+    let mut headers: Vec<(String, String)> = req.headers.clone();
+    let mut cookies: Vec<(String, String)> = req
+        .cookies
+        .iter()
+        .map(|cookie| (cookie.name.clone(), cookie.value.clone()))
+        .collect();
+    let mut api_key_param = None;
+    match &req.auth {
+        AuthType::Basic { username, password } => {
+            let credentials = format!("{}:{}", username, password);
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            headers.push(("Authorization".to_string(), format!("Basic {}", encoded)));
+        }
+        AuthType::Bearer { token }
+        | AuthType::OAuth2 {
+            access_token: token,
+        } => headers.push(("Authorization".to_string(), format!("Bearer {}", token))),
+        AuthType::ApiKey { key, value, add_to } => match add_to {
+            ApiKeyLocation::Header => headers.push((key.clone(), value.clone())),
+            ApiKeyLocation::Query => api_key_param = Some((key.as_str(), value.as_str())),
+            ApiKeyLocation::Cookie => cookies.push((key.clone(), value.clone())),
+        },
+        _ => {}
+    }
+    if !cookies.is_empty() {
+        let header: Vec<String> = cookies
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        headers.push(("Cookie".to_string(), header.join("; ")));
+    }
+
+    let post_data = har_post_data(&req.body)?;
+    let has_content_type = headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+    if let Some(mime_type) = post_data
+        .as_ref()
+        .and_then(|data| data["mimeType"].as_str())
+        .filter(|_| !has_content_type)
+    {
+        headers.push(("Content-Type".to_string(), mime_type.to_string()));
+    }
+
+    // Collection requests may hold `{{variables}}` the url crate won't parse.
+    let url = build_url_with_params(&req.url, &req.query_params, api_key_param)
+        .unwrap_or_else(|_| req.url.clone());
+    let query: Vec<Value> = url::Url::parse(&url)
+        .map(|parsed| {
+            parsed
+                .query_pairs()
+                .map(|(name, value)| name_value(&name, &value))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut request = json!({
+        "method": method_to_curl_string(&req.method),
+        "url": url,
+        "httpVersion": response.map_or("HTTP/1.1", |r| r.http_version.as_str()),
+        "cookies": cookies
+            .iter()
+            .map(|(name, value)| name_value(name, value))
+            .collect::<Vec<_>>(),
+        "headers": headers
+            .iter()
+            .map(|(name, value)| name_value(name, value))
+            .collect::<Vec<_>>(),
+        "queryString": query,
+        "headersSize": size(response.map(|r| r.request_size.headers_bytes)),
+        "bodySize": match (&post_data, response) {
+            (None, _) => json!(0),
+            (Some(_), response) => size(response.map(|r| r.request_size.body_bytes)),
+        },
+    });
+    if let Some(post_data) = post_data {
+        request["postData"] = post_data;
+    }
+    Ok(request)
+}
+
+fn har_cookie(cookie: &Cookie) -> Value {
+    let mut exported = name_value(&cookie.name, &cookie.value);
+    if let Some(path) = &cookie.path {
+        exported["path"] = json!(path);
+    }
+    if let Some(domain) = &cookie.domain {
+        exported["domain"] = json!(domain);
+    }
+    if let Some(expires) = cookie
+        .expires_at
+        .and_then(|at| chrono::DateTime::from_timestamp(at, 0))
+    {
+        exported["expires"] = json!(expires.to_rfc3339_opts(SecondsFormat::Millis, true));
+    }
+    if let Some(http_only) = cookie.http_only {
+        exported["httpOnly"] = json!(http_only);
+    }
+    if let Some(secure) = cookie.secure {
+        exported["secure"] = json!(secure);
+    }
+    if let Some(same_site) = &cookie.same_site {
+        exported["sameSite"] = json!(same_site);
+    }
+    exported
+}
+
+fn har_response(response: Option<&ApiResponse>) -> Value {
+    let Some(response) = response else {
+        return json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "x-unknown" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+            "comment": "No response was received",
+        });
+    };
+
+    let header = |wanted: &str| {
+        response
+            .headers_list
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+            .map(|(_, value)| value.as_str())
+    };
+    let engine = base64::engine::general_purpose::STANDARD;
+    let body = engine.decode(&response.body_base64).unwrap_or_default();
+    let mut content = json!({
+        "size": body.len(),
+        "mimeType": header("content-type").unwrap_or("x-unknown"),
+    });
+    // Transcoded text when the body wasn't UTF-8 to begin with.
+    let text = response
+        .body_text_base64
+        .as_ref()
+        .and_then(|text| engine.decode(text).ok())
+        .unwrap_or_else(|| body.clone());
+    if response.body_file_path.is_some() || response.saved_path.is_some() {
+        content["comment"] = json!("The body was written to disk and isn't included");
+    } else {
+        match String::from_utf8(text) {
+            Ok(text) => content["text"] = json!(text),
+            Err(_) => {
+                content["text"] = json!(response.body_base64);
+                content["encoding"] = json!("base64");
+            }
+        }
+    }
+
+    json!({
+        "status": response.status,
+        "statusText": response.status_text,
+        "httpVersion": response.http_version,
+        "cookies": response.cookies.iter().map(har_cookie).collect::<Vec<_>>(),
+        "headers": response
+            .headers_list
+            .iter()
+            .map(|(name, value)| name_value(name, value))
+            .collect::<Vec<_>>(),
+        "content": content,
+        "redirectURL": header("location").unwrap_or_default(),
+        "headersSize": response.response_size.headers_bytes,
+        "bodySize": response.response_size.body_bytes,
+    })
+}
+
+/// HAR timings for the final hop. Time spent on earlier redirect hops is
+/// counted as `blocked`, so the phases add up to the response's total.
+fn har_timings(timing: &TimingInfo, tls: bool) -> Value {
+    json!({
+        "blocked": timing.redirect_ms,
+        "dns": timing.dns_lookup_ms,
+        // HAR counts the TLS handshake as part of connecting.
+        "connect": timing.tcp_handshake_ms + timing.tls_handshake_ms,
+        "ssl": if tls { timing.tls_handshake_ms } else { -1.0 },
+        "send": timing.transfer_start_ms,
+        "wait": timing.ttfb_ms,
+        "receive": timing.content_download_ms,
+    })
+}
+
+fn har_entry(entry: &HarEntry) -> Result<Value, String> {
+    let response = entry.response.as_ref();
+    let started = entry
+        .started_at
+        .clone()
+        .unwrap_or_else(|| Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true));
+    let (time, timings) = match response {
+        Some(response) => (
+            response.timing.redirect_ms
+                + response.timing.dns_lookup_ms
+                + response.timing.tcp_handshake_ms
+                + response.timing.tls_handshake_ms
+                + response.timing.transfer_start_ms
+                + response.timing.ttfb_ms
+                + response.timing.content_download_ms,
+            har_timings(&response.timing, response.tls.is_some()),
+        ),
+        None => (0.0, json!({ "send": 0, "wait": 0, "receive": 0 })),
+    };
+
+    let mut exported = json!({
+        "startedDateTime": started,
+        "time": time,
+        "request": har_request(&entry.request, response)?,
+        "response": har_response(response),
+        "cache": {},
+        "timings": timings,
+    });
+    // `remote_addr` is `ip:port`, with IPv6 addresses in brackets.
+    let server_ip = response
+        .and_then(|r| r.remote_addr.as_deref())
+        .and_then(|addr| addr.rsplit_once(':'))
+        .map(|(ip, _)| ip.trim_start_matches('[').trim_end_matches(']'));
+    if let Some(ip) = server_ip {
+        exported["serverIPAddress"] = json!(ip);
+    }
+    Ok(exported)
+}
+
+/// The archive for `entries`, credited to the app `creator` and `version`.
+fn har_document(entries: &[HarEntry], creator: &str, version: &str) -> Result<Value, String> {
+    let entries = entries
+        .iter()
+        .map(har_entry)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": creator, "version": version },
+            "entries": entries,
+        }
+    }))
+}
+
+/// Write requests and their responses as a HAR 1.2 archive. History lives in
+/// the frontend, so the caller passes the entries; requests from a collection
+/// can be exported without responses. Headers are written as they were sent,
+/// auth included.
+#[tauri::command]
+#[specta::specta]
+pub fn export_har(app: AppHandle, entries: Vec<HarEntry>, path: String) -> Result<(), String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+
+    let document = har_document(
+        &entries,
+        &app.package_info().name,
+        &app.package_info().version.to_string(),
+    )?;
+    let text = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::types::Methods;
+
+    const SCHEMA: &str = include_str!("testdata/har-1.2-schema.json");
+
+    fn schema_errors(document: &Value) -> Vec<String> {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        validator
+            .iter_errors(document)
+            .map(|error| format!("{} at {}", error, error.instance_path()))
+            .collect()
+    }
+
+    fn response(body: &[u8], tls: bool) -> ApiResponse {
+        let timing = json!({
+            "total_ms": 42.5, "redirect_ms": 3.0, "redirect_count": 1, "dns_lookup_ms": 1.5,
+            "tcp_handshake_ms": 2.0, "tls_handshake_ms": 4.0, "transfer_start_ms": 0.5,
+            "ttfb_ms": 30.0, "content_download_ms": 1.5,
+        });
+        let size = json!({ "headers_bytes": 120, "body_bytes": body.len(), "total_bytes": 0 });
+        let mut response = json!({
+            "status": 200,
+            "status_text": "OK",
+            "headers": {},
+            "headers_list": [
+                ["Content-Type", "application/json"],
+                ["Set-Cookie", "sid=1; Path=/; HttpOnly"],
+            ],
+            "cookies": [{
+                "name": "sid",
+                "value": "1",
+                "path": "/",
+                "domain": "example.com",
+                "expires_at": 1_900_000_000,
+                "http_only": true,
+                "secure": true,
+                "same_site": "Lax",
+            }],
+            "body_base64": base64::engine::general_purpose::STANDARD.encode(body),
+            "timing": timing,
+            "request_size": size,
+            "response_size": size,
+            "redirects": [],
+            "attempts": 1,
+            "attempt_statuses": [200],
+            "remote_addr": "[2001:db8::1]:443",
+            "connection": { "reused": false, "num_connects": 1 },
+            "tls_exception_used": false,
+            "http_version": "HTTP/2",
+            "available_renderers": [],
+            "soap_fault": false,
+            "protocol_used": "h2",
+            "negotiated_h3": false,
+            "partial": false,
+            "truncated": false,
+            "warnings": [],
+        });
+        if tls {
+            response["tls"] = json!({ "certificates": [], "leaf_expiring": false });
+        }
+        serde_json::from_value(response).unwrap()
+    }
+
+    fn entry(request: ApiRequest, response: Option<ApiResponse>) -> HarEntry {
+        HarEntry {
+            request,
+            response,
+            started_at: Some("2024-05-01T12:00:00.000Z".to_string()),
+        }
+    }
+
+    fn sample_entries() -> Vec<HarEntry> {
+        vec![
+            entry(
+                ApiRequest {
+                    method: Methods::GET,
+                    url: "https://example.com/users?page=2".to_string(),
+                    query_params: vec![("q".to_string(), "a b".to_string())],
+                    auth: AuthType::ApiKey {
+                        key: "session".to_string(),
+                        value: "k".to_string(),
+                        add_to: ApiKeyLocation::Cookie,
+                    },
+                    ..Default::default()
+                },
+                Some(response(b"[{\"id\":1}]", true)),
+            ),
+            entry(
+                ApiRequest {
+                    method: Methods::POST,
+                    url: "http://example.com/form".to_string(),
+                    body: BodyType::FormUrlEncoded {
+                        fields: HashMap::from([("name".to_string(), "ana".to_string())]),
+                    },
+                    auth: AuthType::Basic {
+                        username: "u".to_string(),
+                        password: "p".to_string(),
+                    },
+                    ..Default::default()
+                },
+                Some(response(&[0xff, 0xfe, 0x00], false)),
+            ),
+            entry(
+                ApiRequest {
+                    method: Methods::PUT,
+                    url: "https://example.com/upload".to_string(),
+                    body: BodyType::Multipart {
+                        fields: vec![
+                            MultipartField {
+                                name: "note".to_string(),
+                                value: MultipartValue::Text("hi".to_string()),
+                            },
+                            MultipartField {
+                                name: "doc".to_string(),
+                                value: MultipartValue::FilePath {
+                                    path: "/tmp/doc.pdf".to_string(),
+                                    filename: None,
+                                    content_type: None,
+                                },
+                            },
+                        ],
+                    },
+                    ..Default::default()
+                },
+                None,
+            ),
+            entry(
+                ApiRequest {
+                    method: Methods::PATCH,
+                    url: "{{host}}/blob".to_string(),
+                    body: BodyType::Binary {
+                        data: vec![0, 159, 146, 150],
+                        filename: None,
+                    },
+                    ..Default::default()
+                },
+                None,
+            ),
+            entry(
+                ApiRequest {
+                    method: Methods::POST,
+                    url: "https://example.com/graphql".to_string(),
+                    body: BodyType::GraphQL {
+                        query: "{ me { id } }".to_string(),
+                        variables: None,
+                        operation_name: None,
+                    },
+                    ..Default::default()
+                },
+                None,
+            ),
+        ]
+    }
+
+    #[test]
+    fn export_matches_the_har_schema() {
+        let document = har_document(&sample_entries(), "mandy", "1.0.0").unwrap();
+        assert_eq!(schema_errors(&document), Vec::<String>::new());
+    }
+
+    #[test]
+    fn timings_add_up_to_the_entry_time() {
+        let document = har_document(&sample_entries(), "mandy", "1.0.0").unwrap();
+        let entry = &document["log"]["entries"][0];
+        let phases: f64 = ["blocked", "dns", "connect", "send", "wait", "receive"]
+            .iter()
+            .map(|phase| entry["timings"][phase].as_f64().unwrap())
+            .sum();
+        assert_eq!(entry["time"], phases);
+        assert_eq!(entry["timings"]["ssl"], 4.0);
+        assert_eq!(entry["serverIPAddress"], "2001:db8::1");
+        assert_eq!(document["log"]["entries"][1]["timings"]["ssl"], -1.0);
+    }
+
+    #[test]
+    fn exported_archive_imports_back() {
+        let document = har_document(&sample_entries(), "mandy", "1.0.0").unwrap();
+        let path = std::env::temp_dir().join(format!("mandy-{}.har", uuid::Uuid::new_v4()));
+        std::fs::write(&path, document.to_string()).unwrap();
+        let imported = import_archive(path.to_str().unwrap(), &mut |_| {});
+        std::fs::remove_file(&path).unwrap();
+        let imported = imported.unwrap();
+        assert_eq!(imported.items.len(), 4);
+        // Only the request left with an unresolved `{{host}}` can't come back.
+        assert_eq!(imported.report.len(), 1);
+        assert!(imported.report[0].starts_with("Entry 4: skipped"));
+        let ImportedItem::Request { request, .. } = &imported.items[1] else {
+            panic!("expected a request");
+        };
+        // The form comes back as the text it was sent as.
+        let BodyType::Raw {
+            content,
+            content_type,
+        } = &request.body
+        else {
+            panic!("expected a raw body");
+        };
+        assert_eq!(content, "name=ana");
+        assert_eq!(
+            content_type.as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+    }
+}
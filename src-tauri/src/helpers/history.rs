@@ -0,0 +1,334 @@
+//! Sent requests kept under `history/` in the workspace dir, one
+//! `<sent_at_ms>_<id>.json` file per entry so age and size come from a
+//! directory listing alone. Pinning moves the file into `history/pinned/`,
+//! which pruning never looks at. Retention limits are kept in
+//! `history_retention.json`.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use tauri::AppHandle;
+
+use crate::helpers::collections::write_atomic;
+use crate::helpers::environments::active_environment_id;
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::{
+    ApiRequest, ApiResponse, HistoryEntry, HistoryPruneResult, HistoryRetention, HistoryStats,
+};
+
+/// Serializes changes to the history dir.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Entries recorded since the last automatic prune.
+static RECORDED: AtomicU32 = AtomicU32::new(0);
+
+static PRUNING: AtomicBool = AtomicBool::new(false);
+
+/// Files removed per hold of `STORE_LOCK`, so recording isn't held up by a
+/// long prune.
+const PRUNE_BATCH: usize = 100;
+
+fn history_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("history"))
+}
+
+fn retention_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("history_retention.json"))
+}
+
+fn load_retention(app: &AppHandle) -> Result<HistoryRetention, String> {
+    match std::fs::read(retention_path(app)?) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(HistoryRetention::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+struct StoredEntry {
+    path: PathBuf,
+    id: String,
+    sent_at_ms: i64,
+    bytes: u64,
+    pinned: bool,
+}
+
+/// The entries in `dir`; other files are ignored.
+fn scan_dir(dir: &Path, pinned: bool) -> Result<Vec<StoredEntry>, String> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+    Ok(entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            let (sent_at_ms, id) = name.strip_suffix(".json")?.split_once('_')?;
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(StoredEntry {
+                path: entry.path(),
+                id: id.to_string(),
+                sent_at_ms: sent_at_ms.parse().ok()?,
+                bytes: metadata.len(),
+                pinned,
+            })
+        })
+        .collect())
+}
+
+/// Every entry, pinned or not, oldest first.
+fn scan(app: &AppHandle) -> Result<Vec<StoredEntry>, String> {
+    let dir = history_dir(app)?;
+    let mut entries = scan_dir(&dir, false)?;
+    entries.extend(scan_dir(&dir.join("pinned"), true)?);
+    entries.sort_by_key(|entry| entry.sent_at_ms);
+    Ok(entries)
+}
+
+/// The unpinned entries retention removes, oldest first: those past the age
+/// limit, then as many more as it takes to get under the count and size limits.
+fn prune_plan<'a>(
+    entries: &'a [StoredEntry],
+    retention: &HistoryRetention,
+    now_ms: i64,
+) -> Vec<&'a StoredEntry> {
+    let cutoff = retention
+        .max_age_days
+        .map(|days| now_ms - i64::from(days) * 86_400_000);
+    let max_bytes = retention.max_total_mb.map(|mb| u64::from(mb) * 1024 * 1024);
+    let mut count = entries.len();
+    let mut bytes: u64 = entries.iter().map(|entry| entry.bytes).sum();
+    let mut plan = Vec::new();
+    for entry in entries.iter().filter(|entry| !entry.pinned) {
+        let expired = cutoff.is_some_and(|cutoff| entry.sent_at_ms < cutoff);
+        let too_many = retention
+            .max_entries
+            .is_some_and(|max| count > max as usize);
+        let too_big = max_bytes.is_some_and(|max| bytes > max);
+        // Later entries are newer and the totals only shrink.
+        if !(expired || too_many || too_big) {
+            break;
+        }
+        plan.push(entry);
+        count -= 1;
+        bytes -= entry.bytes;
+    }
+    plan
+}
+
+fn read_entry(stored: &StoredEntry) -> Result<HistoryEntry, String> {
+    let bytes = std::fs::read(&stored.path).map_err(|e| e.to_string())?;
+    let entry: HistoryEntry = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+    // Where the file is, not what it says, decides whether it's pinned.
+    Ok(HistoryEntry {
+        pinned: stored.pinned,
+        ..entry
+    })
+}
+
+/// Cut a base64 body to its first `max` bytes (rounded down to a multiple of
+/// three, so it stays valid base64). Returns whether anything was cut.
+fn truncate_base64(body: &mut String, max: u32) -> bool {
+    let keep = max as usize / 3 * 4;
+    if body.len() <= keep {
+        return false;
+    }
+    body.truncate(keep);
+    true
+}
+
+fn prune_blocking(app: &AppHandle) -> Result<HistoryPruneResult, String> {
+    let retention = load_retention(app)?;
+    let plan: Vec<(PathBuf, u64)> = {
+        let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+        let entries = scan(app)?;
+        prune_plan(&entries, &retention, Utc::now().timestamp_millis())
+            .into_iter()
+            .map(|entry| (entry.path.clone(), entry.bytes))
+            .collect()
+    };
+
+    let mut result = HistoryPruneResult {
+        removed: 0,
+        freed_bytes: 0.0,
+    };
+    for batch in plan.chunks(PRUNE_BATCH) {
+        let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+        for (path, bytes) in batch {
+            match std::fs::remove_file(path) {
+                Ok(()) => {
+                    result.removed += 1;
+                    result.freed_bytes += *bytes as f64;
+                }
+                // Pinned or deleted since the plan was made.
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Only one prune runs at a time; one started during another removes nothing.
+async fn prune(app: AppHandle) -> Result<HistoryPruneResult, String> {
+    if PRUNING.swap(true, Ordering::SeqCst) {
+        return Ok(HistoryPruneResult {
+            removed: 0,
+            freed_bytes: 0.0,
+        });
+    }
+    let result = tokio::task::spawn_blocking(move || prune_blocking(&app))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|result| result);
+    PRUNING.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Prune without waiting for it, as done at startup and every
+/// `HistoryRetention::prune_every` recorded entries.
+pub(crate) fn prune_in_background(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let _ = prune(app).await;
+    });
+}
+
+/// Keep a sent request and its response. `request` is the one before
+/// `{{variable}}` resolution, so secrets stay out of history; the environment
+/// it was resolved from is recorded with it. The response body is truncated
+/// to the retention's `max_body_bytes`.
+#[tauri::command]
+#[specta::specta]
+pub fn record_history(
+    app: AppHandle,
+    request: ApiRequest,
+    response: Option<ApiResponse>,
+) -> Result<HistoryEntry, String> {
+    let retention = load_retention(&app)?;
+    let environment_id = match request.environment_id {
+        Some(ref id) => Some(id.clone()),
+        None => active_environment_id(&app)?,
+    };
+    let now = Utc::now();
+    let mut entry = HistoryEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        request,
+        response,
+        sent_at: now.to_rfc3339(),
+        environment_id,
+        pinned: false,
+        body_truncated: false,
+    };
+    if let (Some(response), Some(max)) = (entry.response.as_mut(), retention.max_body_bytes) {
+        let raw = truncate_base64(&mut response.body_base64, max);
+        let text = response
+            .body_text_base64
+            .as_mut()
+            .is_some_and(|text| truncate_base64(text, max));
+        entry.body_truncated = raw || text;
+    }
+
+    let path = history_dir(&app)?.join(format!("{}_{}.json", now.timestamp_millis(), entry.id));
+    let json = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+    {
+        let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+        write_atomic(&path, &json)?;
+    }
+
+    if RECORDED.fetch_add(1, Ordering::SeqCst) + 1 >= retention.prune_every.max(1) {
+        RECORDED.store(0, Ordering::SeqCst);
+        prune_in_background(app);
+    }
+    Ok(entry)
+}
+
+/// Newest first, at most `limit`. Entries that can't be read are skipped.
+#[tauri::command]
+#[specta::specta]
+pub fn list_history(app: AppHandle, limit: Option<u32>) -> Result<Vec<HistoryEntry>, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let entries = scan(&app)?;
+    Ok(entries
+        .iter()
+        .rev()
+        .filter_map(|stored| read_entry(stored).ok())
+        .take(limit.map_or(usize::MAX, |limit| limit as usize))
+        .collect())
+}
+
+/// Returns `false` when there was no such entry.
+#[tauri::command]
+#[specta::specta]
+pub fn set_history_pinned(app: AppHandle, id: String, pinned: bool) -> Result<bool, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let Some(stored) = scan(&app)?.into_iter().find(|entry| entry.id == id) else {
+        return Ok(false);
+    };
+    if stored.pinned != pinned {
+        let dir = history_dir(&app)?;
+        let dir = if pinned { dir.join("pinned") } else { dir };
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let name = stored
+            .path
+            .file_name()
+            .ok_or("Invalid history entry path")?;
+        std::fs::rename(&stored.path, dir.join(name)).map_err(|e| e.to_string())?;
+    }
+    Ok(true)
+}
+
+/// Delete an entry, pinned or not. Returns `false` when there was no such entry.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_history_entry(app: AppHandle, id: String) -> Result<bool, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let Some(stored) = scan(&app)?.into_iter().find(|entry| entry.id == id) else {
+        return Ok(false);
+    };
+    std::fs::remove_file(&stored.path).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_history_retention(app: AppHandle) -> Result<HistoryRetention, String> {
+    load_retention(&app)
+}
+
+/// Entries already recorded keep their bodies; new limits apply at the next prune.
+#[tauri::command]
+#[specta::specta]
+pub fn set_history_retention(app: AppHandle, retention: HistoryRetention) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(&retention).map_err(|e| e.to_string())?;
+    write_atomic(&retention_path(&app)?, &json)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_history_stats(app: AppHandle) -> Result<HistoryStats, String> {
+    let retention = load_retention(&app)?;
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let entries = scan(&app)?;
+    let plan = prune_plan(&entries, &retention, Utc::now().timestamp_millis());
+    Ok(HistoryStats {
+        entries: entries.len() as u32,
+        pinned: entries.iter().filter(|entry| entry.pinned).count() as u32,
+        total_bytes: entries.iter().map(|entry| entry.bytes as f64).sum(),
+        oldest_sent_at: entries
+            .first()
+            .and_then(|entry| DateTime::<Utc>::from_timestamp_millis(entry.sent_at_ms))
+            .map(|sent_at| sent_at.to_rfc3339()),
+        prunable_entries: plan.len() as u32,
+        prunable_bytes: plan.iter().map(|entry| entry.bytes as f64).sum(),
+    })
+}
+
+/// Remove unpinned entries past the retention limits, in batches so recording
+/// isn't blocked meanwhile. Returns nothing removed if a prune was already running.
+#[tauri::command]
+#[specta::specta]
+pub async fn prune_history(app: AppHandle) -> Result<HistoryPruneResult, String> {
+    prune(app).await
+}
@@ -0,0 +1,1012 @@
+//! `.http` / `.rest` files in the VS Code REST Client format: requests
+//! separated by `###` lines, each a request line, headers, a blank line and
+//! the body. `{{variable}}` references are kept as written, read and written.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::curl_command::parse_command;
+use crate::helpers::jwt::sign_jwt;
+use crate::helpers::rest::{
+    graphql_envelope, jsonrpc_body, method_from_string, method_to_curl_string, mime_from_extension,
+    soap_envelope, soap_headers,
+};
+use crate::types::{
+    ApiKeyLocation, ApiRequest, AuthType, BodyType, MultipartField, MultipartValue,
+};
+
+/// Fixed so re-exporting an unchanged request gives the same file.
+const MULTIPART_BOUNDARY: &str = "MandyFormBoundary";
+
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+fn is_comment(line: &str) -> bool {
+    line.starts_with('#') || line.starts_with("//")
+}
+
+/// The `@key value` metadata in a comment line, like `# @name login`.
+fn metadata(line: &str) -> Option<(&str, &str)> {
+    let comment = line
+        .strip_prefix("//")
+        .or_else(|| line.strip_prefix('#'))?
+        .trim_start();
+    let rest = comment.strip_prefix('@')?;
+    Some(match rest.split_once(char::is_whitespace) {
+        Some((key, value)) => (key, value.trim()),
+        None => (rest, ""),
+    })
+}
+
+/// `METHOD URL [HTTP/x]`, where the method is optional and defaults to GET.
+fn request_line(line: &str) -> (String, String) {
+    let mut parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() > 1
+        && parts
+            .last()
+            .is_some_and(|last| last.to_ascii_uppercase().starts_with("HTTP/"))
+    {
+        parts.pop();
+    }
+    match parts.as_slice() {
+        [method, rest @ ..]
+            if !rest.is_empty() && METHODS.contains(&method.to_ascii_uppercase().as_str()) =>
+        {
+            (method.to_ascii_uppercase(), rest.join(" "))
+        }
+        _ => ("GET".to_string(), parts.join(" ")),
+    }
+}
+
+/// REST Client encodes `Basic user:password` and `Basic user password`
+/// itself; an already-encoded value is left as a header.
+fn basic_credentials(value: &str) -> Option<AuthType> {
+    let credentials = value.strip_prefix("Basic ")?.trim();
+    let (username, password) = credentials
+        .split_once(':')
+        .or_else(|| credentials.split_once(' '))?;
+    Some(AuthType::Basic {
+        username: username.to_string(),
+        password: password.trim().to_string(),
+    })
+}
+
+/// A body of `< ./file` (or `<@ ./file` with its variables processed) reads
+/// the file, relative to the `.http` file.
+fn file_reference(body: &str, dir: &Path) -> Option<String> {
+    let line = body.trim();
+    if line.contains('\n') {
+        return None;
+    }
+    let rest = line.strip_prefix('<')?;
+    // `<@` and `<@latin1` take an optional encoding after the `@`.
+    let rest = match rest.strip_prefix('@') {
+        Some(rest) => rest.trim_start_matches(|c: char| !c.is_whitespace()),
+        None => rest,
+    };
+    let path = rest.trim();
+    let path = path.strip_prefix("./").unwrap_or(path);
+    (!path.is_empty()).then(|| dir.join(path).to_string_lossy().into_owned())
+}
+
+/// A `key="value"` (or unquoted) parameter of a header value.
+fn header_param<'v>(value: &'v str, key: &str) -> Option<&'v str> {
+    value.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.trim().split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case(key)
+            .then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// A `multipart/form-data` body written out by hand, as `export_http_file`
+/// does, with `< file` parts read back as file paths. `None` when the body
+/// isn't split by the header's boundary.
+fn multipart_fields(body: &str, content_type: &str, dir: &Path) -> Option<Vec<MultipartField>> {
+    if !content_type
+        .trim_start()
+        .to_ascii_lowercase()
+        .starts_with("multipart/form-data")
+    {
+        return None;
+    }
+    let boundary = header_param(content_type, "boundary")?;
+    let delimiter = format!("--{}", boundary);
+    let closing = format!("--{}--", boundary);
+
+    let mut parts: Vec<Vec<&str>> = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_end();
+        if trimmed == delimiter || trimmed == closing {
+            parts.extend(current.take());
+            if trimmed == delimiter {
+                current = Some(Vec::new());
+            }
+        } else if let Some(part) = current.as_mut() {
+            part.push(line);
+        }
+    }
+    parts.extend(current);
+    if parts.is_empty() {
+        return None;
+    }
+
+    let mut fields = Vec::new();
+    for part in parts {
+        let blank = part.iter().position(|line| line.trim().is_empty())?;
+        let (mut name, mut filename, mut part_type) = (None, None, None);
+        for header in &part[..blank] {
+            let (key, value) = header.split_once(':')?;
+            if key.trim().eq_ignore_ascii_case("content-disposition") {
+                name = header_param(value, "name").map(str::to_string);
+                filename = header_param(value, "filename").map(str::to_string);
+            } else if key.trim().eq_ignore_ascii_case("content-type") {
+                part_type = Some(value.trim().to_string());
+            }
+        }
+        let content = part[blank + 1..].join("\n");
+        let value = match file_reference(&content, dir) {
+            Some(path) => MultipartValue::FilePath {
+                path,
+                filename,
+                content_type: part_type,
+            },
+            None => MultipartValue::Text(content),
+        };
+        fields.push(MultipartField { name: name?, value });
+    }
+    Some(fields)
+}
+
+fn parse_block(lines: &[&str], dir: &Path) -> Result<Option<ApiRequest>, String> {
+    let mut name = None;
+    let mut follow_redirects = None;
+    let mut index = 0;
+    // Comments, metadata and file variables (`@host = ...`) before the request line.
+    while let Some(line) = lines.get(index).map(|line| line.trim()) {
+        if line.is_empty() || line.starts_with('@') {
+            index += 1;
+        } else if is_comment(line) {
+            match metadata(line) {
+                Some(("name", value)) if !value.is_empty() => name = Some(value.to_string()),
+                Some(("no-redirect", _)) => follow_redirects = Some(false),
+                _ => {}
+            }
+            index += 1;
+        } else {
+            break;
+        }
+    }
+    let Some(first) = lines.get(index).map(|line| line.trim()) else {
+        return Ok(None);
+    };
+
+    if first.starts_with("curl ") {
+        let command = lines[index..].join("\n");
+        let mut request = parse_command(&command)?.request;
+        request.request_label = name;
+        return Ok(Some(request));
+    }
+
+    let (method, url) = request_line(first);
+    index += 1;
+    // Query parameters continued on the following lines, kept apart from the URL.
+    let mut query_params = Vec::new();
+    while let Some(line) = lines.get(index).map(|line| line.trim()) {
+        if !line.starts_with('?') && !line.starts_with('&') {
+            break;
+        }
+        query_params.extend(
+            url::form_urlencoded::parse(&line.as_bytes()[1..])
+                .map(|(key, value)| (key.into_owned(), value.into_owned())),
+        );
+        index += 1;
+    }
+
+    let mut headers = Vec::new();
+    let mut auth = AuthType::None;
+    while let Some(line) = lines.get(index).map(|line| line.trim()) {
+        index += 1;
+        if line.is_empty() {
+            break;
+        }
+        if is_comment(line) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.eq_ignore_ascii_case("authorization") {
+            let encoded = value.strip_prefix("Basic ").is_some_and(|v| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(v.trim())
+                    .is_ok()
+            });
+            if let Some(basic) = basic_credentials(value).filter(|_| !encoded) {
+                auth = basic;
+                continue;
+            }
+        }
+        headers.push((key.to_string(), value.to_string()));
+    }
+
+    let mut body_lines: Vec<&str> = lines.get(index..).unwrap_or_default().to_vec();
+    while body_lines.last().is_some_and(|line| line.trim().is_empty()) {
+        body_lines.pop();
+    }
+    let body = body_lines.join("\n");
+    let content_type = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.clone());
+    let graphql = headers.iter().any(|(key, value)| {
+        key.eq_ignore_ascii_case("x-request-type") && value.eq_ignore_ascii_case("graphql")
+    });
+
+    let body = if body.is_empty() {
+        BodyType::None
+    } else if let Some(path) = file_reference(&body, dir) {
+        BodyType::RawFile { path, content_type }
+    } else if let Some(fields) = content_type
+        .as_deref()
+        .and_then(|content_type| multipart_fields(&body, content_type, dir))
+    {
+        // The boundary is picked again when sending.
+        headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-type"));
+        BodyType::Multipart { fields }
+    } else if graphql {
+        // The query, then a blank line and the variables as JSON.
+        headers.retain(|(key, _)| !key.eq_ignore_ascii_case("x-request-type"));
+        let (query, variables) = match body.split_once("\n\n") {
+            Some((query, variables)) if variables.trim_start().starts_with('{') => {
+                (query.to_string(), Some(variables.trim().to_string()))
+            }
+            _ => (body, None),
+        };
+        BodyType::GraphQL {
+            query,
+            variables,
+            operation_name: None,
+        }
+    } else {
+        // Carried by the body, as the exporter reads it.
+        headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-type"));
+        BodyType::Raw {
+            content: body,
+            content_type,
+        }
+    };
+
+    let mut request = ApiRequest {
+        method: method_from_string(&method),
+        url,
+        query_params,
+        headers,
+        body,
+        auth,
+        request_label: name,
+        ..Default::default()
+    };
+    if follow_redirects.is_some() {
+        request.follow_redirects = follow_redirects;
+    }
+    Ok(Some(request))
+}
+
+fn parse_http_file(content: &str, dir: &Path) -> Result<Vec<ApiRequest>, String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut requests = Vec::new();
+    for block in lines.split(|line| line.trim_start().starts_with("###")) {
+        if let Some(request) = parse_block(block, dir)? {
+            requests.push(request);
+        }
+    }
+    Ok(requests)
+}
+
+/// Read the requests in a REST Client `.http` or `.rest` file, in file order.
+/// `# @name` comments become each request's `request_label`, and `< file`
+/// bodies are read from disk relative to the `.http` file when sent.
+#[tauri::command]
+#[specta::specta]
+pub fn import_http_file(app: AppHandle, path: String) -> Result<Vec<ApiRequest>, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let dir = Path::new(&path).parent().unwrap_or(Path::new(""));
+    parse_http_file(&content, dir)
+}
+
+/// Percent-encodes a query name or value, leaving `{{variable}}` references readable.
+fn query_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~{}$:/@,!*'()".contains(&byte) {
+            escaped.push(byte as char);
+        } else {
+            escaped.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    escaped
+}
+
+fn has_header(headers: &[(String, String)], name: &str) -> bool {
+    headers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case(name))
+}
+
+/// Sets Content-Type unless the request already has one.
+fn default_type(headers: &mut Vec<(String, String)>, content_type: &str) {
+    if !has_header(headers, "content-type") {
+        headers.push(("Content-Type".to_string(), content_type.to_string()));
+    }
+}
+
+struct Exporter {
+    /// The `.http` file's folder, which references are relative to.
+    dir: PathBuf,
+    /// Where in-memory file bodies and parts are written.
+    files_dir: PathBuf,
+    written: HashSet<String>,
+    report: Vec<String>,
+}
+
+impl Exporter {
+    /// A `< file` body line, relative when the file is beside the `.http` file.
+    fn reference(&self, path: &str) -> String {
+        match Path::new(path).strip_prefix(&self.dir) {
+            Ok(relative) => format!("< ./{}", relative.to_string_lossy().replace('\\', "/")),
+            Err(_) => format!("< {}", path),
+        }
+    }
+
+    /// Write file contents into `files_dir` and reference them.
+    fn extract(&mut self, filename: &str, data: &[u8]) -> Result<String, String> {
+        let base = Path::new(filename)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "body.bin".to_string());
+        let mut name = base.clone();
+        let mut copy = 1;
+        while !self.written.insert(name.clone()) {
+            copy += 1;
+            name = format!("{}-{}", copy, base);
+        }
+        std::fs::create_dir_all(&self.files_dir).map_err(|e| e.to_string())?;
+        let path = self.files_dir.join(&name);
+        std::fs::write(&path, data).map_err(|e| e.to_string())?;
+        Ok(self.reference(&path.to_string_lossy()))
+    }
+
+    fn auth(
+        &mut self,
+        label: &str,
+        req: &ApiRequest,
+        headers: &mut Vec<(String, String)>,
+        query: &mut Vec<(String, String)>,
+        cookies: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let mut authorization = |value: String| {
+            headers.push(("Authorization".to_string(), value));
+        };
+        match &req.auth {
+            AuthType::None => {}
+            // REST Client encodes the credentials itself.
+            AuthType::Basic { username, password } => {
+                authorization(format!("Basic {}:{}", username, password));
+            }
+            AuthType::Bearer { token }
+            | AuthType::OAuth2 {
+                access_token: token,
+            } => authorization(format!("Bearer {}", token)),
+            AuthType::Jwt {
+                algorithm,
+                secret_or_key_pem,
+                claims_json,
+                header_overrides,
+                expires_in_secs,
+            } => {
+                let token = sign_jwt(
+                    algorithm,
+                    secret_or_key_pem,
+                    claims_json,
+                    header_overrides.as_deref(),
+                    *expires_in_secs,
+                )?;
+                authorization(format!("Bearer {}", token));
+                if expires_in_secs.is_some() {
+                    self.report.push(format!(
+                        "{}: the JWT was signed at export and will expire",
+                        label
+                    ));
+                }
+            }
+            AuthType::ApiKey { key, value, add_to } => match add_to {
+                ApiKeyLocation::Header => headers.push((key.clone(), value.clone())),
+                ApiKeyLocation::Query => query.push((key.clone(), value.clone())),
+                ApiKeyLocation::Cookie => cookies.push(format!("{}={}", key, value)),
+            },
+            AuthType::Ntlm { .. } => self.report.push(format!(
+                "{}: NTLM auth isn't supported and was left out",
+                label
+            )),
+            AuthType::Negotiate { .. } => self.report.push(format!(
+                "{}: Negotiate auth isn't supported and was left out",
+                label
+            )),
+        }
+        Ok(())
+    }
+
+    fn multipart(&mut self, fields: &[MultipartField]) -> Result<String, String> {
+        let mut lines = Vec::new();
+        for field in fields {
+            lines.push(format!("--{}", MULTIPART_BOUNDARY));
+            let (filename, content_type, content) = match &field.value {
+                MultipartValue::Text(text) => (None, None, text.clone()),
+                MultipartValue::File {
+                    data,
+                    filename,
+                    content_type,
+                } => (
+                    Some(filename.clone()),
+                    content_type.clone(),
+                    self.extract(filename, data)?,
+                ),
+                MultipartValue::FilePath {
+                    path,
+                    filename,
+                    content_type,
+                } => {
+                    let filename = filename.clone().or_else(|| {
+                        Path::new(path)
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                    });
+                    (filename, content_type.clone(), self.reference(path))
+                }
+            };
+            let mut disposition =
+                format!("Content-Disposition: form-data; name=\"{}\"", field.name);
+            if let Some(filename) = filename {
+                disposition.push_str(&format!("; filename=\"{}\"", filename));
+            }
+            lines.push(disposition);
+            if let Some(content_type) = content_type {
+                lines.push(format!("Content-Type: {}", content_type));
+            }
+            lines.push(String::new());
+            lines.push(content);
+        }
+        lines.push(format!("--{}--", MULTIPART_BOUNDARY));
+        Ok(lines.join("\n"))
+    }
+
+    fn body(
+        &mut self,
+        req: &ApiRequest,
+        headers: &mut Vec<(String, String)>,
+    ) -> Result<Option<String>, String> {
+        let body = match &req.body {
+            BodyType::None => return Ok(None),
+            BodyType::Raw {
+                content,
+                content_type,
+            } => {
+                if let Some(content_type) = content_type {
+                    default_type(headers, content_type);
+                }
+                content.clone()
+            }
+            BodyType::FormUrlEncoded { fields } => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort();
+                default_type(headers, "application/x-www-form-urlencoded");
+                fields
+                    .into_iter()
+                    .map(|(name, value)| format!("{}={}", query_escape(name), query_escape(value)))
+                    .collect::<Vec<_>>()
+                    .join("&")
+            }
+            BodyType::Multipart { fields } => {
+                // The parts are split by our boundary, not whatever the request had.
+                headers.retain(|(key, _)| !key.eq_ignore_ascii_case("content-type"));
+                headers.push((
+                    "Content-Type".to_string(),
+                    format!("multipart/form-data; boundary={}", MULTIPART_BOUNDARY),
+                ));
+                self.multipart(fields)?
+            }
+            BodyType::Binary { data, filename } => {
+                default_type(headers, "application/octet-stream");
+                self.extract(filename.as_deref().unwrap_or("body.bin"), data)?
+            }
+            BodyType::RawFile { path, content_type } => {
+                default_type(
+                    headers,
+                    content_type
+                        .as_deref()
+                        .unwrap_or("application/octet-stream"),
+                );
+                self.reference(path)
+            }
+            BodyType::BinaryFile { path, content_type } => {
+                default_type(
+                    headers,
+                    content_type
+                        .as_deref()
+                        .unwrap_or_else(|| mime_from_extension(Path::new(path))),
+                );
+                self.reference(path)
+            }
+            // REST Client's GraphQL bodies have no operation name, so one that
+            // needs it is sent as the JSON envelope instead.
+            BodyType::GraphQL {
+                query,
+                variables,
+                operation_name: None,
+            } => {
+                headers.push(("X-Request-Type".to_string(), "GraphQL".to_string()));
+                match variables.as_deref().filter(|v| !v.trim().is_empty()) {
+                    Some(variables) => format!("{}\n\n{}", query.trim_end(), variables),
+                    None => query.clone(),
+                }
+            }
+            BodyType::GraphQL {
+                query,
+                variables,
+                operation_name: Some(operation_name),
+            } => {
+                default_type(headers, "application/json");
+                graphql_envelope(query, variables.as_deref(), Some(operation_name))?
+            }
+            BodyType::Soap {
+                action,
+                envelope_body,
+                soap_version,
+            } => {
+                let (content_type, soap_action) = soap_headers(action, *soap_version);
+                if !has_header(headers, "soapaction") {
+                    if let Some(soap_action) = soap_action {
+                        headers.push(("SOAPAction".to_string(), soap_action));
+                    }
+                }
+                default_type(headers, &content_type);
+                soap_envelope(envelope_body, *soap_version)?
+            }
+            BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } => {
+                default_type(headers, "application/json");
+                let envelope = jsonrpc_body(&req.body)?;
+                serde_json::to_string_pretty(&envelope).map_err(|e| e.to_string())?
+            }
+        };
+        Ok(Some(body))
+    }
+
+    fn request(&mut self, req: &ApiRequest) -> Result<String, String> {
+        let method = method_to_curl_string(&req.method);
+        let label = req
+            .request_label
+            .clone()
+            .unwrap_or_else(|| format!("{} {}", method, req.url));
+
+        let mut headers = req.headers.clone();
+        let mut query = req.query_params.clone();
+        let mut cookies: Vec<String> = req
+            .cookies
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        self.auth(&label, req, &mut headers, &mut query, &mut cookies)?;
+        if !cookies.is_empty() {
+            headers.push(("Cookie".to_string(), cookies.join("; ")));
+        }
+        let body = self.body(req, &mut headers)?;
+
+        let mut lines = Vec::new();
+        if let Some(name) = &req.request_label {
+            // Names end at whitespace.
+            let name = name.split_whitespace().collect::<Vec<_>>().join("-");
+            lines.push(format!("# @name {}", name));
+        }
+        if req.follow_redirects == Some(false) {
+            lines.push("# @no-redirect".to_string());
+        }
+        lines.push(format!("{} {}", method, req.url));
+        let mut separator = if req.url.contains('?') { '&' } else { '?' };
+        for (name, value) in &query {
+            if value.is_empty() {
+                lines.push(format!("    {}{}", separator, query_escape(name)));
+            } else {
+                lines.push(format!(
+                    "    {}{}={}",
+                    separator,
+                    query_escape(name),
+                    query_escape(value)
+                ));
+            }
+            separator = '&';
+        }
+        for (name, value) in &headers {
+            lines.push(format!("{}: {}", name, value));
+        }
+        if let Some(body) = body {
+            if body
+                .lines()
+                .any(|line| line.trim_start().starts_with("###"))
+            {
+                self.report.push(format!(
+                    "{}: a body line starting with ### will be read as a separator",
+                    label
+                ));
+            }
+            lines.push(String::new());
+            lines.push(body);
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+/// The `.http` text for `requests` as they'd be saved at `file`, and what
+/// couldn't be represented. In-memory file bodies are written out already.
+fn write_requests(requests: &[ApiRequest], file: &Path) -> Result<(String, Vec<String>), String> {
+    let dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+    let stem = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "requests".to_string());
+    let mut exporter = Exporter {
+        files_dir: dir.join(format!("{}_files", stem)),
+        dir,
+        written: HashSet::new(),
+        report: Vec::new(),
+    };
+    let blocks = requests
+        .iter()
+        .map(|req| exporter.request(req))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut content = blocks.join("\n\n###\n\n");
+    content.push('\n');
+    Ok((content, exporter.report))
+}
+
+/// Write requests as a REST Client `.http` file. In-memory file bodies and parts
+/// are written to a `<name>_files` folder beside it and referenced with `< file`.
+/// Connection settings (timeouts, proxies, TLS) have no place in the format and
+/// are left out. Returns what couldn't be represented.
+#[tauri::command]
+#[specta::specta]
+pub fn export_http_file(
+    app: AppHandle,
+    requests: Vec<ApiRequest>,
+    path: String,
+) -> Result<Vec<String>, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+
+    let (content, report) = write_requests(&requests, Path::new(&path))?;
+    std::fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::types::Methods;
+
+    /// Exports `requests` into a fresh folder and reads the file back, with
+    /// the text written and the folder it's in.
+    fn round_trip(requests: &[ApiRequest]) -> (Vec<ApiRequest>, String, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("mandy-http-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (content, _) = write_requests(requests, &dir.join("api.http")).unwrap();
+        let imported = parse_http_file(&content, &dir).unwrap();
+        assert_eq!(imported.len(), requests.len());
+        (imported, content, dir)
+    }
+
+    fn header<'r>(req: &'r ApiRequest, name: &str) -> Option<&'r str> {
+        req.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn query(req: &ApiRequest) -> Vec<(String, String)> {
+        let url = url::Url::parse(&req.url).unwrap();
+        let mut pairs: Vec<_> = url.query_pairs().into_owned().collect();
+        pairs.extend(req.query_params.iter().cloned());
+        pairs
+    }
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn url_query_headers_and_cookies_survive() {
+        let req = ApiRequest {
+            method: Methods::PATCH,
+            url: "https://api.example.com/items?fixed=1".to_string(),
+            query_params: pairs(&[("q", "a b&c=d"), ("empty", ""), ("ref", "{{ref}}")]),
+            headers: pairs(&[("Accept", "application/json"), ("X-Trace", "{{trace}}")]),
+            auth: AuthType::ApiKey {
+                key: "api_key".to_string(),
+                value: "k 1".to_string(),
+                add_to: ApiKeyLocation::Query,
+            },
+            ..Default::default()
+        };
+        let (imported, _, dir) = round_trip(std::slice::from_ref(&req));
+        std::fs::remove_dir_all(dir).unwrap();
+        let imported = &imported[0];
+        assert!(matches!(imported.method, Methods::PATCH));
+        assert_eq!(
+            query(imported),
+            pairs(&[
+                ("fixed", "1"),
+                ("q", "a b&c=d"),
+                ("empty", ""),
+                ("ref", "{{ref}}"),
+                ("api_key", "k 1"),
+            ])
+        );
+        assert_eq!(imported.headers, req.headers);
+        assert!(matches!(imported.auth, AuthType::None));
+    }
+
+    #[test]
+    fn auth_comes_back_as_the_same_credentials() {
+        let basic = ApiRequest {
+            url: "https://api.example.com/me".to_string(),
+            auth: AuthType::Basic {
+                username: "ana".to_string(),
+                password: "p@ss:word".to_string(),
+            },
+            ..Default::default()
+        };
+        let bearer = ApiRequest {
+            url: "https://api.example.com/me".to_string(),
+            auth: AuthType::Bearer {
+                token: "{{token}}".to_string(),
+            },
+            ..Default::default()
+        };
+        let cookie = ApiRequest {
+            url: "https://api.example.com/me".to_string(),
+            auth: AuthType::ApiKey {
+                key: "session".to_string(),
+                value: "s1".to_string(),
+                add_to: ApiKeyLocation::Cookie,
+            },
+            ..Default::default()
+        };
+        let (imported, _, dir) = round_trip(&[basic, bearer, cookie]);
+        std::fs::remove_dir_all(dir).unwrap();
+        assert!(matches!(
+            &imported[0].auth,
+            AuthType::Basic { username, password } if username == "ana" && password == "p@ss:word"
+        ));
+        assert!(imported[0].headers.is_empty());
+        // REST Client sends these headers as written, which is what the auth did.
+        assert_eq!(
+            header(&imported[1], "Authorization"),
+            Some("Bearer {{token}}")
+        );
+        assert_eq!(header(&imported[2], "Cookie"), Some("session=s1"));
+    }
+
+    #[test]
+    fn text_bodies_survive() {
+        let json = "{\n  \"name\": \"ana\",\n\n  \"tags\": [\"a\", \"b\"]\n}";
+        let raw = ApiRequest {
+            method: Methods::POST,
+            url: "https://api.example.com/users".to_string(),
+            body: BodyType::Raw {
+                content: json.to_string(),
+                content_type: Some("application/json".to_string()),
+            },
+            ..Default::default()
+        };
+        let form = ApiRequest {
+            method: Methods::POST,
+            url: "https://api.example.com/login".to_string(),
+            body: BodyType::FormUrlEncoded {
+                fields: HashMap::from([
+                    ("user".to_string(), "ana maria".to_string()),
+                    ("next".to_string(), "/a?b=c&d".to_string()),
+                ]),
+            },
+            ..Default::default()
+        };
+        let graphql = ApiRequest {
+            method: Methods::POST,
+            url: "https://api.example.com/graphql".to_string(),
+            body: BodyType::GraphQL {
+                query: "query Me($id: ID!) {\n  user(id: $id) { name }\n}".to_string(),
+                variables: Some("{\"id\": \"1\"}".to_string()),
+                operation_name: None,
+            },
+            ..Default::default()
+        };
+        let (imported, _, dir) = round_trip(&[raw, form, graphql]);
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert!(matches!(
+            &imported[0].body,
+            BodyType::Raw { content, content_type: Some(content_type) }
+                if content == json && content_type == "application/json"
+        ));
+
+        let BodyType::Raw {
+            content,
+            content_type,
+        } = &imported[1].body
+        else {
+            panic!("expected the form as text");
+        };
+        let fields: HashMap<String, String> = url::form_urlencoded::parse(content.as_bytes())
+            .into_owned()
+            .collect();
+        assert_eq!(fields["user"], "ana maria");
+        assert_eq!(fields["next"], "/a?b=c&d");
+        assert_eq!(
+            content_type.as_deref(),
+            Some("application/x-www-form-urlencoded")
+        );
+
+        assert!(matches!(
+            &imported[2].body,
+            BodyType::GraphQL { query, variables: Some(variables), .. }
+                if query.starts_with("query Me($id: ID!)") && variables == "{\"id\": \"1\"}"
+        ));
+        assert!(imported[2].headers.is_empty());
+    }
+
+    #[test]
+    fn file_bodies_point_at_the_same_bytes() {
+        let multipart = ApiRequest {
+            method: Methods::POST,
+            url: "https://api.example.com/upload".to_string(),
+            body: BodyType::Multipart {
+                fields: vec![
+                    MultipartField {
+                        name: "note".to_string(),
+                        value: MultipartValue::Text("line one\nline two".to_string()),
+                    },
+                    MultipartField {
+                        name: "doc".to_string(),
+                        value: MultipartValue::FilePath {
+                            path: "/srv/docs/report.pdf".to_string(),
+                            filename: None,
+                            content_type: Some("application/pdf".to_string()),
+                        },
+                    },
+                    MultipartField {
+                        name: "blob".to_string(),
+                        value: MultipartValue::File {
+                            data: vec![0, 1, 2, 255],
+                            filename: "blob.bin".to_string(),
+                            content_type: None,
+                        },
+                    },
+                ],
+            },
+            ..Default::default()
+        };
+        let binary = ApiRequest {
+            method: Methods::PUT,
+            url: "https://api.example.com/raw".to_string(),
+            body: BodyType::Binary {
+                data: vec![9, 8, 7],
+                filename: Some("data.bin".to_string()),
+            },
+            ..Default::default()
+        };
+        let (imported, _, dir) = round_trip(&[multipart, binary]);
+
+        let BodyType::Multipart { fields } = &imported[0].body else {
+            panic!("expected a multipart body");
+        };
+        assert_eq!(fields.len(), 3);
+        assert!(
+            matches!(&fields[0].value, MultipartValue::Text(text) if text == "line one\nline two")
+        );
+        assert!(matches!(
+            &fields[1].value,
+            MultipartValue::FilePath { path, filename: Some(filename), content_type: Some(content_type) }
+                if path == "/srv/docs/report.pdf" && filename == "report.pdf" && content_type == "application/pdf"
+        ));
+        let MultipartValue::FilePath { path, filename, .. } = &fields[2].value else {
+            panic!("expected the in-memory part written to a file");
+        };
+        assert_eq!(filename.as_deref(), Some("blob.bin"));
+        assert_eq!(std::fs::read(path).unwrap(), [0, 1, 2, 255]);
+
+        let BodyType::RawFile { path, content_type } = &imported[1].body else {
+            panic!("expected a file body");
+        };
+        assert_eq!(std::fs::read(path).unwrap(), [9, 8, 7]);
+        assert_eq!(content_type.as_deref(), Some("application/octet-stream"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn names_and_redirect_settings_survive() {
+        let req = ApiRequest {
+            url: "https://api.example.com/".to_string(),
+            request_label: Some("login".to_string()),
+            follow_redirects: Some(false),
+            ..Default::default()
+        };
+        let plain = ApiRequest {
+            url: "https://api.example.com/health".to_string(),
+            ..Default::default()
+        };
+        let (imported, _, dir) = round_trip(&[req, plain]);
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(imported[0].request_label.as_deref(), Some("login"));
+        assert_eq!(imported[0].follow_redirects, Some(false));
+        assert_eq!(imported[1].request_label, None);
+        assert_eq!(
+            imported[1].follow_redirects,
+            ApiRequest::default().follow_redirects
+        );
+    }
+
+    #[test]
+    fn exporting_an_imported_file_gives_the_same_text() {
+        let requests = vec![
+            ApiRequest {
+                method: Methods::POST,
+                url: "{{host}}/users".to_string(),
+                query_params: pairs(&[("verbose", "1")]),
+                headers: pairs(&[("X-Trace", "abc")]),
+                body: BodyType::Raw {
+                    content: "{\"name\": \"ana\"}".to_string(),
+                    content_type: Some("application/json".to_string()),
+                },
+                auth: AuthType::Basic {
+                    username: "u".to_string(),
+                    password: "p".to_string(),
+                },
+                request_label: Some("create user".to_string()),
+                ..Default::default()
+            },
+            ApiRequest {
+                method: Methods::POST,
+                url: "{{host}}/graphql".to_string(),
+                body: BodyType::GraphQL {
+                    query: "{ me { id } }".to_string(),
+                    variables: None,
+                    operation_name: None,
+                },
+                ..Default::default()
+            },
+        ];
+        let (imported, content, dir) = round_trip(&requests);
+        let (again, _) = write_requests(&imported, &dir.join("api.http")).unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(again, content);
+    }
+}
@@ -0,0 +1,757 @@
+//! Insomnia v4 exports (JSON or YAML): a flat list of resources linked by
+//! `parentId`, rebuilt here into folders and requests.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::body_formats::yaml_to_json_value;
+use crate::helpers::postman::{enabled_entries, scalar};
+use crate::helpers::rest::method_from_string;
+use crate::types::{
+    ApiKeyLocation, ApiRequest, AuthType, BodyType, ImportResult, ImportedEnvironment,
+    ImportedItem, ImportedVariable, MultipartField, MultipartValue,
+};
+
+fn field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// The enabled `name`/`value` entries of a header or parameter list.
+fn named_pairs(list: Option<&Value>) -> Vec<(String, String)> {
+    enabled_entries(list)
+        .filter(|entry| !field(entry, "name").is_empty())
+        .map(|entry| {
+            let value = entry.get("value").map(scalar).unwrap_or_default();
+            (field(entry, "name").to_string(), value)
+        })
+        .collect()
+}
+
+/// Environment data flattened to dotted keys: `{"api": {"host": ..}}` becomes
+/// `api.host`. Arrays are kept as JSON text.
+fn flatten(prefix: &str, data: &Map<String, Value>, out: &mut Vec<ImportedVariable>) {
+    for (key, value) in data {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Object(nested) => flatten(&key, nested, out),
+            other => {
+                out.retain(|variable| variable.key != key);
+                out.push(ImportedVariable {
+                    key,
+                    value: scalar(other),
+                    enabled: true,
+                    secret: false,
+                });
+            }
+        }
+    }
+}
+
+struct Importer<'a> {
+    /// Resources by `parentId`, in `metaSortKey` order.
+    children: HashMap<&'a str, Vec<&'a Value>>,
+    report: Vec<String>,
+}
+
+impl Importer<'_> {
+    fn note(&mut self, at: &str, what: String) {
+        self.report.push(format!("{}: {}", at, what));
+    }
+
+    /// `{{ _.name }}` references become Mandy's `{{name}}`. Nunjucks tags and
+    /// filters can't be evaluated here, so they're kept as written.
+    fn template(&mut self, at: &str, value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        let mut rest = value;
+        let mut kept = false;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let Some(len) = rest[start..].find("}}") else {
+                break;
+            };
+            let inner = rest[start + 2..start + len].trim();
+            let name = inner.strip_prefix("_.").unwrap_or(inner);
+            let plain = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "_-.".contains(c));
+            if plain {
+                out.push_str(&format!("{{{{{}}}}}", name));
+            } else {
+                out.push_str(&rest[start..start + len + 2]);
+                kept = true;
+            }
+            rest = &rest[start + len + 2..];
+        }
+        out.push_str(rest);
+        if kept || value.contains("{%") {
+            self.note(
+                at,
+                format!("Template tag in '{}' was kept as written", value),
+            );
+        }
+        out
+    }
+
+    fn pairs(&mut self, at: &str, list: Option<&Value>) -> Vec<(String, String)> {
+        named_pairs(list)
+            .into_iter()
+            .map(|(name, value)| (self.template(at, &name), self.template(at, &value)))
+            .collect()
+    }
+
+    /// `None` when the resource inherits its folder's auth.
+    fn auth(&mut self, at: &str, resource: &Value) -> Option<AuthType> {
+        let auth = resource.get("authentication")?;
+        let kind = field(auth, "type");
+        if kind.is_empty() {
+            return None;
+        }
+        if auth.get("disabled").and_then(Value::as_bool) == Some(true) {
+            return Some(AuthType::None);
+        }
+        let mut value = |key: &str| self.template(at, field(auth, key));
+        Some(match kind {
+            "none" => AuthType::None,
+            "basic" => AuthType::Basic {
+                username: value("username"),
+                password: value("password"),
+            },
+            "bearer" => {
+                let token = value("token");
+                let prefix = field(auth, "prefix");
+                if !prefix.is_empty() && !prefix.eq_ignore_ascii_case("bearer") {
+                    self.note(
+                        at,
+                        format!("Token prefix '{}' was replaced with Bearer", prefix),
+                    );
+                }
+                AuthType::Bearer { token }
+            }
+            "apikey" => {
+                let add_to = match field(auth, "addTo") {
+                    "queryParams" => ApiKeyLocation::Query,
+                    "cookie" => ApiKeyLocation::Cookie,
+                    _ => ApiKeyLocation::Header,
+                };
+                AuthType::ApiKey {
+                    key: value("key"),
+                    value: value("value"),
+                    add_to,
+                }
+            }
+            "ntlm" => AuthType::Ntlm {
+                username: value("username"),
+                password: value("password"),
+                domain: None,
+            },
+            // Insomnia keeps fetched tokens outside the export.
+            "oauth2" => {
+                self.note(
+                    at,
+                    "OAuth 2.0 settings were not imported; fetch a token in Mandy".to_string(),
+                );
+                AuthType::OAuth2 {
+                    access_token: String::new(),
+                }
+            }
+            other => {
+                self.note(
+                    at,
+                    format!("{} auth is not supported and was left out", other),
+                );
+                AuthType::None
+            }
+        })
+    }
+
+    fn body(&mut self, at: &str, body: &Value) -> BodyType {
+        let mime_type = field(body, "mimeType");
+        match mime_type {
+            "application/x-www-form-urlencoded" => {
+                let mut fields = HashMap::new();
+                for (key, value) in self.pairs(at, body.get("params")) {
+                    if fields.insert(key.clone(), value).is_some() {
+                        self.note(
+                            at,
+                            format!("Repeated form field '{}' kept only its last value", key),
+                        );
+                    }
+                }
+                BodyType::FormUrlEncoded { fields }
+            }
+            "multipart/form-data" => {
+                let mut fields = Vec::new();
+                for param in enabled_entries(body.get("params")) {
+                    let name = self.template(at, field(param, "name"));
+                    let value = if field(param, "type") == "file" {
+                        let path = field(param, "fileName");
+                        if path.is_empty() {
+                            self.note(
+                                at,
+                                format!("File field '{}' has no file and was left out", name),
+                            );
+                            continue;
+                        }
+                        MultipartValue::FilePath {
+                            path: path.to_string(),
+                            filename: None,
+                            content_type: None,
+                        }
+                    } else {
+                        MultipartValue::Text(self.template(at, field(param, "value")))
+                    };
+                    fields.push(MultipartField { name, value });
+                }
+                BodyType::Multipart { fields }
+            }
+            // The text is `{"query", "variables", "operationName"}` JSON.
+            "application/graphql" => {
+                let envelope: Value =
+                    serde_json::from_str(field(body, "text")).unwrap_or(Value::Null);
+                let variables = envelope
+                    .get("variables")
+                    .filter(|variables| !variables.is_null())
+                    .map(|variables| serde_json::to_string_pretty(variables).unwrap_or_default());
+                BodyType::GraphQL {
+                    query: field(&envelope, "query").to_string(),
+                    variables: variables.map(|v| self.template(at, &v)),
+                    operation_name: envelope
+                        .get("operationName")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                }
+            }
+            _ if !field(body, "fileName").is_empty() => BodyType::BinaryFile {
+                path: field(body, "fileName").to_string(),
+                content_type: (!mime_type.is_empty()).then(|| mime_type.to_string()),
+            },
+            _ => match body.get("text").and_then(Value::as_str) {
+                Some(text) => BodyType::Raw {
+                    content: self.template(at, text),
+                    content_type: (!mime_type.is_empty()).then(|| mime_type.to_string()),
+                },
+                None => BodyType::None,
+            },
+        }
+    }
+
+    fn request(
+        &mut self,
+        at: &str,
+        resource: &Value,
+        inherited: Option<&AuthType>,
+    ) -> ImportedItem {
+        let name = field(resource, "name").to_string();
+        let auth = match self.auth(at, resource) {
+            Some(auth) => auth,
+            None => inherited.cloned().unwrap_or(AuthType::None),
+        };
+        let mut request = ApiRequest {
+            method: method_from_string(&field(resource, "method").to_uppercase()),
+            url: self.template(at, field(resource, "url")),
+            headers: self.pairs(at, resource.get("headers")),
+            query_params: self.pairs(at, resource.get("parameters")),
+            auth,
+            ..Default::default()
+        };
+        if let Some(body) = resource.get("body") {
+            request.body = self.body(at, body);
+        }
+        match field(resource, "settingFollowRedirects") {
+            "on" => request.follow_redirects = Some(true),
+            "off" => request.follow_redirects = Some(false),
+            _ => {}
+        }
+        ImportedItem::Request {
+            name,
+            description: resource
+                .get("description")
+                .and_then(Value::as_str)
+                .filter(|d| !d.is_empty())
+                .map(str::to_string),
+            request: Box::new(request),
+            inherit_auth: false,
+            example_response: None,
+        }
+    }
+
+    /// The folders and requests under `parent`, with folder auth passed down
+    /// to the requests that inherit it.
+    fn items(
+        &mut self,
+        path: &str,
+        parent: &str,
+        inherited: Option<&AuthType>,
+    ) -> Vec<ImportedItem> {
+        let mut items = Vec::new();
+        let children = self.children.get(parent).cloned().unwrap_or_default();
+        for resource in children {
+            let name = field(resource, "name");
+            let at = if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", path, name)
+            };
+            match field(resource, "_type") {
+                "request_group" => {
+                    let auth = self.auth(&at, resource);
+                    let id = field(resource, "_id");
+                    let children = self.items(&at, id, auth.as_ref().or(inherited));
+                    if resource
+                        .get("environment")
+                        .and_then(Value::as_object)
+                        .is_some_and(|environment| !environment.is_empty())
+                    {
+                        self.note(&at, "Folder environment was not imported".to_string());
+                    }
+                    items.push(ImportedItem::Folder {
+                        name: name.to_string(),
+                        description: resource
+                            .get("description")
+                            .and_then(Value::as_str)
+                            .filter(|d| !d.is_empty())
+                            .map(str::to_string),
+                        items: children,
+                    });
+                }
+                "request" => items.push(self.request(&at, resource, inherited)),
+                "grpc_request" => self.note(&at, "gRPC requests are not imported".to_string()),
+                "websocket_request" => {
+                    self.note(&at, "WebSocket requests are not imported".to_string())
+                }
+                _ => {}
+            }
+        }
+        items
+    }
+
+    /// The base environment of `workspace`, with its first sub-environment's
+    /// values laid over it.
+    fn environment(&mut self, workspace: &str, name: &str) -> Option<ImportedEnvironment> {
+        let base = self
+            .children
+            .get(workspace)?
+            .iter()
+            .copied()
+            .find(|resource| field(resource, "_type") == "environment")?;
+        let subs: Vec<&Value> = self
+            .children
+            .get(field(base, "_id"))
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|resource| field(resource, "_type") == "environment")
+            .collect();
+
+        let mut variables = Vec::new();
+        let mut environment_name = name.to_string();
+        for environment in std::iter::once(base).chain(subs.first().copied()) {
+            if let Some(data) = environment.get("data").and_then(Value::as_object) {
+                flatten("", data, &mut variables);
+            }
+            if !std::ptr::eq(environment, base) {
+                environment_name = field(environment, "name").to_string();
+            }
+        }
+        for skipped in subs.iter().skip(1) {
+            self.note(
+                name,
+                format!(
+                    "Environment '{}' was not imported; only '{}' was",
+                    field(skipped, "name"),
+                    environment_name
+                ),
+            );
+        }
+        for variable in &mut variables {
+            variable.value = self.template(name, &variable.value);
+        }
+        (!variables.is_empty()).then_some(ImportedEnvironment {
+            name: environment_name,
+            variables,
+        })
+    }
+}
+
+fn import_export(document: &Value) -> Result<ImportResult, String> {
+    if document
+        .get("type")
+        .and_then(Value::as_str)
+        .is_some_and(|t| t.starts_with("collection.insomnia"))
+    {
+        return Err(
+            "Insomnia v5 collections aren't supported; export as Insomnia v4 (JSON) instead"
+                .to_string(),
+        );
+    }
+    if document.get("__export_format").and_then(Value::as_u64) != Some(4) {
+        return Err("Not an Insomnia v4 export: `__export_format` is not 4".to_string());
+    }
+    let resources = document
+        .get("resources")
+        .and_then(Value::as_array)
+        .ok_or("Not an Insomnia export: `resources` is missing")?;
+
+    let mut children: HashMap<&str, Vec<&Value>> = HashMap::new();
+    for resource in resources {
+        let parent = resource
+            .get("parentId")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        children.entry(parent).or_default().push(resource);
+    }
+    let sort_key = |resource: &&Value| {
+        resource
+            .get("metaSortKey")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0)
+    };
+    for list in children.values_mut() {
+        list.sort_by(|a, b| sort_key(a).total_cmp(&sort_key(b)));
+    }
+
+    let workspaces: Vec<&Value> = resources
+        .iter()
+        .filter(|resource| field(resource, "_type") == "workspace")
+        .collect();
+    let mut importer = Importer {
+        children,
+        report: Vec::new(),
+    };
+
+    let (name, description, items, environment) = match workspaces.as_slice() {
+        // Requests exported on their own hang off a workspace id that isn't included.
+        [] => {
+            let roots: Vec<&str> = resources
+                .iter()
+                .filter(|resource| {
+                    field(resource, "_type") == "request_group"
+                        || field(resource, "_type") == "request"
+                })
+                .filter_map(|resource| resource.get("parentId").and_then(Value::as_str))
+                .filter(|parent| !resources.iter().any(|r| field(r, "_id") == *parent))
+                .collect();
+            let mut items = Vec::new();
+            let mut seen = Vec::new();
+            for root in roots {
+                if !seen.contains(&root) {
+                    seen.push(root);
+                    items.extend(importer.items("", root, None));
+                }
+            }
+            ("Insomnia Import".to_string(), None, items, None)
+        }
+        [workspace] => {
+            let name = field(workspace, "name").to_string();
+            let id = field(workspace, "_id");
+            let items = importer.items("", id, None);
+            let environment = importer.environment(id, &name);
+            let description = workspace
+                .get("description")
+                .and_then(Value::as_str)
+                .filter(|d| !d.is_empty())
+                .map(str::to_string);
+            (name, description, items, environment)
+        }
+        // Several workspaces become one folder each.
+        [first, ..] => {
+            let mut items = Vec::new();
+            for workspace in &workspaces {
+                let name = field(workspace, "name");
+                items.push(ImportedItem::Folder {
+                    name: name.to_string(),
+                    description: None,
+                    items: importer.items(name, field(workspace, "_id"), None),
+                });
+            }
+            let environment = importer.environment(field(first, "_id"), field(first, "name"));
+            ("Insomnia Import".to_string(), None, items, environment)
+        }
+    };
+
+    Ok(ImportResult {
+        name,
+        description,
+        items,
+        auth: None,
+        environment,
+        report: importer.report,
+    })
+}
+
+/// An export file's JSON or YAML as one JSON value.
+fn parse_export(content: &str) -> Result<Value, String> {
+    if content.trim_start().starts_with('{') {
+        serde_json::from_str(content).map_err(|e| format!("Invalid Insomnia JSON: {}", e))
+    } else {
+        serde_yaml::from_str::<serde_yaml::Value>(content)
+            .map(yaml_to_json_value)
+            .map_err(|e| format!("Invalid Insomnia YAML: {}", e))
+    }
+}
+
+/// Read an Insomnia v4 export (JSON or YAML). Request groups become folders,
+/// and the base environment (with its first sub-environment) becomes the
+/// collection's variables. Template tags Mandy can't evaluate are kept as
+/// written and listed in `report`.
+#[tauri::command]
+#[specta::specta]
+pub fn import_insomnia(app: AppHandle, path: String) -> Result<ImportResult, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    import_export(&parse_export(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Methods;
+
+    const EXPORT_JSON: &str = include_str!("testdata/insomnia-v4.json");
+    const EXPORT_YAML: &str = include_str!("testdata/insomnia-v4.yaml");
+
+    fn import(content: &str) -> ImportResult {
+        import_export(&parse_export(content).unwrap()).unwrap()
+    }
+
+    fn folder<'a>(items: &'a [ImportedItem], wanted: &str) -> &'a [ImportedItem] {
+        items
+            .iter()
+            .find_map(|item| match item {
+                ImportedItem::Folder { name, items, .. } if name == wanted => Some(&items[..]),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("no folder '{}'", wanted))
+    }
+
+    fn request<'a>(items: &'a [ImportedItem], wanted: &str) -> &'a ApiRequest {
+        items
+            .iter()
+            .find_map(|item| match item {
+                ImportedItem::Request { name, request, .. } if name == wanted => Some(&**request),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("no request '{}'", wanted))
+    }
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn workspace_becomes_the_collection() {
+        let imported = import(EXPORT_JSON);
+        assert_eq!(imported.name, "Petstore");
+        assert_eq!(imported.description, None);
+        let names: Vec<_> = imported
+            .items
+            .iter()
+            .map(|item| match item {
+                ImportedItem::Folder { name, .. } | ImportedItem::Request { name, .. } => {
+                    name.as_str()
+                }
+            })
+            .collect();
+        // In `metaSortKey` order (newest first, as Insomnia lists them),
+        // without the WebSocket request.
+        assert_eq!(names, ["Viewer", "Token", "Users"]);
+        let ImportedItem::Folder { description, .. } = &imported.items[2] else {
+            panic!("expected the Users folder last");
+        };
+        assert_eq!(description.as_deref(), Some("Account management."));
+    }
+
+    #[test]
+    fn requests_keep_their_enabled_pairs_and_templates() {
+        let imported = import(EXPORT_JSON);
+        let users = folder(&imported.items, "Users");
+        let get = request(users, "Get user");
+        assert!(matches!(get.method, Methods::GET));
+        assert_eq!(get.url, "{{baseUrl}}/users/{{userId}}");
+        assert_eq!(get.query_params, pairs(&[("expand", "teams")]));
+        assert_eq!(
+            get.headers,
+            pairs(&[
+                ("Accept", "application/json"),
+                ("X-Request-Id", "{% uuid 'v4' %}")
+            ])
+        );
+        assert!(imported
+            .report
+            .iter()
+            .any(|line| line.starts_with("Users/Get user: Template tag")));
+
+        let create = request(users, "Create user");
+        let BodyType::Raw {
+            content,
+            content_type,
+        } = &create.body
+        else {
+            panic!("expected a raw body");
+        };
+        assert_eq!(
+            content,
+            "{\n\t\"name\": \"Ana\",\n\t\"email\": \"{{email}}\"\n}"
+        );
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(create.follow_redirects, Some(false));
+        // `global` leaves Mandy's default.
+        assert_eq!(get.follow_redirects, Some(true));
+    }
+
+    #[test]
+    fn folder_auth_is_inherited_and_request_auth_wins() {
+        let imported = import(EXPORT_JSON);
+        let users = folder(&imported.items, "Users");
+        assert!(matches!(
+            &request(users, "Get user").auth,
+            AuthType::Bearer { token } if token == "{{token}}"
+        ));
+        assert!(matches!(
+            &request(users, "Upload avatar").auth,
+            AuthType::Basic { username, password } if username == "ana" && password == "{{password}}"
+        ));
+        assert!(matches!(
+            &request(&imported.items, "Token").auth,
+            AuthType::ApiKey { key, add_to: ApiKeyLocation::Header, .. } if key == "X-Api-Key"
+        ));
+        assert!(matches!(
+            &request(&imported.items, "Viewer").auth,
+            AuthType::OAuth2 { access_token } if access_token.is_empty()
+        ));
+    }
+
+    #[test]
+    fn bodies_of_each_kind_are_read() {
+        let imported = import(EXPORT_JSON);
+        let upload = request(folder(&imported.items, "Users"), "Upload avatar");
+        let BodyType::Multipart { fields } = &upload.body else {
+            panic!("expected a multipart body");
+        };
+        assert_eq!(fields.len(), 2);
+        assert!(matches!(&fields[0].value, MultipartValue::Text(text) if text == "Profile photo"));
+        assert!(matches!(
+            &fields[1].value,
+            MultipartValue::FilePath { path, .. } if path == "/Users/ana/Pictures/avatar.png"
+        ));
+
+        let token = request(&imported.items, "Token");
+        let BodyType::FormUrlEncoded { fields } = &token.body else {
+            panic!("expected a form body");
+        };
+        assert_eq!(
+            fields,
+            &HashMap::from([("grant_type".to_string(), "client_credentials".to_string())])
+        );
+        assert_eq!(token.follow_redirects, Some(true));
+
+        let BodyType::GraphQL {
+            query,
+            variables,
+            operation_name,
+        } = &request(&imported.items, "Viewer").body
+        else {
+            panic!("expected a GraphQL body");
+        };
+        assert!(query.starts_with("query Viewer($first: Int)"));
+        assert_eq!(variables.as_deref(), Some("{\n  \"first\": 10\n}"));
+        assert_eq!(operation_name.as_deref(), Some("Viewer"));
+    }
+
+    #[test]
+    fn base_environment_takes_the_first_sub_environment() {
+        let imported = import(EXPORT_JSON);
+        let environment = imported.environment.unwrap();
+        assert_eq!(environment.name, "Staging");
+        let value = |key: &str| {
+            environment
+                .variables
+                .iter()
+                .find(|variable| variable.key == key)
+                .map(|variable| variable.value.as_str())
+        };
+        assert_eq!(
+            value("baseUrl"),
+            Some("https://staging.petstore.example.com/v1")
+        );
+        assert_eq!(value("userId"), Some("42"));
+        assert_eq!(value("auth.realm"), Some("pets"));
+        assert_eq!(value("token"), Some("stg-token"));
+        assert!(imported.report.iter().any(|line| line
+            == "Petstore: Environment 'Production' was not imported; only 'Staging' was"));
+        assert!(imported
+            .report
+            .iter()
+            .any(|line| line == "Live updates: WebSocket requests are not imported"));
+    }
+
+    #[test]
+    fn yaml_export_with_nested_folders() {
+        let imported = import(EXPORT_YAML);
+        assert_eq!(imported.name, "Storefront");
+        let shop = folder(&imported.items, "Shop");
+        let orders = folder(shop, "Orders");
+
+        // Disabled auth opts out of the folder's Basic auth.
+        let cancel = request(orders, "Cancel order");
+        assert!(matches!(cancel.method, Methods::DELETE));
+        assert!(matches!(cancel.auth, AuthType::None));
+        assert_eq!(cancel.url, "https://httpbin.org/anything/{{orderId}}");
+        assert_eq!(
+            cancel.query_params,
+            pairs(&[("reason", "{{ _.reason | default('none') }}")])
+        );
+
+        let checkout = request(shop, "Checkout");
+        assert!(matches!(
+            &checkout.auth,
+            AuthType::Basic { username, .. } if username == "admin"
+        ));
+        assert!(matches!(
+            &checkout.body,
+            BodyType::Raw { content, .. } if content == "<checkout cart=\"{{cartId}}\"/>"
+        ));
+        assert!(matches!(
+            &request(shop, "Upload invoice").body,
+            BodyType::BinaryFile { path, content_type: Some(content_type) }
+                if path == "/home/ana/invoices/2024-04.pdf" && content_type == "application/pdf"
+        ));
+
+        let environment = imported.environment.unwrap();
+        assert_eq!(environment.name, "Storefront");
+        assert_eq!(environment.variables.len(), 2);
+        for line in [
+            "Shop/Orders: Folder environment was not imported",
+            "Inventory stream: gRPC requests are not imported",
+        ] {
+            assert!(imported.report.iter().any(|l| l == line), "{}", line);
+        }
+    }
+
+    #[test]
+    fn other_formats_are_refused() {
+        let v5 = "type: collection.insomnia.rest/5.0\nname: Petstore\n";
+        let error = import_export(&parse_export(v5).unwrap()).err().unwrap();
+        assert!(error.starts_with("Insomnia v5 collections aren't supported"));
+        let postman = r#"{"info": {"name": "x"}, "item": []}"#;
+        let error = import_export(&parse_export(postman).unwrap())
+            .err()
+            .unwrap();
+        assert!(error.starts_with("Not an Insomnia v4 export"));
+    }
+}
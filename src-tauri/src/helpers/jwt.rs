@@ -0,0 +1,83 @@
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{Map, Value};
+
+use crate::types::JwtAlgorithm;
+
+fn parse_object(json: &str, what: &str) -> Result<Map<String, Value>, String> {
+    match serde_json::from_str::<Value>(json) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(format!("JWT {} must be a JSON object", what)),
+        Err(e) => Err(format!("Invalid JWT {} JSON: {}", what, e)),
+    }
+}
+
+fn encoding_key(algorithm: &JwtAlgorithm, secret_or_key_pem: &str) -> Result<EncodingKey, String> {
+    let pem = secret_or_key_pem.as_bytes();
+    let key = match algorithm {
+        JwtAlgorithm::HS256 | JwtAlgorithm::HS384 | JwtAlgorithm::HS512 => {
+            if pem.is_empty() {
+                return Err("JWT secret is empty".to_string());
+            }
+            Ok(EncodingKey::from_secret(pem))
+        }
+        JwtAlgorithm::RS256 => EncodingKey::from_rsa_pem(pem),
+        JwtAlgorithm::ES256 => EncodingKey::from_ec_pem(pem),
+        JwtAlgorithm::EdDSA => EncodingKey::from_ed_pem(pem),
+    };
+    key.map_err(|e| format!("Invalid {:?} signing key: {}", algorithm, e))
+}
+
+/// Signs a fresh token for `AuthType::Jwt`. `iat` is always now and `exp`
+/// is set from `expires_in_secs`, overriding any value in the claims.
+pub fn sign_jwt(
+    algorithm: &JwtAlgorithm,
+    secret_or_key_pem: &str,
+    claims_json: &str,
+    header_overrides: Option<&str>,
+    expires_in_secs: Option<u32>,
+) -> Result<String, String> {
+    let mut claims = parse_object(claims_json, "claims")?;
+    let alg = match algorithm {
+        JwtAlgorithm::HS256 => Algorithm::HS256,
+        JwtAlgorithm::HS384 => Algorithm::HS384,
+        JwtAlgorithm::HS512 => Algorithm::HS512,
+        JwtAlgorithm::RS256 => Algorithm::RS256,
+        JwtAlgorithm::ES256 => Algorithm::ES256,
+        JwtAlgorithm::EdDSA => Algorithm::EdDSA,
+    };
+
+    let mut header = Header::new(alg);
+    if let Some(overrides) = header_overrides.filter(|h| !h.trim().is_empty()) {
+        let overrides = parse_object(overrides, "header")?;
+        let mut merged = match serde_json::to_value(&header) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        for (name, value) in &overrides {
+            if name == "alg" {
+                return Err("JWT header `alg` is set by the algorithm field".to_string());
+            }
+            merged.insert(name.clone(), value.clone());
+        }
+        header = serde_json::from_value(Value::Object(merged))
+            .map_err(|e| format!("Invalid JWT header: {}", e))?;
+        // Header has a fixed field set; anything it dropped would be silently lost.
+        let kept = serde_json::to_value(&header).map_err(|e| e.to_string())?;
+        if let Some((name, _)) = overrides
+            .iter()
+            .find(|(name, value)| !value.is_null() && kept.get(name.as_str()).is_none())
+        {
+            return Err(format!("Unsupported JWT header field `{}`", name));
+        }
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    claims.insert("iat".to_string(), Value::from(now));
+    if let Some(secs) = expires_in_secs {
+        let exp = now + i64::from(secs);
+        claims.insert("exp".to_string(), Value::from(exp));
+    }
+
+    let key = encoding_key(algorithm, secret_or_key_pem)?;
+    jsonwebtoken::encode(&header, &claims, &key).map_err(|e| format!("Failed to sign JWT: {}", e))
+}
@@ -1,6 +1,35 @@
 pub mod rest;
+pub mod response_body;
+pub mod body_formats;
+pub mod curl_command;
+pub mod cookie_jar;
+pub mod oauth;
+pub mod jwt;
+pub mod netrc;
+pub mod proxy_rules;
+pub mod system_proxy;
+pub mod tls;
+pub mod tls_exceptions;
 pub mod request_notify;
 pub mod websocket;
 pub mod graphql;
+pub mod grpc;
+pub mod proto_registry;
+pub mod postman;
+pub mod openapi;
+pub mod har;
+pub mod insomnia;
+pub mod http_file;
+pub mod dotenv;
+pub mod collections;
+pub mod collection_dir;
+pub mod environments;
+pub mod variables;
+pub mod history;
+pub mod drafts;
+pub mod runner;
+pub mod settings;
+pub mod workspaces;
 pub mod socketio;
+pub mod sse;
 pub mod mqtt;
@@ -0,0 +1,157 @@
+//! A `.netrc` reader, used to report which login libcurl will pick up for
+//! `ApiRequest::use_netrc` and to point at the line of a malformed file.
+
+use std::path::{Path, PathBuf};
+
+/// Credentials from one `machine` (or `default`) entry.
+#[derive(Default)]
+pub struct NetrcEntry {
+    pub login: Option<String>,
+    pub password: Option<String>,
+}
+
+pub struct NetrcError {
+    pub line: u32,
+    pub detail: String,
+}
+
+/// Where libcurl looks when no `netrc_file` is given.
+pub fn default_netrc_path(home: &Path) -> PathBuf {
+    // On Windows curl prefers `_netrc` and falls back to `.netrc`.
+    if cfg!(windows) && home.join("_netrc").exists() {
+        return home.join("_netrc");
+    }
+    home.join(".netrc")
+}
+
+/// Splits a line into tokens. Quoted tokens may contain spaces and the
+/// escapes `\"`, `\\`, `\n`, `\r` and `\t`.
+fn tokenize_line(line: &str, line_no: u32) -> Result<Vec<String>, NetrcError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        if c == '"' {
+            chars.next();
+            let mut closed = false;
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => {
+                        closed = true;
+                        break;
+                    }
+                    '\\' => match chars.next() {
+                        Some('n') => token.push('\n'),
+                        Some('r') => token.push('\r'),
+                        Some('t') => token.push('\t'),
+                        Some(other) => token.push(other),
+                        None => break,
+                    },
+                    _ => token.push(c),
+                }
+            }
+            if !closed {
+                return Err(NetrcError {
+                    line: line_no,
+                    detail: "unterminated quoted string".to_string(),
+                });
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// The entry for `host`, else the `default` entry. The whole file is parsed
+/// so an error is reported wherever it is, not only in the entry used.
+pub fn netrc_lookup(text: &str, host: &str) -> Result<Option<NetrcEntry>, NetrcError> {
+    // `None` as the machine is the `default` entry.
+    let mut entries: Vec<(Option<String>, NetrcEntry)> = Vec::new();
+    let mut in_macdef = false;
+    // A keyword whose value is on a later line, with the line it appeared on.
+    let mut pending: Option<(String, u32)> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index as u32 + 1;
+        // A macro body runs up to the next empty line.
+        if in_macdef {
+            in_macdef = !line.trim().is_empty();
+            continue;
+        }
+        let mut tokens = tokenize_line(line, line_no)?.into_iter();
+        while let Some(token) = tokens.next() {
+            if let Some((keyword, _)) = pending.take() {
+                apply_value(&mut entries, &keyword, token, line_no)?;
+                continue;
+            }
+            match token.as_str() {
+                "default" => entries.push((None, NetrcEntry::default())),
+                "macdef" => {
+                    // The macro name is the rest of the line; its body follows.
+                    in_macdef = true;
+                    break;
+                }
+                "machine" | "login" | "password" | "account" => match tokens.next() {
+                    Some(value) => apply_value(&mut entries, &token, value, line_no)?,
+                    None => pending = Some((token, line_no)),
+                },
+                _ => {
+                    return Err(NetrcError {
+                        line: line_no,
+                        detail: format!("unexpected token `{}`", token),
+                    })
+                }
+            }
+        }
+    }
+    if let Some((keyword, line)) = pending {
+        return Err(NetrcError {
+            line,
+            detail: format!("`{}` has no value", keyword),
+        });
+    }
+
+    let host = host.to_ascii_lowercase();
+    let position = entries
+        .iter()
+        .position(|(machine, _)| machine.as_deref() == Some(host.as_str()))
+        .or_else(|| entries.iter().position(|(machine, _)| machine.is_none()));
+    Ok(position.map(|i| entries.swap_remove(i).1))
+}
+
+fn apply_value(
+    entries: &mut Vec<(Option<String>, NetrcEntry)>,
+    keyword: &str,
+    value: String,
+    line: u32,
+) -> Result<(), NetrcError> {
+    if keyword == "machine" {
+        entries.push((Some(value.to_ascii_lowercase()), NetrcEntry::default()));
+        return Ok(());
+    }
+    let Some((_, entry)) = entries.last_mut() else {
+        return Err(NetrcError {
+            line,
+            detail: format!("`{}` before any `machine` or `default`", keyword),
+        });
+    };
+    match keyword {
+        "login" => entry.login = Some(value),
+        "password" => entry.password = Some(value),
+        // `account` is only used by FTP.
+        _ => {}
+    }
+    Ok(())
+}
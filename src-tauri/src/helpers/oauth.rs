@@ -0,0 +1,818 @@
+//! OAuth 2.0 token helpers. The authorization code flow uses PKCE (RFC 7636): the
+//! browser is sent to the provider and comes back to a short-lived loopback
+//! listener (RFC 8252). Token endpoints are called through the regular REST
+//! request path.
+//!
+//! Auth profiles are kept in `auth_profiles.json` in the workspace dir. Their
+//! refresh token and client secret go to the OS keychain, as environment
+//! secrets do.
+
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD},
+    Engine,
+};
+use dashmap::DashMap;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_opener::OpenerExt;
+use url::Url;
+
+use crate::helpers::collections::write_atomic;
+use crate::helpers::environments::{delete_secret, read_secret, write_secret};
+use crate::helpers::rest::execute_curl_request;
+use crate::helpers::workspaces::{active_workspace_id, workspace_dir, DEFAULT_WORKSPACE_ID};
+use crate::types::{
+    ApiRequest, AuthProfile, AuthType, BodyType, Methods, OAuth2ClientAuth,
+    OAuth2ClientCredentialsConfig, OAuth2Config, OAuth2DeviceAuthorization, OAuth2DeviceConfig,
+    OAuth2DevicePollEvent, OAuth2Token, RequestError,
+};
+
+/// Event carrying `OAuth2DevicePollEvent` payloads while a device flow is polled.
+pub const OAUTH_DEVICE_POLL_EVENT: &str = "oauth-device-poll";
+
+const DEFAULT_TIMEOUT_SECS: u32 = 300;
+
+/// RFC 8628 §3.2: poll every 5 seconds unless the server says otherwise, and
+/// back off by another 5 on each `slow_down`.
+const DEFAULT_DEVICE_INTERVAL_SECS: u32 = 5;
+const SLOW_DOWN_STEP_SECS: u32 = 5;
+
+/// Default for `ApiRequest::auth_refresh_skew_secs`.
+const DEFAULT_REFRESH_SKEW_SECS: u32 = 60;
+const DEFAULT_REDIRECT_PATH: &str = "/callback";
+
+/// How often the listener checks for the browser while waiting.
+const CALLBACK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Random bytes, base64url-encoded without padding (PKCE verifier, `state`).
+fn random_token(bytes: usize) -> String {
+    let mut buf = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buf);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn authorization_url(
+    config: &OAuth2Config,
+    redirect_uri: &str,
+    state: &str,
+    challenge: &str,
+) -> Result<String, String> {
+    let mut url = Url::parse(&config.authorization_url)
+        .map_err(|e| format!("Invalid authorization URL: {}", e))?;
+    {
+        let mut query = url.query_pairs_mut();
+        query
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &config.client_id)
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("state", state)
+            .append_pair("code_challenge", challenge)
+            .append_pair("code_challenge_method", "S256");
+        if !config.scopes.is_empty() {
+            query.append_pair("scope", &config.scopes.join(" "));
+        }
+        for (key, value) in &config.extra_params {
+            query.append_pair(key, value);
+        }
+    }
+    Ok(url.into())
+}
+
+fn respond(stream: &mut TcpStream, status: &str, message: &str) {
+    let body = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Mandy</title></head>\
+         <body style=\"font-family: system-ui, sans-serif; padding: 2rem\"><p>{}</p></body></html>",
+        message
+    );
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+}
+
+/// Read one request off the loopback listener. Returns `None` for requests that
+/// aren't the redirect (e.g. the browser asking for `/favicon.ico`).
+fn handle_callback(
+    mut stream: TcpStream,
+    path: &str,
+    expected_state: &str,
+) -> Option<Result<String, String>> {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 16 * 1024 {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    let request = String::from_utf8_lossy(&request);
+    let target = request.lines().next()?.split(' ').nth(1)?;
+    let url = Url::parse(&format!("http://127.0.0.1{}", target)).ok()?;
+    if url.path() != path {
+        respond(&mut stream, "404 Not Found", "Not found.");
+        return None;
+    }
+
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let result = if let Some(error) = params.get("error") {
+        let description = params
+            .get("error_description")
+            .map(|d| format!(" ({})", d))
+            .unwrap_or_default();
+        Err(format!("Authorization failed: {}{}", error, description))
+    } else if params.get("state").map(String::as_str) != Some(expected_state) {
+        Err("Authorization redirect had a mismatched state and was rejected".to_string())
+    } else {
+        params
+            .get("code")
+            .cloned()
+            .ok_or_else(|| "Authorization redirect did not include a code".to_string())
+    };
+
+    match result {
+        Ok(_) => respond(
+            &mut stream,
+            "200 OK",
+            "Authorization complete. You can close this window and return to Mandy.",
+        ),
+        Err(ref e) => respond(
+            &mut stream,
+            "400 Bad Request",
+            &format!("{}. You can close this window.", e),
+        ),
+    }
+    Some(result)
+}
+
+/// Block until the browser hits the redirect URI or `deadline` passes.
+fn wait_for_callback(
+    listener: TcpListener,
+    path: &str,
+    state: &str,
+    deadline: Instant,
+) -> Result<String, String> {
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Some(result) = handle_callback(stream, path, state) {
+                    return result;
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                if Instant::now() >= deadline {
+                    return Err("Timed out waiting for the authorization redirect".to_string());
+                }
+                std::thread::sleep(CALLBACK_POLL_INTERVAL);
+            }
+            Err(e) => return Err(format!("Redirect listener failed: {}", e)),
+        }
+    }
+}
+
+/// POST a form to an OAuth endpoint and return the status and JSON body.
+fn post_form(
+    app: &AppHandle,
+    url: &str,
+    fields: HashMap<String, String>,
+    auth: AuthType,
+) -> Result<(u16, serde_json::Value), String> {
+    let req = ApiRequest {
+        method: Methods::POST,
+        url: url.to_string(),
+        headers: vec![("Accept".to_string(), "application/json".to_string())],
+        body: BodyType::FormUrlEncoded { fields },
+        auth,
+        ..Default::default()
+    };
+
+    let response = execute_curl_request(app, req, None).map_err(|e| e.to_string())?;
+    let body = BASE64
+        .decode(&response.body_base64)
+        .map_err(|e| e.to_string())?;
+    let raw = serde_json::from_slice(&body).map_err(|_| {
+        format!(
+            "OAuth endpoint returned a non-JSON response (HTTP {})",
+            response.status
+        )
+    })?;
+    Ok((response.status, raw))
+}
+
+fn string_field(raw: &serde_json::Value, name: &str) -> Option<String> {
+    raw.get(name).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Some providers send numbers such as `expires_in` as strings.
+fn u32_field(raw: &serde_json::Value, name: &str) -> Option<u32> {
+    raw.get(name).and_then(|v| match v {
+        serde_json::Value::Number(n) => n.as_u64().map(|n| n as u32),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    })
+}
+
+/// `error` plus `error_description` from an OAuth error response, verbatim.
+fn oauth_error(status: u16, raw: &serde_json::Value) -> String {
+    let error = string_field(raw, "error").unwrap_or_else(|| format!("HTTP {}", status));
+    let description = string_field(raw, "error_description")
+        .map(|d| format!(" ({})", d))
+        .unwrap_or_default();
+    format!("{}{}", error, description)
+}
+
+/// Turn a token endpoint response into a token, or its error into a message.
+fn token_from_response(status: u16, raw: serde_json::Value) -> Result<OAuth2Token, String> {
+    if !(200..300).contains(&status) {
+        return Err(format!(
+            "Token request failed: {}",
+            oauth_error(status, &raw)
+        ));
+    }
+    let field = |name: &str| string_field(&raw, name);
+    let access_token = field("access_token")
+        .ok_or_else(|| "Token response did not include an access_token".to_string())?;
+    let expires_in = u32_field(&raw, "expires_in");
+    Ok(OAuth2Token {
+        access_token,
+        refresh_token: field("refresh_token"),
+        token_type: field("token_type"),
+        expires_in,
+        scope: field("scope"),
+        raw,
+    })
+}
+
+fn exchange_code(
+    app: &AppHandle,
+    config: &OAuth2Config,
+    code: &str,
+    verifier: &str,
+    redirect_uri: &str,
+) -> Result<OAuth2Token, String> {
+    let mut fields = HashMap::from([
+        ("grant_type".to_string(), "authorization_code".to_string()),
+        ("code".to_string(), code.to_string()),
+        ("redirect_uri".to_string(), redirect_uri.to_string()),
+        ("client_id".to_string(), config.client_id.clone()),
+        ("code_verifier".to_string(), verifier.to_string()),
+    ]);
+    if let Some(ref secret) = config.client_secret {
+        fields.insert("client_secret".to_string(), secret.clone());
+    }
+    let (status, raw) = post_form(app, &config.token_url, fields, AuthType::None)?;
+    token_from_response(status, raw)
+}
+
+/// Run the authorization code flow: open the provider's consent page in the
+/// browser, wait for it to redirect back to a loopback listener, and exchange
+/// the code (with its PKCE verifier) for tokens.
+#[tauri::command]
+#[specta::specta]
+pub async fn oauth2_authorization_code(
+    app: AppHandle,
+    config: OAuth2Config,
+) -> Result<OAuth2Token, String> {
+    let verifier = random_token(32);
+    let state = random_token(16);
+
+    let listener = TcpListener::bind(("127.0.0.1", config.redirect_port.unwrap_or(0)))
+        .map_err(|e| format!("Could not start the redirect listener: {}", e))?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+    let path = config
+        .redirect_path
+        .clone()
+        .filter(|p| p.starts_with('/'))
+        .unwrap_or_else(|| DEFAULT_REDIRECT_PATH.to_string());
+    let redirect_uri = format!("http://127.0.0.1:{}{}", port, path);
+
+    let auth_url = authorization_url(&config, &redirect_uri, &state, &pkce_challenge(&verifier))?;
+    app.opener()
+        .open_url(auth_url, None::<&str>)
+        .map_err(|e| format!("Could not open the browser: {}", e))?;
+
+    let timeout = config.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    let deadline = Instant::now() + Duration::from_secs(timeout as u64);
+    tokio::task::spawn_blocking(move || {
+        let code = wait_for_callback(listener, &path, &state, deadline)?;
+        let token = exchange_code(&app, &config, &code, &verifier, &redirect_uri)?;
+        if let Some(ref profile) = config.profile {
+            save_auth_profile(
+                &app,
+                profile,
+                &config.token_url,
+                &config.client_id,
+                config.client_secret.as_deref(),
+                &token,
+            )?;
+        }
+        Ok(token)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+/// Fetch a token with the client credentials grant, for service-to-service APIs.
+#[tauri::command]
+#[specta::specta]
+pub async fn oauth2_client_credentials(
+    app: AppHandle,
+    config: OAuth2ClientCredentialsConfig,
+) -> Result<OAuth2Token, String> {
+    let mut fields = HashMap::new();
+    fields.insert("grant_type".to_string(), "client_credentials".to_string());
+    if let Some(scope) = config.scope.filter(|s| !s.is_empty()) {
+        fields.insert("scope".to_string(), scope);
+    }
+    if let Some(audience) = config.audience.filter(|a| !a.is_empty()) {
+        fields.insert("audience".to_string(), audience);
+    }
+    let auth = match config.auth_style {
+        OAuth2ClientAuth::BasicHeader => AuthType::Basic {
+            username: config.client_id.clone(),
+            password: config.client_secret.clone(),
+        },
+        OAuth2ClientAuth::Body => {
+            fields.insert("client_id".to_string(), config.client_id.clone());
+            fields.insert("client_secret".to_string(), config.client_secret.clone());
+            AuthType::None
+        }
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let (status, raw) = post_form(&app, &config.token_url, fields, auth)?;
+        let token = token_from_response(status, raw)?;
+        if let Some(ref profile) = config.profile {
+            save_auth_profile(
+                &app,
+                profile,
+                &config.token_url,
+                &config.client_id,
+                Some(&config.client_secret),
+                &token,
+            )?;
+        }
+        Ok(token)
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))?
+}
+
+/// A device authorization started by `oauth2_device_start`, keyed by device code.
+struct DeviceFlow {
+    user_code: String,
+    interval: u32,
+    expires_at: Instant,
+    cancel: Arc<AtomicBool>,
+}
+
+static DEVICE_FLOWS: OnceLock<DashMap<String, DeviceFlow>> = OnceLock::new();
+
+fn device_flows() -> &'static DashMap<String, DeviceFlow> {
+    DEVICE_FLOWS.get_or_init(DashMap::new)
+}
+
+fn client_fields(client_id: &str, client_secret: Option<&str>) -> HashMap<String, String> {
+    let mut fields = HashMap::from([("client_id".to_string(), client_id.to_string())]);
+    if let Some(secret) = client_secret {
+        fields.insert("client_secret".to_string(), secret.to_string());
+    }
+    fields
+}
+
+/// Request a device and user code (RFC 8628). Show `user_code` and
+/// `verification_uri` to the user, then call `oauth2_device_poll`.
+#[tauri::command]
+#[specta::specta]
+pub async fn oauth2_device_start(
+    app: AppHandle,
+    config: OAuth2DeviceConfig,
+) -> Result<OAuth2DeviceAuthorization, String> {
+    let mut fields = client_fields(&config.client_id, config.client_secret.as_deref());
+    if let Some(scope) = config.scope.filter(|s| !s.is_empty()) {
+        fields.insert("scope".to_string(), scope);
+    }
+    let (status, raw) = tokio::task::spawn_blocking(move || {
+        post_form(
+            &app,
+            &config.device_authorization_url,
+            fields,
+            AuthType::None,
+        )
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e))??;
+    if !(200..300).contains(&status) {
+        return Err(format!(
+            "Device authorization failed: {}",
+            oauth_error(status, &raw)
+        ));
+    }
+
+    let required = |name: &str| {
+        string_field(&raw, name)
+            .ok_or_else(|| format!("Device authorization response did not include {}", name))
+    };
+    let authorization = OAuth2DeviceAuthorization {
+        device_code: required("device_code")?,
+        user_code: required("user_code")?,
+        // Google's older endpoint says `verification_url`.
+        verification_uri: required("verification_uri")
+            .or_else(|e| string_field(&raw, "verification_url").ok_or(e))?,
+        verification_uri_complete: string_field(&raw, "verification_uri_complete"),
+        interval: u32_field(&raw, "interval").unwrap_or(DEFAULT_DEVICE_INTERVAL_SECS),
+        expires_in: u32_field(&raw, "expires_in").ok_or_else(|| {
+            "Device authorization response did not include expires_in".to_string()
+        })?,
+    };
+
+    device_flows().insert(
+        authorization.device_code.clone(),
+        DeviceFlow {
+            user_code: authorization.user_code.clone(),
+            interval: authorization.interval,
+            expires_at: Instant::now() + Duration::from_secs(authorization.expires_in as u64),
+            cancel: Arc::new(AtomicBool::new(false)),
+        },
+    );
+    Ok(authorization)
+}
+
+/// Poll the token endpoint until the user approves the device, the code expires,
+/// or `oauth2_device_cancel` is called. Emits `oauth-device-poll` after each
+/// pending attempt.
+#[tauri::command]
+#[specta::specta]
+pub async fn oauth2_device_poll(
+    app: AppHandle,
+    config: OAuth2DeviceConfig,
+    device_code: String,
+) -> Result<OAuth2Token, String> {
+    let (user_code, mut interval, expires_at, cancel) = {
+        let flow = device_flows()
+            .get(&device_code)
+            .ok_or_else(|| "Unknown device code; start the flow again".to_string())?;
+        (
+            flow.user_code.clone(),
+            flow.interval,
+            flow.expires_at,
+            Arc::clone(&flow.cancel),
+        )
+    };
+    let key = device_code.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let mut attempt = 0;
+        loop {
+            // Sleep in short steps so cancellation is noticed promptly.
+            let wake = Instant::now() + Duration::from_secs(interval as u64);
+            while Instant::now() < wake {
+                if cancel.load(Ordering::SeqCst) {
+                    return Err("Device authorization cancelled".to_string());
+                }
+                std::thread::sleep(CALLBACK_POLL_INTERVAL);
+            }
+            if Instant::now() >= expires_at {
+                return Err("Device code expired before it was authorized".to_string());
+            }
+
+            attempt += 1;
+            let mut fields = client_fields(&config.client_id, config.client_secret.as_deref());
+            fields.insert(
+                "grant_type".to_string(),
+                "urn:ietf:params:oauth:grant-type:device_code".to_string(),
+            );
+            fields.insert("device_code".to_string(), device_code.clone());
+            let (status, raw) = post_form(&app, &config.token_url, fields, AuthType::None)?;
+
+            let pending = match string_field(&raw, "error").as_deref() {
+                Some("authorization_pending") => "authorization_pending",
+                Some("slow_down") => {
+                    interval += SLOW_DOWN_STEP_SECS;
+                    "slow_down"
+                }
+                _ => {
+                    let token = token_from_response(status, raw)?;
+                    if let Some(ref profile) = config.profile {
+                        save_auth_profile(
+                            &app,
+                            profile,
+                            &config.token_url,
+                            &config.client_id,
+                            config.client_secret.as_deref(),
+                            &token,
+                        )?;
+                    }
+                    return Ok(token);
+                }
+            };
+            let remaining = expires_at.saturating_duration_since(Instant::now());
+            let _ = app.emit(
+                OAUTH_DEVICE_POLL_EVENT,
+                OAuth2DevicePollEvent {
+                    user_code: user_code.clone(),
+                    attempt,
+                    status: pending.to_string(),
+                    interval,
+                    remaining_secs: remaining.as_secs() as u32,
+                },
+            );
+        }
+    })
+    .await
+    .map_err(|e| format!("Task error: {}", e));
+
+    device_flows().remove(&key);
+    result?
+}
+
+/// Stop an in-progress `oauth2_device_poll`. Returns false for unknown codes.
+#[tauri::command]
+#[specta::specta]
+pub fn oauth2_device_cancel(device_code: String) -> Result<bool, String> {
+    match device_flows().get(&device_code) {
+        Some(flow) => {
+            flow.cancel.store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Serializes read-modify-write cycles on the auth profiles file.
+static PROFILES_LOCK: Mutex<()> = Mutex::new(());
+
+/// One lock per profile, held while its token is checked and refreshed, so
+/// concurrent requests wait for a single refresh instead of each spending the
+/// refresh token.
+static REFRESH_LOCKS: OnceLock<DashMap<String, Arc<Mutex<()>>>> = OnceLock::new();
+
+fn refresh_lock(workspace_id: &str, name: &str) -> Arc<Mutex<()>> {
+    REFRESH_LOCKS
+        .get_or_init(DashMap::new)
+        .entry(format!("{}/{}", workspace_id, name))
+        .or_default()
+        .clone()
+}
+
+fn profiles_path(dir: &Path) -> PathBuf {
+    dir.join("auth_profiles.json")
+}
+
+fn load_profiles(dir: &Path) -> Result<Vec<AuthProfile>, String> {
+    match std::fs::read(profiles_path(dir)) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn save_profiles(dir: &Path, profiles: &[AuthProfile]) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(profiles).map_err(|e| e.to_string())?;
+    write_atomic(&profiles_path(dir), &json)
+}
+
+/// The keychain account of a profile's secret, prefixed with the workspace
+/// id outside the default workspace as environment secrets are.
+fn profile_secret_account(workspace_id: &str, name: &str, field: &str) -> String {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        format!("auth/{}/{}", name, field)
+    } else {
+        format!("{}/auth/{}/{}", workspace_id, name, field)
+    }
+}
+
+/// The fields kept in the keychain. In the file they're `""` when the
+/// keychain holds a value and `null` when there is none.
+fn secret_fields(profile: &mut AuthProfile) -> [(&'static str, &mut Option<String>); 2] {
+    [
+        ("refresh_token", &mut profile.refresh_token),
+        ("client_secret", &mut profile.client_secret),
+    ]
+}
+
+/// `profile` as read from the file, with its secrets back from the keychain.
+fn with_secrets(workspace_id: &str, mut profile: AuthProfile) -> Result<AuthProfile, String> {
+    let name = profile.name.clone();
+    for (field, value) in secret_fields(&mut profile) {
+        if value.as_deref() == Some("") {
+            let account = profile_secret_account(workspace_id, &name, field);
+            *value = Some(read_secret(&account, field)?);
+        }
+    }
+    Ok(profile)
+}
+
+/// Add or replace a profile in the active workspace, moving its secrets to
+/// the keychain.
+fn store_profile(app: &AppHandle, mut profile: AuthProfile) -> Result<(), String> {
+    let _guard = PROFILES_LOCK.lock().map_err(|e| e.to_string())?;
+    let dir = workspace_dir(app)?;
+    let workspace_id = active_workspace_id(app)?;
+    let name = profile.name.clone();
+    for (field, value) in secret_fields(&mut profile) {
+        let account = profile_secret_account(&workspace_id, &name, field);
+        match value.take().filter(|secret| !secret.is_empty()) {
+            Some(secret) => {
+                write_secret(&account, &secret)?;
+                *value = Some(String::new());
+            }
+            None => delete_secret(&account)?,
+        }
+    }
+    let mut profiles = load_profiles(&dir)?;
+    profiles.retain(|p| p.name != name);
+    profiles.push(profile);
+    save_profiles(&dir, &profiles)
+}
+
+/// The profile named `name` in the active workspace, secrets included.
+fn load_profile(app: &AppHandle, name: &str) -> Result<Option<AuthProfile>, String> {
+    let workspace_id = active_workspace_id(app)?;
+    load_profiles(&workspace_dir(app)?)?
+        .into_iter()
+        .find(|p| p.name == name)
+        .map(|profile| with_secrets(&workspace_id, profile))
+        .transpose()
+}
+
+/// Remove the keychain entries of every profile in a workspace being deleted.
+pub(crate) fn delete_workspace_auth_secrets(dir: &Path, workspace_id: &str) -> Result<(), String> {
+    let _guard = PROFILES_LOCK.lock().map_err(|e| e.to_string())?;
+    for mut profile in load_profiles(dir)? {
+        let name = profile.name.clone();
+        for (field, _) in secret_fields(&mut profile) {
+            delete_secret(&profile_secret_account(workspace_id, &name, field))?;
+        }
+    }
+    Ok(())
+}
+
+fn expires_at(expires_in: Option<u32>) -> Option<i64> {
+    expires_in.map(|secs| chrono::Utc::now().timestamp() + secs as i64)
+}
+
+fn save_auth_profile(
+    app: &AppHandle,
+    name: &str,
+    token_url: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    token: &OAuth2Token,
+) -> Result<(), String> {
+    store_profile(
+        app,
+        AuthProfile {
+            name: name.to_string(),
+            token_url: token_url.to_string(),
+            client_id: client_id.to_string(),
+            client_secret: client_secret.map(str::to_string),
+            access_token: token.access_token.clone(),
+            refresh_token: token.refresh_token.clone(),
+            expires_at: expires_at(token.expires_in),
+            scope: token.scope.clone(),
+        },
+    )
+}
+
+/// Exchange a profile's refresh token for a new access token. Providers that
+/// don't rotate refresh tokens omit it from the response, so the old one is kept.
+fn refresh_auth_profile(
+    app: &AppHandle,
+    profile: &AuthProfile,
+) -> Result<AuthProfile, RequestError> {
+    let failed = |error: Option<String>, detail: String| RequestError::TokenRefreshFailed {
+        profile: profile.name.clone(),
+        error,
+        detail,
+    };
+    let refresh_token = profile.refresh_token.clone().ok_or_else(|| {
+        failed(
+            None,
+            "the token has expired and there is no refresh token".to_string(),
+        )
+    })?;
+
+    let mut fields = client_fields(&profile.client_id, profile.client_secret.as_deref());
+    fields.insert("grant_type".to_string(), "refresh_token".to_string());
+    fields.insert("refresh_token".to_string(), refresh_token.clone());
+    let (status, raw) = post_form(app, &profile.token_url, fields, AuthType::None)
+        .map_err(|detail| failed(None, detail))?;
+    if !(200..300).contains(&status) {
+        return Err(failed(
+            string_field(&raw, "error"),
+            oauth_error(status, &raw),
+        ));
+    }
+    let token = token_from_response(status, raw).map_err(|detail| failed(None, detail))?;
+
+    Ok(AuthProfile {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token.or(Some(refresh_token)),
+        expires_at: expires_at(token.expires_in),
+        scope: token.scope.or_else(|| profile.scope.clone()),
+        ..profile.clone()
+    })
+}
+
+/// The access token stored under `name`, refreshed first when it expires within
+/// `skew_secs` (60 by default). Runs on the blocking request thread.
+pub(crate) fn auth_profile_token(
+    app: &AppHandle,
+    name: &str,
+    skew_secs: Option<u32>,
+) -> Result<String, RequestError> {
+    let skew = skew_secs.unwrap_or(DEFAULT_REFRESH_SKEW_SECS) as i64;
+    let due = |profile: &AuthProfile| {
+        profile
+            .expires_at
+            .is_some_and(|at| at - skew <= chrono::Utc::now().timestamp())
+    };
+    let load = || {
+        load_profile(app, name)?.ok_or_else(|| RequestError::Other {
+            detail: format!("No auth profile named '{}'", name),
+        })
+    };
+    let profile = load()?;
+    if !due(&profile) {
+        return Ok(profile.access_token);
+    }
+
+    let lock = refresh_lock(&active_workspace_id(app)?, name);
+    let _guard = lock.lock().map_err(|e| e.to_string())?;
+    // Another request may have refreshed it while this one waited.
+    let profile = load()?;
+    if !due(&profile) {
+        return Ok(profile.access_token);
+    }
+    let refreshed = refresh_auth_profile(app, &profile)?;
+    let access_token = refreshed.access_token.clone();
+    store_profile(app, refreshed)?;
+    Ok(access_token)
+}
+
+/// The stored access token for `name` as-is, without refreshing it.
+pub(crate) fn stored_auth_profile_token(
+    app: &AppHandle,
+    name: &str,
+) -> Result<Option<String>, String> {
+    Ok(load_profiles(&workspace_dir(app)?)?
+        .into_iter()
+        .find(|p| p.name == name)
+        .map(|p| p.access_token))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_auth_profiles(app: AppHandle) -> Result<Vec<AuthProfile>, String> {
+    let workspace_id = active_workspace_id(&app)?;
+    let mut profiles = load_profiles(&workspace_dir(&app)?)?
+        .into_iter()
+        .map(|profile| with_secrets(&workspace_id, profile))
+        .collect::<Result<Vec<_>, _>>()?;
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(profiles)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_auth_profile(app: AppHandle, name: String) -> Result<Option<AuthProfile>, String> {
+    load_profile(&app, &name)
+}
+
+/// Returns false when no profile had that name.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_auth_profile(app: AppHandle, name: String) -> Result<bool, String> {
+    let _guard = PROFILES_LOCK.lock().map_err(|e| e.to_string())?;
+    let dir = workspace_dir(&app)?;
+    let workspace_id = active_workspace_id(&app)?;
+    let mut profiles = load_profiles(&dir)?;
+    let Some(index) = profiles.iter().position(|p| p.name == name) else {
+        return Ok(false);
+    };
+    let mut profile = profiles.remove(index);
+    for (field, _) in secret_fields(&mut profile) {
+        delete_secret(&profile_secret_account(&workspace_id, &name, field))?;
+    }
+    save_profiles(&dir, &profiles)?;
+    Ok(true)
+}
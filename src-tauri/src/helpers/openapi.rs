@@ -0,0 +1,1457 @@
+//! OpenAPI 3 documents: imported as a collection with one request per
+//! operation, and written from a collection as a skeleton spec. Only
+//! references within the document (`#/...`) are followed on import;
+//! operations that need anything else are skipped and listed in the report.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::body_formats::yaml_to_json_value;
+use crate::helpers::postman::split_query;
+use crate::helpers::rest::{
+    execute_fetch_url, graphql_envelope, jsonrpc_body, method_from_string, method_to_curl_string,
+    soap_envelope, soap_headers,
+};
+use crate::types::{
+    ApiKeyLocation, ApiRequest, AuthType, BodyType, CollectionExport, ExampleResponse,
+    ImportResult, ImportedEnvironment, ImportedItem, MultipartField, MultipartValue, OpenApiExport,
+    OpenApiExportOptions,
+};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Most `$ref`s followed from one reference before giving up on a cycle.
+const MAX_REF_HOPS: usize = 32;
+
+/// Deepest chain of schema references expanded into a sample body.
+const MAX_SAMPLE_DEPTH: usize = 8;
+
+/// `value`, or what its `$ref` points to.
+fn resolve<'a>(document: &'a Value, value: &'a Value) -> Result<&'a Value, String> {
+    let mut value = value;
+    for _ in 0..MAX_REF_HOPS {
+        let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+            return Ok(value);
+        };
+        let pointer = reference
+            .strip_prefix('#')
+            .ok_or_else(|| format!("external reference '{}'", reference))?;
+        value = document
+            .pointer(pointer)
+            .ok_or_else(|| format!("unresolved reference '{}'", reference))?;
+    }
+    Err("reference cycle".to_string())
+}
+
+/// Parameter values as they'd be typed: arrays comma-joined, strings as-is.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        Value::Array(items) => items.iter().map(scalar).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+fn string_sample(format: Option<&str>) -> &'static str {
+    match format.unwrap_or_default() {
+        "date-time" => "1970-01-01T00:00:00Z",
+        "date" => "1970-01-01",
+        "time" => "00:00:00",
+        "email" => "user@example.com",
+        "uuid" => "00000000-0000-0000-0000-000000000000",
+        "uri" | "url" => "https://example.com",
+        "hostname" => "example.com",
+        "ipv4" => "192.0.2.1",
+        "ipv6" => "2001:db8::1",
+        "byte" | "binary" | "password" => "",
+        _ => "string",
+    }
+}
+
+/// The `type` of a schema, inferring `object`/`array` when it's left out.
+fn schema_type(schema: &Value) -> &str {
+    match schema.get("type") {
+        Some(Value::String(kind)) => kind,
+        // 3.1 allows `["string", "null"]`.
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|kind| *kind != "null")
+            .unwrap_or("null"),
+        _ if schema.get("properties").is_some() => "object",
+        _ if schema.get("items").is_some() => "array",
+        _ => "",
+    }
+}
+
+fn first_example(schema: &Value) -> Option<&Value> {
+    schema
+        .get("example")
+        .or_else(|| schema.get("default"))
+        .or_else(|| schema.get("const"))
+        .or_else(|| schema.get("enum")?.as_array()?.first())
+        // 3.1 `examples` is a plain list.
+        .or_else(|| schema.get("examples")?.as_array()?.first())
+}
+
+/// A value matching `schema`: its example, default or first enum value when it
+/// has one, built from the properties otherwise. When some properties are
+/// required, optional ones are only included if they carry an example. `refs`
+/// holds the references being expanded, so recursive schemas end in `null`.
+fn schema_sample(
+    document: &Value,
+    schema: &Value,
+    refs: &mut Vec<String>,
+) -> Result<Value, String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if refs.len() >= MAX_SAMPLE_DEPTH || refs.iter().any(|open| open == reference) {
+            return Ok(Value::Null);
+        }
+        refs.push(reference.to_string());
+        let sample =
+            resolve(document, schema).and_then(|target| schema_sample(document, target, refs));
+        refs.pop();
+        return sample;
+    }
+    if let Some(example) = first_example(schema) {
+        return Ok(example.clone());
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in parts {
+            match schema_sample(document, part, refs)? {
+                Value::Object(fields) => merged.extend(fields),
+                other if parts.len() == 1 => return Ok(other),
+                _ => {}
+            }
+        }
+        if let Value::Object(fields) = object_sample(document, schema, refs)? {
+            merged.extend(fields);
+        }
+        return Ok(Value::Object(merged));
+    }
+    let alternative = ["oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema.get(*key)?.as_array()?.first());
+    if let Some(alternative) = alternative {
+        return schema_sample(document, alternative, refs);
+    }
+
+    let number = |key| schema.get(key).and_then(Value::as_f64);
+    Ok(match schema_type(schema) {
+        "object" => object_sample(document, schema, refs)?,
+        "array" => match schema.get("items") {
+            Some(items) => match schema_sample(document, items, refs)? {
+                Value::Null => json!([]),
+                item => json!([item]),
+            },
+            None => json!([]),
+        },
+        "string" => json!(string_sample(schema.get("format").and_then(Value::as_str))),
+        "integer" => json!(number("minimum").unwrap_or(0.0) as i64),
+        "number" => json!(number("minimum").unwrap_or(0.0)),
+        "boolean" => json!(false),
+        _ => Value::Null,
+    })
+}
+
+fn object_sample(
+    document: &Value,
+    schema: &Value,
+    refs: &mut Vec<String>,
+) -> Result<Value, String> {
+    let required: Option<Vec<&str>> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect());
+    let mut object = Map::new();
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, property) in properties.into_iter().flatten() {
+        let resolved = resolve(document, property)?;
+        // Fields the server fills in don't belong in a request.
+        if resolved.get("readOnly").and_then(Value::as_bool) == Some(true) {
+            continue;
+        }
+        let wanted = match &required {
+            Some(required) => {
+                required.contains(&name.as_str()) || first_example(resolved).is_some()
+            }
+            None => true,
+        };
+        if wanted {
+            object.insert(name.clone(), schema_sample(document, property, refs)?);
+        }
+    }
+    Ok(Value::Object(object))
+}
+
+/// A media type's example: `example`, the first of `examples`, then a sample
+/// built from its schema.
+fn media_example(document: &Value, media: &Value) -> Result<Value, String> {
+    if let Some(example) = media.get("example") {
+        return Ok(example.clone());
+    }
+    let named = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next());
+    if let Some(named) = named {
+        if let Some(value) = resolve(document, named)?.get("value") {
+            return Ok(value.clone());
+        }
+    }
+    match media.get("schema") {
+        Some(schema) => schema_sample(document, schema, &mut Vec::new()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// The content type a request is built from, in order of preference.
+fn pick_media_type(content: &Map<String, Value>) -> Option<&str> {
+    let types: Vec<&str> = content.keys().map(String::as_str).collect();
+    let preferred: [&dyn Fn(&str) -> bool; 6] = [
+        &|t| t == "application/json",
+        &|t| t.contains("json"),
+        &|t| t == "application/x-www-form-urlencoded",
+        &|t| t == "multipart/form-data",
+        &|t| t.contains("xml"),
+        &|t| t.starts_with("text/"),
+    ];
+    preferred
+        .iter()
+        .find_map(|matches| types.iter().copied().find(|t| matches(t)))
+        .or_else(|| types.first().copied())
+}
+
+/// Server URL with its variables set to their defaults and no trailing `/`.
+/// Relative URLs are resolved against where the document was fetched from.
+fn server_url(server: &Value, source: Option<&str>) -> String {
+    let mut url = server
+        .get("url")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let variables = server.get("variables").and_then(Value::as_object);
+    for (name, variable) in variables.into_iter().flatten() {
+        let default = variable.get("default").map(scalar).unwrap_or_default();
+        url = url.replace(&format!("{{{}}}", name), &default);
+    }
+    if let Some(base) = source.and_then(|source| url::Url::parse(source).ok()) {
+        if url::Url::parse(&url).is_err() {
+            if let Ok(joined) = base.join(&url) {
+                url = joined.to_string();
+            }
+        }
+    }
+    url.trim_end_matches('/').to_string()
+}
+
+/// A parameter's example, from the parameter itself or its schema.
+fn parameter_example(document: &Value, parameter: &Value) -> Result<Option<String>, String> {
+    if let Some(example) = parameter.get("example") {
+        return Ok(Some(scalar(example)));
+    }
+    let named = parameter
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next());
+    if let Some(value) = named
+        .map(|n| resolve(document, n))
+        .transpose()?
+        .and_then(|n| n.get("value"))
+    {
+        return Ok(Some(scalar(value)));
+    }
+    match parameter.get("schema") {
+        Some(schema) => Ok(first_example(resolve(document, schema)?).map(scalar)),
+        None => Ok(None),
+    }
+}
+
+struct Importer<'a> {
+    document: &'a Value,
+    source: Option<&'a str>,
+    report: Vec<String>,
+}
+
+impl Importer<'_> {
+    /// The closest `AuthType` to a security requirement list. An empty list
+    /// means the operation needs no auth.
+    fn security(&mut self, at: &str, requirements: &Value) -> AuthType {
+        let schemes = self
+            .document
+            .pointer("/components/securitySchemes")
+            .and_then(Value::as_object);
+        let names = requirements
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+            .flat_map(|requirement| requirement.keys());
+        let mut unsupported = Vec::new();
+        for name in names {
+            let Some(scheme) = schemes
+                .and_then(|schemes| schemes.get(name))
+                .and_then(|scheme| resolve(self.document, scheme).ok())
+            else {
+                unsupported.push(format!("'{}' (not defined)", name));
+                continue;
+            };
+            let field = |key| scheme.get(key).and_then(Value::as_str).unwrap_or_default();
+            match (field("type"), field("scheme").to_ascii_lowercase().as_str()) {
+                ("http", "bearer") => {
+                    return AuthType::Bearer {
+                        token: String::new(),
+                    }
+                }
+                ("http", "basic") => {
+                    return AuthType::Basic {
+                        username: String::new(),
+                        password: String::new(),
+                    }
+                }
+                ("apiKey", _) => {
+                    let add_to = match field("in") {
+                        "query" => ApiKeyLocation::Query,
+                        "cookie" => ApiKeyLocation::Cookie,
+                        _ => ApiKeyLocation::Header,
+                    };
+                    return AuthType::ApiKey {
+                        key: field("name").to_string(),
+                        value: String::new(),
+                        add_to,
+                    };
+                }
+                ("oauth2" | "openIdConnect", _) => {
+                    return AuthType::OAuth2 {
+                        access_token: String::new(),
+                    }
+                }
+                (kind, _) => unsupported.push(format!("'{}' ({})", name, kind)),
+            }
+        }
+        if !unsupported.is_empty() {
+            self.report.push(format!(
+                "{}: security scheme {} has no equivalent and was left out",
+                at,
+                unsupported.join(", ")
+            ));
+        }
+        AuthType::None
+    }
+
+    fn body(&mut self, at: &str, request_body: &Value) -> Result<BodyType, String> {
+        let request_body = resolve(self.document, request_body)?;
+        let Some(content) = request_body.get("content").and_then(Value::as_object) else {
+            return Ok(BodyType::None);
+        };
+        let Some(media_type) = pick_media_type(content) else {
+            return Ok(BodyType::None);
+        };
+        let example = media_example(self.document, &content[media_type])?;
+
+        Ok(match media_type {
+            "application/x-www-form-urlencoded" => BodyType::FormUrlEncoded {
+                fields: example
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, value)| (key.clone(), scalar(value)))
+                    .collect::<HashMap<_, _>>(),
+            },
+            "multipart/form-data" => {
+                let schema = content[media_type]
+                    .get("schema")
+                    .map(|schema| resolve(self.document, schema))
+                    .transpose()?;
+                let mut fields = Vec::new();
+                for (name, value) in example.as_object().into_iter().flatten() {
+                    let format = schema
+                        .and_then(|schema| schema.get("properties")?.get(name))
+                        .and_then(|property| resolve(self.document, property).ok())
+                        .and_then(|property| property.get("format")?.as_str());
+                    if matches!(format, Some("binary" | "base64")) {
+                        self.report
+                            .push(format!("{}: choose a file for form field '{}'", at, name));
+                    }
+                    fields.push(MultipartField {
+                        name: name.clone(),
+                        value: MultipartValue::Text(scalar(value)),
+                    });
+                }
+                BodyType::Multipart { fields }
+            }
+            _ => {
+                let content = match example {
+                    Value::String(text) => text,
+                    Value::Null => String::new(),
+                    _ if media_type.contains("xml") => {
+                        self.report
+                            .push(format!("{}: no XML example to fill the body with", at));
+                        String::new()
+                    }
+                    other => serde_json::to_string_pretty(&other).map_err(|e| e.to_string())?,
+                };
+                BodyType::Raw {
+                    content,
+                    content_type: Some(media_type.to_string()),
+                }
+            }
+        })
+    }
+
+    /// The request for one operation, and whether it inherits the document's
+    /// auth.
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        path_item: &Value,
+        operation: &Value,
+    ) -> Result<(ApiRequest, bool), String> {
+        let at = format!("{} {}", method.to_uppercase(), path);
+
+        // Operation parameters override path-level ones with the same name and location.
+        let mut parameters: Vec<&Value> = Vec::new();
+        for list in [path_item.get("parameters"), operation.get("parameters")] {
+            for parameter in list.and_then(Value::as_array).into_iter().flatten() {
+                let parameter = resolve(self.document, parameter)?;
+                let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+                parameters.retain(|existing| key(existing) != key(parameter));
+                parameters.push(parameter);
+            }
+        }
+
+        let mut url = path.to_string();
+        let mut query_params = Vec::new();
+        let mut headers = Vec::new();
+        for parameter in parameters {
+            let name = parameter
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+            let example = parameter_example(self.document, parameter)?;
+            match parameter
+                .get("in")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+            {
+                // Path parameters without an example become variables to fill in.
+                "path" => {
+                    let value = example.unwrap_or_else(|| format!("{{{{{}}}}}", name));
+                    url = url.replace(&format!("{{{}}}", name), &value);
+                }
+                "query" if required || example.is_some() => {
+                    query_params.push((name.to_string(), example.unwrap_or_default()))
+                }
+                // These come from the body and auth instead.
+                "header"
+                    if ["accept", "content-type", "authorization"]
+                        .contains(&name.to_ascii_lowercase().as_str()) => {}
+                "header" if required || example.is_some() => {
+                    headers.push((name.to_string(), example.unwrap_or_default()))
+                }
+                "cookie" if required => self
+                    .report
+                    .push(format!("{}: required cookie '{}' was left out", at, name)),
+                _ => {}
+            }
+        }
+
+        let servers = [operation, path_item, self.document]
+            .iter()
+            .find_map(|holder| holder.get("servers")?.as_array()?.first());
+        let base = servers
+            .map(|server| server_url(server, self.source))
+            .unwrap_or_default();
+
+        let body = match operation.get("requestBody") {
+            Some(request_body) => self.body(&at, request_body)?,
+            None => BodyType::None,
+        };
+        let (auth, inherit_auth) = match operation.get("security") {
+            Some(requirements) => (self.security(&at, requirements), false),
+            None => (AuthType::None, true),
+        };
+
+        let request = ApiRequest {
+            method: method_from_string(&method.to_uppercase()),
+            url: format!("{}{}", base, url),
+            headers,
+            query_params,
+            body,
+            auth,
+            ..Default::default()
+        };
+        Ok((request, inherit_auth))
+    }
+}
+
+/// The folder list for a tag; `/` in a tag nests folders.
+fn folder_for<'a>(
+    items: &'a mut Vec<ImportedItem>,
+    tag: &str,
+    descriptions: &HashMap<&str, String>,
+) -> &'a mut Vec<ImportedItem> {
+    let mut items = items;
+    let mut walked = String::new();
+    for part in tag
+        .split('/')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        if !walked.is_empty() {
+            walked.push('/');
+        }
+        walked.push_str(part);
+        let index = items
+            .iter()
+            .position(|item| matches!(item, ImportedItem::Folder { name, .. } if name == part));
+        let index = index.unwrap_or_else(|| {
+            items.push(ImportedItem::Folder {
+                name: part.to_string(),
+                description: descriptions.get(walked.as_str()).cloned(),
+                items: Vec::new(),
+            });
+            items.len() - 1
+        });
+        let ImportedItem::Folder {
+            items: children, ..
+        } = &mut items[index]
+        else {
+            unreachable!()
+        };
+        items = children;
+    }
+    items
+}
+
+fn import_document(document: &Value, source: Option<&str>) -> Result<ImportResult, String> {
+    let version = document
+        .get("openapi")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !version.starts_with("3.") {
+        return Err(if document.get("swagger").is_some() {
+            "Swagger 2.0 documents aren't supported; convert it to OpenAPI 3 first".to_string()
+        } else {
+            "Not an OpenAPI 3 document: `openapi` is missing".to_string()
+        });
+    }
+    let info = document.get("info");
+    let name = info
+        .and_then(|info| info.get("title"))
+        .and_then(Value::as_str)
+        .unwrap_or("Imported API")
+        .to_string();
+    let descriptions: HashMap<&str, String> = document
+        .get("tags")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|tag| {
+            Some((
+                tag.get("name")?.as_str()?,
+                tag.get("description")?.as_str()?.to_string(),
+            ))
+        })
+        .collect();
+
+    let mut importer = Importer {
+        document,
+        source,
+        report: Vec::new(),
+    };
+    let auth = document
+        .get("security")
+        .map(|requirements| importer.security(&name, requirements));
+
+    let mut items = Vec::new();
+    let paths = document.get("paths").and_then(Value::as_object);
+    for (path, path_item) in paths.into_iter().flatten() {
+        let path_item = match resolve(document, path_item) {
+            Ok(path_item) => path_item,
+            Err(e) => {
+                importer.report.push(format!("Skipped {}: {}", path, e));
+                continue;
+            }
+        };
+        for method in METHODS {
+            let Some(operation) = path_item.get(method) else {
+                continue;
+            };
+            let (request, inherit_auth) =
+                match importer.operation(path, method, path_item, operation) {
+                    Ok(built) => built,
+                    Err(e) => {
+                        importer.report.push(format!(
+                            "Skipped {} {}: {}",
+                            method.to_uppercase(),
+                            path,
+                            e
+                        ));
+                        continue;
+                    }
+                };
+            let field = |key| operation.get(key).and_then(Value::as_str);
+            let item = ImportedItem::Request {
+                name: field("summary")
+                    .or_else(|| field("operationId"))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path)),
+                description: field("description").map(str::to_string),
+                request: Box::new(request),
+                inherit_auth,
+                example_response: None,
+            };
+            let tag = operation
+                .get("tags")
+                .and_then(Value::as_array)
+                .and_then(|tags| tags.first())
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            folder_for(&mut items, tag, &descriptions).push(item);
+        }
+    }
+
+    Ok(ImportResult {
+        description: info
+            .and_then(|info| info.get("description"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        name,
+        items,
+        auth,
+        environment: None,
+        report: importer.report,
+    })
+}
+
+fn parse_document(content: &str) -> Result<Value, String> {
+    // JSON is YAML too, but serde_json reports JSON mistakes more clearly.
+    if content.trim_start().starts_with('{') {
+        return serde_json::from_str(content).map_err(|e| format!("Invalid OpenAPI JSON: {}", e));
+    }
+    serde_yaml::from_str::<serde_yaml::Value>(content)
+        .map(yaml_to_json_value)
+        .map_err(|e| format!("Invalid OpenAPI YAML: {}", e))
+}
+
+/// Read an OpenAPI 3 document (YAML or JSON) from a file or an http(s) URL.
+/// Operations are grouped into folders by their first tag, with sample bodies
+/// generated from their schemas; operations that couldn't be read are listed
+/// in `report`.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_openapi(app: AppHandle, path_or_url: String) -> Result<ImportResult, String> {
+    let is_url = path_or_url.starts_with("http://") || path_or_url.starts_with("https://");
+    let content = if is_url {
+        let url = path_or_url.clone();
+        tokio::task::spawn_blocking(move || execute_fetch_url(url))
+            .await
+            .map_err(|e| e.to_string())??
+            .body
+    } else {
+        let allowed = app
+            .try_fs_scope()
+            .is_some_and(|scope| scope.is_allowed(&path_or_url));
+        if !allowed {
+            return Err(format!("Access to '{}' is not allowed", path_or_url));
+        }
+        std::fs::read_to_string(&path_or_url).map_err(|e| e.to_string())?
+    };
+    let document = parse_document(&content)?;
+    import_document(&document, is_url.then_some(path_or_url.as_str()))
+}
+
+/// `url` without its query, split into the origin (scheme and authority, or a
+/// leading `{{variable}}`) and the path.
+fn split_origin(url: &str) -> (&str, &str) {
+    if let Some(start) = url.find("://").map(|i| i + 3) {
+        let end = url[start..].find('/').map_or(url.len(), |i| start + i);
+        return url.split_at(end);
+    }
+    if url.starts_with("{{") {
+        if let Some(end) = url.find("}}") {
+            return url.split_at(end + 2);
+        }
+    }
+    ("", url)
+}
+
+/// `path` with `:name`, `{{name}}` and `{name}` segments as `{name}`, and the
+/// parameter names in order.
+fn path_template(path: &str) -> (String, Vec<String>) {
+    let mut names = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| {
+            let name = segment
+                .strip_prefix(':')
+                .or_else(|| segment.strip_prefix("{{")?.strip_suffix("}}"))
+                .or_else(|| segment.strip_prefix('{')?.strip_suffix('}'));
+            match name {
+                Some(name) if !name.is_empty() => {
+                    names.push(name.to_string());
+                    format!("{{{}}}", name)
+                }
+                _ => segment.to_string(),
+            }
+        })
+        .collect();
+    let path = segments.join("/");
+    if path.starts_with('/') {
+        (path, names)
+    } else {
+        (format!("/{}", path), names)
+    }
+}
+
+/// A schema describing the shape of `value`.
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullable": true }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => json!({
+            "type": "array",
+            "items": items.first().map(infer_schema).unwrap_or_else(|| json!({})),
+        }),
+        Value::Object(fields) => json!({
+            "type": "object",
+            "properties": fields
+                .iter()
+                .map(|(key, value)| (key.clone(), infer_schema(value)))
+                .collect::<Map<_, _>>(),
+        }),
+    }
+}
+
+/// A parameter value as the JSON it most likely stands for.
+fn text_value(value: &str) -> Value {
+    match value {
+        "true" | "false" => json!(value == "true"),
+        _ => value
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| {
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|n| serde_json::Number::from_f64(n).map(Value::Number))
+            })
+            .unwrap_or_else(|| json!(value)),
+    }
+}
+
+/// A media type object for `text` sent or received as `content_type`: JSON
+/// gets a schema inferred from it, anything else is described as a string.
+fn text_media(content_type: &str, text: &str) -> Value {
+    match serde_json::from_str::<Value>(text) {
+        Ok(value) if content_type.contains("json") => {
+            json!({ "schema": infer_schema(&value), "example": value })
+        }
+        _ if text.is_empty() => json!({ "schema": { "type": "string" } }),
+        _ => json!({ "schema": { "type": "string" }, "example": text }),
+    }
+}
+
+fn binary_media() -> Value {
+    json!({ "schema": { "type": "string", "format": "binary" } })
+}
+
+/// The content type and media type object of a request body.
+fn body_media(body: &BodyType) -> Result<Option<(String, Value)>, String> {
+    let content_type = |content_type: &Option<String>, default: &str| {
+        content_type.clone().unwrap_or_else(|| default.to_string())
+    };
+    Ok(Some(match body {
+        BodyType::None => return Ok(None),
+        BodyType::Raw {
+            content,
+            content_type: ct,
+        } => {
+            let ct = content_type(ct, "text/plain");
+            let media = text_media(&ct, content);
+            (ct, media)
+        }
+        BodyType::FormUrlEncoded { fields } => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(key, value)| (key.clone(), json!({ "type": "string", "example": value })))
+                .collect();
+            (
+                "application/x-www-form-urlencoded".to_string(),
+                json!({ "schema": { "type": "object", "properties": properties } }),
+            )
+        }
+        BodyType::Multipart { fields } => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|field| {
+                    let schema = match &field.value {
+                        MultipartValue::Text(_) => json!({ "type": "string" }),
+                        _ => json!({ "type": "string", "format": "binary" }),
+                    };
+                    (field.name.clone(), schema)
+                })
+                .collect();
+            (
+                "multipart/form-data".to_string(),
+                json!({ "schema": { "type": "object", "properties": properties } }),
+            )
+        }
+        BodyType::Binary { .. } => ("application/octet-stream".to_string(), binary_media()),
+        BodyType::RawFile {
+            content_type: ct, ..
+        }
+        | BodyType::BinaryFile {
+            content_type: ct, ..
+        } => (content_type(ct, "application/octet-stream"), binary_media()),
+        BodyType::GraphQL {
+            query,
+            variables,
+            operation_name,
+        } => {
+            let envelope =
+                graphql_envelope(query, variables.as_deref(), operation_name.as_deref())?;
+            (
+                "application/json".to_string(),
+                text_media("application/json", &envelope),
+            )
+        }
+        BodyType::Soap {
+            action,
+            envelope_body,
+            soap_version,
+        } => {
+            let (ct, _) = soap_headers(action, *soap_version);
+            let envelope = soap_envelope(envelope_body, *soap_version)?;
+            let media = text_media(&ct, &envelope);
+            (ct, media)
+        }
+        BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } => {
+            let envelope = jsonrpc_body(body)?;
+            (
+                "application/json".to_string(),
+                json!({ "schema": infer_schema(&envelope), "example": envelope }),
+            )
+        }
+    }))
+}
+
+/// The security scheme name and definition closest to `auth`.
+fn security_scheme(auth: &AuthType) -> Option<(String, Value)> {
+    Some(match auth {
+        AuthType::Basic { .. } => (
+            "basicAuth".to_string(),
+            json!({ "type": "http", "scheme": "basic" }),
+        ),
+        // Without the provider's URLs an OAuth2 flow can't be described;
+        // the token is sent as a bearer token either way.
+        AuthType::Bearer { .. } | AuthType::OAuth2 { .. } => (
+            "bearerAuth".to_string(),
+            json!({ "type": "http", "scheme": "bearer" }),
+        ),
+        AuthType::Jwt { .. } => (
+            "jwtAuth".to_string(),
+            json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" }),
+        ),
+        AuthType::ApiKey { key, add_to, .. } => {
+            let location = match add_to {
+                ApiKeyLocation::Header => "header",
+                ApiKeyLocation::Query => "query",
+                ApiKeyLocation::Cookie => "cookie",
+            };
+            // Component names may only use `[A-Za-z0-9._-]`.
+            let name: String = key
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || ".-_".contains(c) {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            (
+                format!("apiKey_{}", name),
+                json!({ "type": "apiKey", "in": location, "name": key }),
+            )
+        }
+        AuthType::None | AuthType::Ntlm { .. } | AuthType::Negotiate { .. } => return None,
+    })
+}
+
+/// Adds the entries of `extra` whose key `same` doesn't find in `list`.
+fn merge_list(list: &mut Value, extra: &Value, same: impl Fn(&Value, &Value) -> bool) {
+    let (Some(list), Some(extra)) = (list.as_array_mut(), extra.as_array()) else {
+        return;
+    };
+    for entry in extra {
+        if !list.iter().any(|existing| same(existing, entry)) {
+            list.push(entry.clone());
+        }
+    }
+}
+
+/// Adds the keys of `extra` missing from `object`.
+fn merge_object(object: &mut Value, extra: &Value) {
+    let (Some(object), Some(extra)) = (object.as_object_mut(), extra.as_object()) else {
+        return;
+    };
+    for (key, value) in extra {
+        object.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+}
+
+struct Exporter<'a> {
+    /// Stripped from request URLs, leaving the path; the origin is used otherwise.
+    server_url: Option<&'a str>,
+    environment: Option<&'a ImportedEnvironment>,
+    collection_auth: Option<&'a AuthType>,
+    paths: Map<String, Value>,
+    /// The request each operation was first written from, for merge warnings.
+    origins: HashMap<(String, String), String>,
+    servers: Vec<String>,
+    schemes: Map<String, Value>,
+    tags: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Exporter<'_> {
+    /// The server URL for an origin, with a `{{variable}}` origin filled in
+    /// from the environment.
+    fn server(&self, origin: &str) -> String {
+        let Some(name) = origin.strip_prefix("{{").and_then(|o| o.strip_suffix("}}")) else {
+            return origin.to_string();
+        };
+        self.environment
+            .and_then(|environment| {
+                environment
+                    .variables
+                    .iter()
+                    .find(|variable| variable.key == name && !variable.secret)
+            })
+            .map(|variable| variable.value.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| format!("{{{}}}", name))
+    }
+
+    fn security(&mut self, at: &str, auth: &AuthType) -> Option<String> {
+        if let AuthType::Ntlm { .. } | AuthType::Negotiate { .. } = auth {
+            self.warnings.push(format!(
+                "{}: NTLM and Negotiate auth can't be described in OpenAPI",
+                at
+            ));
+        }
+        let (name, scheme) = security_scheme(auth)?;
+        self.schemes.insert(name.clone(), scheme);
+        Some(name)
+    }
+
+    fn request(
+        &mut self,
+        name: &str,
+        description: Option<&str>,
+        req: &ApiRequest,
+        inherit_auth: bool,
+        example_response: Option<&ExampleResponse>,
+        tag: Option<&str>,
+    ) -> Result<(), String> {
+        let method = method_to_curl_string(&req.method).to_ascii_lowercase();
+        if !METHODS.contains(&method.as_str()) {
+            self.warnings.push(format!(
+                "{}: skipped, OpenAPI has no {} operations",
+                name,
+                method.to_uppercase()
+            ));
+            return Ok(());
+        }
+
+        let (base, mut query) = split_query(&req.url);
+        query.extend(req.query_params.iter().cloned());
+        let server_path = self
+            .server_url
+            .and_then(|server| base.strip_prefix(server.trim_end_matches('/')));
+        let (origin, path) = match server_path {
+            Some(path) => ("", path),
+            None => split_origin(&base),
+        };
+        if !origin.is_empty() {
+            let server = self.server(origin);
+            if !self.servers.contains(&server) {
+                self.servers.push(server);
+            }
+        }
+        let (path, path_names) = path_template(path);
+        let at = format!("{} {}", method.to_uppercase(), path);
+
+        let mut parameters: Vec<Value> = path_names
+            .iter()
+            .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+            .collect();
+        let mut parameter = |name: &str, location: &str, value: &str| {
+            let value = text_value(value);
+            let mut parameter =
+                json!({ "name": name, "in": location, "schema": infer_schema(&value) });
+            if value != "" {
+                parameter["example"] = value;
+            }
+            parameters.push(parameter);
+        };
+        for (key, value) in &query {
+            parameter(key, "query", value);
+        }
+        for (key, value) in &req.headers {
+            // OpenAPI describes these through the body and security instead.
+            if ["accept", "content-type", "authorization"]
+                .contains(&key.to_ascii_lowercase().as_str())
+            {
+                continue;
+            }
+            parameter(key, "header", value);
+        }
+        for cookie in &req.cookies {
+            parameter(&cookie.name, "cookie", &cookie.value);
+        }
+
+        let mut operation = json!({ "summary": name, "parameters": parameters });
+        if let Some(description) = description {
+            operation["description"] = json!(description);
+        }
+        if let Some(tag) = tag {
+            operation["tags"] = json!([tag]);
+        }
+        if let Some((content_type, media)) = body_media(&req.body)? {
+            operation["requestBody"] = json!({ "content": { content_type: media } });
+        }
+        operation["responses"] = match example_response {
+            Some(response) => {
+                let content_type = response.content_type.as_deref().unwrap_or("text/plain");
+                // Parameters like `; charset=utf-8` don't belong in the key.
+                let media_type = content_type.split(';').next().unwrap_or_default().trim();
+                let mut described = json!({ "description": "Example response" });
+                if !response.body.is_empty() {
+                    described["content"] =
+                        json!({ media_type: text_media(media_type, &response.body) });
+                }
+                json!({ response.status.to_string(): described })
+            }
+            None => json!({ "default": { "description": "Response" } }),
+        };
+        if !inherit_auth {
+            match self.security(&at, &req.auth) {
+                Some(scheme) => operation["security"] = json!([{ scheme: [] }]),
+                // Opting out of the collection's auth.
+                None if self.collection_auth.is_some() => operation["security"] = json!([]),
+                None => {}
+            }
+        }
+
+        let operations = self.paths.entry(path.clone()).or_insert_with(|| json!({}));
+        let key = (path.clone(), method.clone());
+        match operations.get_mut(&method) {
+            None => {
+                operations[&method] = operation;
+                self.origins.insert(key, name.to_string());
+            }
+            Some(existing) => {
+                merge_list(
+                    &mut existing["parameters"],
+                    &operation["parameters"],
+                    |a, b| a["name"] == b["name"] && a["in"] == b["in"],
+                );
+                if let Some(body) = operation.get("requestBody") {
+                    match existing.get_mut("requestBody") {
+                        Some(existing_body) => {
+                            merge_object(&mut existing_body["content"], &body["content"])
+                        }
+                        None => existing["requestBody"] = body.clone(),
+                    }
+                }
+                if existing["responses"].get("default").is_some() {
+                    existing["responses"] = operation["responses"].clone();
+                } else if operation["responses"].get("default").is_none() {
+                    merge_object(&mut existing["responses"], &operation["responses"]);
+                }
+                self.warnings.push(format!(
+                    "{}: '{}' was merged into '{}'",
+                    at,
+                    name,
+                    self.origins
+                        .get(&key)
+                        .map(String::as_str)
+                        .unwrap_or_default()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Requests are tagged with their folder path, `/`-joined.
+    fn items(&mut self, items: &[ImportedItem], tag: Option<&str>) -> Result<(), String> {
+        for item in items {
+            match item {
+                ImportedItem::Folder { name, items, .. } => {
+                    let tag = match tag {
+                        Some(parent) => format!("{}/{}", parent, name),
+                        None => name.clone(),
+                    };
+                    self.items(items, Some(&tag))?;
+                }
+                ImportedItem::Request {
+                    name,
+                    description,
+                    request,
+                    inherit_auth,
+                    example_response,
+                } => {
+                    self.request(
+                        name,
+                        description.as_deref(),
+                        request,
+                        *inherit_auth,
+                        example_response.as_ref(),
+                        tag,
+                    )?;
+                    if let Some(tag) = tag.filter(|tag| !self.tags.iter().any(|t| t == tag)) {
+                        self.tags.push(tag.to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The OpenAPI document for `collection`, and the warnings for what it
+/// couldn't describe.
+fn export_document(
+    collection: &CollectionExport,
+    options: &OpenApiExportOptions,
+) -> Result<(Value, Vec<String>), String> {
+    let mut exporter = Exporter {
+        server_url: options.server_url.as_deref(),
+        environment: collection.environment.as_ref(),
+        collection_auth: collection
+            .auth
+            .as_ref()
+            .filter(|auth| !matches!(auth, AuthType::None)),
+        paths: Map::new(),
+        origins: HashMap::new(),
+        servers: Vec::new(),
+        schemes: Map::new(),
+        tags: Vec::new(),
+        warnings: Vec::new(),
+    };
+    let collection_scheme = match exporter.collection_auth {
+        Some(auth) => exporter.security(&collection.name, auth),
+        None => None,
+    };
+    exporter.items(&collection.items, None)?;
+
+    let mut info = json!({
+        "title": collection.name,
+        "version": options.version.as_deref().unwrap_or("1.0.0"),
+    });
+    if let Some(description) = &collection.description {
+        info["description"] = json!(description);
+    }
+    let servers = match &options.server_url {
+        Some(url) => vec![url.trim_end_matches('/').to_string()],
+        None => exporter.servers,
+    };
+    let mut document = json!({
+        "openapi": "3.0.3",
+        "info": info,
+        "paths": exporter.paths,
+    });
+    if !servers.is_empty() {
+        document["servers"] = servers
+            .iter()
+            .map(|url| {
+                // An origin left as `{name}` needs the variable declared.
+                let mut server = json!({ "url": url });
+                if let Some(name) = url.strip_prefix('{').and_then(|u| u.strip_suffix('}')) {
+                    server["variables"] = json!({ name: { "default": "" } });
+                }
+                server
+            })
+            .collect();
+    }
+    if !exporter.tags.is_empty() {
+        document["tags"] = exporter
+            .tags
+            .iter()
+            .map(|name| json!({ "name": name }))
+            .collect();
+    }
+    if !exporter.schemes.is_empty() {
+        document["components"] = json!({ "securitySchemes": exporter.schemes });
+    }
+    if let Some(scheme) = collection_scheme {
+        document["security"] = json!([{ scheme: [] }]);
+    }
+    Ok((document, exporter.warnings))
+}
+
+/// Describe a collection as an OpenAPI 3.0 document: one operation per path
+/// and method, with `:id` and `{{id}}` segments as path parameters. Requests
+/// sharing a path and method are merged, which is noted in `warnings`.
+/// Collections live in the frontend's store, so the caller passes the one to
+/// export.
+#[tauri::command]
+#[specta::specta]
+pub fn export_openapi(
+    collection: CollectionExport,
+    options: OpenApiExportOptions,
+) -> Result<OpenApiExport, String> {
+    let (document, warnings) = export_document(&collection, &options)?;
+    let document = if options.yaml {
+        serde_yaml::to_string(&document).map_err(|e| e.to_string())?
+    } else {
+        serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?
+    };
+    Ok(OpenApiExport { document, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ImportedVariable, Methods};
+
+    const SCHEMA: &str = include_str!("testdata/openapi-3.0-schema.json");
+
+    fn schema_errors(document: &Value) -> Vec<String> {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        validator
+            .iter_errors(document)
+            .map(|error| format!("{} at {}", error, error.instance_path()))
+            .collect()
+    }
+
+    fn request(name: &str, req: ApiRequest) -> ImportedItem {
+        ImportedItem::Request {
+            name: name.to_string(),
+            description: Some(format!("{} request", name)),
+            request: Box::new(req),
+            inherit_auth: false,
+            example_response: None,
+        }
+    }
+
+    fn sample_collection() -> CollectionExport {
+        let url = |path: &str| format!("{{{{host}}}}/api/{}", path);
+        let users = vec![
+            ImportedItem::Request {
+                name: "list users".to_string(),
+                description: None,
+                request: Box::new(ApiRequest {
+                    method: Methods::GET,
+                    url: url("users?page=2"),
+                    query_params: vec![("verbose".to_string(), "true".to_string())],
+                    headers: vec![
+                        ("X-Trace".to_string(), "abc".to_string()),
+                        ("Accept".to_string(), "application/json".to_string()),
+                    ],
+                    ..Default::default()
+                }),
+                inherit_auth: true,
+                example_response: Some(ExampleResponse {
+                    status: 200,
+                    content_type: Some("application/json; charset=utf-8".to_string()),
+                    body: "[{\"id\":1,\"name\":\"ana\",\"admin\":false}]".to_string(),
+                }),
+            },
+            request(
+                "get user",
+                ApiRequest {
+                    method: Methods::GET,
+                    url: url("users/:id/posts/{{postId}}"),
+                    auth: AuthType::ApiKey {
+                        key: "session".to_string(),
+                        value: "k".to_string(),
+                        add_to: ApiKeyLocation::Cookie,
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "create user",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: url("users"),
+                    body: BodyType::Raw {
+                        content: "{\"name\":\"ana\",\"age\":30,\"tags\":[\"a\"]}".to_string(),
+                        content_type: Some("application/json".to_string()),
+                    },
+                    auth: AuthType::Basic {
+                        username: "u".to_string(),
+                        password: "p".to_string(),
+                    },
+                    ..Default::default()
+                },
+            ),
+            // Same path and method as above, so merged into it.
+            request(
+                "create user from form",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: url("users"),
+                    body: BodyType::FormUrlEncoded {
+                        fields: HashMap::from([("name".to_string(), "ana".to_string())]),
+                    },
+                    ..Default::default()
+                },
+            ),
+        ];
+        let bodies = vec![
+            request(
+                "upload",
+                ApiRequest {
+                    method: Methods::PUT,
+                    url: url("files/{{fileId}}"),
+                    body: BodyType::Multipart {
+                        fields: vec![
+                            MultipartField {
+                                name: "note".to_string(),
+                                value: MultipartValue::Text("hi".to_string()),
+                            },
+                            MultipartField {
+                                name: "doc".to_string(),
+                                value: MultipartValue::FilePath {
+                                    path: "/tmp/doc.pdf".to_string(),
+                                    filename: None,
+                                    content_type: Some("application/pdf".to_string()),
+                                },
+                            },
+                        ],
+                    },
+                    auth: AuthType::OAuth2 {
+                        access_token: "t".to_string(),
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "binary",
+                ApiRequest {
+                    method: Methods::PATCH,
+                    url: url("files/raw"),
+                    body: BodyType::BinaryFile {
+                        path: "/tmp/data.bin".to_string(),
+                        content_type: None,
+                    },
+                    auth: AuthType::Ntlm {
+                        username: "u".to_string(),
+                        password: "p".to_string(),
+                        domain: None,
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "graphql",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: url("graphql"),
+                    body: BodyType::GraphQL {
+                        query: "{ me { id } }".to_string(),
+                        variables: Some("{\"a\":1}".to_string()),
+                        operation_name: None,
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "elsewhere",
+                ApiRequest {
+                    method: Methods::DELETE,
+                    url: "https://other.example.com/v1/items/{{itemId}}".to_string(),
+                    ..Default::default()
+                },
+            ),
+        ];
+        CollectionExport {
+            name: "Sample".to_string(),
+            description: Some("Operations of every shape".to_string()),
+            items: vec![
+                ImportedItem::Folder {
+                    name: "Users".to_string(),
+                    description: None,
+                    items: users,
+                },
+                ImportedItem::Folder {
+                    name: "Bodies".to_string(),
+                    description: None,
+                    items: bodies,
+                },
+            ],
+            auth: Some(AuthType::Bearer {
+                token: "{{token}}".to_string(),
+            }),
+            environment: Some(ImportedEnvironment {
+                name: "Sample".to_string(),
+                variables: vec![ImportedVariable {
+                    key: "host".to_string(),
+                    value: "https://api.example.com/".to_string(),
+                    enabled: true,
+                    secret: false,
+                }],
+            }),
+        }
+    }
+
+    fn options() -> OpenApiExportOptions {
+        OpenApiExportOptions {
+            version: None,
+            server_url: None,
+            yaml: false,
+        }
+    }
+
+    #[test]
+    fn export_matches_the_published_schema() {
+        let (document, _) = export_document(&sample_collection(), &options()).unwrap();
+        assert_eq!(schema_errors(&document), Vec::<String>::new());
+    }
+
+    #[test]
+    fn export_with_a_server_url_matches_the_published_schema() {
+        let options = OpenApiExportOptions {
+            version: Some("2.1.0".to_string()),
+            server_url: Some("{{host}}/api".to_string()),
+            yaml: false,
+        };
+        let (document, _) = export_document(&sample_collection(), &options).unwrap();
+        assert_eq!(schema_errors(&document), Vec::<String>::new());
+    }
+
+    #[test]
+    fn exported_document_imports_back() {
+        let (document, warnings) = export_document(&sample_collection(), &options()).unwrap();
+        assert!(warnings.iter().any(|w| w.contains("was merged into")));
+        assert!(warnings.iter().any(|w| w.contains("NTLM")));
+        let imported = import_document(&document, None).unwrap();
+        assert_eq!(imported.name, "Sample");
+        let folder = |wanted: &str| {
+            imported.items.iter().find_map(|item| match item {
+                ImportedItem::Folder { name, items, .. } if name == wanted => Some(items.len()),
+                _ => None,
+            })
+        };
+        assert_eq!(folder("Users"), Some(3));
+        assert_eq!(folder("Bodies"), Some(4));
+    }
+}
@@ -0,0 +1,1228 @@
+//! Postman Collection v2.1 files, read and written (v2.0 reads the same way).
+//! `{{variable}}` references are kept as written either way; the frontend
+//! resolves them at send time.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::rest::{
+    graphql_envelope, jsonrpc_body, method_from_string, method_to_curl_string, soap_envelope,
+    soap_headers,
+};
+use crate::types::{
+    ApiKeyLocation, ApiRequest, AuthType, BodyType, CollectionExport, ExampleResponse,
+    ImportResult, ImportedEnvironment, ImportedItem, ImportedVariable, MultipartField,
+    MultipartValue,
+};
+
+/// A plain string, or the text of a `{ "content": ... }` description.
+fn text(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(text) => Some(text.clone()),
+        Value::Object(map) => map.get("content")?.as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Values may be any JSON; strings are taken as-is, the rest as JSON text.
+pub(crate) fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn is_disabled(entry: &Value) -> bool {
+    entry
+        .get("disabled")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+pub(crate) fn enabled_entries(list: Option<&Value>) -> impl Iterator<Item = &Value> {
+    list.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|entry| !is_disabled(entry))
+}
+
+/// The enabled `key`/`value` entries of a header, query or form list.
+fn pairs(list: Option<&Value>) -> Vec<(String, String)> {
+    enabled_entries(list)
+        .filter_map(|entry| {
+            let key = entry.get("key")?.as_str()?.to_string();
+            Some((key, entry.get("value").map(scalar).unwrap_or_default()))
+        })
+        .collect()
+}
+
+/// `raw` split at `?`, without decoding: Postman stores what was typed.
+pub(crate) fn split_query(raw: &str) -> (String, Vec<(String, String)>) {
+    let raw = raw.split('#').next().unwrap_or_default();
+    let Some((base, query)) = raw.split_once('?') else {
+        return (raw.to_string(), Vec::new());
+    };
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (pair.to_string(), String::new()),
+        })
+        .collect();
+    (base.to_string(), params)
+}
+
+/// Rebuilds a URL object that has no `raw` from its parts.
+fn assemble_url(url: &Value) -> String {
+    let join = |value: Option<&Value>, separator: &str| match value {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .map(|part| match part {
+                Value::Object(map) => map.get("value").map(scalar).unwrap_or_default(),
+                other => scalar(other),
+            })
+            .collect::<Vec<_>>()
+            .join(separator),
+        Some(other) => scalar(other),
+        None => String::new(),
+    };
+    let mut assembled = String::new();
+    if let Some(protocol) = url.get("protocol").and_then(Value::as_str) {
+        assembled.push_str(&format!("{}://", protocol));
+    }
+    assembled.push_str(&join(url.get("host"), "."));
+    if let Some(port) = url.get("port").map(scalar).filter(|p| !p.is_empty()) {
+        assembled.push_str(&format!(":{}", port));
+    }
+    let path = join(url.get("path"), "/");
+    if !path.is_empty() {
+        assembled.push('/');
+        assembled.push_str(&path);
+    }
+    assembled
+}
+
+/// The URL without its query, and the query as params. `:name` path
+/// segments with a value in `variable` are filled in.
+fn parse_url(url: &Value) -> (String, Vec<(String, String)>) {
+    let raw = match url {
+        Value::String(raw) => return split_query(raw),
+        Value::Object(_) => url
+            .get("raw")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| assemble_url(url)),
+        _ => return (String::new(), Vec::new()),
+    };
+    let (mut base, raw_query) = split_query(&raw);
+    let query = match url.get("query") {
+        Some(_) => pairs(url.get("query")),
+        None => raw_query,
+    };
+    for (key, value) in pairs(url.get("variable")) {
+        if value.is_empty() {
+            continue;
+        }
+        let segment = format!(":{}", key);
+        base = base
+            .split('/')
+            .map(|part| {
+                if part == segment {
+                    value.as_str()
+                } else {
+                    part
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+    }
+    (base, query)
+}
+
+fn raw_content_type(language: Option<&str>) -> &'static str {
+    match language {
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("html") => "text/html",
+        Some("javascript") => "application/javascript",
+        _ => "text/plain",
+    }
+}
+
+#[derive(Default)]
+struct Importer {
+    report: Vec<String>,
+}
+
+impl Importer {
+    fn note(&mut self, at: &str, what: String) {
+        self.report.push(format!("{}: {}", at, what));
+    }
+
+    /// Scripts can't run here, so each non-empty one is reported.
+    fn scripts(&mut self, at: &str, node: &Value) {
+        for event in enabled_entries(node.get("event")) {
+            let has_code = match event.pointer("/script/exec") {
+                Some(Value::Array(lines)) => lines
+                    .iter()
+                    .any(|line| line.as_str().is_some_and(|l| !l.trim().is_empty())),
+                Some(Value::String(code)) => !code.trim().is_empty(),
+                _ => false,
+            };
+            if has_code {
+                let kind = match event.get("listen").and_then(Value::as_str) {
+                    Some("prerequest") => "Pre-request script",
+                    Some("test") => "Test script",
+                    _ => "Script",
+                };
+                self.note(at, format!("{} was not imported", kind));
+            }
+        }
+    }
+
+    /// `None` when the node inherits its parent's auth.
+    fn auth(&mut self, at: &str, node: &Value) -> Option<AuthType> {
+        let auth = node.get("auth").filter(|auth| !auth.is_null())?;
+        let kind = auth.get("type").and_then(Value::as_str)?;
+        // Attributes are a list of `{key, value}` in v2.1 and a map in v2.0.
+        let attribute = |name: &str| match auth.get(kind) {
+            Some(Value::Array(list)) => list
+                .iter()
+                .find(|entry| entry.get("key").and_then(Value::as_str) == Some(name))
+                .and_then(|entry| entry.get("value"))
+                .map(scalar)
+                .unwrap_or_default(),
+            Some(Value::Object(map)) => map.get(name).map(scalar).unwrap_or_default(),
+            _ => String::new(),
+        };
+        Some(match kind {
+            "inherit" => return None,
+            "noauth" => AuthType::None,
+            "basic" => AuthType::Basic {
+                username: attribute("username"),
+                password: attribute("password"),
+            },
+            "bearer" => AuthType::Bearer {
+                token: attribute("token"),
+            },
+            "apikey" => AuthType::ApiKey {
+                key: attribute("key"),
+                value: attribute("value"),
+                add_to: if attribute("in") == "query" {
+                    ApiKeyLocation::Query
+                } else {
+                    ApiKeyLocation::Header
+                },
+            },
+            other => {
+                self.note(
+                    at,
+                    format!("{} auth is not supported and was left out", other),
+                );
+                AuthType::None
+            }
+        })
+    }
+
+    fn body(&mut self, at: &str, body: &Value, has_content_type: bool) -> BodyType {
+        if is_disabled(body) {
+            return BodyType::None;
+        }
+        match body.get("mode").and_then(Value::as_str) {
+            Some("raw") => {
+                let language = body
+                    .pointer("/options/raw/language")
+                    .and_then(Value::as_str);
+                BodyType::Raw {
+                    content: body.get("raw").map(scalar).unwrap_or_default(),
+                    content_type: (!has_content_type)
+                        .then(|| raw_content_type(language).to_string()),
+                }
+            }
+            Some("urlencoded") => {
+                let mut fields = HashMap::new();
+                for (key, value) in pairs(body.get("urlencoded")) {
+                    if fields.insert(key.clone(), value).is_some() {
+                        self.note(
+                            at,
+                            format!("Repeated form field '{}' kept only its last value", key),
+                        );
+                    }
+                }
+                BodyType::FormUrlEncoded { fields }
+            }
+            Some("formdata") => {
+                let mut fields = Vec::new();
+                for entry in enabled_entries(body.get("formdata")) {
+                    let Some(name) = entry.get("key").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let name = name.to_string();
+                    if entry.get("type").and_then(Value::as_str) != Some("file") {
+                        let value = entry.get("value").map(scalar).unwrap_or_default();
+                        fields.push(MultipartField {
+                            name,
+                            value: MultipartValue::Text(value),
+                        });
+                        continue;
+                    }
+                    // `src` is a path, a list of paths, or null when never picked.
+                    let paths: Vec<String> = match entry.get("src") {
+                        Some(Value::String(path)) => vec![path.clone()],
+                        Some(Value::Array(list)) => list
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .map(str::to_string)
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+                    if paths.is_empty() {
+                        self.note(
+                            at,
+                            format!("File field '{}' has no file and was left out", name),
+                        );
+                    }
+                    let content_type = entry
+                        .get("contentType")
+                        .and_then(Value::as_str)
+                        .filter(|ct| !ct.is_empty())
+                        .map(str::to_string);
+                    for path in paths {
+                        fields.push(MultipartField {
+                            name: name.clone(),
+                            value: MultipartValue::FilePath {
+                                path,
+                                filename: None,
+                                content_type: content_type.clone(),
+                            },
+                        });
+                    }
+                }
+                BodyType::Multipart { fields }
+            }
+            Some("file") => match body.get("file") {
+                Some(file) if file.get("src").is_some_and(Value::is_string) => {
+                    BodyType::BinaryFile {
+                        path: scalar(&file["src"]),
+                        content_type: None,
+                    }
+                }
+                Some(file) if file.get("content").is_some_and(Value::is_string) => BodyType::Raw {
+                    content: scalar(&file["content"]),
+                    content_type: None,
+                },
+                _ => {
+                    self.note(at, "File body has no file and was left out".to_string());
+                    BodyType::None
+                }
+            },
+            Some("graphql") => {
+                let variables = match body.pointer("/graphql/variables") {
+                    Some(Value::String(text)) => Some(text.clone()),
+                    Some(Value::Null) | None => None,
+                    Some(other) => Some(other.to_string()),
+                };
+                BodyType::GraphQL {
+                    query: body
+                        .pointer("/graphql/query")
+                        .map(scalar)
+                        .unwrap_or_default(),
+                    variables: variables.filter(|v| !v.trim().is_empty()),
+                    operation_name: None,
+                }
+            }
+            Some(other) => {
+                self.note(
+                    at,
+                    format!("{} body is not supported and was left out", other),
+                );
+                BodyType::None
+            }
+            None => BodyType::None,
+        }
+    }
+
+    fn request(
+        &mut self,
+        at: &str,
+        name: String,
+        item: &Value,
+        inherited: Option<&AuthType>,
+    ) -> ImportedItem {
+        let request = item.get("request").unwrap_or(&Value::Null);
+        // A request may be given as nothing but its URL.
+        let (method, url) = match request {
+            Value::String(_) => ("GET", request),
+            _ => (
+                request
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .unwrap_or("GET"),
+                request.get("url").unwrap_or(&Value::Null),
+            ),
+        };
+        let (url, query_params) = parse_url(url);
+        let headers = pairs(request.get("header"));
+        let has_content_type = headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+        let body = match request.get("body") {
+            Some(body) if !body.is_null() => self.body(at, body, has_content_type),
+            _ => BodyType::None,
+        };
+        let (auth, inherit_auth) = match self.auth(at, request) {
+            Some(auth) => (auth, false),
+            None => match inherited {
+                Some(auth) => (auth.clone(), false),
+                None => (AuthType::None, true),
+            },
+        };
+        self.scripts(at, item);
+        let examples = item
+            .get("response")
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len);
+        if examples > 0 {
+            self.note(
+                at,
+                format!("{} saved example response(s) were not imported", examples),
+            );
+        }
+
+        let mut api_request = ApiRequest {
+            method: method_from_string(&method.to_ascii_uppercase()),
+            url,
+            headers,
+            body,
+            auth,
+            query_params,
+            ..Default::default()
+        };
+        if let Some(behavior) = item.get("protocolProfileBehavior") {
+            if let Some(follow) = behavior.get("followRedirects").and_then(Value::as_bool) {
+                api_request.follow_redirects = Some(follow);
+            }
+            if let Some(max) = behavior.get("maxRedirects").and_then(Value::as_u64) {
+                api_request.max_redirects = Some(max.min(u32::MAX as u64) as u32);
+            }
+            if let Some(strict) = behavior.get("strictSSL").and_then(Value::as_bool) {
+                api_request.verify_ssl = Some(strict);
+            }
+        }
+
+        // The first saved response, for describing the request elsewhere.
+        let example_response = item
+            .get("response")
+            .and_then(Value::as_array)
+            .and_then(|responses| responses.first())
+            .and_then(|response| {
+                Some(ExampleResponse {
+                    status: response.get("code")?.as_u64()?.try_into().ok()?,
+                    content_type: pairs(response.get("header"))
+                        .into_iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                        .map(|(_, value)| value),
+                    body: response.get("body").map(scalar).unwrap_or_default(),
+                })
+            });
+
+        ImportedItem::Request {
+            name,
+            description: text(item.get("description")).or_else(|| text(request.get("description"))),
+            request: Box::new(api_request),
+            inherit_auth,
+            example_response,
+        }
+    }
+
+    /// Folders pass their own auth down to requests that inherit it; only
+    /// the collection's stays inherited.
+    fn items(
+        &mut self,
+        path: &str,
+        list: &[Value],
+        inherited: Option<&AuthType>,
+    ) -> Vec<ImportedItem> {
+        let mut items = Vec::new();
+        for item in list {
+            let name = item
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or("Untitled")
+                .to_string();
+            let at = if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", path, name)
+            };
+            match item.get("item").and_then(Value::as_array) {
+                Some(children) => {
+                    self.scripts(&at, item);
+                    let auth = self.auth(&at, item);
+                    let children = self.items(&at, children, auth.as_ref().or(inherited));
+                    items.push(ImportedItem::Folder {
+                        name,
+                        description: text(item.get("description")),
+                        items: children,
+                    });
+                }
+                None => items.push(self.request(&at, name, item, inherited)),
+            }
+        }
+        items
+    }
+}
+
+fn import_collection(document: &Value) -> Result<ImportResult, String> {
+    // Collections fetched from the Postman API come wrapped in `collection`.
+    let collection = match document.get("collection") {
+        Some(inner) if document.get("info").is_none() => inner,
+        _ => document,
+    };
+    let info = collection
+        .get("info")
+        .ok_or("Not a Postman collection: `info` is missing")?;
+    let schema = info
+        .get("schema")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !schema.is_empty() && !schema.contains("/v2.") {
+        return Err(format!(
+            "Unsupported collection schema '{}'; export it from Postman as v2.1",
+            schema
+        ));
+    }
+    let list = collection
+        .get("item")
+        .and_then(Value::as_array)
+        .ok_or("Not a Postman collection: `item` is missing")?;
+    let name = info
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or("Imported Collection")
+        .to_string();
+
+    let mut importer = Importer::default();
+    importer.scripts(&name, collection);
+    let auth = importer.auth(&name, collection);
+    let items = importer.items("", list, None);
+
+    let variables: Vec<ImportedVariable> = collection
+        .get("variable")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|variable| {
+            Some(ImportedVariable {
+                key: variable.get("key")?.as_str()?.to_string(),
+                value: variable.get("value").map(scalar).unwrap_or_default(),
+                enabled: !is_disabled(variable),
+                secret: variable.get("type").and_then(Value::as_str) == Some("secret"),
+            })
+        })
+        .collect();
+    let environment = (!variables.is_empty()).then(|| ImportedEnvironment {
+        name: name.clone(),
+        variables,
+    });
+
+    Ok(ImportResult {
+        description: text(info.get("description")),
+        name,
+        items,
+        auth,
+        environment,
+        report: importer.report,
+    })
+}
+
+/// Read a Postman Collection v2.1 file. Folders, requests and collection
+/// variables are returned for the frontend to save; scripts, unsupported auth
+/// and other parts that can't be carried over are listed in `report`.
+#[tauri::command]
+#[specta::specta]
+pub fn import_postman(app: AppHandle, path: String) -> Result<ImportResult, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let document: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid Postman collection JSON: {}", e))?;
+    import_collection(&document)
+}
+
+const SCHEMA_V2_1: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+fn language(content_type: &str) -> &'static str {
+    if content_type.contains("json") {
+        "json"
+    } else if content_type.contains("xml") {
+        "xml"
+    } else if content_type.contains("html") {
+        "html"
+    } else if content_type.contains("javascript") {
+        "javascript"
+    } else {
+        "text"
+    }
+}
+
+fn attributes(pairs: &[(&str, &str)]) -> Value {
+    pairs
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": value, "type": "string" }))
+        .collect()
+}
+
+/// The `url` object: `raw` with the query, plus the parts Postman shows.
+fn url_object(url: &str, query: &[(String, String)]) -> Value {
+    let mut raw = url.to_string();
+    if !query.is_empty() {
+        let joined: Vec<String> = query
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        raw.push(if url.contains('?') { '&' } else { '?' });
+        raw.push_str(&joined.join("&"));
+    }
+
+    let mut object = json!({ "raw": raw });
+    let (protocol, rest) = match url.split_once("://") {
+        Some((protocol, rest)) => (Some(protocol), rest),
+        None => (None, url),
+    };
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|b| b.is_ascii_digit()) => (host, Some(port)),
+        _ => (authority, None),
+    };
+    if let Some(protocol) = protocol {
+        object["protocol"] = json!(protocol);
+    }
+    // Host labels are split on dots, except inside a `{{variable}}`.
+    object["host"] = if host.starts_with("{{") {
+        json!([host])
+    } else {
+        json!(host.split('.').collect::<Vec<_>>())
+    };
+    if let Some(port) = port {
+        object["port"] = json!(port);
+    }
+    if !path.is_empty() {
+        object["path"] = json!(path.split('/').collect::<Vec<_>>());
+    }
+    if !query.is_empty() {
+        object["query"] = query
+            .iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect();
+    }
+    object
+}
+
+#[derive(Default)]
+struct Exporter {
+    report: Vec<String>,
+}
+
+impl Exporter {
+    fn note(&mut self, at: &str, what: String) {
+        self.report.push(format!("{}: {}", at, what));
+    }
+
+    fn auth(&mut self, at: &str, auth: &AuthType) -> Value {
+        match auth {
+            AuthType::None => json!({ "type": "noauth" }),
+            AuthType::Basic { username, password } => json!({
+                "type": "basic",
+                "basic": attributes(&[("username", username), ("password", password)]),
+            }),
+            AuthType::Bearer { token } => json!({
+                "type": "bearer",
+                "bearer": attributes(&[("token", token)]),
+            }),
+            AuthType::ApiKey { key, value, add_to } => {
+                let location = match add_to {
+                    ApiKeyLocation::Header => "header",
+                    ApiKeyLocation::Query => "query",
+                    ApiKeyLocation::Cookie => {
+                        self.note(at, "API key cookie was exported as a header".to_string());
+                        "header"
+                    }
+                };
+                json!({
+                    "type": "apikey",
+                    "apikey": attributes(&[("key", key), ("value", value), ("in", location)]),
+                })
+            }
+            AuthType::OAuth2 { access_token } => json!({
+                "type": "oauth2",
+                "oauth2": attributes(&[("accessToken", access_token), ("addTokenTo", "header")]),
+            }),
+            AuthType::Ntlm {
+                username,
+                password,
+                domain,
+            } => json!({
+                "type": "ntlm",
+                "ntlm": attributes(&[
+                    ("username", username),
+                    ("password", password),
+                    ("domain", domain.as_deref().unwrap_or_default()),
+                ]),
+            }),
+            AuthType::Negotiate { .. } | AuthType::Jwt { .. } => {
+                let kind = match auth {
+                    AuthType::Negotiate { .. } => "Negotiate",
+                    _ => "JWT",
+                };
+                self.note(
+                    at,
+                    format!("{} auth has no Postman equivalent and was left out", kind),
+                );
+                json!({ "type": "noauth" })
+            }
+        }
+    }
+
+    /// The body object, adding the headers Mandy would send with it.
+    fn body(
+        &mut self,
+        at: &str,
+        body: &BodyType,
+        headers: &mut Vec<(String, String)>,
+    ) -> Result<Option<Value>, String> {
+        let content_type = |headers: &mut Vec<(String, String)>, value: &str| {
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(("Content-Type".to_string(), value.to_string()));
+            }
+        };
+        let raw = |content: String, content_type: &str| {
+            json!({
+                "mode": "raw",
+                "raw": content,
+                "options": { "raw": { "language": language(content_type) } },
+            })
+        };
+        Ok(Some(match body {
+            BodyType::None => return Ok(None),
+            BodyType::Raw {
+                content,
+                content_type: ct,
+            } => {
+                if let Some(ct) = ct {
+                    content_type(headers, ct);
+                }
+                raw(content.clone(), ct.as_deref().unwrap_or_default())
+            }
+            BodyType::FormUrlEncoded { fields } => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort();
+                json!({
+                    "mode": "urlencoded",
+                    "urlencoded": fields
+                        .into_iter()
+                        .map(|(key, value)| json!({ "key": key, "value": value }))
+                        .collect::<Vec<_>>(),
+                })
+            }
+            BodyType::Multipart { fields } => {
+                let mut parts = Vec::new();
+                for field in fields {
+                    parts.push(match &field.value {
+                        MultipartValue::Text(text) => {
+                            json!({ "key": field.name, "value": text, "type": "text" })
+                        }
+                        MultipartValue::FilePath {
+                            path, content_type, ..
+                        } => {
+                            let mut part = json!({ "key": field.name, "type": "file", "src": path });
+                            if let Some(ct) = content_type {
+                                part["contentType"] = json!(ct);
+                            }
+                            part
+                        }
+                        MultipartValue::File { filename, .. } => {
+                            self.note(
+                                at,
+                                format!(
+                                    "File field '{}' ({}) isn't on disk; pick the file again in Postman",
+                                    field.name, filename
+                                ),
+                            );
+                            json!({ "key": field.name, "type": "file", "src": null })
+                        }
+                    });
+                }
+                json!({ "mode": "formdata", "formdata": parts })
+            }
+            BodyType::Binary { data, .. } => match String::from_utf8(data.clone()) {
+                Ok(text) => {
+                    content_type(headers, "application/octet-stream");
+                    raw(text, "")
+                }
+                Err(_) => {
+                    self.note(at, "Binary body isn't on disk and was left out".to_string());
+                    return Ok(None);
+                }
+            },
+            BodyType::RawFile {
+                path,
+                content_type: ct,
+            }
+            | BodyType::BinaryFile {
+                path,
+                content_type: ct,
+            } => {
+                if let Some(ct) = ct {
+                    content_type(headers, ct);
+                }
+                json!({ "mode": "file", "file": { "src": path } })
+            }
+            // The v2.1 schema has no `graphql` mode, so the JSON envelope is sent raw.
+            BodyType::GraphQL {
+                query,
+                variables,
+                operation_name,
+            } => {
+                let envelope =
+                    graphql_envelope(query, variables.as_deref(), operation_name.as_deref())?;
+                content_type(headers, "application/json");
+                raw(envelope, "application/json")
+            }
+            BodyType::Soap {
+                action,
+                envelope_body,
+                soap_version,
+            } => {
+                let (ct, soap_action) = soap_headers(action, *soap_version);
+                if let Some(soap_action) = soap_action {
+                    if !headers
+                        .iter()
+                        .any(|(name, _)| name.eq_ignore_ascii_case("soapaction"))
+                    {
+                        headers.push(("SOAPAction".to_string(), soap_action));
+                    }
+                }
+                content_type(headers, &ct);
+                raw(soap_envelope(envelope_body, *soap_version)?, &ct)
+            }
+            BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } => {
+                let envelope = serde_json::to_string_pretty(&jsonrpc_body(body)?)
+                    .map_err(|e| e.to_string())?;
+                content_type(headers, "application/json");
+                raw(envelope, "application/json")
+            }
+        }))
+    }
+
+    fn request(
+        &mut self,
+        at: &str,
+        req: &ApiRequest,
+        inherit_auth: bool,
+        description: Option<&str>,
+    ) -> Result<Value, String> {
+        let mut headers = req.headers.clone();
+        let body = self.body(at, &req.body, &mut headers)?;
+        if !req.cookies.is_empty() {
+            let cookies: Vec<String> = req
+                .cookies
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect();
+            headers.push(("Cookie".to_string(), cookies.join("; ")));
+        }
+        if req.proxy.is_some() || req.client_cert.is_some() {
+            self.note(
+                at,
+                "Proxy and client certificate settings were left out".to_string(),
+            );
+        }
+
+        let mut request = json!({
+            "method": method_to_curl_string(&req.method),
+            "header": headers
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": value, "type": "text" }))
+                .collect::<Vec<_>>(),
+            "url": url_object(&req.url, &req.query_params),
+        });
+        if let Some(body) = body {
+            request["body"] = body;
+        }
+        if !inherit_auth {
+            request["auth"] = self.auth(at, &req.auth);
+        }
+        if let Some(description) = description {
+            request["description"] = json!(description);
+        }
+        Ok(request)
+    }
+
+    fn items(&mut self, path: &str, items: &[ImportedItem]) -> Result<Vec<Value>, String> {
+        let mut exported = Vec::new();
+        for item in items {
+            exported.push(match item {
+                ImportedItem::Folder {
+                    name,
+                    description,
+                    items,
+                } => {
+                    let at = format!("{}/{}", path, name);
+                    let mut folder = json!({ "name": name, "item": self.items(&at, items)? });
+                    if let Some(description) = description {
+                        folder["description"] = json!(description);
+                    }
+                    folder
+                }
+                ImportedItem::Request {
+                    name,
+                    description,
+                    request,
+                    inherit_auth,
+                    ..
+                } => {
+                    let at = format!("{}/{}", path, name);
+                    let mut item = json!({
+                        "name": name,
+                        "request": self.request(&at, request, *inherit_auth, description.as_deref())?,
+                        "response": [],
+                    });
+                    let mut behavior = serde_json::Map::new();
+                    if request.follow_redirects == Some(false) {
+                        behavior.insert("followRedirects".to_string(), json!(false));
+                    }
+                    if let Some(max) = request.max_redirects.filter(|_| request.follow_redirects != Some(false)) {
+                        behavior.insert("maxRedirects".to_string(), json!(max));
+                    }
+                    if request.verify_ssl == Some(false) {
+                        behavior.insert("strictSSL".to_string(), json!(false));
+                    }
+                    if !behavior.is_empty() {
+                        item["protocolProfileBehavior"] = Value::Object(behavior);
+                    }
+                    item
+                }
+            });
+        }
+        Ok(exported)
+    }
+}
+
+/// The collection as a v2.1 document, and what couldn't be represented in it.
+fn export_collection(collection: &CollectionExport) -> Result<(Value, Vec<String>), String> {
+    let mut exporter = Exporter::default();
+    let mut info = json!({
+        "_postman_id": uuid::Uuid::new_v4().to_string(),
+        "name": collection.name,
+        "schema": SCHEMA_V2_1,
+    });
+    if let Some(ref description) = collection.description {
+        info["description"] = json!(description);
+    }
+    let mut document = json!({
+        "info": info,
+        "item": exporter.items(&collection.name, &collection.items)?,
+    });
+    if let Some(ref auth) = collection.auth {
+        document["auth"] = exporter.auth(&collection.name, auth);
+    }
+    if let Some(ref environment) = collection.environment {
+        let mut variables = Vec::new();
+        for variable in &environment.variables {
+            // v2.1 has no secret variable type, so only the name is exported.
+            let value = if variable.secret {
+                exporter.note(
+                    &collection.name,
+                    format!("Secret '{}' was exported without its value", variable.key),
+                );
+                ""
+            } else {
+                variable.value.as_str()
+            };
+            let mut exported = json!({ "key": variable.key, "value": value, "type": "string" });
+            if !variable.enabled {
+                exported["disabled"] = json!(true);
+            }
+            variables.push(exported);
+        }
+        document["variable"] = Value::Array(variables);
+    }
+
+    Ok((document, exporter.report))
+}
+
+/// Write a collection as a Postman Collection v2.1 file. Collections live in
+/// the frontend's store, so the caller passes the one to export. Returns what
+/// couldn't be represented in Postman's format.
+#[tauri::command]
+#[specta::specta]
+pub fn export_postman(
+    app: AppHandle,
+    collection: CollectionExport,
+    path: String,
+) -> Result<Vec<String>, String> {
+    let allowed = app
+        .try_fs_scope()
+        .is_some_and(|scope| scope.is_allowed(&path));
+    if !allowed {
+        return Err(format!("Access to '{}' is not allowed", path));
+    }
+
+    let (document, report) = export_collection(&collection)?;
+    let text = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Methods, SoapVersion};
+
+    const SCHEMA: &str = include_str!("testdata/postman-collection-v2.1.0.json");
+
+    fn schema_errors(document: &Value) -> Vec<String> {
+        let schema: Value = serde_json::from_str(SCHEMA).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        validator
+            .iter_errors(document)
+            .map(|error| format!("{} at {}", error, error.instance_path()))
+            .collect()
+    }
+
+    fn request(name: &str, req: ApiRequest) -> ImportedItem {
+        ImportedItem::Request {
+            name: name.to_string(),
+            description: Some(format!("{} request", name)),
+            request: Box::new(req),
+            inherit_auth: false,
+            example_response: None,
+        }
+    }
+
+    fn sample_collection() -> CollectionExport {
+        let url = |path: &str| format!("https://{{{{host}}}}:8443/api/{}", path);
+        let bodies = vec![
+            request(
+                "raw json",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: url("users"),
+                    query_params: vec![("verbose".to_string(), "1".to_string())],
+                    headers: vec![("X-Trace".to_string(), "abc".to_string())],
+                    body: BodyType::Raw {
+                        content: "{\"name\":\"ana\"}".to_string(),
+                        content_type: Some("application/json".to_string()),
+                    },
+                    auth: AuthType::Bearer {
+                        token: "{{token}}".to_string(),
+                    },
+                    follow_redirects: Some(false),
+                    verify_ssl: Some(false),
+                    ..Default::default()
+                },
+            ),
+            request(
+                "form",
+                ApiRequest {
+                    method: Methods::PUT,
+                    url: url("form"),
+                    body: BodyType::FormUrlEncoded {
+                        fields: HashMap::from([("a".to_string(), "1".to_string())]),
+                    },
+                    auth: AuthType::ApiKey {
+                        key: "X-Key".to_string(),
+                        value: "k".to_string(),
+                        add_to: ApiKeyLocation::Query,
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "multipart",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: url("upload"),
+                    body: BodyType::Multipart {
+                        fields: vec![
+                            MultipartField {
+                                name: "note".to_string(),
+                                value: MultipartValue::Text("hi".to_string()),
+                            },
+                            MultipartField {
+                                name: "doc".to_string(),
+                                value: MultipartValue::FilePath {
+                                    path: "/tmp/doc.pdf".to_string(),
+                                    filename: None,
+                                    content_type: Some("application/pdf".to_string()),
+                                },
+                            },
+                            MultipartField {
+                                name: "blob".to_string(),
+                                value: MultipartValue::File {
+                                    data: vec![1, 2],
+                                    filename: "blob.bin".to_string(),
+                                    content_type: None,
+                                },
+                            },
+                        ],
+                    },
+                    auth: AuthType::Basic {
+                        username: "u".to_string(),
+                        password: "p".to_string(),
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "file",
+                ApiRequest {
+                    method: Methods::PATCH,
+                    url: url("file"),
+                    body: BodyType::BinaryFile {
+                        path: "/tmp/data.bin".to_string(),
+                        content_type: None,
+                    },
+                    auth: AuthType::Ntlm {
+                        username: "u".to_string(),
+                        password: "p".to_string(),
+                        domain: Some("CORP".to_string()),
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "graphql",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: url("graphql"),
+                    body: BodyType::GraphQL {
+                        query: "{ me { id } }".to_string(),
+                        variables: None,
+                        operation_name: None,
+                    },
+                    auth: AuthType::OAuth2 {
+                        access_token: "t".to_string(),
+                    },
+                    ..Default::default()
+                },
+            ),
+            request(
+                "soap",
+                ApiRequest {
+                    method: Methods::POST,
+                    url: "http://localhost/soap".to_string(),
+                    body: BodyType::Soap {
+                        action: "urn:Ping".to_string(),
+                        envelope_body: "<Ping/>".to_string(),
+                        soap_version: SoapVersion::Soap1_1,
+                    },
+                    ..Default::default()
+                },
+            ),
+        ];
+        CollectionExport {
+            name: "Sample".to_string(),
+            description: Some("Every body and auth kind".to_string()),
+            items: vec![
+                ImportedItem::Folder {
+                    name: "Bodies".to_string(),
+                    description: None,
+                    items: bodies,
+                },
+                ImportedItem::Request {
+                    name: "inherited".to_string(),
+                    description: None,
+                    request: Box::new(ApiRequest {
+                        method: Methods::DELETE,
+                        url: "https://example.com/items/1".to_string(),
+                        ..Default::default()
+                    }),
+                    inherit_auth: true,
+                    example_response: None,
+                },
+            ],
+            auth: Some(AuthType::Bearer {
+                token: "{{token}}".to_string(),
+            }),
+            environment: Some(ImportedEnvironment {
+                name: "Sample".to_string(),
+                variables: vec![
+                    ImportedVariable {
+                        key: "host".to_string(),
+                        value: "api.example.com".to_string(),
+                        enabled: true,
+                        secret: false,
+                    },
+                    ImportedVariable {
+                        key: "token".to_string(),
+                        value: "s3cr3t".to_string(),
+                        enabled: true,
+                        secret: true,
+                    },
+                    ImportedVariable {
+                        key: "old".to_string(),
+                        value: "x".to_string(),
+                        enabled: false,
+                        secret: false,
+                    },
+                ],
+            }),
+        }
+    }
+
+    #[test]
+    fn export_matches_the_published_schema() {
+        let (document, _) = export_collection(&sample_collection()).unwrap();
+        assert_eq!(schema_errors(&document), Vec::<String>::new());
+    }
+
+    #[test]
+    fn secrets_are_exported_empty_with_their_names() {
+        let (document, report) = export_collection(&sample_collection()).unwrap();
+        let variables = document["variable"].as_array().unwrap();
+        let keys: Vec<_> = variables
+            .iter()
+            .map(|v| v["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, ["host", "token", "old"]);
+        assert_eq!(variables[0]["value"], "api.example.com");
+        assert_eq!(variables[1]["value"], "");
+        assert_eq!(variables[2]["disabled"], true);
+        assert!(!document.to_string().contains("s3cr3t"));
+        assert!(report.iter().any(|line| line.contains("'token'")));
+    }
+
+    #[test]
+    fn exported_collection_imports_back() {
+        let (document, _) = export_collection(&sample_collection()).unwrap();
+        let imported = import_collection(&document).unwrap();
+        assert_eq!(imported.name, "Sample");
+        let ImportedItem::Folder { items, .. } = &imported.items[0] else {
+            panic!("expected the folder first");
+        };
+        assert_eq!(items.len(), 6);
+        let ImportedItem::Request { request, .. } = &items[0] else {
+            panic!("expected a request");
+        };
+        assert_eq!(
+            request.query_params,
+            [("verbose".to_string(), "1".to_string())]
+        );
+        assert_eq!(request.follow_redirects, Some(false));
+    }
+}
@@ -0,0 +1,229 @@
+//! Descriptors compiled from `.proto` sources, for gRPC servers without
+//! reflection. Each import is kept under `proto_registries/` in the workspace
+//! dir as an encoded `FileDescriptorSet`, named by its registry id.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use prost_reflect::{DescriptorPool, FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor};
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+use tauri_plugin_fs::FsExt;
+
+use crate::helpers::grpc::service_info;
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::{ProtoFieldSchema, ProtoMessageSchema, ProtoRegistryInfo};
+
+fn registry_path(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    // Ids are generated uuids; anything else could name a file elsewhere.
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Unknown proto registry '{}'", id));
+    }
+    Ok(workspace_dir(app)?
+        .join("proto_registries")
+        .join(format!("{}.bin", id)))
+}
+
+/// The descriptors stored by `import_proto_files` under `id`.
+pub(crate) fn load_registry(app: &AppHandle, id: &str) -> Result<DescriptorPool, String> {
+    let bytes = match std::fs::read(registry_path(app, id)?) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!("Unknown proto registry '{}'", id))
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    DescriptorPool::decode(bytes.as_slice())
+        .map_err(|e| format!("Proto registry '{}' is unreadable: {}", id, e))
+}
+
+/// `file:line: message`, with whichever location parts the error carries.
+fn compile_error(error: &protox::Error) -> String {
+    let line = error
+        .labels()
+        .and_then(|mut labels| labels.next())
+        .and_then(|label| {
+            let contents = error.source_code()?.read_span(label.inner(), 0, 0).ok()?;
+            Some(contents.line() + 1)
+        });
+    match (error.file(), line) {
+        (Some(file), Some(line)) => format!("{}:{}: {}", file, line, error),
+        (Some(file), None) => format!("{}: {}", file, error),
+        _ => error.to_string(),
+    }
+}
+
+fn kind_name(kind: &Kind) -> String {
+    match kind {
+        Kind::Double => "double",
+        Kind::Float => "float",
+        Kind::Int32 => "int32",
+        Kind::Int64 => "int64",
+        Kind::Uint32 => "uint32",
+        Kind::Uint64 => "uint64",
+        Kind::Sint32 => "sint32",
+        Kind::Sint64 => "sint64",
+        Kind::Fixed32 => "fixed32",
+        Kind::Fixed64 => "fixed64",
+        Kind::Sfixed32 => "sfixed32",
+        Kind::Sfixed64 => "sfixed64",
+        Kind::Bool => "bool",
+        Kind::String => "string",
+        Kind::Bytes => "bytes",
+        Kind::Message(message) => return message.full_name().to_string(),
+        Kind::Enum(enumeration) => return enumeration.full_name().to_string(),
+    }
+    .to_string()
+}
+
+/// The `oneof` a field was declared in, leaving out the implicit one that
+/// wraps each proto3 `optional` field.
+fn declared_oneof(field: &FieldDescriptor) -> Option<OneofDescriptor> {
+    if field.field_descriptor_proto().proto3_optional() {
+        return None;
+    }
+    field.containing_oneof()
+}
+
+fn field_schema(field: &FieldDescriptor) -> ProtoFieldSchema {
+    let type_name = match field.kind() {
+        Kind::Message(entry) if field.is_map() => format!(
+            "map<{}, {}>",
+            kind_name(&entry.map_entry_key_field().kind()),
+            kind_name(&entry.map_entry_value_field().kind())
+        ),
+        kind => kind_name(&kind),
+    };
+    ProtoFieldSchema {
+        name: field.name().to_string(),
+        json_name: field.json_name().to_string(),
+        number: field.number(),
+        type_name,
+        repeated: field.is_list(),
+        map: field.is_map(),
+        oneof: declared_oneof(field).map(|oneof| oneof.name().to_string()),
+    }
+}
+
+/// The JSON mapping's default for a single value of `kind`. `stack` holds the
+/// messages being expanded, so recursive types end in `null`.
+fn kind_skeleton(kind: Kind, stack: &mut Vec<String>) -> Value {
+    match kind {
+        // 64-bit integers are strings in the JSON mapping.
+        Kind::Int64 | Kind::Uint64 | Kind::Sint64 | Kind::Fixed64 | Kind::Sfixed64 => json!("0"),
+        Kind::Double | Kind::Float => json!(0.0),
+        Kind::Bool => json!(false),
+        Kind::String | Kind::Bytes => json!(""),
+        Kind::Enum(enumeration) => json!(enumeration.default_value().name()),
+        Kind::Message(message) => message_skeleton(&message, stack),
+        _ => json!(0),
+    }
+}
+
+fn message_skeleton(message: &MessageDescriptor, stack: &mut Vec<String>) -> Value {
+    let name = message.full_name();
+    match name {
+        "google.protobuf.Timestamp" => return json!("1970-01-01T00:00:00Z"),
+        "google.protobuf.Duration" => return json!("0s"),
+        "google.protobuf.FieldMask" => return json!(""),
+        "google.protobuf.Struct" | "google.protobuf.Empty" => return json!({}),
+        "google.protobuf.ListValue" => return json!([]),
+        // `Any`, `Value` and the wrapper types: left unset.
+        _ if name.starts_with("google.protobuf.") => return Value::Null,
+        _ if stack.iter().any(|open| open == name) => return Value::Null,
+        _ => {}
+    }
+
+    stack.push(name.to_string());
+    let mut object = Map::new();
+    let mut oneofs_seen = HashSet::new();
+    for field in message.fields() {
+        if let Some(oneof) = declared_oneof(&field) {
+            if !oneofs_seen.insert(oneof.name().to_string()) {
+                continue;
+            }
+        }
+        let value = if field.is_map() {
+            json!({})
+        } else if field.is_list() {
+            json!([kind_skeleton(field.kind(), stack)])
+        } else {
+            kind_skeleton(field.kind(), stack)
+        };
+        object.insert(field.json_name().to_string(), value);
+    }
+    stack.pop();
+    Value::Object(object)
+}
+
+fn registry_info(registry_id: String, pool: &DescriptorPool) -> Result<ProtoRegistryInfo, String> {
+    let mut messages = Vec::new();
+    for message in pool.all_messages().filter(|m| !m.is_map_entry()) {
+        let skeleton = message_skeleton(&message, &mut Vec::new());
+        messages.push(ProtoMessageSchema {
+            name: message.full_name().to_string(),
+            fields: message.fields().map(|f| field_schema(&f)).collect(),
+            skeleton_json: serde_json::to_string_pretty(&skeleton).map_err(|e| e.to_string())?,
+        });
+    }
+    Ok(ProtoRegistryInfo {
+        registry_id,
+        files: pool.files().map(|f| f.name().to_string()).collect(),
+        services: pool.services().map(|s| service_info(&s)).collect(),
+        messages,
+    })
+}
+
+fn compile(paths: &[String], include_dirs: &[String]) -> Result<DescriptorPool, String> {
+    // Without include dirs, each file's own directory is searched for imports.
+    let includes: Vec<PathBuf> = if include_dirs.is_empty() {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for parent in paths.iter().filter_map(|p| Path::new(p).parent()) {
+            if !dirs.iter().any(|dir| dir == parent) {
+                dirs.push(parent.to_path_buf());
+            }
+        }
+        dirs
+    } else {
+        include_dirs.iter().map(PathBuf::from).collect()
+    };
+    let mut compiler = protox::Compiler::new(includes).map_err(|e| compile_error(&e))?;
+    compiler.include_imports(true);
+    compiler.open_files(paths).map_err(|e| compile_error(&e))?;
+    Ok(compiler.descriptor_pool())
+}
+
+/// Compile `.proto` files and store the descriptors for gRPC calls to servers
+/// without reflection. Imports are resolved against `include_dirs`, or each
+/// file's own directory when none are given.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_proto_files(
+    app: AppHandle,
+    paths: Vec<String>,
+    include_dirs: Vec<String>,
+) -> Result<ProtoRegistryInfo, String> {
+    if paths.is_empty() {
+        return Err("No .proto files given".to_string());
+    }
+    for path in paths.iter().chain(&include_dirs) {
+        let allowed = app
+            .try_fs_scope()
+            .is_some_and(|scope| scope.is_allowed(path));
+        if !allowed {
+            return Err(format!("Access to '{}' is not allowed", path));
+        }
+    }
+
+    let pool = tokio::task::spawn_blocking(move || compile(&paths, &include_dirs))
+        .await
+        .map_err(|e| e.to_string())??;
+    let registry_id = uuid::Uuid::new_v4().to_string();
+    let path = registry_path(&app, &registry_id)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(path, pool.encode_to_vec()).map_err(|e| e.to_string())?;
+    registry_info(registry_id, &pool)
+}
@@ -0,0 +1,134 @@
+//! Per-host proxy routing, kept in `proxy_rules.json` in the workspace dir.
+//! Rules only apply to requests that don't set `ApiRequest::proxy` themselves.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tauri::AppHandle;
+
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::{ProxyRule, ProxyRuleTarget};
+
+/// Serializes read-modify-write cycles on the rules file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("proxy_rules.json"))
+}
+
+fn load(app: &AppHandle) -> Result<Vec<ProxyRule>, String> {
+    match std::fs::read(store_path(app)?) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn save(app: &AppHandle, rules: &[ProxyRule]) -> Result<(), String> {
+    let path = store_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_vec_pretty(rules).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Lowercases the pattern and spells a leading-dot suffix as `*.domain`.
+fn normalize_pattern(pattern: &str) -> Result<String, String> {
+    let pattern = pattern.trim().trim_end_matches('.').to_ascii_lowercase();
+    let pattern = match pattern.strip_prefix('.') {
+        Some(suffix) => format!("*.{}", suffix),
+        None => pattern,
+    };
+    let host = pattern.strip_prefix("*.").unwrap_or(&pattern);
+    if pattern != "*" && (host.is_empty() || host.contains('*')) {
+        return Err(format!(
+            "Invalid proxy rule pattern '{}': expected a host, *.domain or *",
+            pattern
+        ));
+    }
+    Ok(pattern)
+}
+
+/// How specifically `pattern` matches `host`, or `None` if it doesn't.
+/// Exact hosts beat wildcards, and longer suffixes beat shorter ones.
+fn specificity(pattern: &str, host: &str) -> Option<(u8, usize)> {
+    if pattern == "*" {
+        return Some((0, 0));
+    }
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host
+            .ends_with(&format!(".{}", suffix))
+            .then_some((1, suffix.len())),
+        None => (pattern == host).then_some((2, pattern.len())),
+    }
+}
+
+/// The most specific rule matching `host`, if any.
+pub(crate) fn proxy_rule_for(app: &AppHandle, host: &str) -> Option<ProxyRule> {
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    let _guard = STORE_LOCK.lock().ok()?;
+    load(app)
+        .ok()?
+        .into_iter()
+        .filter_map(|rule| Some((specificity(&rule.pattern, &host)?, rule)))
+        .max_by_key(|(rank, _)| *rank)
+        .map(|(_, rule)| rule)
+}
+
+/// Route hosts matching `pattern` to `target`. Setting an existing pattern
+/// again replaces its target and keeps its id.
+#[tauri::command]
+#[specta::specta]
+pub fn set_proxy_rule(
+    app: AppHandle,
+    pattern: String,
+    target: ProxyRuleTarget,
+) -> Result<ProxyRule, String> {
+    let pattern = normalize_pattern(&pattern)?;
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut rules = load(&app)?;
+    let rule = match rules.iter_mut().find(|r| r.pattern == pattern) {
+        Some(existing) => {
+            existing.target = target;
+            existing.clone()
+        }
+        None => {
+            let rule = ProxyRule {
+                id: uuid::Uuid::new_v4().to_string(),
+                pattern,
+                target,
+            };
+            rules.push(rule.clone());
+            rule
+        }
+    };
+    save(&app, &rules)?;
+    Ok(rule)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_proxy_rules(app: AppHandle) -> Result<Vec<ProxyRule>, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    load(&app)
+}
+
+/// Returns `false` when no rule had this id.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_proxy_rule(app: AppHandle, id: String) -> Result<bool, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut rules = load(&app)?;
+    let before = rules.len();
+    rules.retain(|r| r.id != id);
+    if rules.len() == before {
+        return Ok(false);
+    }
+    save(&app, &rules)?;
+    Ok(true)
+}
@@ -0,0 +1,178 @@
+//! Buffers REST response bodies. Small bodies stay in memory; once a body grows
+//! past the spill threshold it is written to a temp file so multi-hundred-MB
+//! downloads never have to be base64-encoded and pushed through IPC. Requests
+//! with `download_to` skip memory entirely and stream into the chosen file.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Bodies larger than this are spilled to disk unless the request overrides it.
+pub const DEFAULT_SPILL_THRESHOLD_BYTES: u32 = 5 * 1024 * 1024;
+
+/// How much of a spilled body is kept in memory for previews and renderer detection.
+const PREVIEW_BYTES: usize = 64 * 1024;
+
+/// Directory holding spilled response bodies for this machine.
+pub fn spill_dir() -> PathBuf {
+    std::env::temp_dir().join("mandy-responses")
+}
+
+/// Remove every spilled body. Called on app exit.
+pub fn cleanup_spill_dir() {
+    let _ = fs::remove_dir_all(spill_dir());
+}
+
+/// Delete one spilled body file. Paths outside `spill_dir()` are rejected.
+pub fn discard_spilled_body(path: &str) -> Result<(), String> {
+    let dir = spill_dir();
+    let path = Path::new(path);
+    let inside_spill_dir = path
+        .parent()
+        .and_then(|p| p.canonicalize().ok())
+        .zip(dir.canonicalize().ok())
+        .is_some_and(|(parent, dir)| parent == dir);
+    if !inside_spill_dir {
+        return Err("Not a spilled response body".to_string());
+    }
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove response body file: {e}")),
+    }
+}
+
+/// `<target>.part`, where a download is written until it completes.
+fn part_path(target: &Path) -> PathBuf {
+    let mut name = target.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+pub struct ResponseBodySink {
+    threshold: usize,
+    /// The full body, or only its first `PREVIEW_BYTES` once spilled.
+    memory: Vec<u8>,
+    file: Option<(BufWriter<File>, PathBuf)>,
+    len: u64,
+    /// Set for downloads: the file the body ends up in once the transfer completes.
+    download_target: Option<PathBuf>,
+}
+
+impl ResponseBodySink {
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold: threshold as usize,
+            memory: Vec::new(),
+            file: None,
+            len: 0,
+            download_target: None,
+        }
+    }
+
+    /// Stream the whole body into `<target>.part`, renamed to `target` by `finish`.
+    pub fn download(target: PathBuf) -> Self {
+        Self {
+            threshold: 0,
+            memory: Vec::new(),
+            file: None,
+            len: 0,
+            download_target: Some(target),
+        }
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.len += data.len() as u64;
+
+        if let Some((writer, _)) = self.file.as_mut() {
+            let room = PREVIEW_BYTES.saturating_sub(self.memory.len());
+            self.memory.extend_from_slice(&data[..room.min(data.len())]);
+            return writer.write_all(data);
+        }
+
+        self.memory.extend_from_slice(data);
+        if self.memory.len() > self.threshold {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        let path = match self.download_target {
+            Some(ref target) => part_path(target),
+            None => {
+                let dir = spill_dir();
+                fs::create_dir_all(&dir)?;
+                dir.join(format!("{}.body", uuid::Uuid::new_v4()))
+            }
+        };
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writer.write_all(&self.memory)?;
+        self.memory.truncate(PREVIEW_BYTES);
+        self.memory.shrink_to_fit();
+        self.file = Some((writer, path));
+        Ok(())
+    }
+
+    /// Total bytes received, whether held in memory or on disk.
+    pub fn received_bytes(&self) -> u64 {
+        self.len
+    }
+
+    /// The whole body when in memory, otherwise the preview prefix.
+    pub fn bytes(&self) -> &[u8] {
+        &self.memory
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    pub fn is_download(&self) -> bool {
+        self.download_target.is_some()
+    }
+
+    /// Flush the temp file (if any) and return its path. A download is moved
+    /// from `.part` to its target, even when the body was empty.
+    pub fn finish(&mut self) -> io::Result<Option<PathBuf>> {
+        if self.download_target.is_some() && self.file.is_none() {
+            self.spill()?;
+        }
+        let part = self.flush()?;
+        match (part, self.download_target.as_ref()) {
+            (Some(part), Some(target)) => {
+                fs::rename(&part, target)?;
+                Ok(Some(target.clone()))
+            }
+            (part, _) => Ok(part),
+        }
+    }
+
+    /// Like `finish`, but a download stays at `.part` because the body is incomplete.
+    pub fn finish_incomplete(&mut self) -> io::Result<Option<PathBuf>> {
+        if self.download_target.is_some() {
+            self.flush()
+        } else {
+            self.finish()
+        }
+    }
+
+    /// Drop the body, deleting any file written so far.
+    pub fn discard(&mut self) {
+        if let Some((writer, path)) = self.file.take() {
+            drop(writer);
+            let _ = fs::remove_file(path);
+        }
+        self.memory.clear();
+    }
+
+    fn flush(&mut self) -> io::Result<Option<PathBuf>> {
+        match self.file.as_mut() {
+            Some((writer, path)) => {
+                writer.flush()?;
+                Ok(Some(path.clone()))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -1,29 +1,479 @@
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
-use curl::easy::{Easy, HttpVersion, List};
+use curl::easy::{
+    Auth, Easy, HttpVersion, InfoType, IpResolve, List, NetRc, ProxyType, ReadError, SslVersion,
+};
 use dashmap::DashMap;
+use encoding_rs::{Encoding, UTF_8};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::io::Read;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::ffi::CString;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use url::Url;
 
+use crate::helpers::body_formats::{sniff_csv_delimiter, sniff_markdown, sniff_yaml};
+use crate::helpers::cookie_jar::{
+    cookie_mismatch, parse_cookie_date, session_jar, store_session_cookies, CookieJar,
+};
+use crate::helpers::jwt::sign_jwt;
+use crate::helpers::netrc::{default_netrc_path, netrc_lookup};
+use crate::helpers::oauth::{auth_profile_token, stored_auth_profile_token};
+use crate::helpers::proxy_rules::proxy_rule_for;
 use crate::helpers::request_notify::{
     notify_request_completed_if_background, pick_display_name,
 };
+use crate::helpers::response_body::{
+    discard_spilled_body, spill_dir, ResponseBodySink, DEFAULT_SPILL_THRESHOLD_BYTES,
+};
+use crate::helpers::settings::apply_settings;
+use crate::helpers::system_proxy::{no_proxy_matches, redact_proxy, system_proxy_for};
+use crate::helpers::tls::{leaf_certificate, PeerCertificate, TlsHandshake};
+use crate::helpers::tls_exceptions::tls_exception_for;
+use crate::helpers::variables::{resolve_for_preview, resolve_for_send};
 use crate::types::{
-    ApiKeyLocation, ApiRequest, ApiResponse, AuthType, BodyType, Cookie,
-    FetchUrlResponse, Methods, ResponseRenderer, SizeInfo, TimingInfo,
+    ApiKeyLocation, ApiRequest, ApiResponse, AuthType, BodyCompression, BodyType, ClientCert,
+    ConnectionInfo, Cookie, FetchUrlResponse, HttpProtocol, IpVersion, JsonRpcError, Methods,
+    NetrcMode, ProxyAuthScheme, ProxyConfig, ProxyKind, ProxyRule, ProxyRuleTarget, RedirectEntry,
+    RequestError, RequestFailure, RequestPreview, RequestProgressEvent, ResolveOverride,
+    ResponseRenderer, SizeInfo, SoapVersion, TimingInfo, TlsVersion, UploadProgressEvent,
+    VerboseDirection, VerboseEntry,
 };
-use tauri::AppHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_fs::FsExt;
 
 static REST_CANCEL_FLAGS: OnceLock<DashMap<String, Arc<AtomicBool>>> = OnceLock::new();
 
+/// Next id for JSON-RPC calls sent without one.
+static JSONRPC_NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Event carrying `RequestProgressEvent` payloads while a response downloads.
+pub const REQUEST_PROGRESS_EVENT: &str = "request-progress";
+
+/// Event carrying `UploadProgressEvent` payloads while a request body uploads.
+pub const UPLOAD_PROGRESS_EVENT: &str = "upload-progress";
+
+/// Minimum spacing between progress events so large transfers don't flood IPC.
+pub(crate) const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
 fn rest_cancel_flags() -> &'static DashMap<String, Arc<AtomicBool>> {
     REST_CANCEL_FLAGS.get_or_init(DashMap::new)
 }
 
-fn method_to_curl_string(method: &Methods) -> &'static str {
+fn active_request_id(req: &ApiRequest) -> Option<String> {
+    req.request_id.clone().or_else(|| req.cancel_key.clone())
+}
+
+fn signal_cancel(request_id: &str) -> bool {
+    match rest_cancel_flags().get(request_id) {
+        Some(entry) => {
+            entry.value().store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Failures a `RetryPolicy` with `retry_on_connection_errors` may retry.
+fn is_connection_error(err: &RequestError) -> bool {
+    matches!(
+        err,
+        RequestError::DnsResolution { .. }
+            | RequestError::ConnectionRefused
+            | RequestError::ConnectionClosed { .. }
+            | RequestError::Timeout { .. }
+    )
+}
+
+/// Which part of the transfer was running when a timeout fired, judged by the
+/// last phase timestamp libcurl recorded.
+fn timeout_phase(easy: &mut Easy) -> &'static str {
+    let reached = |time: Result<Duration, curl::Error>| time.is_ok_and(|t| !t.is_zero());
+    if reached(easy.starttransfer_time()) {
+        "download"
+    } else if reached(easy.pretransfer_time()) {
+        "ttfb"
+    } else if reached(easy.connect_time()) {
+        "tls"
+    } else if reached(easy.namelookup_time()) {
+        "connect"
+    } else {
+        "dns"
+    }
+}
+
+/// `CURLcode` is `c_int` on MSVC and `c_uint` elsewhere.
+#[allow(clippy::unnecessary_cast)]
+fn curl_code(e: &curl::Error) -> u32 {
+    e.code() as u32
+}
+
+/// Map a failed `perform` onto a `RequestError`.
+fn classify_curl_error(
+    easy: &mut Easy,
+    e: &curl::Error,
+    host: &str,
+    ip_version: Option<&IpVersion>,
+) -> RequestError {
+    if e.code() == curl_sys::CURLE_SSL_PINNEDPUBKEYNOTMATCH {
+        RequestError::PublicKeyPinMismatch {
+            detail: e.extra_description().unwrap_or_default().to_string(),
+        }
+    } else if e.is_couldnt_resolve_host() {
+        RequestError::DnsResolution {
+            host: host.to_string(),
+            ip_version: ip_version.filter(|v| **v != IpVersion::Any).cloned(),
+        }
+    } else if e.is_couldnt_connect() {
+        RequestError::ConnectionRefused
+    } else if e.is_operation_timedout() {
+        RequestError::Timeout {
+            phase: timeout_phase(easy).to_string(),
+        }
+    } else if e.is_ssl_certproblem() {
+        // CURLE_SSL_CERTPROBLEM is about the local cert: bad format or wrong password.
+        RequestError::ClientCertificate {
+            detail: e.to_string(),
+        }
+    } else if e.is_peer_failed_verification() {
+        RequestError::UntrustedCertificate {
+            host: host.to_string(),
+            fingerprint_sha256: None,
+            detail: e.to_string(),
+        }
+    } else if e.is_ssl_cipher() {
+        RequestError::TlsVerification {
+            detail: format!("Cipher list not accepted. {}", e),
+        }
+    } else if e.is_ssl_connect_error()
+        || e.is_ssl_cacert()
+        || e.is_ssl_cacert_badfile()
+        || e.is_ssl_issuer_error()
+    {
+        RequestError::TlsVerification {
+            detail: e.to_string(),
+        }
+    } else if e.is_url_malformed() || e.is_unsupported_protocol() {
+        RequestError::InvalidUrl {
+            reason: e.to_string(),
+        }
+    } else if e.is_send_error() || e.is_recv_error() || e.is_got_nothing() || e.is_partial_file() {
+        RequestError::ConnectionClosed {
+            detail: e.to_string(),
+        }
+    } else {
+        RequestError::Curl {
+            code: curl_code(e),
+            detail: format_curl_error(e),
+        }
+    }
+}
+
+/// `CURLINFO_NUM_CONNECTS`, which the curl crate doesn't wrap.
+fn num_connects(easy: &Easy) -> u32 {
+    let mut count: std::os::raw::c_long = 0;
+    // SAFETY: `easy.raw()` is a live handle and NUM_CONNECTS writes a long.
+    let code = unsafe {
+        curl_sys::curl_easy_getinfo(easy.raw(), curl_sys::CURLINFO_NUM_CONNECTS, &mut count)
+    };
+    if code == curl_sys::CURLE_OK {
+        count.max(0) as u32
+    } else {
+        0
+    }
+}
+
+/// Resets a string option to libcurl's default, which the curl crate has no setter for.
+fn clear_string_option(easy: &mut Easy, option: curl_sys::CURLoption) {
+    // SAFETY: `easy.raw()` is a live handle; a null pointer clears a string option.
+    unsafe {
+        curl_sys::curl_easy_setopt(easy.raw(), option, std::ptr::null::<std::os::raw::c_char>());
+    }
+}
+
+/// Trusts only the request's CA bundle. Inline PEM is written under the spill
+/// directory, named by its hash so repeated sends reuse one file.
+fn apply_ca_bundle(easy: &mut Easy, req: &ApiRequest) -> Result<(), RequestError> {
+    let tls_error = |detail: String| RequestError::TlsVerification { detail };
+    let path = match (&req.ca_cert_path, &req.ca_cert_pem) {
+        (Some(_), Some(_)) => {
+            return Err(tls_error(
+                "Set either ca_cert_path or ca_cert_pem, not both".to_string(),
+            ))
+        }
+        (Some(path), None) => PathBuf::from(path),
+        (None, Some(pem)) => {
+            if !pem.contains("-----BEGIN CERTIFICATE-----") {
+                return Err(tls_error("Inline CA certificate is not PEM".to_string()));
+            }
+            let digest: String = Sha256::digest(pem.as_bytes())
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            let path = spill_dir().join(format!("ca-{}.pem", &digest[..16]));
+            std::fs::create_dir_all(spill_dir())
+                .and_then(|_| std::fs::write(&path, pem))
+                .map_err(|e| RequestError::Io {
+                    detail: format!("Failed to write inline CA certificate: {}", e),
+                })?;
+            path
+        }
+        (None, None) => return Ok(()),
+    };
+
+    let shown = path.display();
+    let metadata = std::fs::metadata(&path)
+        .map_err(|e| tls_error(format!("CA certificate {}: {}", shown, e)))?;
+    let path_str = path.to_string_lossy();
+    if metadata.is_dir() {
+        easy.capath(&*path_str).map_err(|e| e.to_string())?;
+        clear_string_option(easy, curl_sys::CURLOPT_CAINFO);
+    } else {
+        let contents = std::fs::read(&path)
+            .map_err(|e| tls_error(format!("CA certificate {}: {}", shown, e)))?;
+        let contents = String::from_utf8_lossy(&contents);
+        if !contents.contains("-----BEGIN CERTIFICATE-----") {
+            return Err(tls_error(format!(
+                "CA certificate {}: no PEM certificates found",
+                shown
+            )));
+        }
+        easy.cainfo(&*path_str).map_err(|e| e.to_string())?;
+        clear_string_option(easy, curl_sys::CURLOPT_CAPATH);
+    }
+    Ok(())
+}
+
+fn client_cert_paths(cert: &ClientCert) -> Vec<&str> {
+    match cert {
+        ClientCert::Pem {
+            cert_path,
+            key_path,
+            ..
+        } => std::iter::once(cert_path.as_str())
+            .chain(key_path.as_deref())
+            .collect(),
+        ClientCert::Pkcs12 { path, .. } => vec![path.as_str()],
+    }
+}
+
+/// Points libcurl at the client certificate, checking the files exist first so
+/// a typo fails before any connection is made.
+fn apply_client_cert(easy: &mut Easy, cert: &ClientCert) -> Result<(), RequestError> {
+    let readable = |path: &str| {
+        std::fs::File::open(path)
+            .map(|_| ())
+            .map_err(|e| RequestError::ClientCertificate {
+                detail: format!("Cannot read {}: {}", path, e),
+            })
+    };
+    let (cert_path, cert_type, key_path, password) = match cert {
+        ClientCert::Pem {
+            cert_path,
+            key_path,
+            key_password,
+        } => (cert_path, "PEM", key_path.as_ref(), key_password.as_ref()),
+        ClientCert::Pkcs12 { path, password } => (path, "P12", None, password.as_ref()),
+    };
+    readable(cert_path)?;
+    easy.ssl_cert(cert_path).map_err(|e| e.to_string())?;
+    easy.ssl_cert_type(cert_type).map_err(|e| e.to_string())?;
+    if let Some(key_path) = key_path {
+        readable(key_path)?;
+        easy.ssl_key(key_path).map_err(|e| e.to_string())?;
+        easy.ssl_key_type("PEM").map_err(|e| e.to_string())?;
+    }
+    if let Some(password) = password {
+        easy.key_password(password).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Name of the `WWW-Authenticate` scheme libcurl negotiates for `auth`.
+fn negotiated_scheme(auth: &AuthType) -> Option<&'static str> {
+    match auth {
+        AuthType::Ntlm { .. } => Some("NTLM"),
+        AuthType::Negotiate { .. } => Some("Negotiate"),
+        _ => None,
+    }
+}
+
+/// Hands NTLM or SPNEGO credentials to libcurl, which runs the challenge
+/// round trips over one connection inside a single perform. `send` is false on
+/// cross-origin redirect hops, where the credentials are withdrawn again.
+fn apply_negotiated_auth(easy: &mut Easy, auth: &AuthType, send: bool) -> Result<(), RequestError> {
+    if negotiated_scheme(auth).is_none() {
+        return Ok(());
+    }
+    if !send {
+        clear_string_option(easy, curl_sys::CURLOPT_USERNAME);
+        clear_string_option(easy, curl_sys::CURLOPT_PASSWORD);
+        return Ok(());
+    }
+    let version = curl::Version::get();
+    let (user, password) = match auth {
+        AuthType::Ntlm {
+            username,
+            password,
+            domain,
+        } => {
+            if !version.feature_ntlm() {
+                return Err(RequestError::AuthNotSupported {
+                    scheme: "NTLM".to_string(),
+                    detail: format!("libcurl {} was built without NTLM", version.version()),
+                });
+            }
+            easy.http_auth(Auth::new().ntlm(true))
+                .map_err(|e| e.to_string())?;
+            let user = match domain.as_deref().filter(|d| !d.is_empty()) {
+                Some(domain) => format!("{}\\{}", domain, username),
+                None => username.clone(),
+            };
+            (user, password.clone())
+        }
+        AuthType::Negotiate {
+            username,
+            password,
+            delegate,
+        } => {
+            if !cfg!(feature = "negotiate") {
+                return Err(RequestError::AuthNotSupported {
+                    scheme: "Negotiate".to_string(),
+                    detail: "negotiate not supported by this build (missing `negotiate` feature)"
+                        .to_string(),
+                });
+            }
+            if !version.feature_spnego() {
+                return Err(RequestError::AuthNotSupported {
+                    scheme: "Negotiate".to_string(),
+                    detail: format!(
+                        "negotiate not supported by this build: libcurl {} lacks GSS-API/SPNEGO",
+                        version.version()
+                    ),
+                });
+            }
+            easy.http_auth(Auth::new().gssnegotiate(true))
+                .map_err(|e| e.to_string())?;
+            let delegation = if *delegate {
+                curl_sys::CURLGSSAPI_DELEGATION_FLAG
+            } else {
+                curl_sys::CURLGSSAPI_DELEGATION_NONE
+            };
+            // SAFETY: `easy.raw()` is a live handle and GSSAPI_DELEGATION takes a long.
+            unsafe {
+                curl_sys::curl_easy_setopt(
+                    easy.raw(),
+                    curl_sys::CURLOPT_GSSAPI_DELEGATION,
+                    delegation as std::os::raw::c_long,
+                );
+            }
+            // Empty credentials use the ticket cache, like `curl --negotiate -u :`.
+            (
+                username.clone().unwrap_or_default(),
+                password.clone().unwrap_or_default(),
+            )
+        }
+        _ => unreachable!(),
+    };
+    easy.username(&user).map_err(|e| e.to_string())?;
+    easy.password(&password).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Points libcurl at the request's netrc file and returns the login it will
+/// send to the request's host, if the file supplies one.
+fn apply_netrc(
+    app: &AppHandle,
+    easy: &mut Easy,
+    req: &ApiRequest,
+    prepared: &PreparedRequest,
+) -> Result<Option<String>, RequestError> {
+    let mode = match req.use_netrc {
+        None | Some(NetrcMode::Ignored) => return Ok(None),
+        Some(NetrcMode::Optional) => NetRc::Optional,
+        Some(NetrcMode::Required) => NetRc::Required,
+    };
+    let path = match req.netrc_file {
+        Some(ref path) => PathBuf::from(path),
+        None => default_netrc_path(&app.path().home_dir().map_err(|e| e.to_string())?),
+    };
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        // Only a file the request names has to exist.
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && req.netrc_file.is_none() => {
+            return Ok(None)
+        }
+        Err(e) => {
+            return Err(RequestError::Io {
+                detail: format!("Failed to read netrc file {}: {}", path.display(), e),
+            })
+        }
+    };
+    let url = Url::parse(&prepared.url).ok();
+    let host = url.as_ref().and_then(Url::host_str).unwrap_or_default();
+    let entry = netrc_lookup(&text, host).map_err(|e| RequestError::NetrcParse {
+        path: path.display().to_string(),
+        line: e.line,
+        detail: e.detail,
+    })?;
+
+    easy.netrc(mode).map_err(|e| e.to_string())?;
+    let path = CString::new(path.to_string_lossy().into_owned()).map_err(|e| e.to_string())?;
+    // SAFETY: `easy.raw()` is a live handle and libcurl copies string options.
+    unsafe {
+        curl_sys::curl_easy_setopt(easy.raw(), curl_sys::CURLOPT_NETRC_FILE, path.as_ptr());
+    }
+
+    // libcurl leaves the file alone when the request brings its own credentials.
+    let has_own_credentials = negotiated_scheme(&req.auth).is_some()
+        || prepared
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+        || (matches!(mode, NetRc::Optional) && url.is_some_and(|u| !u.username().is_empty()));
+    Ok(entry
+        .filter(|entry| !has_own_credentials && entry.password.is_some())
+        .and_then(|entry| entry.login))
+}
+
+/// Scheme names from `WWW-Authenticate` headers, e.g. `Negotiate` and `NTLM`.
+fn offered_auth_schemes(headers: &[(String, String)]) -> Vec<String> {
+    headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("www-authenticate"))
+        .filter_map(|(_, value)| value.split_whitespace().next())
+        .map(|scheme| scheme.trim_end_matches(',').to_string())
+        .collect()
+}
+
+fn is_idempotent(method: &Methods) -> bool {
+    !matches!(
+        method,
+        Methods::POST | Methods::PATCH | Methods::CONNECT | Methods::Custom(_)
+    )
+}
+
+/// Sleep for `duration`, waking early if the request is cancelled. Returns false when cancelled.
+fn backoff_unless_cancelled(duration: Duration, cancel: Option<&AtomicBool>) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if cancel.is_some_and(|c| c.load(Ordering::SeqCst)) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        std::thread::sleep((deadline - now).min(Duration::from_millis(50)));
+    }
+}
+
+pub(crate) fn method_to_curl_string(method: &Methods) -> &str {
     match method {
         Methods::GET => "GET",
         Methods::POST => "POST",
@@ -34,6 +484,395 @@ fn method_to_curl_string(method: &Methods) -> &'static str {
         Methods::OPTIONS => "OPTIONS",
         Methods::TRACE => "TRACE",
         Methods::CONNECT => "CONNECT",
+        Methods::Custom(verb) => verb,
+    }
+}
+
+/// The method named by `method`. Names are case-sensitive, so anything but
+/// the standard upper-case spellings is a custom verb.
+pub(crate) fn method_from_string(method: &str) -> Methods {
+    match method {
+        "GET" => Methods::GET,
+        "POST" => Methods::POST,
+        "PUT" => Methods::PUT,
+        "DELETE" => Methods::DELETE,
+        "PATCH" => Methods::PATCH,
+        "HEAD" => Methods::HEAD,
+        "OPTIONS" => Methods::OPTIONS,
+        "TRACE" => Methods::TRACE,
+        "CONNECT" => Methods::CONNECT,
+        other => Methods::Custom(other.to_string()),
+    }
+}
+
+/// RFC 9110 `token`: visible ASCII excluding separators, no whitespace or controls.
+fn validate_method_token(method: &str) -> Result<(), String> {
+    let is_tchar = |c: char| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c);
+    if method.is_empty() || !method.chars().all(is_tchar) {
+        return Err(format!("Invalid HTTP method '{}'", method));
+    }
+    Ok(())
+}
+
+fn curl_http_version(protocol: Option<&HttpProtocol>) -> Result<HttpVersion, String> {
+    match protocol.cloned().unwrap_or_default() {
+        HttpProtocol::Tcp => Ok(HttpVersion::V2TLS),
+        HttpProtocol::Auto => Ok(HttpVersion::Any),
+        HttpProtocol::Http1_1 => Ok(HttpVersion::V11),
+        HttpProtocol::Http2 => Ok(HttpVersion::V2),
+        HttpProtocol::Http2PriorKnowledge => Ok(HttpVersion::V2PriorKnowledge),
+        HttpProtocol::Quic => {
+            if !curl::Version::get().feature_http3() {
+                return Err(format!(
+                    "HTTP/3 requested but libcurl {} was built without HTTP/3 support",
+                    curl::Version::get().version()
+                ));
+            }
+            Ok(HttpVersion::V3)
+        }
+    }
+}
+
+/// Formats one override as a libcurl `CURLOPT_RESOLVE` entry (`host:port:addr`).
+fn resolve_entry(entry: &ResolveOverride) -> Result<String, String> {
+    let host = entry.host.trim();
+    if host.is_empty() || host.contains(':') {
+        return Err(format!("Invalid host '{}' in resolve override", entry.host));
+    }
+    if entry.port == 0 {
+        return Err(format!("Invalid port 0 in resolve override for {}", host));
+    }
+    let address: std::net::IpAddr = entry.address.trim().parse().map_err(|_| {
+        format!(
+            "Invalid IP address '{}' in resolve override for {}",
+            entry.address, host
+        )
+    })?;
+    Ok(match address {
+        std::net::IpAddr::V4(ip) => format!("{}:{}:{}", host, entry.port, ip),
+        std::net::IpAddr::V6(ip) => format!("{}:{}:[{}]", host, entry.port, ip),
+    })
+}
+
+/// `CURLPROXY_HTTPS` (7.52+), which has no `ProxyType` variant in the curl crate.
+const CURLPROXY_HTTPS: std::os::raw::c_long = 2;
+
+/// Sets the protocol spoken to the proxy from `ProxyConfig::proxy_type`.
+/// A scheme in the proxy URL (`socks5h://...`) still takes precedence, as in curl.
+fn apply_proxy_type(easy: &mut Easy, kind: &ProxyKind) -> Result<(), String> {
+    let proxy_type = match kind {
+        ProxyKind::Http => ProxyType::Http,
+        ProxyKind::Socks4 => ProxyType::Socks4,
+        ProxyKind::Socks5 => ProxyType::Socks5,
+        ProxyKind::Socks5Hostname => ProxyType::Socks5Hostname,
+        ProxyKind::Https => {
+            if !curl::Version::get().feature_https_proxy() {
+                return Err("HTTPS proxies are not supported by this build of libcurl".to_string());
+            }
+            // SAFETY: `easy.raw()` is a live handle and PROXYTYPE takes a long.
+            let code = unsafe {
+                curl_sys::curl_easy_setopt(easy.raw(), curl_sys::CURLOPT_PROXYTYPE, CURLPROXY_HTTPS)
+            };
+            return if code == curl_sys::CURLE_OK {
+                Ok(())
+            } else {
+                Err(curl::Error::new(code).to_string())
+            };
+        }
+    };
+    easy.proxy_type(proxy_type).map_err(|e| e.to_string())
+}
+
+fn proxy_auth(scheme: &ProxyAuthScheme) -> Result<Auth, String> {
+    let version = curl::Version::get();
+    let mut auth = Auth::new();
+    match scheme {
+        ProxyAuthScheme::Basic => auth.basic(true),
+        ProxyAuthScheme::Digest => auth.digest(true),
+        ProxyAuthScheme::Ntlm => {
+            if !version.feature_ntlm() {
+                return Err(format!(
+                    "NTLM proxy auth not supported: libcurl {} was built without NTLM",
+                    version.version()
+                ));
+            }
+            auth.ntlm(true)
+        }
+        ProxyAuthScheme::Negotiate => {
+            if !cfg!(feature = "negotiate") || !version.feature_spnego() {
+                return Err(
+                    "Negotiate proxy auth not supported by this build (missing `negotiate` feature or GSS-API)"
+                        .to_string(),
+                );
+            }
+            auth.gssnegotiate(true)
+        }
+        // libcurl asks the proxy first and skips schemes it was built without.
+        ProxyAuthScheme::Any => auth.auto(true),
+    };
+    Ok(auth)
+}
+
+/// The `Proxy-Authenticate` challenges of the last header block in `raw`,
+/// which is the proxy's own answer when a CONNECT was refused.
+fn proxy_challenges(raw: &[u8]) -> Vec<String> {
+    let mut challenges = Vec::new();
+    for line in String::from_utf8_lossy(raw).lines() {
+        if line.starts_with("HTTP/") {
+            challenges.clear();
+        } else if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("proxy-authenticate") {
+                challenges.push(value.trim().to_string());
+            }
+        }
+    }
+    challenges
+}
+
+/// Routes a request without its own `proxy` by the most specific `ProxyRule`
+/// for its host, returning the rule that applied.
+fn with_proxy_rule(app: &AppHandle, mut req: ApiRequest) -> (ApiRequest, Option<ProxyRule>) {
+    if req.proxy.is_some() {
+        return (req, None);
+    }
+    let rule = Url::parse(&req.url)
+        .ok()
+        .and_then(|url| proxy_rule_for(app, url.host_str()?));
+    match rule.as_ref().map(|rule| &rule.target) {
+        Some(ProxyRuleTarget::Proxy(proxy)) => req.proxy = Some(proxy.clone()),
+        Some(ProxyRuleTarget::Direct) => req.use_system_proxy = Some(false),
+        None => {}
+    }
+    (req, rule)
+}
+
+/// Whether a libcurl log line reports the proxy tunnel as open.
+fn is_tunnel_established(kind: InfoType, data: &[u8]) -> bool {
+    if !matches!(kind, InfoType::Text) {
+        return false;
+    }
+    // Older libcurl prints the second form.
+    let text = String::from_utf8_lossy(data);
+    text.starts_with("CONNECT tunnel established")
+        || text.starts_with("Proxy replied 200 to CONNECT")
+}
+
+/// Applies the environment/OS proxy for `use_system_proxy` requests without an
+/// explicit `proxy`, returning the proxy chosen. Redirect hops reuse it.
+fn apply_system_proxy(easy: &mut Easy, req: &ApiRequest) -> Result<Option<String>, String> {
+    if req.proxy.is_some() || !req.use_system_proxy.unwrap_or(false) {
+        return Ok(None);
+    }
+    let proxy = Url::parse(&req.url)
+        .ok()
+        .and_then(|url| system_proxy_for(&url));
+    // An empty proxy also stops libcurl from reading the proxy variables itself.
+    easy.proxy(proxy.as_deref().unwrap_or(""))
+        .map_err(|e| e.to_string())?;
+    Ok(proxy)
+}
+
+/// The explicit proxy for `url`, or `None` when its `no_proxy` list exempts
+/// the host. Matched here as well as by libcurl, whose CIDR support is recent.
+fn explicit_proxy_for<'a>(req: &'a ApiRequest, url: &str) -> Option<&'a ProxyConfig> {
+    let proxy = req.proxy.as_ref()?;
+    let bypassed = match (&proxy.no_proxy, Url::parse(url)) {
+        (Some(no_proxy), Ok(url)) => no_proxy_matches(&url, no_proxy),
+        _ => false,
+    };
+    (!bypassed).then_some(proxy)
+}
+
+/// Socket-level knobs on `ApiRequest` (where and how the connection is made).
+fn apply_transport_options(easy: &mut Easy, req: &ApiRequest) -> Result<(), String> {
+    if let Some(ref proxy) = req.proxy {
+        easy.proxy(&proxy.url).map_err(|e| e.to_string())?;
+        if let Some(ref no_proxy) = proxy.no_proxy {
+            easy.noproxy(no_proxy).map_err(|e| e.to_string())?;
+        }
+        apply_proxy_type(easy, &proxy.proxy_type)?;
+        match (&proxy.username, &proxy.password) {
+            (Some(user), Some(pass)) => {
+                easy.proxy_username(user).map_err(|e| e.to_string())?;
+                easy.proxy_password(pass).map_err(|e| e.to_string())?;
+            }
+            // SOCKS4 has a user id but no password.
+            (Some(user), None) if proxy.proxy_type == ProxyKind::Socks4 => {
+                easy.proxy_username(user).map_err(|e| e.to_string())?;
+            }
+            // Empty credentials use the ticket cache, like `curl --proxy-negotiate -U :`.
+            (None, None) if proxy.auth_scheme == Some(ProxyAuthScheme::Negotiate) => {
+                easy.proxy_username("").map_err(|e| e.to_string())?;
+                easy.proxy_password("").map_err(|e| e.to_string())?;
+            }
+            _ => {}
+        }
+        if let Some(ref scheme) = proxy.auth_scheme {
+            easy.proxy_auth(&proxy_auth(scheme)?)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    if let Some(ref socket) = req.unix_socket {
+        if !curl::Version::get().feature_unix_domain_socket() {
+            return Err("Unix domain sockets are not supported on this platform".to_string());
+        }
+        if !std::path::Path::new(socket).exists() {
+            return Err(format!("Unix socket not found: {}", socket));
+        }
+        easy.unix_socket(socket).map_err(|e| e.to_string())?;
+    }
+
+    if !req.resolve_overrides.is_empty() {
+        let mut resolve_list = List::new();
+        for entry in &req.resolve_overrides {
+            resolve_list
+                .append(&resolve_entry(entry)?)
+                .map_err(|e| e.to_string())?;
+        }
+        easy.resolve(resolve_list).map_err(|e| e.to_string())?;
+    }
+
+    let ip_resolve = match req.ip_version.clone().unwrap_or_default() {
+        IpVersion::Any => IpResolve::Any,
+        IpVersion::V4Only => IpResolve::V4,
+        IpVersion::V6Only => IpResolve::V6,
+    };
+    easy.ip_resolve(ip_resolve).map_err(|e| e.to_string())?;
+
+    if let Some(ref interface) = req.local_interface {
+        easy.interface(interface.trim())
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some((start, end)) = req.local_port_range {
+        if start == 0 || end < start {
+            return Err(format!("Invalid local port range {}-{}", start, end));
+        }
+        easy.set_local_port(start).map_err(|e| e.to_string())?;
+        easy.local_port_range(end - start + 1)
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(ref keepalive) = req.tcp_keepalive {
+        easy.tcp_keepalive(true).map_err(|e| e.to_string())?;
+        easy.tcp_keepidle(Duration::from_secs(keepalive.idle_secs as u64))
+            .map_err(|e| e.to_string())?;
+        easy.tcp_keepintvl(Duration::from_secs(keepalive.interval_secs as u64))
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(timeout) = req.connect_timeout_ms.filter(|&t| t > 0) {
+        easy.connect_timeout(Duration::from_millis(timeout as u64))
+            .map_err(|e| e.to_string())?;
+    }
+
+    if let Some(limit) = req.max_recv_speed_bytes.filter(|&l| l > 0) {
+        easy.max_recv_speed(limit as u64)
+            .map_err(|e| e.to_string())?;
+    }
+    if let Some(limit) = req.max_send_speed_bytes.filter(|&l| l > 0) {
+        easy.max_send_speed(limit as u64)
+            .map_err(|e| e.to_string())?;
+    }
+
+    apply_tls_versions(easy, req)?;
+    apply_tls_ciphers(easy, req)?;
+
+    Ok(())
+}
+
+/// `CURLOPT_TLS13_CIPHERS` (7.61+), which neither the curl crate nor curl-sys define.
+const CURLOPT_TLS13_CIPHERS: curl_sys::CURLoption = curl_sys::CURLOPTTYPE_OBJECTPOINT + 276;
+
+/// Catches obvious typos up front; whether the suites exist is only known once
+/// the TLS backend parses the list during the handshake.
+fn validate_cipher_list(field: &str, list: &str) -> Result<(), String> {
+    let valid = !list.trim().is_empty()
+        && list
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-+!@=.:, ".contains(c));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid {} '{}'", field, list))
+    }
+}
+
+/// OpenSSL-style cipher lists, like `curl --ciphers` / `--tls13-ciphers`.
+fn apply_tls_ciphers(easy: &mut Easy, req: &ApiRequest) -> Result<(), String> {
+    if let Some(ref ciphers) = req.tls_ciphers {
+        validate_cipher_list("tls_ciphers", ciphers)?;
+        easy.ssl_cipher_list(ciphers)
+            .map_err(|e| format!("tls_ciphers rejected: {}", e))?;
+    }
+    if let Some(ref ciphers) = req.tls13_ciphers {
+        validate_cipher_list("tls13_ciphers", ciphers)?;
+        let value = std::ffi::CString::new(ciphers.as_str()).map_err(|e| e.to_string())?;
+        // SAFETY: `easy.raw()` is a live handle; libcurl copies string options.
+        let code = unsafe {
+            curl_sys::curl_easy_setopt(easy.raw(), CURLOPT_TLS13_CIPHERS, value.as_ptr())
+        };
+        if code != curl_sys::CURLE_OK {
+            return Err(format!(
+                "tls13_ciphers rejected: {}",
+                curl::Error::new(code)
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Restricts the TLS versions libcurl will negotiate, like `curl --tlsv1.2 --tls-max 1.3`.
+fn apply_tls_versions(easy: &mut Easy, req: &ApiRequest) -> Result<(), String> {
+    let (min, max) = (req.tls_min_version.as_ref(), req.tls_max_version.as_ref());
+    if min.is_none() && max.is_none() {
+        return Ok(());
+    }
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(format!(
+                "tls_min_version {:?} is newer than tls_max_version {:?}",
+                min, max
+            ));
+        }
+    }
+    let version = curl::Version::get();
+    // TLS 1.3 arrived in libcurl 7.52 and an upper bound in 7.54.
+    let needs_1_3 = [min, max].contains(&Some(&TlsVersion::Tls1_3));
+    if (needs_1_3 && version.version_num() < 0x073400)
+        || (max.is_some() && version.version_num() < 0x073600)
+    {
+        return Err(format!(
+            "TLS version not supported by this build (libcurl {})",
+            version.version()
+        ));
+    }
+    let to_curl = |v: Option<&TlsVersion>| match v {
+        None => SslVersion::Default,
+        Some(TlsVersion::Tls1_0) => SslVersion::Tlsv10,
+        Some(TlsVersion::Tls1_1) => SslVersion::Tlsv11,
+        Some(TlsVersion::Tls1_2) => SslVersion::Tlsv12,
+        Some(TlsVersion::Tls1_3) => SslVersion::Tlsv13,
+    };
+    easy.ssl_min_max_version(to_curl(min), to_curl(max))
+        .map_err(|e| format!("TLS version not supported by this build: {}", e))
+}
+
+/// The name of the encoding a leading byte order mark announces, if any.
+fn detect_bom(body: &[u8]) -> Option<String> {
+    Encoding::for_bom(body).map(|(encoding, _)| encoding.name().to_string())
+}
+
+/// The body as UTF-8 for text sniffing: a leading BOM is dropped and UTF-16 is transcoded.
+fn sniffable_text(body: &[u8]) -> Cow<'_, [u8]> {
+    match Encoding::for_bom(body) {
+        Some((encoding, bom_len)) if encoding == UTF_8 => Cow::Borrowed(&body[bom_len..]),
+        Some((encoding, _)) => {
+            let (text, _) = encoding.decode_with_bom_removal(body);
+            Cow::Owned(text.into_owned().into_bytes())
+        }
+        None => Cow::Borrowed(body),
     }
 }
 
@@ -41,13 +880,15 @@ fn detect_renderers(content_type: Option<&str>, body: &[u8]) -> Vec<ResponseRend
     let mut renderers = vec![ResponseRenderer::Raw];
 
     let ct = content_type.unwrap_or("").to_lowercase();
+    let text = sniffable_text(body);
+    let text = text.as_ref();
 
     if ct.contains("application/json") || ct.contains("+json") {
-        if serde_json::from_slice::<serde_json::Value>(body).is_ok() {
+        if serde_json::from_slice::<serde_json::Value>(text).is_ok() {
             renderers.push(ResponseRenderer::Json);
         }
-    } else if body.starts_with(b"{") || body.starts_with(b"[") {
-        if serde_json::from_slice::<serde_json::Value>(body).is_ok() {
+    } else if text.starts_with(b"{") || text.starts_with(b"[") {
+        if serde_json::from_slice::<serde_json::Value>(text).is_ok() {
             renderers.push(ResponseRenderer::Json);
         }
     }
@@ -62,7 +903,7 @@ fn detect_renderers(content_type: Option<&str>, body: &[u8]) -> Vec<ResponseRend
     } else if is_xml_content_type {
         renderers.push(ResponseRenderer::Xml);
     } else if !is_html_content_type && !is_xml_content_type {
-        let body_str = String::from_utf8_lossy(body);
+        let body_str = String::from_utf8_lossy(text);
         if body_str.trim_start().starts_with("<?xml") {
             renderers.push(ResponseRenderer::Xml);
         } else if body_str.trim_start().starts_with("<!DOCTYPE html")
@@ -96,9 +937,87 @@ fn detect_renderers(content_type: Option<&str>, body: &[u8]) -> Vec<ResponseRend
         renderers.push(ResponseRenderer::Video);
     }
 
+    if ct.contains("yaml") {
+        renderers.push(ResponseRenderer::Yaml);
+    } else if (ct.is_empty() || ct.contains("text/plain"))
+        && !renderers.contains(&ResponseRenderer::Json)
+        && sniff_yaml(text)
+    {
+        renderers.push(ResponseRenderer::Yaml);
+    }
+
+    if ct.contains("msgpack") || ct.contains("messagepack") {
+        renderers.push(ResponseRenderer::MsgPack);
+    }
+
+    if ct.contains("protobuf") {
+        renderers.push(ResponseRenderer::Protobuf);
+    }
+
+    if ct.contains("text/markdown") || ct.contains("text/x-markdown") {
+        renderers.push(ResponseRenderer::Markdown);
+    } else if ct.contains("text/plain") && sniff_markdown(text) {
+        renderers.push(ResponseRenderer::Markdown);
+    }
+
+    if ct.contains("text/csv") {
+        renderers.push(ResponseRenderer::Csv);
+    } else if (ct.is_empty() || ct.contains("text/plain"))
+        && !renderers.contains(&ResponseRenderer::Json)
+        && sniff_csv_delimiter(text).is_some()
+    {
+        renderers.push(ResponseRenderer::Csv);
+    }
+
+    // Headers are often wrong or generic (S3 serves everything as octet-stream),
+    // so also look at the body's signature. This only ever adds renderers.
+    if let Some(sniffed) = sniff_renderer(body) {
+        if !renderers.contains(&sniffed) {
+            renderers.push(sniffed);
+        }
+    }
+
     renderers
 }
 
+/// Only this many leading bytes are inspected by `sniff_renderer`.
+const SNIFF_BYTES: usize = 512;
+
+/// Renderer implied by a body's magic bytes, if it has a known signature.
+fn sniff_renderer(body: &[u8]) -> Option<ResponseRenderer> {
+    let head = &body[..body.len().min(SNIFF_BYTES)];
+    let riff_form = head.starts_with(b"RIFF").then(|| head.get(8..12)).flatten();
+    let ftyp_brand = (head.get(4..8) == Some(b"ftyp".as_slice()))
+        .then(|| head.get(8..12))
+        .flatten();
+
+    if head.starts_with(b"\x89PNG\r\n\x1a\n")
+        || head.starts_with(b"\xff\xd8\xff")
+        || head.starts_with(b"GIF87a")
+        || head.starts_with(b"GIF89a")
+        || riff_form == Some(b"WEBP".as_slice())
+        // "BM" alone is too common in text; the reserved header bytes must be zero too.
+        || (head.starts_with(b"BM") && head.get(6..10) == Some([0u8; 4].as_slice()))
+        || head.starts_with(b"\x00\x00\x01\x00")
+    {
+        Some(ResponseRenderer::Image)
+    } else if head.starts_with(b"%PDF-") {
+        Some(ResponseRenderer::Pdf)
+    } else if riff_form == Some(b"WAVE".as_slice())
+        || head.starts_with(b"ID3")
+        || head.starts_with(b"fLaC")
+        || ftyp_brand == Some(b"M4A ".as_slice())
+    {
+        Some(ResponseRenderer::Audio)
+    } else if ftyp_brand.is_some() {
+        Some(ResponseRenderer::Video)
+    } else if head.starts_with(b"PK\x03\x04") {
+        Some(ResponseRenderer::Archive)
+    } else {
+        None
+    }
+}
+
 fn parse_set_cookie(header_value: &str) -> Option<Cookie> {
     let parts: Vec<&str> = header_value.split(';').collect();
     if parts.is_empty() {
@@ -116,8 +1035,12 @@ fn parse_set_cookie(header_value: &str) -> Option<Cookie> {
         domain: None,
         path: None,
         expires: None,
+        expires_at: None,
+        is_expired: None,
         http_only: None,
         secure: None,
+        max_age: None,
+        same_site: None,
     };
 
     for part in parts.iter().skip(1) {
@@ -129,24 +1052,95 @@ fn parse_set_cookie(header_value: &str) -> Option<Cookie> {
             "domain" => cookie.domain = attr_value,
             "path" => cookie.path = attr_value,
             "expires" => cookie.expires = attr_value,
+            "max-age" => cookie.max_age = attr_value.and_then(|v| v.parse().ok()),
+            "samesite" => cookie.same_site = attr_value,
             "httponly" => cookie.http_only = Some(true),
             "secure" => cookie.secure = Some(true),
             _ => {}
         }
     }
 
+    // Max-Age wins over Expires (RFC 6265 §5.3).
+    let now = chrono::Utc::now().timestamp();
+    cookie.expires_at = match cookie.max_age {
+        Some(secs) => Some(now.saturating_add(secs)),
+        None => cookie.expires.as_deref().and_then(parse_cookie_date),
+    };
+    cookie.is_expired = cookie.expires_at.map(|at| at <= now);
+
     Some(cookie)
 }
 
-fn build_cookie_header(cookies: &[Cookie]) -> String {
-    cookies
+fn cookies_from_headers(headers: &[(String, String)]) -> Vec<Cookie> {
+    headers
         .iter()
-        .map(|c| format!("{}={}", c.name, c.value))
-        .collect::<Vec<_>>()
-        .join("; ")
+        .filter(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+        .filter_map(|(_, value)| parse_set_cookie(value))
+        .collect()
 }
 
-fn status_text(status: u16) -> String {
+/// Collapse repeated headers into one comma-joined value per name (legacy map shape).
+fn fold_headers(headers: &[(String, String)]) -> HashMap<String, String> {
+    let mut folded: HashMap<String, String> = HashMap::new();
+    for (name, value) in headers {
+        if let Some(existing) = folded.get_mut(name) {
+            existing.push_str(", ");
+            existing.push_str(value);
+        } else {
+            folded.insert(name.clone(), value.clone());
+        }
+    }
+    folded
+}
+
+/// RFC 6265 `cookie-octet`: what a cookie value may contain unquoted.
+fn is_cookie_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+/// Validate a cookie name against the RFC 2616 token grammar and make the value
+/// safe to join: valid values, bare or in double quotes, pass through, and
+/// anything else has its bytes outside `cookie-octet` percent-encoded.
+fn encode_cookie_pair(name: &str, value: &str) -> Result<String, String> {
+    const SEPARATORS: &[u8] = b"()<>@,;:\\\"/[]?={} \t";
+    let valid_name = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| (0x21..0x7F).contains(&b) && !SEPARATORS.contains(&b));
+    if !valid_name {
+        return Err(format!(
+            "Invalid cookie name '{}': expected a token without spaces, control characters or separators",
+            name
+        ));
+    }
+
+    let unquoted = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+    if unquoted.bytes().all(is_cookie_octet) {
+        return Ok(format!("{}={}", name, value));
+    }
+    let mut encoded = String::with_capacity(value.len() * 3);
+    for b in value.bytes() {
+        if is_cookie_octet(b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    Ok(format!("{}={}", name, encoded))
+}
+
+fn build_cookie_header(cookies: &[(String, String)]) -> Result<String, String> {
+    Ok(cookies
+        .iter()
+        .map(|(name, value)| encode_cookie_pair(name, value))
+        .collect::<Result<Vec<_>, _>>()?
+        .join("; "))
+}
+
+pub(crate) fn status_text(status: u16) -> String {
     match status {
         100 => "Continue".to_string(),
         101 => "Switching Protocols".to_string(),
@@ -183,17 +1177,269 @@ fn status_text(status: u16) -> String {
     }
 }
 
-fn build_url_with_params(
+/// The `charset` parameter of a Content-Type value, e.g. `text/html; charset=Shift_JIS`.
+fn charset_from_content_type(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Work out a body's text encoding from its BOM or declared charset and, unless it
+/// is already UTF-8, transcode it. Returns `(charset, base64 of the UTF-8 text)`.
+fn decode_body_text(
+    body: &[u8],
+    charset: Option<&str>,
+    warnings: &mut Vec<String>,
+) -> (Option<String>, Option<String>) {
+    let declared = match charset {
+        Some(label) => match Encoding::for_label(label.as_bytes()) {
+            Some(encoding) => Some(encoding),
+            None => {
+                warnings.push(format!(
+                    "Unknown charset '{}'; body decoded as UTF-8",
+                    label
+                ));
+                let text = String::from_utf8_lossy(body);
+                return (
+                    Some(label.to_string()),
+                    Some(BASE64.encode(text.as_bytes())),
+                );
+            }
+        },
+        None => None,
+    };
+
+    // A byte order mark wins over the declared charset, as it does in browsers.
+    let Some(encoding) = Encoding::for_bom(body)
+        .map(|(encoding, _)| encoding)
+        .or(declared)
+    else {
+        return (None, None);
+    };
+    if encoding == UTF_8 {
+        return (Some(encoding.name().to_string()), None);
+    }
+    let (text, _, had_errors) = encoding.decode(body);
+    if had_errors {
+        warnings.push(format!(
+            "Body contains bytes that are not valid {}; they were replaced",
+            encoding.name()
+        ));
+    }
+    (
+        Some(encoding.name().to_string()),
+        Some(BASE64.encode(text.as_bytes())),
+    )
+}
+
+/// The `protocol_used` reported for a status line's version (`HTTP/2` for `HTTP/2.0` too).
+fn protocol_label(http_version: &str) -> String {
+    if http_version.contains('3') {
+        "HTTP/3".to_string()
+    } else if http_version.contains('2') {
+        "HTTP/2".to_string()
+    } else {
+        http_version.to_string()
+    }
+}
+
+/// True for a status line that starts the real response (not an interim `1xx`).
+fn is_final_status_line(line: &[u8]) -> bool {
+    if !line.starts_with(b"HTTP/") {
+        return false;
+    }
+    let line = String::from_utf8_lossy(line);
+    line.split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u16>().ok())
+        .is_some_and(|status| status >= 200)
+}
+
+/// Collects libcurl debug records for `capture_verbose`. Payload records are
+/// reduced to byte counts, merging consecutive chunks in the same direction.
+#[derive(Default)]
+struct VerboseLog {
+    entries: Vec<VerboseEntry>,
+    /// Running byte count of the last entry when it is a payload record.
+    open_data_bytes: Option<usize>,
+}
+
+impl VerboseLog {
+    fn record(&mut self, kind: InfoType, data: &[u8]) {
+        let direction = match kind {
+            InfoType::Text => VerboseDirection::Info,
+            InfoType::HeaderIn => VerboseDirection::HeaderIn,
+            InfoType::HeaderOut => VerboseDirection::HeaderOut,
+            InfoType::DataIn | InfoType::SslDataIn => VerboseDirection::DataIn,
+            InfoType::DataOut | InfoType::SslDataOut => VerboseDirection::DataOut,
+            _ => return,
+        };
+
+        let is_data = matches!(
+            direction,
+            VerboseDirection::DataIn | VerboseDirection::DataOut
+        );
+        if !is_data {
+            self.open_data_bytes = None;
+            let text = String::from_utf8_lossy(data).trim_end().to_string();
+            self.entries.push(VerboseEntry { direction, text });
+            return;
+        }
+
+        let continues_last = self
+            .entries
+            .last()
+            .is_some_and(|e| e.direction == direction);
+        match (self.open_data_bytes, self.entries.last_mut()) {
+            (Some(bytes), Some(last)) if continues_last => {
+                let total = bytes + data.len();
+                last.text = format!("{} bytes", total);
+                self.open_data_bytes = Some(total);
+            }
+            _ => {
+                self.entries.push(VerboseEntry {
+                    direction,
+                    text: format!("{} bytes", data.len()),
+                });
+                self.open_data_bytes = Some(data.len());
+            }
+        }
+    }
+}
+
+fn compress_body(data: &[u8], compression: &BodyCompression) -> Result<Vec<u8>, String> {
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+
+    let compressed = match compression {
+        BodyCompression::None => return Ok(data.to_vec()),
+        BodyCompression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).and_then(|_| encoder.finish())
+        }
+        // HTTP's "deflate" coding is the zlib format (RFC 9110 §8.4.1.2).
+        BodyCompression::Deflate => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).and_then(|_| encoder.finish())
+        }
+    };
+    compressed.map_err(|e| format!("Failed to compress request body: {}", e))
+}
+
+fn resolve_redirect_location(base: &str, location: &str) -> String {
+    Url::parse(base)
+        .and_then(|b| b.join(location))
+        .map(|u| u.to_string())
+        .unwrap_or_else(|_| location.to_string())
+}
+
+fn is_redirect_status(status: u16) -> bool {
+    matches!(status, 301 | 302 | 303 | 307 | 308)
+}
+
+/// Browser semantics: 303 turns anything but HEAD into GET, 301/302 turn POST
+/// into GET, and 307/308 keep the method and body.
+fn redirect_rewrites_to_get(status: u16, method: &str) -> bool {
+    match status {
+        303 => method != "HEAD" && method != "GET",
+        301 | 302 => method == "POST",
+        _ => false,
+    }
+}
+
+/// Headers for a request issued after following a redirect. Authorization and the
+/// caller's own cookies only go back to the original origin, and body headers are
+/// dropped once the method has been rewritten to GET.
+fn redirect_hop_headers(
+    base: &[(String, String)],
+    jar: &CookieJar,
+    url: &Url,
+    same_origin: bool,
+    body_dropped: bool,
+) -> Result<Vec<(String, String)>, String> {
+    const BODY_HEADERS: [&str; 4] = [
+        "content-type",
+        "content-encoding",
+        "content-length",
+        "expect",
+    ];
+
+    let mut cookies: Vec<(String, String)> = Vec::new();
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for (name, value) in base {
+        let lower = name.to_ascii_lowercase();
+        if lower == "cookie" {
+            if same_origin {
+                cookies.extend(value.split(';').filter_map(|pair| {
+                    let (k, v) = pair.split_once('=')?;
+                    Some((k.trim().to_string(), v.trim().to_string()))
+                }));
+            }
+        } else if (same_origin || lower != "authorization")
+            && !(body_dropped && BODY_HEADERS.contains(&lower.as_str()))
+        {
+            headers.push((name.clone(), value.clone()));
+        }
+    }
+
+    for cookie in jar.matching(url) {
+        cookies.retain(|(name, _)| *name != cookie.name);
+        cookies.push((cookie.name.clone(), cookie.value.clone()));
+    }
+    if !cookies.is_empty() {
+        headers.push(("Cookie".to_string(), build_cookie_header(&cookies)?));
+    }
+    Ok(headers)
+}
+
+/// Splits a `;` separated pin list, checking each is a SHA-256 digest so a typo
+/// errors instead of failing every handshake.
+fn normalize_public_key_pins(pins: Option<&str>) -> Result<Vec<String>, String> {
+    let Some(pins) = pins else {
+        return Ok(Vec::new());
+    };
+    pins.split(';')
+        .map(str::trim)
+        .filter(|pin| !pin.is_empty())
+        .map(|pin| {
+            let digest = pin
+                .get(..8)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("sha256//"))
+                .and_then(|_| BASE64.decode(&pin[8..]).ok())
+                .filter(|digest| digest.len() == 32)
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid public key pin '{}': expected sha256//<base64 SHA-256 digest>",
+                        pin
+                    )
+                })?;
+            Ok(format!("sha256//{}", BASE64.encode(digest)))
+        })
+        .collect()
+}
+
+pub(crate) fn build_url_with_params(
     base_url: &str,
-    params: &HashMap<String, String>,
+    params: &[(String, String)],
     api_key_param: Option<(&str, &str)>,
 ) -> Result<String, String> {
     let mut url = Url::parse(base_url).map_err(|e| format!("Invalid URL: {}", e))?;
 
+    if params.is_empty() && api_key_param.is_none() {
+        return Ok(url.to_string());
+    }
+
     {
         let mut query_pairs = url.query_pairs_mut();
         for (key, value) in params {
-            query_pairs.append_pair(key, value);
+            if value.is_empty() {
+                query_pairs.append_key_only(key);
+            } else {
+                query_pairs.append_pair(key, value);
+            }
         }
         if let Some((key, value)) = api_key_param {
             query_pairs.append_pair(key, value);
@@ -203,114 +1449,520 @@ fn build_url_with_params(
     Ok(url.to_string())
 }
 
-fn execute_curl_request(
-    req: ApiRequest,
-    cancel: Option<Arc<AtomicBool>>,
-) -> Result<ApiResponse, String> {
-    let mut easy = Easy::new();
+/// Everything derived from an `ApiRequest` before libcurl is involved: final URL,
+/// the headers we add on top of libcurl's own, and the encoded body. Shared by
+/// `execute_curl_request` and `preview_request` so a preview can't drift from the wire.
+struct PreparedRequest {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<RequestBody>,
+    body_size: u32,
+    uncompressed_body_bytes: Option<u32>,
+    /// Why each explicit cookie that doesn't apply to the URL was left out.
+    skipped_cookies: Vec<String>,
+    /// `sha256//BASE64` pins from `pinned_public_key`, validated and re-encoded.
+    pinned_public_keys: Vec<String>,
+}
 
-    let api_key_query = match &req.auth {
-        AuthType::ApiKey {
-            key,
-            value,
-            add_to: ApiKeyLocation::Query,
-        } => Some((key.as_str(), value.as_str())),
-        _ => None,
+/// The standard GraphQL-over-HTTP JSON body. Empty `variables` and
+/// `operation_name` are omitted rather than sent as null.
+pub(crate) fn graphql_envelope(
+    query: &str,
+    variables: Option<&str>,
+    operation_name: Option<&str>,
+) -> Result<String, String> {
+    let mut envelope = serde_json::Map::new();
+    envelope.insert("query".to_string(), query.into());
+    if let Some(variables) = variables.filter(|v| !v.trim().is_empty()) {
+        match serde_json::from_str(variables) {
+            Ok(serde_json::Value::Null) => {}
+            Ok(value @ serde_json::Value::Object(_)) => {
+                envelope.insert("variables".to_string(), value);
+            }
+            Ok(_) => return Err("GraphQL variables must be a JSON object".to_string()),
+            Err(e) => return Err(format!("Invalid GraphQL variables JSON: {}", e)),
+        }
+    }
+    if let Some(name) = operation_name.filter(|n| !n.trim().is_empty()) {
+        envelope.insert("operationName".to_string(), name.into());
+    }
+    serde_json::to_string(&envelope).map_err(|e| e.to_string())
+}
+
+/// One JSON-RPC 2.0 request object. An `id` that looks like an integer is
+/// sent as a number, anything else as a string.
+fn jsonrpc_envelope(
+    method: &str,
+    params: Option<&str>,
+    id: Option<&str>,
+) -> Result<serde_json::Value, String> {
+    let mut envelope = serde_json::Map::new();
+    envelope.insert("jsonrpc".to_string(), "2.0".into());
+    envelope.insert("method".to_string(), method.into());
+    if let Some(params) = params.filter(|p| !p.trim().is_empty()) {
+        match serde_json::from_str(params) {
+            Ok(serde_json::Value::Null) => {}
+            Ok(value @ (serde_json::Value::Array(_) | serde_json::Value::Object(_))) => {
+                envelope.insert("params".to_string(), value);
+            }
+            Ok(_) => {
+                return Err(format!(
+                    "JSON-RPC params for '{}' must be an array or object",
+                    method
+                ))
+            }
+            Err(e) => return Err(format!("Invalid JSON-RPC params for '{}': {}", method, e)),
+        }
+    }
+    let id = match id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(id) => match id.parse::<i64>() {
+            Ok(number) => number.into(),
+            Err(_) => id.into(),
+        },
+        None => JSONRPC_NEXT_ID.fetch_add(1, Ordering::Relaxed).into(),
     };
+    envelope.insert("id".to_string(), id);
+    Ok(serde_json::Value::Object(envelope))
+}
 
-    let url = build_url_with_params(&req.url, &req.query_params, api_key_query)?;
-    easy.url(&url).map_err(|e| format!("URL error: {}", e))?;
+/// The request object of a `JsonRpc` body, or the array of them of a
+/// `JsonRpcBatch` body.
+pub(crate) fn jsonrpc_body(body: &BodyType) -> Result<serde_json::Value, String> {
+    match body {
+        BodyType::JsonRpc { method, params, id } => {
+            jsonrpc_envelope(method, params.as_deref(), id.as_deref())
+        }
+        BodyType::JsonRpcBatch { calls } => {
+            if calls.is_empty() {
+                return Err("A JSON-RPC batch needs at least one call".to_string());
+            }
+            calls
+                .iter()
+                .map(|call| {
+                    jsonrpc_envelope(&call.method, call.params.as_deref(), call.id.as_deref())
+                })
+                .collect::<Result<_, _>>()
+                .map(serde_json::Value::Array)
+        }
+        _ => Err("Not a JSON-RPC body".to_string()),
+    }
+}
 
-    match req.method {
-        Methods::GET => easy.get(true).map_err(|e| e.to_string())?,
-        Methods::POST => easy.post(true).map_err(|e| e.to_string())?,
-        Methods::PUT => easy.put(true).map_err(|e| e.to_string())?,
-        Methods::HEAD => {
-            easy.nobody(true).map_err(|e| e.to_string())?;
-            easy.custom_request("HEAD").map_err(|e| e.to_string())?;
+/// The first `error` in a JSON-RPC response or batch response.
+fn jsonrpc_error(body: &[u8]) -> Option<JsonRpcError> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let responses = match value {
+        serde_json::Value::Array(responses) => responses,
+        response => vec![response],
+    };
+    responses.into_iter().find_map(|response| {
+        if response.get("jsonrpc")?.as_str()? != "2.0" {
+            return None;
         }
-        _ => {
-            easy.custom_request(method_to_curl_string(&req.method))
-                .map_err(|e| e.to_string())?;
+        let error = response.get("error")?;
+        Some(JsonRpcError {
+            code: error.get("code")?.as_i64()?,
+            message: error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            data: error.get("data").map(|data| data.to_string()),
+            id: response.get("id").map(|id| id.to_string()),
+        })
+    })
+}
+
+fn soap_namespace(version: SoapVersion) -> &'static str {
+    match version {
+        SoapVersion::Soap1_1 => "http://schemas.xmlsoap.org/soap/envelope/",
+        SoapVersion::Soap1_2 => "http://www.w3.org/2003/05/soap-envelope",
+    }
+}
+
+/// `envelope_body` wrapped in a SOAP envelope, after checking it is
+/// well-formed. The body may use the `soap:` prefix of the envelope.
+pub(crate) fn soap_envelope(envelope_body: &str, version: SoapVersion) -> Result<String, String> {
+    let namespace = soap_namespace(version);
+    // Wrapped on the first line so error positions still match the body's lines.
+    let wrapped = format!(
+        "<body xmlns:soap=\"{}\">{}</body>",
+        namespace, envelope_body
+    );
+    roxmltree::Document::parse(&wrapped)
+        .map_err(|e| format!("SOAP body is not well-formed XML: {}", e))?;
+    Ok(format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <soap:Envelope xmlns:soap=\"{}\">\n\
+         <soap:Header/>\n\
+         <soap:Body>\n{}\n</soap:Body>\n\
+         </soap:Envelope>\n",
+        namespace,
+        envelope_body.trim()
+    ))
+}
+
+/// The Content-Type of a SOAP request and, for 1.1, its `SOAPAction` value.
+/// 1.1 carries the action in its own header, 1.2 in the media type.
+pub(crate) fn soap_headers(action: &str, version: SoapVersion) -> (String, Option<String>) {
+    match version {
+        SoapVersion::Soap1_1 => (
+            "text/xml; charset=utf-8".to_string(),
+            Some(format!("\"{}\"", action)),
+        ),
+        SoapVersion::Soap1_2 if action.is_empty() => {
+            ("application/soap+xml; charset=utf-8".to_string(), None)
         }
+        SoapVersion::Soap1_2 => (
+            format!("application/soap+xml; charset=utf-8; action=\"{}\"", action),
+            None,
+        ),
     }
+}
 
-    // Always use HTTP/2 over TCP (QUIC removed)
-    easy.http_version(HttpVersion::V2TLS)
-        .map_err(|e| e.to_string())?;
+/// Whether `body` is a SOAP 1.1 or 1.2 envelope carrying a Fault.
+fn is_soap_fault(body: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(body) else {
+        return false;
+    };
+    let Ok(document) = roxmltree::Document::parse(text) else {
+        return false;
+    };
+    let envelope = document.root_element();
+    let namespace = envelope.tag_name().namespace();
+    let is_soap = [SoapVersion::Soap1_1, SoapVersion::Soap1_2]
+        .into_iter()
+        .any(|version| namespace == Some(soap_namespace(version)));
+    if !is_soap || envelope.tag_name().name() != "Envelope" {
+        return false;
+    }
+    envelope
+        .children()
+        .filter(|node| node.tag_name().name() == "Body" && node.tag_name().namespace() == namespace)
+        .flat_map(|body| body.children())
+        .any(|node| node.tag_name().name() == "Fault" && node.tag_name().namespace() == namespace)
+}
 
-    if let Some(timeout) = req.timeout_ms {
-        easy.timeout(Duration::from_millis(timeout as u64))
-            .map_err(|e| e.to_string())?;
+/// A request payload: built in memory, streamed from disk for `BodyType::RawFile`,
+/// or a mix of both for multipart bodies with `MultipartValue::FilePath` fields.
+enum RequestBody {
+    Bytes(Vec<u8>),
+    File { path: PathBuf, len: u64 },
+    Parts(Vec<RequestBody>),
+}
+
+impl RequestBody {
+    fn len(&self) -> u64 {
+        match self {
+            RequestBody::Bytes(data) => data.len() as u64,
+            RequestBody::File { len, .. } => *len,
+            RequestBody::Parts(parts) => parts.iter().map(RequestBody::len).sum(),
+        }
     }
 
-    let follow = req.follow_redirects.unwrap_or(true);
-    easy.follow_location(follow).map_err(|e| e.to_string())?;
-    if follow {
-        let max = req.max_redirects.unwrap_or(10);
-        easy.max_redirections(max).map_err(|e| e.to_string())?;
+    /// The payload as `preview_request` shows it: in-memory bytes as they'll be
+    /// sent, with a `<file: path, N bytes>` placeholder for each streamed file.
+    fn preview(&self) -> Vec<u8> {
+        match self {
+            RequestBody::Bytes(data) => data.clone(),
+            RequestBody::File { path, len } => {
+                format!("<file: {}, {} bytes>", path.display(), len).into_bytes()
+            }
+            RequestBody::Parts(parts) => parts.iter().flat_map(RequestBody::preview).collect(),
+        }
     }
 
-    let verify = req.verify_ssl.unwrap_or(true);
-    easy.ssl_verify_peer(verify).map_err(|e| e.to_string())?;
-    easy.ssl_verify_host(verify).map_err(|e| e.to_string())?;
+    /// A fresh reader over the payload; called once per transfer so redirects can resend it.
+    fn reader(&self) -> std::io::Result<Box<dyn Read + '_>> {
+        match self {
+            RequestBody::Bytes(data) => Ok(Box::new(std::io::Cursor::new(data.as_slice()))),
+            RequestBody::File { path, len } => Ok(Box::new(FileBodyReader {
+                file: File::open(path)?,
+                path,
+                remaining: *len,
+            })),
+            RequestBody::Parts(parts) => {
+                let mut chained: Box<dyn Read + '_> = Box::new(std::io::empty());
+                for part in parts {
+                    chained = Box::new(chained.chain(part.reader()?));
+                }
+                Ok(chained)
+            }
+        }
+    }
+}
 
-    if let Some(ref proxy) = req.proxy {
-        easy.proxy(&proxy.url).map_err(|e| e.to_string())?;
-        if let (Some(user), Some(pass)) = (&proxy.username, &proxy.password) {
-            easy.proxy_username(user).map_err(|e| e.to_string())?;
-            easy.proxy_password(pass).map_err(|e| e.to_string())?;
+/// Streams exactly the `len` measured when the request was prepared, since that
+/// size has already gone out as Content-Length. A file that grows or shrinks
+/// mid-upload is reported instead of silently sending a corrupt body.
+struct FileBodyReader<'a> {
+    file: File,
+    path: &'a Path,
+    remaining: u64,
+}
+
+impl Read for FileBodyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let changed = || {
+            std::io::Error::other(format!(
+                "'{}' changed size during upload",
+                self.path.display()
+            ))
+        };
+        if self.remaining == 0 {
+            let mut probe = [0u8; 1];
+            return match self.file.read(&mut probe)? {
+                0 => Ok(0),
+                _ => Err(changed()),
+            };
         }
+        let cap = self.remaining.min(buf.len() as u64) as usize;
+        let read = self.file.read(&mut buf[..cap])?;
+        if read == 0 {
+            return Err(changed());
+        }
+        self.remaining -= read as u64;
+        Ok(read)
     }
+}
 
-    let mut header_list = List::new();
+/// Content-Type for a file upload, guessed from its extension.
+pub(crate) fn mime_from_extension(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "yaml" | "yml" => "application/yaml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "wasm" => "application/wasm",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => "application/octet-stream",
+    }
+}
 
-    for (key, val) in &req.headers {
-        header_list
-            .append(&format!("{}: {}", key, val))
-            .map_err(|e| e.to_string())?;
+/// A body streamed from `path`, sized from its metadata. Fails before anything is sent
+/// when the file is missing or not a regular file.
+fn file_body(path: &str) -> Result<RequestBody, String> {
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| format!("Cannot read request body file '{}': {}", path, e))?;
+    if !metadata.is_file() {
+        return Err(format!("Request body path '{}' is not a file", path));
+    }
+    Ok(RequestBody::File {
+        path: PathBuf::from(path),
+        len: metadata.len(),
+    })
+}
+
+/// Every filesystem path the body will read from.
+fn body_file_paths(body: &BodyType) -> Vec<&str> {
+    match body {
+        BodyType::RawFile { path, .. } | BodyType::BinaryFile { path, .. } => {
+            vec![path.as_str()]
+        }
+        BodyType::Multipart { fields } => fields
+            .iter()
+            .filter_map(|field| match &field.value {
+                crate::types::MultipartValue::FilePath { path, .. } => Some(path.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
     }
+}
+
+/// The `(name, value)` of an API key sent as a cookie.
+fn api_key_cookie(auth: &AuthType) -> Option<(&str, &str)> {
+    match auth {
+        AuthType::ApiKey {
+            key,
+            value,
+            add_to: ApiKeyLocation::Cookie,
+        } => Some((key.as_str(), value.as_str())),
+        _ => None,
+    }
+}
+
+fn prepare_request(req: &ApiRequest) -> Result<PreparedRequest, String> {
+    if let Methods::Custom(ref verb) = req.method {
+        validate_method_token(verb)?;
+    }
+
+    let api_key_query = match &req.auth {
+        AuthType::ApiKey {
+            key,
+            value,
+            add_to: ApiKeyLocation::Query,
+        } => Some((key.as_str(), value.as_str())),
+        _ => None,
+    };
+
+    let url = build_url_with_params(&req.url, &req.query_params, api_key_query)?;
+
+    let mut headers: Vec<(String, String)> = req.headers.clone();
 
     match &req.auth {
         AuthType::Basic { username, password } => {
-            easy.username(username).map_err(|e| e.to_string())?;
-            easy.password(password).map_err(|e| e.to_string())?;
+            let credentials = BASE64.encode(format!("{}:{}", username, password));
+            headers.push((
+                "Authorization".to_string(),
+                format!("Basic {}", credentials),
+            ));
         }
         AuthType::Bearer { token } => {
-            header_list
-                .append(&format!("Authorization: Bearer {}", token))
-                .map_err(|e| e.to_string())?;
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+        AuthType::Jwt {
+            algorithm,
+            secret_or_key_pem,
+            claims_json,
+            header_overrides,
+            expires_in_secs,
+        } => {
+            let token = sign_jwt(
+                algorithm,
+                secret_or_key_pem,
+                claims_json,
+                header_overrides.as_deref(),
+                *expires_in_secs,
+            )?;
+            headers.push(("Authorization".to_string(), format!("Bearer {}", token)));
+        }
+        AuthType::OAuth2 { access_token } => {
+            headers.push((
+                "Authorization".to_string(),
+                format!("Bearer {}", access_token),
+            ));
         }
         AuthType::ApiKey {
             key,
             value,
             add_to: ApiKeyLocation::Header,
         } => {
-            header_list
-                .append(&format!("{}: {}", key, value))
-                .map_err(|e| e.to_string())?;
+            headers.push((key.clone(), value.clone()));
         }
-        AuthType::None | AuthType::ApiKey { .. } => {}
+        // Query keys went into the URL above; cookie keys join the Cookie header below.
+        AuthType::ApiKey {
+            add_to: ApiKeyLocation::Query | ApiKeyLocation::Cookie,
+            ..
+        } => {}
+        // Negotiated by libcurl during the transfer, see `apply_negotiated_auth`.
+        AuthType::None | AuthType::Ntlm { .. } | AuthType::Negotiate { .. } => {}
     }
 
-    if !req.cookies.is_empty() {
-        let cookie_str = build_cookie_header(&req.cookies);
-        header_list
-            .append(&format!("Cookie: {}", cookie_str))
-            .map_err(|e| e.to_string())?;
+    let parsed_url = Url::parse(&url).ok();
+    let mut cookies: Vec<(String, String)> = match (&req.session_id, &parsed_url) {
+        (Some(session_id), Some(parsed)) => {
+            let jar = session_jar(session_id);
+            jar.matching(parsed)
+                .map(|c| (c.name.clone(), c.value.clone()))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+    let mut skipped_cookies: Vec<String> = Vec::new();
+    for cookie in &req.cookies {
+        // Explicit cookies are scoped like stored ones so a shared cookie list
+        // can't leak credentials to other hosts.
+        let mismatch = parsed_url
+            .as_ref()
+            .and_then(|parsed| cookie_mismatch(cookie, None, parsed));
+        if let Some(reason) = mismatch {
+            skipped_cookies.push(format!("Cookie '{}' not sent: {}", cookie.name, reason));
+            continue;
+        }
+        cookies.retain(|(name, _)| *name != cookie.name);
+        cookies.push((cookie.name.clone(), cookie.value.clone()));
+    }
+    if let Some((key, value)) = api_key_cookie(&req.auth) {
+        cookies.retain(|(name, _)| name != key);
+        cookies.push((key.to_string(), value.to_string()));
+    }
+    if !cookies.is_empty() {
+        headers.push(("Cookie".to_string(), build_cookie_header(&cookies)?));
     }
 
-    let mut request_body_size: u32 = 0;
-    let post_data: Option<Vec<u8>> = match &req.body {
+    let mut body_size: u32 = 0;
+    let mut body: Option<RequestBody> = match &req.body {
         BodyType::None => None,
         BodyType::Raw { content, content_type } => {
             if let Some(ct) = content_type {
-                header_list
-                    .append(&format!("Content-Type: {}", ct))
-                    .map_err(|e| e.to_string())?;
+                headers.push(("Content-Type".to_string(), ct.clone()));
+            }
+            body_size = content.len() as u32;
+            Some(RequestBody::Bytes(content.as_bytes().to_vec()))
+        }
+        BodyType::GraphQL {
+            query,
+            variables,
+            operation_name,
+        } => {
+            let envelope =
+                graphql_envelope(query, variables.as_deref(), operation_name.as_deref())?;
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            }
+            body_size = envelope.len() as u32;
+            Some(RequestBody::Bytes(envelope.into_bytes()))
+        }
+        BodyType::Soap {
+            action,
+            envelope_body,
+            soap_version,
+        } => {
+            let envelope = soap_envelope(envelope_body, *soap_version)?;
+            let has_header = |wanted: &str| {
+                headers
+                    .iter()
+                    .any(|(name, _)| name.eq_ignore_ascii_case(wanted))
+            };
+            let (has_soap_action, has_content_type) =
+                (has_header("soapaction"), has_header("content-type"));
+            let (content_type, soap_action) = soap_headers(action, *soap_version);
+            if let Some(soap_action) = soap_action.filter(|_| !has_soap_action) {
+                headers.push(("SOAPAction".to_string(), soap_action));
             }
-            request_body_size = content.len() as u32;
-            Some(content.as_bytes().to_vec())
+            if !has_content_type {
+                headers.push(("Content-Type".to_string(), content_type));
+            }
+            body_size = envelope.len() as u32;
+            Some(RequestBody::Bytes(envelope.into_bytes()))
+        }
+        BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } => {
+            let envelope = jsonrpc_body(&req.body)?;
+            let envelope = serde_json::to_string(&envelope).map_err(|e| e.to_string())?;
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            }
+            body_size = envelope.len() as u32;
+            Some(RequestBody::Bytes(envelope.into_bytes()))
         }
         BodyType::FormUrlEncoded { fields } => {
             let encoded: String = fields
@@ -318,30 +1970,32 @@ fn execute_curl_request(
                 .map(|(k, v)| format!("{}={}", urlencoding(k), urlencoding(v)))
                 .collect::<Vec<_>>()
                 .join("&");
-            header_list
-                .append("Content-Type: application/x-www-form-urlencoded")
-                .map_err(|e| e.to_string())?;
-            request_body_size = encoded.len() as u32;
-            Some(encoded.into_bytes())
+            headers.push((
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            ));
+            body_size = encoded.len() as u32;
+            Some(RequestBody::Bytes(encoded.into_bytes()))
         }
         BodyType::Multipart { fields } => {
-
             let boundary = format!("----WebKitFormBoundary{}", uuid_simple());
-            let mut body = Vec::new();
+            let mut multipart = Vec::new();
+            // Files referenced by path are spliced in between the in-memory chunks.
+            let mut parts: Vec<RequestBody> = Vec::new();
 
             for field in fields {
-                body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+                multipart.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
                 match &field.value {
                     crate::types::MultipartValue::Text(text) => {
-                        body.extend_from_slice(
+                        multipart.extend_from_slice(
                             format!(
                                 "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
                                 field.name
                             )
                             .as_bytes(),
                         );
-                        body.extend_from_slice(text.as_bytes());
-                        body.extend_from_slice(b"\r\n");
+                        multipart.extend_from_slice(text.as_bytes());
+                        multipart.extend_from_slice(b"\r\n");
                     }
                     crate::types::MultipartValue::File {
                         data,
@@ -352,110 +2006,695 @@ fn execute_curl_request(
                             .as_ref()
                             .map(|s| s.as_str())
                             .unwrap_or("application/octet-stream");
-                        body.extend_from_slice(
+                        multipart.extend_from_slice(
                             format!(
                                 "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
                                 field.name, filename
                             )
                             .as_bytes(),
                         );
-                        body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", ct).as_bytes());
-                        body.extend_from_slice(data);
-                        body.extend_from_slice(b"\r\n");
+                        multipart
+                            .extend_from_slice(format!("Content-Type: {}\r\n\r\n", ct).as_bytes());
+                        multipart.extend_from_slice(data);
+                        multipart.extend_from_slice(b"\r\n");
+                    }
+                    crate::types::MultipartValue::FilePath {
+                        path,
+                        filename,
+                        content_type,
+                    } => {
+                        let file = file_body(path)?;
+                        let filename = filename.clone().unwrap_or_else(|| {
+                            Path::new(path)
+                                .file_name()
+                                .map(|n| n.to_string_lossy().into_owned())
+                                .unwrap_or_default()
+                        });
+                        let ct = content_type
+                            .as_deref()
+                            .unwrap_or("application/octet-stream");
+                        multipart.extend_from_slice(
+                            format!(
+                                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                                field.name, filename
+                            )
+                            .as_bytes(),
+                        );
+                        multipart
+                            .extend_from_slice(format!("Content-Type: {}\r\n\r\n", ct).as_bytes());
+                        parts.push(RequestBody::Bytes(std::mem::take(&mut multipart)));
+                        parts.push(file);
+                        multipart.extend_from_slice(b"\r\n");
                     }
                 }
             }
-            body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
-
-            header_list
-                .append(&format!(
-                    "Content-Type: multipart/form-data; boundary={}",
-                    boundary
-                ))
-                .map_err(|e| e.to_string())?;
-            request_body_size = body.len() as u32;
+            multipart.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+
+            headers.push((
+                "Content-Type".to_string(),
+                format!("multipart/form-data; boundary={}", boundary),
+            ));
+            let body = if parts.is_empty() {
+                RequestBody::Bytes(multipart)
+            } else {
+                parts.push(RequestBody::Bytes(multipart));
+                RequestBody::Parts(parts)
+            };
+            body_size = body.len().min(u32::MAX as u64) as u32;
             Some(body)
         }
         BodyType::Binary { data, .. } => {
-            header_list
-                .append("Content-Type: application/octet-stream")
-                .map_err(|e| e.to_string())?;
-            request_body_size = data.len() as u32;
-            Some(data.clone())
+            headers.push((
+                "Content-Type".to_string(),
+                "application/octet-stream".to_string(),
+            ));
+            body_size = data.len() as u32;
+            Some(RequestBody::Bytes(data.clone()))
+        }
+        BodyType::RawFile { path, content_type } => {
+            let body = file_body(path)?;
+            let ct = content_type
+                .as_deref()
+                .unwrap_or("application/octet-stream");
+            headers.push(("Content-Type".to_string(), ct.to_string()));
+            body_size = body.len().min(u32::MAX as u64) as u32;
+            Some(body)
+        }
+        BodyType::BinaryFile { path, content_type } => {
+            let body = file_body(path)?;
+            let ct = content_type
+                .clone()
+                .unwrap_or_else(|| mime_from_extension(Path::new(path)).to_string());
+            headers.push(("Content-Type".to_string(), ct));
+            body_size = body.len().min(u32::MAX as u64) as u32;
+            Some(body)
         }
     };
 
-    easy.http_headers(header_list)
+    let mut uncompressed_body_bytes: Option<u32> = None;
+    let compression = req.compress_body.clone().unwrap_or_default();
+    if let Some(RequestBody::File { .. } | RequestBody::Parts(_)) = body {
+        if compression != BodyCompression::None {
+            return Err("compress_body is not supported for file bodies".to_string());
+        }
+    }
+    if let Some(RequestBody::Bytes(data)) = body.as_mut().filter(|b| b.len() > 0) {
+        let encoding = match compression {
+            BodyCompression::None => None,
+            BodyCompression::Gzip => Some("gzip"),
+            BodyCompression::Deflate => Some("deflate"),
+        };
+        if let Some(encoding) = encoding {
+            *data = compress_body(data, &compression)?;
+            headers.push(("Content-Encoding".to_string(), encoding.to_string()));
+            uncompressed_body_bytes = Some(body_size);
+            body_size = data.len() as u32;
+        }
+    }
+
+    // An empty value tells libcurl to drop the header it would otherwise add.
+    if body.is_some() && req.disable_expect_continue.unwrap_or(false) {
+        headers.push(("Expect".to_string(), String::new()));
+    }
+
+    Ok(PreparedRequest {
+        method: method_to_curl_string(&req.method).to_string(),
+        url,
+        headers,
+        body,
+        body_size,
+        uncompressed_body_bytes,
+        skipped_cookies,
+        pinned_public_keys: normalize_public_key_pins(req.pinned_public_key.as_deref())?,
+    })
+}
+
+/// Only files the user picked (or the app otherwise allowed) may be read or written.
+fn ensure_paths_allowed(app: &AppHandle, req: &ApiRequest) -> Result<(), RequestError> {
+    let scoped_paths = body_file_paths(&req.body)
+        .into_iter()
+        .chain(req.download_to.as_deref())
+        .chain(req.client_cert.iter().flat_map(client_cert_paths))
+        .chain(req.ca_cert_path.as_deref())
+        .chain(req.netrc_file.as_deref());
+    for path in scoped_paths {
+        let allowed = app
+            .try_fs_scope()
+            .is_some_and(|scope| scope.is_allowed(path));
+        if !allowed {
+            return Err(RequestError::Io {
+                detail: format!("Access to '{}' is not allowed", path),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// One `Name: value` line per header, in order; repeated names are sent as-is.
+fn header_list(headers: &[(String, String)]) -> Result<List, String> {
+    let mut list = List::new();
+    for (name, value) in headers {
+        list.append(&format!("{}: {}", name, value))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(list)
+}
+
+fn apply_method(easy: &mut Easy, method: &Methods, verb: &str) -> Result<(), String> {
+    match method {
+        Methods::GET => easy.get(true).map_err(|e| e.to_string())?,
+        Methods::POST => easy.post(true).map_err(|e| e.to_string())?,
+        Methods::PUT => easy.put(true).map_err(|e| e.to_string())?,
+        Methods::HEAD => {
+            easy.nobody(true).map_err(|e| e.to_string())?;
+            easy.custom_request("HEAD").map_err(|e| e.to_string())?;
+        }
+        _ => {
+            easy.custom_request(verb).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// TLS, proxy, client identity and credential options shared by every
+/// transfer of `req`. Returns the proxy used (redacted) and any netrc login.
+fn apply_connection_settings(
+    app: &AppHandle,
+    easy: &mut Easy,
+    req: &ApiRequest,
+    prepared: &PreparedRequest,
+    proxy_rule: Option<&ProxyRule>,
+) -> Result<(Option<String>, Option<String>), RequestError> {
+    let verify = req.verify_ssl.unwrap_or(true);
+    easy.ssl_verify_peer(verify).map_err(|e| e.to_string())?;
+    easy.ssl_verify_host(verify).map_err(|e| e.to_string())?;
+
+    apply_transport_options(easy, req)?;
+    let proxy_used = match req.proxy {
+        Some(_) => explicit_proxy_for(req, &prepared.url).map(|proxy| proxy.url.clone()),
+        None => apply_system_proxy(easy, req)?,
+    }
+    .map(|proxy| redact_proxy(&proxy));
+    if proxy_rule.is_some_and(|rule| matches!(rule.target, ProxyRuleTarget::Direct)) {
+        // An empty proxy also stops libcurl from reading the proxy variables itself.
+        easy.proxy("").map_err(|e| e.to_string())?;
+    }
+    if let Some(ref cert) = req.client_cert {
+        apply_client_cert(easy, cert)?;
+    }
+    apply_ca_bundle(easy, req)?;
+    apply_negotiated_auth(easy, &req.auth, true)?;
+    let netrc_login = apply_netrc(app, easy, req, prepared)?;
+    if !prepared.pinned_public_keys.is_empty() {
+        easy.pinned_public_key(&prepared.pinned_public_keys.join(";"))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok((proxy_used, netrc_login))
+}
+
+/// Sets `easy` up to run `req` as one open-ended transfer, for streaming
+/// responses like `sse_connect`. libcurl follows redirects itself, there is
+/// no overall timeout, and only in-memory bodies can be sent.
+pub(crate) fn configure_streaming_request(
+    app: &AppHandle,
+    easy: &mut Easy,
+    req: ApiRequest,
+) -> Result<(), RequestError> {
+    ensure_paths_allowed(app, &req)?;
+    let (req, proxy_rule) = with_proxy_rule(app, req);
+    let prepared = prepare_request(&req)?;
+    easy.url(&prepared.url)
+        .map_err(|e| RequestError::InvalidUrl {
+            reason: e.to_string(),
+        })?;
+    match prepared.body {
+        None => {}
+        Some(RequestBody::Bytes(ref data)) => {
+            easy.post_fields_copy(data).map_err(|e| e.to_string())?;
+        }
+        Some(_) => {
+            return Err(RequestError::Other {
+                detail: "File and multipart bodies can't be sent on a stream".to_string(),
+            })
+        }
+    }
+    apply_method(easy, &req.method, &prepared.method)?;
+    easy.http_version(curl_http_version(req.protocol.as_ref())?)
+        .map_err(|e| e.to_string())?;
+    easy.accept_encoding(req.accept_encoding.as_deref().unwrap_or(""))
+        .map_err(|e| e.to_string())?;
+    easy.follow_location(req.follow_redirects.unwrap_or(true))
+        .map_err(|e| e.to_string())?;
+    easy.max_redirections(req.max_redirects.unwrap_or(10))
+        .map_err(|e| e.to_string())?;
+    apply_connection_settings(app, easy, &req, &prepared, proxy_rule.as_ref())?;
+
+    easy.http_headers(header_list(&prepared.headers)?)
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub(crate) fn execute_curl_request(
+    app: &AppHandle,
+    req: ApiRequest,
+    cancel: Option<Arc<AtomicBool>>,
+) -> Result<ApiResponse, RequestError> {
+    let mut easy = Easy::new();
+
+    ensure_paths_allowed(app, &req)?;
+    Url::parse(&req.url).map_err(|e| RequestError::InvalidUrl {
+        reason: e.to_string(),
+    })?;
+    let (req, proxy_rule) = with_proxy_rule(app, req);
+    let prepared = prepare_request(&req)?;
+    let url = prepared.url.clone();
+    let original_origin = Url::parse(&url).ok().map(|u| u.origin());
+
+    apply_method(&mut easy, &req.method, &prepared.method)?;
+    easy.http_version(curl_http_version(req.protocol.as_ref())?)
         .map_err(|e| e.to_string())?;
 
-    if let Some(ref data) = post_data {
-        easy.post_field_size(data.len() as u64)
+    // An empty string lets libcurl advertise (and transparently decode) all it supports.
+    easy.accept_encoding(req.accept_encoding.as_deref().unwrap_or(""))
+        .map_err(|e| e.to_string())?;
+    if req.disable_decompression.unwrap_or(false) {
+        easy.http_content_decoding(false)
             .map_err(|e| e.to_string())?;
     }
 
-    if let Some(flag) = cancel.as_ref() {
+    // The timeout covers the whole redirect chain, so it is re-applied per hop below.
+    let deadline = req
+        .timeout_ms
+        .map(|timeout| Instant::now() + Duration::from_millis(timeout as u64));
+
+    // Redirects are followed by hand so each hop's headers and timing can be reported.
+    easy.follow_location(false).map_err(|e| e.to_string())?;
+    let follow = req.follow_redirects.unwrap_or(true);
+    let max_redirects = req.max_redirects.unwrap_or(10);
+
+    let (proxy_used, netrc_login) =
+        apply_connection_settings(app, &mut easy, &req, &prepared, proxy_rule.as_ref())?;
+
+    let post_data = prepared.body;
+    let request_body_size = prepared.body_size;
+    let uncompressed_body_bytes = prepared.uncompressed_body_bytes;
+    let skipped_cookies = prepared.skipped_cookies;
+
+    if let (Some(flag), Some(request_id)) = (cancel.as_ref(), active_request_id(&req)) {
         easy.progress(true).map_err(|e| e.to_string())?;
         let flag = Arc::clone(flag);
-        easy.progress_function(move |_, _, _, _| !flag.load(Ordering::SeqCst))
-            .map_err(|e| e.to_string())?;
+        let app = app.clone();
+        let started = Instant::now();
+        let mut last_emit: Option<Instant> = None;
+        easy.progress_function(move |dl_total, dl_now, _, _| {
+            if flag.load(Ordering::SeqCst) {
+                return false;
+            }
+            let due = last_emit.is_none_or(|t| t.elapsed() >= PROGRESS_EMIT_INTERVAL);
+            if dl_now > 0.0 && due {
+                last_emit = Some(Instant::now());
+                let _ = app.emit(
+                    REQUEST_PROGRESS_EVENT,
+                    RequestProgressEvent {
+                        request_id: request_id.clone(),
+                        downloaded_bytes: dl_now,
+                        total_bytes: (dl_total > 0.0).then_some(dl_total),
+                        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+                    },
+                );
+            }
+            true
+        })
+        .map_err(|e| e.to_string())?;
     }
 
-    let mut response_headers_raw: Vec<u8> = Vec::new();
-    let mut response_body: Vec<u8> = Vec::new();
+    let is_cancelled = || cancel.as_ref().is_some_and(|f| f.load(Ordering::SeqCst));
+    if is_cancelled() {
+        return Err(RequestError::Cancelled);
+    }
 
-    {
-        let mut transfer = easy.transfer();
+    let max_response_bytes = req.max_response_bytes.map(u64::from);
+    let upload_request_id = active_request_id(&req);
+    let capture_verbose = req.capture_verbose.unwrap_or(false);
+    let mut verbose_log = VerboseLog::default();
+    // Always on: the TLS session details only show up in libcurl's debug output.
+    let mut tls_handshake = TlsHandshake::default();
+    easy.verbose(true).map_err(|e| e.to_string())?;
+    easy.certinfo(true).map_err(|e| e.to_string())?;
+
+    let mut redirects: Vec<RedirectEntry> = Vec::new();
+    let mut redirect_time = 0.0;
+    // When the final hop's proxy tunnel came up, in ms since its perform began.
+    let mut tunnel_ms: Option<f64>;
+    let mut response_cookies: Vec<Cookie> = Vec::new();
+    // Seeded from the session so cross-origin hops get that origin's cookies too;
+    // explicit cookies are only sent to the original origin, so they win there.
+    let mut cookie_jar = match req.session_id {
+        Some(ref session_id) => {
+            let explicit: Vec<&str> = req
+                .cookies
+                .iter()
+                .map(|c| c.name.as_str())
+                .chain(api_key_cookie(&req.auth).map(|(key, _)| key))
+                .collect();
+            session_jar(session_id).without_names(&explicit)
+        }
+        None => CookieJar::default(),
+    };
+    let mut connects: u32 = 0;
+    let mut saw_continue = false;
+    let mut offered_schemes: Vec<String> = Vec::new();
+    let mut transfer_error: Option<String> = None;
+    let mut hop_url = url.clone();
+    let mut hop_method = prepared.method.clone();
+    let mut hop_headers = prepared.headers.clone();
+    let mut hop_body = post_data.as_ref();
+
+    let (mut response_body, response_headers_list, http_version, status, truncated) = loop {
+        let hop_started = Instant::now();
+        easy.url(&hop_url).map_err(|e| RequestError::InvalidUrl {
+            reason: e.to_string(),
+        })?;
+        if req.proxy.is_some() {
+            let proxy = explicit_proxy_for(&req, &hop_url).map_or("", |proxy| &proxy.url);
+            easy.proxy(proxy).map_err(|e| e.to_string())?;
+        }
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            easy.timeout(remaining.max(Duration::from_millis(1)))
+                .map_err(|e| e.to_string())?;
+        }
 
-        transfer
-            .header_function(|header| {
-                response_headers_raw.extend_from_slice(header);
-                true
-            })
+        easy.http_headers(header_list(&hop_headers)?)
             .map_err(|e| e.to_string())?;
 
-        transfer
-            .write_function(|data| {
-                response_body.extend_from_slice(data);
-                Ok(data.len())
-            })
-            .map_err(|e| e.to_string())?;
+        if let Some(body) = hop_body {
+            easy.post_field_size(body.len())
+                .map_err(|e| e.to_string())?;
+        }
+        let body_reader = match hop_body {
+            Some(body) => Some(body.reader().map_err(|e| RequestError::Io {
+                detail: format!("Failed to open request body: {e}"),
+            })?),
+            None => None,
+        };
+
+        let mut response_headers_raw: Vec<u8> = Vec::new();
+        let mut response_body = match req.download_to {
+            Some(ref target) => ResponseBodySink::download(PathBuf::from(target)),
+            None => ResponseBodySink::new(
+                req.spill_threshold_bytes
+                    .unwrap_or(DEFAULT_SPILL_THRESHOLD_BYTES),
+            ),
+        };
+        let mut body_write_error: Option<std::io::Error> = None;
+        let upload_error: Cell<Option<std::io::Error>> = Cell::new(None);
+        let mut truncated = false;
+        let response_started = Cell::new(false);
+        let mut tunnel_established: Option<Instant> = None;
+        let perform_started;
+
+        let perform_result = {
+            let mut transfer = easy.transfer();
+
+            transfer
+                .debug_function(|kind, data| {
+                    tls_handshake.observe(kind, data);
+                    if tunnel_established.is_none() && is_tunnel_established(kind, data) {
+                        tunnel_established = Some(Instant::now());
+                    }
+                    if capture_verbose {
+                        verbose_log.record(kind, data);
+                    }
+                })
+                .map_err(|e| e.to_string())?;
+
+            transfer
+                .header_function(|header| {
+                    if is_final_status_line(header) {
+                        response_started.set(true);
+                    }
+                    response_headers_raw.extend_from_slice(header);
+                    true
+                })
+                .map_err(|e| e.to_string())?;
 
-        if let Some(ref data) = post_data {
-            let mut data_reader = std::io::Cursor::new(data.clone());
             transfer
-                .read_function(move |into| {
-                    let read = data_reader.read(into).unwrap_or(0);
-                    Ok(read)
+                .write_function(|data| {
+                    if is_cancelled() {
+                        // Returning a short count makes libcurl abort with a write error.
+                        return Ok(0);
+                    }
+                    let mut chunk = data;
+                    if let Some(max) = max_response_bytes {
+                        let room = max.saturating_sub(response_body.received_bytes()) as usize;
+                        if chunk.len() > room {
+                            chunk = &chunk[..room];
+                            truncated = true;
+                        }
+                    }
+                    if let Err(e) = response_body.write(chunk) {
+                        body_write_error = Some(e);
+                        return Ok(0);
+                    }
+                    if truncated {
+                        // Short count aborts the transfer; everything up to the limit is kept.
+                        return Ok(0);
+                    }
+                    Ok(data.len())
                 })
                 .map_err(|e| e.to_string())?;
+
+            if let Some(mut body_reader) = body_reader {
+                let total_bytes = hop_body.map_or(0, RequestBody::len) as f64;
+                let mut uploaded: u64 = 0;
+                let mut last_emit: Option<Instant> = None;
+                let response_started = &response_started;
+                let upload_error = &upload_error;
+                let upload_request_id = upload_request_id.as_deref();
+                transfer
+                    .read_function(move |into| {
+                        let read = body_reader.read(into).map_err(|e| {
+                            upload_error.set(Some(e));
+                            ReadError::Abort
+                        })?;
+                        uploaded += read as u64;
+                        if let Some(request_id) = upload_request_id {
+                            let uploaded_bytes = uploaded as f64;
+                            let finished = uploaded_bytes >= total_bytes;
+                            let due =
+                                last_emit.is_none_or(|t| t.elapsed() >= PROGRESS_EMIT_INTERVAL);
+                            if read > 0 && !response_started.get() && (due || finished) {
+                                last_emit = Some(Instant::now());
+                                let _ = app.emit(
+                                    UPLOAD_PROGRESS_EVENT,
+                                    UploadProgressEvent {
+                                        request_id: request_id.to_string(),
+                                        uploaded_bytes,
+                                        total_bytes,
+                                    },
+                                );
+                            }
+                        }
+                        Ok(read)
+                    })
+                    .map_err(|e| e.to_string())?;
+            }
+
+            perform_started = Instant::now();
+            transfer.perform()
+        };
+        tunnel_ms =
+            tunnel_established.map(|at| at.duration_since(perform_started).as_secs_f64() * 1000.0);
+
+        let perform_result = if truncated { Ok(()) } else { perform_result };
+        if let Err(e) = perform_result {
+            if is_cancelled() {
+                return Err(RequestError::Cancelled);
+            }
+            if let Some(io_err) = body_write_error {
+                return Err(RequestError::Io {
+                    detail: format!("Failed to buffer response body: {io_err}"),
+                });
+            }
+            if let Some(io_err) = upload_error.take() {
+                return Err(RequestError::Io {
+                    detail: format!("Failed to read request body: {io_err}"),
+                });
+            }
+            if e.is_interface_failed() {
+                let interface = req.local_interface.as_deref().unwrap_or_default();
+                return Err(RequestError::Curl {
+                    code: curl_code(&e),
+                    detail: format!("Could not bind to local interface '{interface}'. {e}"),
+                });
+            }
+            if easy.http_connectcode().unwrap_or(0) == 407 {
+                return Err(RequestError::ProxyAuthFailed {
+                    proxy_authenticate: proxy_challenges(&response_headers_raw),
+                });
+            }
+            let host = Url::parse(&hop_url)
+                .ok()
+                .and_then(|u| u.host_str().map(str::to_string))
+                .unwrap_or_default();
+            let error = classify_curl_error(&mut easy, &e, &host, req.ip_version.as_ref());
+            // Once the response has started, hand back what arrived instead of nothing.
+            if !response_started.get() {
+                return Err(error);
+            }
+            transfer_error = Some(error.to_string());
+        }
+        connects += num_connects(&easy);
+
+        let headers_str = String::from_utf8_lossy(&response_headers_raw);
+        let mut response_headers_list: Vec<(String, String)> = Vec::new();
+        let mut http_version = String::from("HTTP/1.1");
+
+        for line in headers_str.lines() {
+            if line.starts_with("HTTP/") {
+                // A 1xx block can precede the real response; only the last block
+                // describes it, but cookies set along the way are still kept.
+                response_cookies.extend(cookies_from_headers(&response_headers_list));
+                offered_schemes.extend(offered_auth_schemes(&response_headers_list));
+                response_headers_list.clear();
+                let parts: Vec<&str> = line.splitn(3, ' ').collect();
+                if !parts.is_empty() {
+                    http_version = parts[0].to_string();
+                }
+                saw_continue |= parts.get(1) == Some(&"100");
+            } else if let Some((name, value)) = line.split_once(':') {
+                response_headers_list.push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        offered_schemes.extend(offered_auth_schemes(&response_headers_list));
+        let hop_cookies = cookies_from_headers(&response_headers_list);
+        response_cookies.extend(hop_cookies.iter().cloned());
+        let hop_host = Url::parse(&hop_url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string));
+        if let (Some(session_id), Some(host)) = (req.session_id.as_deref(), hop_host.as_deref()) {
+            store_session_cookies(session_id, host, &hop_cookies);
         }
 
-        transfer.perform().map_err(|e| {
-            if cancel
-                .as_ref()
-                .is_some_and(|f| f.load(Ordering::SeqCst))
-            {
-                return "Request cancelled".to_string();
+        let status = easy.response_code().unwrap_or(0) as u16;
+        // Plain-http requests through a proxy get its 407 as the response itself.
+        if status == 407 && proxy_used.is_some() {
+            return Err(RequestError::ProxyAuthFailed {
+                proxy_authenticate: proxy_challenges(&response_headers_raw),
+            });
+        }
+        let location = response_headers_list
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("location"))
+            .map(|(_, value)| value.clone());
+        let next_url = match location {
+            Some(location) if follow && transfer_error.is_none() && is_redirect_status(status) => {
+                resolve_redirect_location(&hop_url, &location)
+            }
+            _ => {
+                break (
+                    response_body,
+                    response_headers_list,
+                    http_version,
+                    status,
+                    truncated,
+                )
             }
-            format_curl_error(&e)
+        };
+        if redirects.len() as u32 >= max_redirects {
+            response_body.discard();
+            return Err(format!("Maximum ({}) redirects followed", max_redirects).into());
+        }
+
+        // Bodies of intermediate hops are not returned.
+        response_body.discard();
+
+        let next = Url::parse(&next_url).map_err(|e| RequestError::InvalidUrl {
+            reason: format!("redirect to {}: {}", next_url, e),
         })?;
-    }
+        if let Some(host) = hop_host {
+            cookie_jar.store(&host, &hop_cookies);
+        }
+
+        let duration_ms = hop_started.elapsed().as_secs_f64() * 1000.0;
+        redirect_time += duration_ms;
+        redirects.push(RedirectEntry {
+            url: std::mem::replace(&mut hop_url, next_url),
+            status,
+            headers: fold_headers(&response_headers_list),
+            set_cookies: hop_cookies,
+            duration_ms,
+        });
+
+        if redirect_rewrites_to_get(status, &hop_method) {
+            easy.get(true).map_err(|e| e.to_string())?;
+            easy.custom_request("GET").map_err(|e| e.to_string())?;
+            hop_method = "GET".to_string();
+            hop_body = None;
+        }
+        let same_origin = original_origin.as_ref() == Some(&next.origin());
+        apply_negotiated_auth(&mut easy, &req.auth, same_origin)?;
+        offered_schemes.clear();
+        hop_headers = redirect_hop_headers(
+            &prepared.headers,
+            &cookie_jar,
+            &next,
+            same_origin,
+            post_data.is_some() && hop_body.is_none(),
+        )?;
+    };
 
-    let total_time = easy.total_time().unwrap_or_default().as_secs_f64() * 1000.0;
-    let namelookup_time = easy.namelookup_time().unwrap_or_default().as_secs_f64() * 1000.0;
+    // A download cut short by a transfer error stays at `.part`.
+    let finished = if transfer_error.is_some() {
+        response_body.finish_incomplete()
+    } else {
+        response_body.finish()
+    };
+    let finished_path = finished
+        .map_err(|e| RequestError::Io {
+            detail: format!("Failed to buffer response body: {e}"),
+        })?
+        .map(|p| p.to_string_lossy().into_owned());
+    let (body_file_path, saved_path) = if response_body.is_download() {
+        (None, finished_path)
+    } else {
+        (finished_path, None)
+    };
+    let response_body_len = response_body.received_bytes() as u32;
+
+    // libcurl's timers describe only the last perform, i.e. the final hop.
+    let final_hop_time = easy.total_time().unwrap_or_default().as_secs_f64() * 1000.0;
+    let total_time = redirect_time + final_hop_time;
+    // No name resolution happens over a Unix socket.
+    let namelookup_time = if req.unix_socket.is_some() {
+        0.0
+    } else {
+        easy.namelookup_time().unwrap_or_default().as_secs_f64() * 1000.0
+    };
     let connect_time = easy.connect_time().unwrap_or_default().as_secs_f64() * 1000.0;
     let appconnect_time = easy.appconnect_time().unwrap_or_default().as_secs_f64() * 1000.0;
     let pretransfer_time = easy.pretransfer_time().unwrap_or_default().as_secs_f64() * 1000.0;
     let starttransfer_time = easy.starttransfer_time().unwrap_or_default().as_secs_f64() * 1000.0;
+    // libcurl stops the connect clock at the proxy. The CONNECT exchange and any
+    // proxy auth round trips still count as connecting, not as the TLS handshake.
+    let connect_time = match tunnel_ms {
+        Some(tunnel) if appconnect_time > connect_time => {
+            tunnel.clamp(connect_time, appconnect_time)
+        }
+        _ => connect_time,
+    };
 
     let timing = TimingInfo {
         total_ms: total_time,
+        redirect_ms: redirect_time,
+        redirect_count: redirects.len() as u32,
         dns_lookup_ms: namelookup_time,
         tcp_handshake_ms: (connect_time - namelookup_time).max(0.0),
         tls_handshake_ms: (appconnect_time - connect_time).max(0.0),
         transfer_start_ms: (pretransfer_time - appconnect_time).max(0.0),
         ttfb_ms: (starttransfer_time - pretransfer_time).max(0.0),
-        content_download_ms: (total_time - starttransfer_time).max(0.0),
+        content_download_ms: (final_hop_time - starttransfer_time).max(0.0),
     };
 
     let request_header_size = easy.request_size().unwrap_or(0) as u32;
@@ -465,46 +2704,62 @@ fn execute_curl_request(
         headers_bytes: request_header_size,
         body_bytes: request_body_size,
         total_bytes: request_header_size + request_body_size,
+        uncompressed_body_bytes,
     };
 
     let response_size = SizeInfo {
         headers_bytes: response_header_size,
-        body_bytes: response_body.len() as u32,
-        total_bytes: response_header_size + response_body.len() as u32,
+        body_bytes: response_body_len,
+        total_bytes: response_header_size + response_body_len,
+        uncompressed_body_bytes: None,
     };
 
-    let headers_str = String::from_utf8_lossy(&response_headers_raw);
-    let mut response_headers: HashMap<String, String> = HashMap::new();
-    let mut response_cookies: Vec<Cookie> = Vec::new();
-    let mut http_version = String::from("HTTP/1.1");
-
-    for line in headers_str.lines() {
-        if line.starts_with("HTTP/") {
+    let response_headers = fold_headers(&response_headers_list);
 
-            let parts: Vec<&str> = line.splitn(3, ' ').collect();
-            if !parts.is_empty() {
-                http_version = parts[0].to_string();
-            }
-        } else if let Some((name, value)) = line.split_once(':') {
-            let name = name.trim().to_string();
-            let value = value.trim().to_string();
+    let mut warnings: Vec<String> = skipped_cookies;
+    if saw_continue {
+        warnings.push(
+            "Server answered Expect: 100-continue; that extra round trip is included in ttfb_ms"
+                .to_string(),
+        );
+    }
 
-            if name.to_lowercase() == "set-cookie" {
-                if let Some(cookie) = parse_set_cookie(&value) {
-                    response_cookies.push(cookie);
-                }
-            }
+    let tls = hop_url
+        .starts_with("https://")
+        .then(|| tls_handshake.into_info(&easy));
 
-            if let Some(existing) = response_headers.get_mut(&name) {
-                existing.push_str(", ");
-                existing.push_str(&value);
-            } else {
-                response_headers.insert(name, value);
+    let (auth_scheme, mutual_auth) = match negotiated_scheme(&req.auth) {
+        Some(scheme) => {
+            let offered = offered_schemes
+                .iter()
+                .any(|offered| offered.eq_ignore_ascii_case(scheme));
+            if !offered && !offered_schemes.is_empty() {
+                offered_schemes.dedup();
+                warnings.push(format!(
+                    "Server did not offer {} (offered: {})",
+                    scheme,
+                    offered_schemes.join(", ")
+                ));
             }
+            // libcurl fails the transfer if a returned Negotiate token doesn't verify,
+            // so a token on the final response means the server proved itself.
+            let mutual_auth = matches!(req.auth, AuthType::Negotiate { .. }).then(|| {
+                response_headers_list.iter().any(|(name, value)| {
+                    name.eq_ignore_ascii_case("www-authenticate")
+                        && value.split_once(' ').is_some_and(|(s, token)| {
+                            s.eq_ignore_ascii_case(scheme) && !token.trim().is_empty()
+                        })
+                })
+            });
+            let accepted = offered && status != 401;
+            (
+                accepted.then(|| scheme.to_string()),
+                mutual_auth.filter(|_| accepted),
+            )
         }
-    }
+        None => (None, None),
+    };
 
-    let status = easy.response_code().unwrap_or(0) as u16;
     let status_text_str = status_text(status);
 
     let content_type = response_headers
@@ -512,36 +2767,105 @@ fn execute_curl_request(
         .or_else(|| response_headers.get("Content-Type"))
         .cloned();
 
-    let available_renderers = detect_renderers(content_type.as_deref(), &response_body);
+    let available_renderers = detect_renderers(content_type.as_deref(), response_body.bytes());
+    let bom = detect_bom(response_body.bytes());
+
+    // Spilled and downloaded bodies are never rendered inline, so only decode in-memory ones.
+    let (detected_charset, body_text_base64) =
+        if response_body.is_spilled() || response_body.is_download() {
+            (None, None)
+        } else {
+            let charset = content_type.as_deref().and_then(charset_from_content_type);
+            decode_body_text(response_body.bytes(), charset.as_deref(), &mut warnings)
+        };
+
+    let in_memory = !response_body.is_spilled() && !response_body.is_download();
+    let soap_fault = matches!(req.body, BodyType::Soap { .. })
+        && in_memory
+        && is_soap_fault(response_body.bytes());
+    let jsonrpc_error = match req.body {
+        BodyType::JsonRpc { .. } | BodyType::JsonRpcBatch { .. } if in_memory => {
+            jsonrpc_error(response_body.bytes())
+        }
+        _ => None,
+    };
+
+    let remote_addr = match req.unix_socket {
+        Some(ref socket) => Some(format!("unix:{}", socket)),
+        None => easy
+            .primary_ip()
+            .ok()
+            .and_then(|opt| opt.map(|s| s.to_string())),
+    };
+    let local_port = easy.local_port().unwrap_or(0);
+    let local_addr = easy.local_ip().ok().flatten().map(|ip| match local_port {
+        0 => ip.to_string(),
+        port => format!("{}:{}", ip, port),
+    });
+    let connection = ConnectionInfo {
+        reused: num_connects(&easy) == 0,
+        num_connects: connects,
+        local_port: (local_port != 0).then_some(local_port),
+    };
 
-    let remote_addr = easy.primary_ip().ok().and_then(|opt| opt.map(|s| s.to_string()));
+    let protocol_used = protocol_label(&http_version);
+    let negotiated_h3 = http_version.starts_with("HTTP/3");
 
-    let protocol_used = if http_version.contains("3") {
-        "HTTP/3".to_string()
-    } else if http_version.contains("2") {
-        "HTTP/2".to_string()
+    let (body_base64, body_preview_base64) = if response_body.is_download() {
+        (String::new(), None)
+    } else if response_body.is_spilled() {
+        (String::new(), Some(BASE64.encode(response_body.bytes())))
     } else {
-        http_version.clone()
+        (BASE64.encode(response_body.bytes()), None)
     };
 
-    let body_base64 = BASE64.encode(&response_body);
-
     Ok(ApiResponse {
         status,
         status_text: status_text_str,
         headers: response_headers,
+        headers_list: response_headers_list,
         cookies: response_cookies,
         body_base64,
         timing,
         request_size,
         response_size,
-        redirects: Vec::new(), // TODO: Track redirects if needed
+        redirects,
+        attempts: 1,
+        attempt_statuses: vec![Some(status)],
         remote_addr,
+        proxy_used: match proxy_rule {
+            Some(rule) => Some(format!(
+                "{} (rule {})",
+                proxy_used.as_deref().unwrap_or("direct"),
+                rule.pattern
+            )),
+            None => proxy_used,
+        },
+        local_addr,
+        connection,
+        auth_scheme,
+        mutual_auth,
+        netrc_login,
+        tls,
+        tls_exception_used: false,
         http_version,
         available_renderers,
         detected_content_type: content_type,
+        detected_charset,
+        bom,
+        body_text_base64,
+        soap_fault,
+        jsonrpc_error,
         protocol_used,
-        error: None,
+        negotiated_h3,
+        partial: transfer_error.is_some(),
+        error: transfer_error,
+        body_file_path,
+        saved_path,
+        body_preview_base64,
+        truncated,
+        warnings,
+        verbose_log: capture_verbose.then_some(verbose_log.entries),
     })
 }
 
@@ -580,6 +2904,134 @@ fn uuid_simple() -> String {
     format!("{:x}{:x}", now.as_secs(), now.subsec_nanos())
 }
 
+/// Handshake with the request's server without verifying it, to learn which
+/// certificate it presents. Nothing is sent over the connection.
+fn probe_peer_certificate(app: &AppHandle, req: &ApiRequest) -> Option<PeerCertificate> {
+    let (req, _) = with_proxy_rule(app, req.clone());
+    let req = &req;
+    let mut easy = Easy::new();
+    easy.url(&req.url).ok()?;
+    easy.connect_only(true).ok()?;
+    easy.ssl_verify_peer(false).ok()?;
+    easy.ssl_verify_host(false).ok()?;
+    easy.certinfo(true).ok()?;
+    let timeout = Duration::from_millis(req.timeout_ms.unwrap_or(30_000) as u64);
+    easy.timeout(timeout).ok()?;
+    apply_transport_options(&mut easy, req).ok()?;
+    apply_system_proxy(&mut easy, req).ok()?;
+    if let Some(ref cert) = req.client_cert {
+        apply_client_cert(&mut easy, cert).ok()?;
+    }
+    easy.perform().ok()?;
+    leaf_certificate(&easy)
+}
+
+/// Run `execute_curl_request`; if the server's certificate is untrusted but its
+/// host has a `TlsException`, retry without verification. The retry pins the
+/// probed certificate's key, so nothing is sent unless the server still holds it.
+fn execute_with_tls_exception(
+    app: &AppHandle,
+    req: ApiRequest,
+    cancel: Option<Arc<AtomicBool>>,
+) -> Result<ApiResponse, RequestError> {
+    let result = execute_curl_request(app, req.clone(), cancel.clone());
+    let Err(RequestError::UntrustedCertificate { host, detail, .. }) = result else {
+        return result;
+    };
+    let untrusted = |fingerprint_sha256| RequestError::UntrustedCertificate {
+        host: host.clone(),
+        fingerprint_sha256,
+        detail: detail.clone(),
+    };
+    // Exceptions cover the requested host, not hosts a redirect lands on.
+    let requested_host = Url::parse(&req.url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string));
+    if requested_host.as_deref() != Some(host.as_str()) {
+        return Err(untrusted(None));
+    }
+
+    let Some(presented) = probe_peer_certificate(app, &req) else {
+        return Err(untrusted(None));
+    };
+    let Some(exception) = tls_exception_for(app, &host) else {
+        return Err(untrusted(Some(presented.fingerprint_sha256)));
+    };
+    if exception.fingerprint_sha256 != presented.fingerprint_sha256 {
+        return Err(RequestError::CertificateChanged {
+            host,
+            expected: exception.fingerprint_sha256,
+            presented: presented.fingerprint_sha256,
+        });
+    }
+
+    let mut relaxed = req;
+    relaxed.verify_ssl = Some(false);
+    // The request's own pins are at least as strict; keep them when set.
+    relaxed
+        .pinned_public_key
+        .get_or_insert(presented.public_key_pin);
+    execute_curl_request(app, relaxed, cancel).map(|mut response| {
+        response.tls_exception_used = true;
+        response
+    })
+}
+
+/// Run `execute_curl_request`, repeating it as the request's `RetryPolicy` allows.
+fn execute_with_retry(
+    app: &AppHandle,
+    req: ApiRequest,
+    cancel: Option<Arc<AtomicBool>>,
+) -> Result<ApiResponse, RequestError> {
+    let policy = req
+        .retry
+        .clone()
+        .filter(|p| p.max_attempts > 1 && (p.retry_non_idempotent || is_idempotent(&req.method)));
+    let max_attempts = policy.as_ref().map_or(1, |p| p.max_attempts);
+    let mut backoff_ms = policy.as_ref().map_or(0.0, |p| p.backoff_ms as f64);
+    let started = Instant::now();
+    let mut attempt_statuses: Vec<Option<u16>> = Vec::new();
+
+    loop {
+        let result = execute_with_tls_exception(app, req.clone(), cancel.clone());
+        attempt_statuses.push(result.as_ref().ok().map(|r| r.status));
+        let attempts = attempt_statuses.len() as u32;
+
+        let should_retry = attempts < max_attempts
+            && policy.as_ref().is_some_and(|p| match &result {
+                Ok(response) => {
+                    p.retry_on_statuses.contains(&response.status)
+                        || (p.retry_on_connection_errors && response.partial)
+                }
+                Err(e) => p.retry_on_connection_errors && is_connection_error(e),
+            });
+
+        if !should_retry {
+            return result
+                .map(|mut response| {
+                    response.attempts = attempts;
+                    response.attempt_statuses = attempt_statuses;
+                    if attempts > 1 {
+                        response.timing.total_ms = started.elapsed().as_secs_f64() * 1000.0;
+                    }
+                    response
+                });
+        }
+
+        // A discarded attempt's spilled body would otherwise linger until exit.
+        if let Ok(Some(path)) = result.map(|r| r.body_file_path) {
+            let _ = discard_spilled_body(&path);
+        }
+
+        if !backoff_unless_cancelled(Duration::from_millis(backoff_ms as u64), cancel.as_deref()) {
+            return Err(RequestError::Cancelled);
+        }
+        backoff_ms *= policy
+            .as_ref()
+            .map_or(1.0, |p| p.backoff_multiplier.max(0.0));
+    }
+}
+
 fn rest_fallback_label(req: &ApiRequest) -> String {
     let host = Url::parse(&req.url)
         .ok()
@@ -591,27 +3043,97 @@ fn rest_fallback_label(req: &ApiRequest) -> String {
 #[tauri::command]
 #[specta::specta]
 pub fn rest_cancel_request(cancel_key: String) -> Result<(), String> {
-    if let Some(entry) = rest_cancel_flags().get(&cancel_key) {
-        entry.value().store(true, Ordering::SeqCst);
-    }
+    signal_cancel(&cancel_key);
     Ok(())
 }
 
+/// Build the request exactly as `rest_request` would and return it without sending.
+/// Headers libcurl adds on its own (Host, Content-Length, Accept-Encoding) are not listed.
+/// Send the returned `dynamic_values` with the request to reuse the values shown.
+#[tauri::command]
+#[specta::specta]
+pub fn preview_request(app: AppHandle, req: ApiRequest) -> Result<RequestPreview, RequestFailure> {
+    build_preview(&app, req).map_err(RequestFailure::from)
+}
+
+fn build_preview(app: &AppHandle, req: ApiRequest) -> Result<RequestPreview, RequestError> {
+    let mut req = resolve_for_preview(app, req)?;
+    apply_settings(app, &mut req)?;
+    // Previews never refresh, so an expired profile token is shown as-is.
+    if let Some(ref profile) = req.auth_profile {
+        if let Some(access_token) = stored_auth_profile_token(app, profile)? {
+            req.auth = AuthType::OAuth2 { access_token };
+        }
+    }
+    let prepared = prepare_request(&req)?;
+    Ok(RequestPreview {
+        method: prepared.method,
+        url: prepared.url,
+        headers: prepared.headers,
+        body_base64: prepared
+            .body
+            .as_ref()
+            .map(|body| BASE64.encode(body.preview()))
+            .unwrap_or_default(),
+        body_size: prepared.body_size,
+        pinned_public_keys: prepared.pinned_public_keys,
+        dynamic_values: req.dynamic_values,
+    })
+}
+
+/// Delete a response body that `rest_request` spilled to `body_file_path`.
+/// Anything left over is removed when the app exits.
+#[tauri::command]
+#[specta::specta]
+pub fn rest_discard_body_file(path: String) -> Result<(), String> {
+    discard_spilled_body(&path)
+}
+
+/// Abort an in-flight `rest_request` by its `request_id`.
+///
+/// Returns `false` when no request with that id is running (unknown or already
+/// finished); the cancelled request itself resolves with `RequestError::Cancelled`.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_request(request_id: String) -> Result<bool, String> {
+    Ok(signal_cancel(&request_id))
+}
+
+/// Resolve variables, apply the settings and auth profile, then send `req`
+/// with retries. Blocks until the response is in.
+pub(crate) fn send_request(
+    app: &AppHandle,
+    req: ApiRequest,
+    cancel: Option<Arc<AtomicBool>>,
+) -> Result<ApiResponse, RequestError> {
+    let mut req = resolve_for_send(app, req)?;
+    apply_settings(app, &mut req)?;
+    if let Some(ref profile) = req.auth_profile {
+        let access_token = auth_profile_token(app, profile, req.auth_refresh_skew_secs)?;
+        req.auth = AuthType::OAuth2 { access_token };
+    }
+    execute_with_retry(app, req, cancel)
+}
+
 #[tauri::command]
 #[specta::specta]
-pub async fn rest_request(app: AppHandle, req: ApiRequest) -> Result<ApiResponse, String> {
-    let cancel_flag = req.cancel_key.as_ref().map(|key| {
+pub async fn rest_request(app: AppHandle, req: ApiRequest) -> Result<ApiResponse, RequestFailure> {
+    let request_id = active_request_id(&req);
+    let cancel_flag = request_id.as_ref().map(|key| {
         let flag = Arc::new(AtomicBool::new(false));
         rest_cancel_flags().insert(key.clone(), Arc::clone(&flag));
         flag
     });
-    let cancel_key = req.cancel_key.clone();
     let label = req.request_label.clone();
     let fallback = rest_fallback_label(&req);
-    let join_result = tokio::task::spawn_blocking(move || execute_curl_request(req, cancel_flag))
-        .await
-        .map_err(|e| format!("Task error: {}", e));
-    if let Some(ref k) = cancel_key {
+    let app_for_transfer = app.clone();
+    let join_result =
+        tokio::task::spawn_blocking(move || send_request(&app_for_transfer, req, cancel_flag))
+            .await
+            .map_err(|e| RequestError::Other {
+                detail: format!("Task error: {}", e),
+            });
+    if let Some(ref k) = request_id {
         rest_cancel_flags().remove(k);
     }
     let result = join_result?;
@@ -619,10 +3141,10 @@ pub async fn rest_request(app: AppHandle, req: ApiRequest) -> Result<ApiResponse
         let name = pick_display_name(&label, &fallback);
         notify_request_completed_if_background(&app, &name);
     }
-    result
+    result.map_err(RequestFailure::from)
 }
 
-fn execute_fetch_url(url: String) -> Result<FetchUrlResponse, String> {
+pub(crate) fn execute_fetch_url(url: String) -> Result<FetchUrlResponse, String> {
     let mut easy = Easy::new();
 
     easy.url(&url).map_err(|e| format!("URL error: {}", e))?;
@@ -692,3 +3214,797 @@ pub async fn fetch_url(url: String) -> Result<FetchUrlResponse, String> {
         .await
         .map_err(|e| format!("Task error: {}", e))?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TcpKeepalive;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    /// A one-shot HTTP/1.1 server on localhost. It answers the first request
+    /// with `response` and hands back the raw request head.
+    fn serve_once(response: &'static [u8]) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            stream.write_all(response).unwrap();
+            let _ = tx.send(head);
+        });
+        (url, rx)
+    }
+
+    /// Sends `req` the way `execute_curl_request` puts it on the wire, minus
+    /// the app-level settings, and returns the raw response head.
+    fn perform(req: &ApiRequest) -> String {
+        let prepared = prepare_request(req).unwrap();
+        let mut easy = Easy::new();
+        easy.url(&prepared.url).unwrap();
+        if let Some(RequestBody::Bytes(ref data)) = prepared.body {
+            easy.post_fields_copy(data).unwrap();
+        }
+        apply_method(&mut easy, &req.method, &prepared.method).unwrap();
+        easy.http_version(curl_http_version(req.protocol.as_ref()).unwrap())
+            .unwrap();
+        apply_transport_options(&mut easy, req).unwrap();
+        if let Some(timeout) = req.timeout_ms {
+            easy.timeout(Duration::from_millis(timeout as u64)).unwrap();
+        }
+        easy.http_headers(header_list(&prepared.headers).unwrap())
+            .unwrap();
+        let mut head = Vec::new();
+        {
+            let mut transfer = easy.transfer();
+            transfer
+                .header_function(|header| {
+                    head.extend_from_slice(header);
+                    true
+                })
+                .unwrap();
+            transfer.write_function(|data| Ok(data.len())).unwrap();
+            transfer.perform().unwrap();
+        }
+        String::from_utf8_lossy(&head).into_owned()
+    }
+
+    #[test]
+    fn repeated_headers_are_sent_in_order() {
+        let (url, head) = serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n");
+        let req = ApiRequest {
+            url,
+            protocol: Some(HttpProtocol::Http1_1),
+            headers: vec![
+                ("X-Forwarded-For".to_string(), "203.0.113.7".to_string()),
+                ("Accept".to_string(), "*/*".to_string()),
+                ("X-Forwarded-For".to_string(), "198.51.100.2".to_string()),
+            ],
+            ..Default::default()
+        };
+        perform(&req);
+
+        let head = head.recv().unwrap();
+        let forwarded: Vec<&str> = head
+            .lines()
+            .filter_map(|line| line.strip_prefix("X-Forwarded-For: "))
+            .collect();
+        assert_eq!(forwarded, ["203.0.113.7", "198.51.100.2"]);
+    }
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn repeated_query_keys_are_all_kept() {
+        let params = pairs(&[("id", "1"), ("id", "2"), ("id", "3")]);
+        let url = build_url_with_params("https://api.example.com/items", &params, None).unwrap();
+        assert_eq!(url, "https://api.example.com/items?id=1&id=2&id=3");
+    }
+
+    #[test]
+    fn empty_query_value_has_no_equals_sign() {
+        let params = pairs(&[("debug", "")]);
+        let url = build_url_with_params("https://api.example.com/items", &params, None).unwrap();
+        assert_eq!(url, "https://api.example.com/items?debug");
+    }
+
+    #[test]
+    fn api_key_goes_after_the_query_params() {
+        let req = ApiRequest {
+            url: "https://api.example.com/items?page=2".to_string(),
+            query_params: pairs(&[("q", "a b")]),
+            auth: AuthType::ApiKey {
+                key: "api_key".to_string(),
+                value: "s3c=ret".to_string(),
+                add_to: ApiKeyLocation::Query,
+            },
+            ..Default::default()
+        };
+        let prepared = prepare_request(&req).unwrap();
+        assert_eq!(
+            prepared.url,
+            "https://api.example.com/items?page=2&q=a+b&api_key=s3c%3Dret"
+        );
+        assert!(!prepared
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("api_key")));
+    }
+
+    #[test]
+    fn multipart_preview_shows_parts_and_streamed_files() {
+        use crate::types::{MultipartField, MultipartValue};
+
+        let path = std::env::temp_dir().join(format!("mandy-preview-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, b"hello").unwrap();
+        let req = ApiRequest {
+            method: Methods::POST,
+            url: "https://api.example.com/upload".to_string(),
+            body: BodyType::Multipart {
+                fields: vec![
+                    MultipartField {
+                        name: "note".to_string(),
+                        value: MultipartValue::Text("hi".to_string()),
+                    },
+                    MultipartField {
+                        name: "doc".to_string(),
+                        value: MultipartValue::FilePath {
+                            path: path.to_string_lossy().into_owned(),
+                            filename: Some("report.txt".to_string()),
+                            content_type: Some("text/plain".to_string()),
+                        },
+                    },
+                ],
+            },
+            ..Default::default()
+        };
+        let prepared = prepare_request(&req).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let content_type = prepared
+            .headers
+            .iter()
+            .find(|(name, _)| name == "Content-Type")
+            .map(|(_, value)| value.as_str())
+            .unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+        let preview = String::from_utf8(prepared.body.unwrap().preview()).unwrap();
+        assert!(preview.starts_with(&format!("--{}\r\n", boundary)));
+        assert!(preview.contains("Content-Disposition: form-data; name=\"note\"\r\n\r\nhi\r\n"));
+        assert!(preview
+            .contains("Content-Disposition: form-data; name=\"doc\"; filename=\"report.txt\"\r\n"));
+        assert!(preview.contains(&format!(
+            "Content-Type: text/plain\r\n\r\n<file: {}, 5 bytes>\r\n",
+            path.display()
+        )));
+        assert!(preview.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+
+    #[test]
+    fn http1_only_server_is_reported_as_http1() {
+        let mut protocols = vec![
+            ("auto", HttpProtocol::Auto),
+            ("http/1.1", HttpProtocol::Http1_1),
+        ];
+        if curl::Version::get().feature_http2() {
+            // Over cleartext these offer `Upgrade: h2c`, which the server ignores.
+            protocols.extend([("tcp", HttpProtocol::Tcp), ("h2", HttpProtocol::Http2)]);
+        }
+        for (name, protocol) in protocols {
+            let (url, request_head) = serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok");
+            let req = ApiRequest {
+                url,
+                protocol: Some(protocol),
+                ..Default::default()
+            };
+            let response_head = perform(&req);
+
+            let request_head = request_head.recv().unwrap();
+            let request_line = request_head.lines().next().unwrap();
+            assert!(
+                request_line.ends_with(" HTTP/1.1"),
+                "{}: {}",
+                name,
+                request_line
+            );
+            let version = response_head
+                .lines()
+                .rfind(|line| is_final_status_line(line.as_bytes()))
+                .and_then(|line| line.split(' ').next())
+                .unwrap();
+            assert_eq!(protocol_label(version), "HTTP/1.1", "{}", name);
+        }
+    }
+
+    /// The timer libcurl's socket on local `port` is running, from `/proc/net/tcp`
+    /// (`2` is the keepalive timer).
+    #[cfg(target_os = "linux")]
+    fn tcp_timer(port: u16) -> Option<u8> {
+        let table = std::fs::read_to_string("/proc/net/tcp").ok()?;
+        table.lines().skip(1).find_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            if u16::from_str_radix(local_port, 16).ok()? != port {
+                return None;
+            }
+            u8::from_str_radix(fields.get(5)?.split(':').next()?, 16).ok()
+        })
+    }
+
+    #[test]
+    fn keepalive_connection_outlives_the_idle_time() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (tx, timer) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, peer) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                line.clear();
+            }
+            // Sit past the idle time so probes go out before the answer does.
+            std::thread::sleep(Duration::from_millis(2500));
+            #[cfg(target_os = "linux")]
+            let _ = tx.send(tcp_timer(peer.port()));
+            #[cfg(not(target_os = "linux"))]
+            let _ = (tx, peer);
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok")
+                .unwrap();
+        });
+        let req = ApiRequest {
+            url,
+            protocol: Some(HttpProtocol::Http1_1),
+            tcp_keepalive: Some(TcpKeepalive {
+                idle_secs: 1,
+                interval_secs: 1,
+            }),
+            timeout_ms: Some(10_000),
+            ..Default::default()
+        };
+        let head = perform(&req);
+        assert!(head.starts_with("HTTP/1.1 200"), "{}", head);
+        #[cfg(target_os = "linux")]
+        assert_eq!(timer.recv().unwrap(), Some(2));
+        #[cfg(not(target_os = "linux"))]
+        drop(timer);
+    }
+
+    /// Reads one request, body included, then answers `204 No Content`.
+    fn serve_upload() -> (String, mpsc::Receiver<usize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                line.clear();
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+                .unwrap();
+            let _ = tx.send(body.len());
+        });
+        (url, rx)
+    }
+
+    /// 96 KiB at 32 KiB/s should take about three seconds either way. libcurl
+    /// lets the first buffer through before it starts pacing, so allow slack.
+    const LIMITED_BYTES: usize = 96 * 1024;
+    const SPEED_LIMIT: u32 = 32 * 1024;
+    const EXPECTED_SECS: f64 = LIMITED_BYTES as f64 / SPEED_LIMIT as f64;
+
+    #[test]
+    fn receive_speed_limit_paces_the_download() {
+        static RESPONSE: OnceLock<Vec<u8>> = OnceLock::new();
+        let response = RESPONSE.get_or_init(|| {
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n",
+                LIMITED_BYTES
+            )
+            .into_bytes();
+            response.resize(response.len() + LIMITED_BYTES, b'x');
+            response
+        });
+        let (url, _) = serve_once(response);
+        let req = ApiRequest {
+            url,
+            protocol: Some(HttpProtocol::Http1_1),
+            max_recv_speed_bytes: Some(SPEED_LIMIT),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        perform(&req);
+        let elapsed = started.elapsed().as_secs_f64();
+        assert!(
+            (EXPECTED_SECS * 0.6..EXPECTED_SECS * 1.5).contains(&elapsed),
+            "took {:.2}s, expected about {:.2}s",
+            elapsed,
+            EXPECTED_SECS
+        );
+    }
+
+    #[test]
+    fn send_speed_limit_paces_the_upload() {
+        let (url, received) = serve_upload();
+        let req = ApiRequest {
+            method: Methods::POST,
+            url,
+            protocol: Some(HttpProtocol::Http1_1),
+            body: BodyType::Raw {
+                content: "x".repeat(LIMITED_BYTES),
+                content_type: Some("text/plain".to_string()),
+            },
+            max_send_speed_bytes: Some(SPEED_LIMIT),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        perform(&req);
+        let elapsed = started.elapsed().as_secs_f64();
+        assert_eq!(received.recv().unwrap(), LIMITED_BYTES);
+        assert!(
+            (EXPECTED_SECS * 0.6..EXPECTED_SECS * 1.5).contains(&elapsed),
+            "took {:.2}s, expected about {:.2}s",
+            elapsed,
+            EXPECTED_SECS
+        );
+    }
+
+    /// `decode_body_text` output as a string, with its charset and warnings.
+    fn decode_text(body: &[u8], content_type: &str) -> (Option<String>, Option<String>, usize) {
+        let mut warnings = Vec::new();
+        let charset = charset_from_content_type(content_type);
+        let (name, text) = decode_body_text(body, charset.as_deref(), &mut warnings);
+        let text = text.map(|b64| String::from_utf8(BASE64.decode(b64).unwrap()).unwrap());
+        (name, text, warnings.len())
+    }
+
+    #[test]
+    fn charset_parameter_is_read_from_content_type() {
+        assert_eq!(
+            charset_from_content_type("text/html; charset=\"Shift_JIS\"").as_deref(),
+            Some("Shift_JIS")
+        );
+        assert_eq!(
+            charset_from_content_type("text/plain;format=flowed; CHARSET=latin1").as_deref(),
+            Some("latin1")
+        );
+        assert_eq!(charset_from_content_type("application/json"), None);
+    }
+
+    #[test]
+    fn latin1_body_is_transcoded() {
+        let (name, text, warnings) =
+            decode_text(b"caf\xe9 cr\xe8me", "text/plain; charset=ISO-8859-1");
+        // WHATWG maps the Latin-1 labels to windows-1252.
+        assert_eq!(name.as_deref(), Some("windows-1252"));
+        assert_eq!(text.as_deref(), Some("café crème"));
+        assert_eq!(warnings, 0);
+    }
+
+    #[test]
+    fn shift_jis_body_is_transcoded() {
+        let (name, text, warnings) =
+            decode_text(b"\x93\xfa\x96\x7b\x8c\xea", "text/html; charset=Shift_JIS");
+        assert_eq!(name.as_deref(), Some("Shift_JIS"));
+        assert_eq!(text.as_deref(), Some("日本語"));
+        assert_eq!(warnings, 0);
+    }
+
+    #[test]
+    fn utf16_bom_wins_over_declared_charset() {
+        let le = b"\xff\xfeh\x00i\x00";
+        let (name, text, _) = decode_text(le, "text/plain; charset=ISO-8859-1");
+        assert_eq!(name.as_deref(), Some("UTF-16LE"));
+        assert_eq!(text.as_deref(), Some("hi"));
+
+        let be = b"\xfe\xff\x00h\x00i";
+        let (name, text, _) = decode_text(be, "text/plain");
+        assert_eq!(name.as_deref(), Some("UTF-16BE"));
+        assert_eq!(text.as_deref(), Some("hi"));
+    }
+
+    #[test]
+    fn utf8_body_is_left_alone() {
+        let (name, text, _) = decode_text("héllo".as_bytes(), "text/plain; charset=utf-8");
+        assert_eq!(name.as_deref(), Some("UTF-8"));
+        assert_eq!(text, None);
+    }
+
+    #[test]
+    fn unknown_charset_falls_back_to_utf8_with_a_warning() {
+        let (name, text, warnings) = decode_text(b"plain", "text/plain; charset=x-klingon");
+        assert_eq!(name.as_deref(), Some("x-klingon"));
+        assert_eq!(text.as_deref(), Some("plain"));
+        assert_eq!(warnings, 1);
+    }
+
+    fn renders_json(body: &[u8]) -> bool {
+        detect_renderers(None, body).contains(&ResponseRenderer::Json)
+    }
+
+    #[test]
+    fn utf8_bom_is_skipped_when_sniffing() {
+        let body = b"\xef\xbb\xbf{\"ok\":true}";
+        assert_eq!(detect_bom(body).as_deref(), Some("UTF-8"));
+        assert!(renders_json(body));
+    }
+
+    #[test]
+    fn utf16le_bom_body_is_transcoded_before_sniffing() {
+        let body = b"\xff\xfe[\x001\x00]\x00";
+        assert_eq!(detect_bom(body).as_deref(), Some("UTF-16LE"));
+        assert!(renders_json(body));
+    }
+
+    #[test]
+    fn utf16be_bom_body_is_transcoded_before_sniffing() {
+        let body = b"\xfe\xff\x00<\x00?\x00x\x00m\x00l\x00 \x00?\x00>";
+        assert_eq!(detect_bom(body).as_deref(), Some("UTF-16BE"));
+        assert!(detect_renderers(None, body).contains(&ResponseRenderer::Xml));
+    }
+
+    #[test]
+    fn body_without_bom_reports_none() {
+        assert_eq!(detect_bom(b"{}"), None);
+        assert!(renders_json(b"{}"));
+    }
+
+    // Leading bytes of real files, trimmed to what the sniffer needs to see.
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+    const JPEG: &[u8] = b"\xff\xd8\xff\xe0\x00\x10JFIF\x00";
+    const GIF87: &[u8] = b"GIF87a\x01\x00\x01\x00";
+    const GIF89: &[u8] = b"GIF89a\x01\x00\x01\x00";
+    const WEBP: &[u8] = b"RIFF\x24\x00\x00\x00WEBPVP8 ";
+    const BMP: &[u8] = b"BM\x3a\x00\x00\x00\x00\x00\x00\x00\x36\x00";
+    const ICO: &[u8] = b"\x00\x00\x01\x00\x01\x00\x10\x10";
+    const PDF: &[u8] = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3";
+    const WAV: &[u8] = b"RIFF\x24\x08\x00\x00WAVEfmt ";
+    const MP3: &[u8] = b"ID3\x04\x00\x00\x00\x00\x00\x00";
+    const FLAC: &[u8] = b"fLaC\x00\x00\x00\x22";
+    const M4A: &[u8] = b"\x00\x00\x00\x20ftypM4A \x00\x00\x00\x00";
+    const MP4: &[u8] = b"\x00\x00\x00\x18ftypmp42\x00\x00\x00\x00";
+    const ZIP: &[u8] = b"PK\x03\x04\x14\x00\x00\x00\x08\x00";
+
+    fn sniffs_as(body: &[u8], expected: ResponseRenderer) -> bool {
+        sniff_renderer(body) == Some(expected)
+    }
+
+    #[test]
+    fn png_signature_is_an_image() {
+        assert!(sniffs_as(PNG, ResponseRenderer::Image));
+    }
+
+    #[test]
+    fn jpeg_signature_is_an_image() {
+        assert!(sniffs_as(JPEG, ResponseRenderer::Image));
+    }
+
+    #[test]
+    fn gif_signatures_are_images() {
+        assert!(sniffs_as(GIF87, ResponseRenderer::Image));
+        assert!(sniffs_as(GIF89, ResponseRenderer::Image));
+    }
+
+    #[test]
+    fn webp_signature_is_an_image() {
+        assert!(sniffs_as(WEBP, ResponseRenderer::Image));
+    }
+
+    #[test]
+    fn bmp_signature_needs_zeroed_reserved_bytes() {
+        assert!(sniffs_as(BMP, ResponseRenderer::Image));
+        assert!(sniff_renderer(b"BMW is a car maker").is_none());
+    }
+
+    #[test]
+    fn ico_signature_is_an_image() {
+        assert!(sniffs_as(ICO, ResponseRenderer::Image));
+    }
+
+    #[test]
+    fn pdf_signature_is_a_pdf() {
+        assert!(sniffs_as(PDF, ResponseRenderer::Pdf));
+    }
+
+    #[test]
+    fn wave_signature_is_audio() {
+        assert!(sniffs_as(WAV, ResponseRenderer::Audio));
+    }
+
+    #[test]
+    fn id3_signature_is_audio() {
+        assert!(sniffs_as(MP3, ResponseRenderer::Audio));
+    }
+
+    #[test]
+    fn flac_signature_is_audio() {
+        assert!(sniffs_as(FLAC, ResponseRenderer::Audio));
+    }
+
+    #[test]
+    fn m4a_brand_is_audio() {
+        assert!(sniffs_as(M4A, ResponseRenderer::Audio));
+    }
+
+    #[test]
+    fn other_ftyp_brands_are_video() {
+        assert!(sniffs_as(MP4, ResponseRenderer::Video));
+    }
+
+    #[test]
+    fn zip_signature_is_an_archive() {
+        assert!(sniffs_as(ZIP, ResponseRenderer::Archive));
+    }
+
+    #[test]
+    fn sniffed_renderer_is_added_to_an_octet_stream() {
+        let renderers = detect_renderers(Some("application/octet-stream"), PDF);
+        assert!(renderers.contains(&ResponseRenderer::Pdf));
+    }
+
+    #[test]
+    fn plain_text_has_no_signature() {
+        assert!(sniff_renderer(b"hello world").is_none());
+        assert!(sniff_renderer(b"").is_none());
+    }
+
+    #[test]
+    fn set_cookie_attributes_are_all_read() {
+        let cookie = parse_set_cookie(
+            "sid=abc123; Domain=example.com; Path=/api; Max-Age=3600; SameSite=Lax; Secure; HttpOnly",
+        )
+        .unwrap();
+        assert_eq!(cookie.name, "sid");
+        assert_eq!(cookie.value, "abc123");
+        assert_eq!(cookie.domain.as_deref(), Some("example.com"));
+        assert_eq!(cookie.path.as_deref(), Some("/api"));
+        assert_eq!(cookie.max_age, Some(3600));
+        assert_eq!(cookie.same_site.as_deref(), Some("Lax"));
+        assert_eq!(cookie.secure, Some(true));
+        assert_eq!(cookie.http_only, Some(true));
+        assert_eq!(cookie.is_expired, Some(false));
+    }
+
+    #[test]
+    fn set_cookie_attribute_names_are_case_insensitive() {
+        let cookie = parse_set_cookie("a=1; PATH=/; httponly; SECURE; samesite=Strict").unwrap();
+        assert_eq!(cookie.path.as_deref(), Some("/"));
+        assert_eq!(cookie.http_only, Some(true));
+        assert_eq!(cookie.secure, Some(true));
+        assert_eq!(cookie.same_site.as_deref(), Some("Strict"));
+    }
+
+    #[test]
+    fn value_less_attributes_only_set_flags() {
+        let cookie = parse_set_cookie("a=1; Secure").unwrap();
+        assert_eq!(cookie.secure, Some(true));
+        assert_eq!(cookie.http_only, None);
+
+        // Attributes that need a value are left unset without one.
+        let cookie = parse_set_cookie("a=1; Domain; Path; Max-Age").unwrap();
+        assert_eq!(cookie.domain, None);
+        assert_eq!(cookie.path, None);
+        assert_eq!(cookie.max_age, None);
+        assert_eq!(cookie.expires_at, None);
+    }
+
+    #[test]
+    fn unknown_attributes_are_ignored() {
+        let cookie =
+            parse_set_cookie("a=1; Priority=High; Partitioned; X-Custom=y; Path=/docs").unwrap();
+        assert_eq!(cookie.value, "1");
+        assert_eq!(cookie.path.as_deref(), Some("/docs"));
+        assert_eq!(cookie.secure, None);
+    }
+
+    #[test]
+    fn max_age_wins_over_expires() {
+        let cookie =
+            parse_set_cookie("a=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT; Max-Age=60").unwrap();
+        assert_eq!(
+            cookie.expires.as_deref(),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+        assert_eq!(cookie.is_expired, Some(false));
+
+        let cookie = parse_set_cookie("a=1; Max-Age=0").unwrap();
+        assert_eq!(cookie.is_expired, Some(true));
+    }
+
+    #[test]
+    fn set_cookie_value_keeps_embedded_equals_signs() {
+        let cookie = parse_set_cookie("token=a=b==; Path=/").unwrap();
+        assert_eq!(cookie.value, "a=b==");
+        assert!(parse_set_cookie("no-value-at-all; Path=/").is_none());
+    }
+
+    fn cookie_pair(name: &str, value: &str) -> String {
+        encode_cookie_pair(name, value).unwrap()
+    }
+
+    #[test]
+    fn cookie_value_semicolons_are_encoded() {
+        assert_eq!(cookie_pair("a", "x;y"), "a=x%3By");
+    }
+
+    #[test]
+    fn cookie_value_equals_signs_pass_through() {
+        assert_eq!(cookie_pair("token", "a=b=="), "token=a=b==");
+    }
+
+    #[test]
+    fn cookie_value_spaces_are_encoded() {
+        assert_eq!(cookie_pair("hi", "hello world"), "hi=hello%20world");
+    }
+
+    #[test]
+    fn cookie_value_non_ascii_is_encoded_as_utf8_bytes() {
+        assert_eq!(cookie_pair("name", "José"), "name=Jos%C3%A9");
+    }
+
+    #[test]
+    fn quoted_cookie_value_is_kept_when_valid() {
+        assert_eq!(cookie_pair("q", "\"abc\""), "q=\"abc\"");
+        assert_eq!(cookie_pair("q", "\"a b\""), "q=%22a%20b%22");
+    }
+
+    #[test]
+    fn invalid_cookie_names_are_rejected() {
+        assert!(encode_cookie_pair("", "v").is_err());
+        assert!(encode_cookie_pair("a b", "v").is_err());
+        assert!(encode_cookie_pair("a;b", "v").is_err());
+        assert!(encode_cookie_pair("caf\u{e9}", "v").is_err());
+    }
+
+    #[test]
+    fn cookie_header_joins_encoded_pairs() {
+        let header = build_cookie_header(&pairs(&[("a", "1"), ("b", "x;y")])).unwrap();
+        assert_eq!(header, "a=1; b=x%3By");
+    }
+
+    /// A file under `helpers/testdata`.
+    #[cfg(target_os = "linux")]
+    fn testdata(name: &str) -> String {
+        let path = std::path::Path::new(file!()).with_file_name("testdata");
+        path.join(name).to_string_lossy().into_owned()
+    }
+
+    /// A one-shot HTTPS server on localhost that only completes the handshake
+    /// with a client certificate signed by the test CA. It answers with the
+    /// certificate's common name.
+    #[cfg(target_os = "linux")]
+    fn serve_mtls() -> String {
+        use openssl::nid::Nid;
+        use openssl::ssl::{SslAcceptor, SslFiletype, SslMethod, SslVerifyMode};
+
+        let mut acceptor = SslAcceptor::mozilla_intermediate(SslMethod::tls()).unwrap();
+        acceptor
+            .set_certificate_chain_file(testdata("server.pem"))
+            .unwrap();
+        acceptor
+            .set_private_key_file(testdata("server.key"), SslFiletype::PEM)
+            .unwrap();
+        acceptor.set_ca_file(testdata("ca.pem")).unwrap();
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let url = format!("https://localhost:{}/", port);
+        std::thread::spawn(move || {
+            let (tcp, _) = listener.accept().unwrap();
+            let Ok(mut stream) = acceptor.accept(tcp) else {
+                return;
+            };
+            let mut reader = std::io::BufReader::new(&mut stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 0 && line != "\r\n" {
+                line.clear();
+            }
+            let peer = stream.ssl().peer_certificate().unwrap();
+            let name = peer.subject_name().entries_by_nid(Nid::COMMONNAME).next();
+            let name = name.unwrap().data().as_utf8().unwrap().to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                name.len(),
+                name
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            let _ = stream.shutdown();
+        });
+        url
+    }
+
+    #[cfg(target_os = "linux")]
+    fn fetch_with_client_cert(cert: Option<ClientCert>) -> Result<String, String> {
+        let req = ApiRequest {
+            url: serve_mtls(),
+            client_cert: cert,
+            ca_cert_path: Some(testdata("ca.pem")),
+            ..Default::default()
+        };
+        let mut easy = Easy::new();
+        easy.url(&req.url).unwrap();
+        easy.timeout(Duration::from_secs(5)).unwrap();
+        if let Some(ref cert) = req.client_cert {
+            apply_client_cert(&mut easy, cert).map_err(|e| e.to_string())?;
+        }
+        apply_ca_bundle(&mut easy, &req).map_err(|e| e.to_string())?;
+        let mut body = Vec::new();
+        {
+            let mut transfer = easy.transfer();
+            transfer
+                .write_function(|data| {
+                    body.extend_from_slice(data);
+                    Ok(data.len())
+                })
+                .unwrap();
+            transfer.perform().map_err(|e| e.to_string())?;
+        }
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pem_client_certificate_passes_mutual_tls() {
+        let cert = ClientCert::Pem {
+            cert_path: testdata("client.pem"),
+            key_path: Some(testdata("client.key")),
+            key_password: None,
+        };
+        assert_eq!(fetch_with_client_cert(Some(cert)).unwrap(), "mandy-client");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pkcs12_client_certificate_passes_mutual_tls() {
+        let cert = ClientCert::Pkcs12 {
+            path: testdata("client.p12"),
+            password: Some("mandy".to_string()),
+        };
+        assert_eq!(fetch_with_client_cert(Some(cert)).unwrap(), "mandy-client");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn mutual_tls_fails_without_a_client_certificate() {
+        assert!(fetch_with_client_cert(None).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn wrong_pkcs12_password_is_an_error() {
+        let cert = ClientCert::Pkcs12 {
+            path: testdata("client.p12"),
+            password: Some("wrong".to_string()),
+        };
+        assert!(fetch_with_client_cert(Some(cert)).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Collection runs: every request in a collection or folder sent one after
+//! another through `send_request`, depth-first in the order they're listed.
+//! Results are only kept in memory, for the last `MAX_KEPT_RUNS` runs.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use dashmap::DashMap;
+use tauri::{AppHandle, Emitter};
+
+use crate::helpers::collections::get_collection_tree;
+use crate::helpers::rest::send_request;
+use crate::types::{
+    ApiRequest, CollectionNode, RequestError, RunItemResult, RunOptions, RunProgressEvent,
+    RunResult, RunStatus,
+};
+
+/// Event carrying a `RunProgressEvent` after each request in a run.
+pub const RUN_PROGRESS_EVENT: &str = "run-progress";
+
+/// Event carrying the `RunResult` of a run that ended, however it ended.
+pub const RUN_FINISHED_EVENT: &str = "run-finished";
+
+const MAX_KEPT_RUNS: usize = 20;
+
+/// How often a delay between requests checks for cancellation.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+static RUNS: OnceLock<DashMap<String, RunResult>> = OnceLock::new();
+static RUN_CANCEL_FLAGS: OnceLock<DashMap<String, Arc<AtomicBool>>> = OnceLock::new();
+
+fn runs() -> &'static DashMap<String, RunResult> {
+    RUNS.get_or_init(DashMap::new)
+}
+
+fn run_cancel_flags() -> &'static DashMap<String, Arc<AtomicBool>> {
+    RUN_CANCEL_FLAGS.get_or_init(DashMap::new)
+}
+
+/// A request to send, with the collection item it came from.
+struct RunItem {
+    id: String,
+    name: String,
+    request: ApiRequest,
+}
+
+/// Requests under `items`, depth-first.
+fn collect_requests(items: &[CollectionNode], out: &mut Vec<RunItem>) {
+    for node in items {
+        match node {
+            CollectionNode::Folder { items, .. } => collect_requests(items, out),
+            CollectionNode::Request { id, name, request } => out.push(RunItem {
+                id: id.clone(),
+                name: name.clone(),
+                request: (**request).clone(),
+            }),
+        }
+    }
+}
+
+fn find_folder<'a>(items: &'a [CollectionNode], id: &str) -> Option<&'a [CollectionNode]> {
+    items.iter().find_map(|node| match node {
+        CollectionNode::Folder {
+            id: folder_id,
+            items,
+            ..
+        } => {
+            if folder_id == id {
+                Some(items.as_slice())
+            } else {
+                find_folder(items, id)
+            }
+        }
+        CollectionNode::Request { .. } => None,
+    })
+}
+
+/// Sleep for `delay`, returning early with `false` if the run is cancelled.
+fn wait(delay: Duration, cancel: &AtomicBool) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return false;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return true;
+        }
+        std::thread::sleep(CANCEL_POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+fn send_item(app: &AppHandle, item: RunItem, cancel: &Arc<AtomicBool>) -> Option<RunItemResult> {
+    let started = Instant::now();
+    let result = send_request(app, item.request, Some(Arc::clone(cancel)));
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let (status, status_text, size_bytes, passed, error) = match result {
+        Ok(response) => (
+            Some(response.status),
+            Some(response.status_text),
+            Some(response.response_size.total_bytes),
+            response.status < 400,
+            None,
+        ),
+        Err(RequestError::Cancelled) => return None,
+        Err(e) => (None, None, None, false, Some(e.to_string())),
+    };
+    Some(RunItemResult {
+        item_id: item.id,
+        name: item.name,
+        status,
+        status_text,
+        duration_ms,
+        size_bytes,
+        passed,
+        error,
+    })
+}
+
+/// Send `items` in order, recording each result under `run_id`.
+fn run(
+    app: &AppHandle,
+    run_id: &str,
+    items: Vec<RunItem>,
+    options: &RunOptions,
+    cancel: &Arc<AtomicBool>,
+) -> RunStatus {
+    let total = items.len() as u32;
+    let delay = Duration::from_millis(u64::from(options.delay_ms.unwrap_or(0)));
+    for (index, mut item) in items.into_iter().enumerate() {
+        if index > 0 && !wait(delay, cancel) {
+            return RunStatus::Cancelled;
+        }
+        if options.environment_id.is_some() {
+            item.request.environment_id = options.environment_id.clone();
+        }
+        let Some(result) = send_item(app, item, cancel) else {
+            return RunStatus::Cancelled;
+        };
+        let passed = result.passed;
+        if let Some(mut entry) = runs().get_mut(run_id) {
+            entry.items.push(result.clone());
+        }
+        let _ = app.emit(
+            RUN_PROGRESS_EVENT,
+            RunProgressEvent {
+                run_id: run_id.to_string(),
+                index: index as u32,
+                total,
+                item: result,
+            },
+        );
+        if cancel.load(Ordering::SeqCst) {
+            return RunStatus::Cancelled;
+        }
+        if !passed && options.stop_on_failure {
+            return RunStatus::Stopped;
+        }
+    }
+    RunStatus::Completed
+}
+
+/// Drop the oldest finished runs past `MAX_KEPT_RUNS`.
+fn evict_old_runs() {
+    let mut finished: Vec<(String, String)> = runs()
+        .iter()
+        .filter(|entry| entry.status != RunStatus::Running)
+        .map(|entry| (entry.started_at.clone(), entry.key().clone()))
+        .collect();
+    if finished.len() <= MAX_KEPT_RUNS {
+        return;
+    }
+    finished.sort();
+    for (_, run_id) in &finished[..finished.len() - MAX_KEPT_RUNS] {
+        runs().remove(run_id);
+    }
+}
+
+/// Send every request in a collection, or in one of its folders, one after
+/// another. Returns the run id right away; progress comes as `run-progress`
+/// events and the outcome from `get_run_result`.
+#[tauri::command]
+#[specta::specta]
+pub fn run_collection(
+    app: AppHandle,
+    collection_id: String,
+    folder_id: Option<String>,
+    options: RunOptions,
+) -> Result<String, String> {
+    let collection = get_collection_tree(app.clone(), collection_id.clone())?;
+    let nodes = match folder_id {
+        Some(ref folder_id) => find_folder(&collection.items, folder_id)
+            .ok_or_else(|| format!("Folder '{}' not found", folder_id))?,
+        None => collection.items.as_slice(),
+    };
+    let mut items = Vec::new();
+    collect_requests(nodes, &mut items);
+
+    let run_id = uuid::Uuid::new_v4().to_string();
+    let cancel = Arc::new(AtomicBool::new(false));
+    run_cancel_flags().insert(run_id.clone(), Arc::clone(&cancel));
+    runs().insert(
+        run_id.clone(),
+        RunResult {
+            run_id: run_id.clone(),
+            collection_id,
+            folder_id,
+            status: RunStatus::Running,
+            total: items.len() as u32,
+            items: Vec::new(),
+            started_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+        },
+    );
+
+    let id = run_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let status = tokio::task::spawn_blocking({
+            let (app, id) = (app.clone(), id.clone());
+            move || run(&app, &id, items, &options, &cancel)
+        })
+        .await
+        .unwrap_or(RunStatus::Failed);
+        run_cancel_flags().remove(&id);
+        let finished = runs().get_mut(&id).map(|mut entry| {
+            entry.status = status;
+            entry.finished_at = Some(Utc::now().to_rfc3339());
+            entry.clone()
+        });
+        if let Some(result) = finished {
+            let _ = app.emit(RUN_FINISHED_EVENT, result);
+        }
+        evict_old_runs();
+    });
+    Ok(run_id)
+}
+
+/// The results so far of a running run, or all of them once it ended.
+#[tauri::command]
+#[specta::specta]
+pub fn get_run_result(run_id: String) -> Result<RunResult, String> {
+    runs()
+        .get(&run_id)
+        .map(|entry| entry.clone())
+        .ok_or_else(|| format!("Run '{}' not found", run_id))
+}
+
+/// Stop a run, aborting the request in flight. Returns `false` when the run
+/// isn't running (unknown or already ended).
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_run(run_id: String) -> Result<bool, String> {
+    match run_cancel_flags().get(&run_id) {
+        Some(entry) => {
+            entry.value().store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,126 @@
+//! Settings, kept in `settings.json` in the workspace dir. Keys this version
+//! doesn't know are left in the file, so settings written by a newer version
+//! survive. History retention stays in the history store and is only read
+//! and written through here.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde_json::{Map, Value};
+use tauri::AppHandle;
+use url::Url;
+
+use crate::helpers::collections::write_atomic;
+use crate::helpers::history::{get_history_retention, set_history_retention};
+use crate::helpers::proxy_rules::proxy_rule_for;
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::{ApiRequest, AppSettings, HistoryRetention};
+
+/// Serializes read-modify-write cycles on the settings file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Kept in `history_retention.json` rather than the settings file.
+const HISTORY_KEY: &str = "history";
+
+fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("settings.json"))
+}
+
+/// The file's JSON object, unknown keys included.
+fn load_raw(app: &AppHandle) -> Result<Map<String, Value>, String> {
+    match std::fs::read(store_path(app)?) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Settings from the file with defaults for missing keys, without `history`.
+pub(crate) fn load_settings(app: &AppHandle) -> Result<AppSettings, String> {
+    let raw = {
+        let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+        load_raw(app)?
+    };
+    serde_json::from_value(Value::Object(raw)).map_err(|e| e.to_string())
+}
+
+/// Fill what `req` leaves unset from the settings. The request's own values
+/// always win, and a matching `ProxyRule` wins over the default proxy.
+pub(crate) fn apply_settings(app: &AppHandle, req: &mut ApiRequest) -> Result<(), String> {
+    let settings = load_settings(app)?;
+    req.timeout_ms = req.timeout_ms.or(settings.timeout_ms);
+    req.connect_timeout_ms = req.connect_timeout_ms.or(settings.connect_timeout_ms);
+    req.follow_redirects = Some(req.follow_redirects.unwrap_or(settings.follow_redirects));
+    req.max_redirects = Some(req.max_redirects.unwrap_or(settings.max_redirects));
+    req.verify_ssl = Some(req.verify_ssl.unwrap_or(settings.verify_ssl));
+    req.max_response_bytes = req.max_response_bytes.or(settings.max_response_bytes);
+    if req.proxy.is_none() {
+        let has_rule = Url::parse(&req.url)
+            .ok()
+            .and_then(|url| proxy_rule_for(app, url.host_str()?))
+            .is_some();
+        if !has_rule {
+            req.proxy = settings.proxy;
+        }
+    }
+    if let Some(user_agent) = settings.user_agent {
+        let has_user_agent = req
+            .headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("user-agent"));
+        if !has_user_agent {
+            req.headers.push(("User-Agent".to_string(), user_agent));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_settings(app: AppHandle) -> Result<AppSettings, String> {
+    let mut settings = load_settings(&app)?;
+    settings.history = get_history_retention(app)?;
+    Ok(settings)
+}
+
+/// Change the settings named in `patch`, a JSON object shaped like
+/// `AppSettings`; others are left as they are. A key set to `null` clears an
+/// optional setting, and `history` may hold only the retention fields to
+/// change. Returns the settings after the change.
+#[tauri::command]
+#[specta::specta]
+pub fn update_settings(app: AppHandle, patch: Value) -> Result<AppSettings, String> {
+    let Value::Object(mut patch) = patch else {
+        return Err("Settings patch must be a JSON object".to_string());
+    };
+    let history_patch = patch.remove(HISTORY_KEY);
+
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut raw = load_raw(&app)?;
+    // Nested objects such as `proxy` are replaced whole.
+    raw.extend(patch);
+    raw.remove(HISTORY_KEY);
+    let mut settings: AppSettings =
+        serde_json::from_value(Value::Object(raw.clone())).map_err(|e| e.to_string())?;
+
+    settings.history = match history_patch {
+        Some(Value::Object(history_patch)) => {
+            let current = serde_json::to_value(get_history_retention(app.clone())?)
+                .map_err(|e| e.to_string())?;
+            let Value::Object(mut retention) = current else {
+                return Err("History retention is not an object".to_string());
+            };
+            retention.extend(history_patch);
+            let retention: HistoryRetention =
+                serde_json::from_value(Value::Object(retention)).map_err(|e| e.to_string())?;
+            set_history_retention(app.clone(), retention.clone())?;
+            retention
+        }
+        Some(_) => return Err("'history' must be a JSON object".to_string()),
+        None => get_history_retention(app.clone())?,
+    };
+
+    let json = serde_json::to_vec_pretty(&raw).map_err(|e| e.to_string())?;
+    write_atomic(&store_path(&app)?, &json)?;
+    Ok(settings)
+}
@@ -0,0 +1,316 @@
+//! Server-Sent Events (`text/event-stream`) over the REST request machinery.
+//! The response is parsed as it arrives and each event is pushed to the
+//! frontend; the stream stays open until the server ends it or `sse_close`.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use curl::easy::Easy;
+use dashmap::DashMap;
+use tauri::{AppHandle, Emitter};
+
+use crate::helpers::rest::{configure_streaming_request, status_text};
+use crate::types::{
+    ApiRequest, RequestError, RequestFailure, SseClosedEvent, SseConnectedEvent, SseKeepaliveEvent,
+    SseMessageEvent,
+};
+
+/// Event carrying `SseConnectedEvent` payloads.
+pub const SSE_CONNECTED_EVENT: &str = "sse-connected";
+
+/// Event carrying `SseMessageEvent` payloads.
+pub const SSE_MESSAGE_EVENT: &str = "sse-event";
+
+/// Event carrying `SseKeepaliveEvent` payloads.
+pub const SSE_KEEPALIVE_EVENT: &str = "sse-keepalive";
+
+/// Event carrying `SseClosedEvent` payloads.
+pub const SSE_CLOSED_EVENT: &str = "sse-closed";
+
+/// How much of a non-2xx response body is kept for the closed event.
+const ERROR_BODY_LIMIT: usize = 64 * 1024;
+
+static SSE_STREAMS: OnceLock<DashMap<String, Arc<AtomicBool>>> = OnceLock::new();
+
+/// Close flags of the open streams, keyed by stream id.
+fn sse_streams() -> &'static DashMap<String, Arc<AtomicBool>> {
+    SSE_STREAMS.get_or_init(DashMap::new)
+}
+
+fn now_ms() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as f64
+}
+
+enum SseItem {
+    Event {
+        event: String,
+        data: String,
+        id: Option<String>,
+        retry_ms: Option<u32>,
+    },
+    Comment(String),
+}
+
+/// Incremental parser for the event stream format of the HTML spec.
+#[derive(Default)]
+struct SseParser {
+    line: Vec<u8>,
+    /// The last chunk ended in CR, so a leading LF in the next one is part of it.
+    after_cr: bool,
+    started: bool,
+    event: String,
+    data: String,
+    last_id: Option<String>,
+    retry_ms: Option<u32>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8]) -> Vec<SseItem> {
+        let mut items = Vec::new();
+        for &byte in chunk {
+            if std::mem::take(&mut self.after_cr) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\r' => {
+                    self.after_cr = true;
+                    self.end_line(&mut items);
+                }
+                b'\n' => self.end_line(&mut items),
+                _ => self.line.push(byte),
+            }
+        }
+        items
+    }
+
+    fn end_line(&mut self, items: &mut Vec<SseItem>) {
+        let bytes = std::mem::take(&mut self.line);
+        let mut line = String::from_utf8_lossy(&bytes).into_owned();
+        if !std::mem::replace(&mut self.started, true) {
+            if let Some(rest) = line.strip_prefix('\u{feff}') {
+                line = rest.to_string();
+            }
+        }
+
+        if line.is_empty() {
+            // A blank line dispatches; without data lines there's nothing to send.
+            let event = std::mem::take(&mut self.event);
+            let retry_ms = self.retry_ms.take();
+            if self.data.is_empty() {
+                return;
+            }
+            let mut data = std::mem::take(&mut self.data);
+            data.pop();
+            items.push(SseItem::Event {
+                event: if event.is_empty() {
+                    "message".to_string()
+                } else {
+                    event
+                },
+                data,
+                id: self.last_id.clone(),
+                retry_ms,
+            });
+            return;
+        }
+        if let Some(comment) = line.strip_prefix(':') {
+            items.push(SseItem::Comment(comment.trim_start().to_string()));
+            return;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_str(), ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry_ms = value.parse().ok();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Runs the transfer to completion, emitting events as they arrive. Returns
+/// the error for the closed event.
+fn run_stream(
+    app: &AppHandle,
+    stream_id: &str,
+    mut easy: Easy,
+    follow_redirects: bool,
+    closed: &AtomicBool,
+) -> Option<String> {
+    let status = Cell::new(0u16);
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut parser = SseParser::default();
+    let mut error_body: Vec<u8> = Vec::new();
+
+    // The progress callback also fires while the stream is idle, so a close
+    // doesn't have to wait for the next event.
+    if let Err(e) = easy.progress(true) {
+        return Some(e.to_string());
+    }
+    let result = (|| {
+        let mut transfer = easy.transfer();
+        transfer.header_function(|line| {
+            let line = String::from_utf8_lossy(line);
+            let line = line.trim_end();
+            if line.starts_with("HTTP/") {
+                let code = line.split(' ').nth(1).and_then(|c| c.parse().ok());
+                status.set(code.unwrap_or(0));
+                headers.clear();
+            } else if line.is_empty() {
+                // Interim and followed-redirect responses aren't the stream.
+                let code = status.get();
+                let redirected = follow_redirects
+                    && (300..400).contains(&code)
+                    && headers
+                        .iter()
+                        .any(|(name, _)| name.eq_ignore_ascii_case("location"));
+                if code >= 200 && !redirected {
+                    let _ = app.emit(
+                        SSE_CONNECTED_EVENT,
+                        SseConnectedEvent {
+                            stream_id: stream_id.to_string(),
+                            status: code,
+                            status_text: status_text(code),
+                            headers: headers.clone(),
+                        },
+                    );
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+            true
+        })?;
+        transfer.write_function(|data| {
+            if closed.load(Ordering::SeqCst) {
+                // Returning a short count makes libcurl abort with a write error.
+                return Ok(0);
+            }
+            if !(200..300).contains(&status.get()) {
+                let room = ERROR_BODY_LIMIT.saturating_sub(error_body.len());
+                error_body.extend_from_slice(&data[..data.len().min(room)]);
+                return Ok(data.len());
+            }
+            for item in parser.feed(data) {
+                let _ = match item {
+                    SseItem::Event {
+                        event,
+                        data,
+                        id,
+                        retry_ms,
+                    } => app.emit(
+                        SSE_MESSAGE_EVENT,
+                        SseMessageEvent {
+                            stream_id: stream_id.to_string(),
+                            event,
+                            data,
+                            id,
+                            retry_ms,
+                            timestamp_ms: now_ms(),
+                        },
+                    ),
+                    SseItem::Comment(comment) => app.emit(
+                        SSE_KEEPALIVE_EVENT,
+                        SseKeepaliveEvent {
+                            stream_id: stream_id.to_string(),
+                            comment,
+                            timestamp_ms: now_ms(),
+                        },
+                    ),
+                };
+            }
+            Ok(data.len())
+        })?;
+        transfer.progress_function(|_, _, _, _| !closed.load(Ordering::SeqCst))?;
+        transfer.perform()
+    })();
+
+    if closed.load(Ordering::SeqCst) {
+        return None;
+    }
+    match result {
+        Err(e) => Some(e.to_string()),
+        Ok(()) if !(200..300).contains(&status.get()) => Some(format!(
+            "HTTP {}: {}",
+            status.get(),
+            String::from_utf8_lossy(&error_body)
+        )),
+        Ok(()) => None,
+    }
+}
+
+/// Open an event stream and return its id. `req.request_id` is used as the
+/// id when set, so listeners can be in place before any event arrives.
+///
+/// Events are emitted as `sse-connected`, `sse-event`, `sse-keepalive` and
+/// finally `sse-closed`, each carrying the stream id.
+#[tauri::command]
+#[specta::specta]
+pub async fn sse_connect(app: AppHandle, req: ApiRequest) -> Result<String, RequestFailure> {
+    let stream_id = req
+        .request_id
+        .clone()
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    if sse_streams().contains_key(&stream_id) {
+        return Err(RequestError::Other {
+            detail: format!("Stream '{}' is already open", stream_id),
+        }
+        .into());
+    }
+
+    let mut req = req;
+    if !req
+        .headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case("accept"))
+    {
+        req.headers
+            .push(("Accept".to_string(), "text/event-stream".to_string()));
+    }
+    let follow_redirects = req.follow_redirects.unwrap_or(true);
+    let mut easy = Easy::new();
+    configure_streaming_request(&app, &mut easy, req)?;
+
+    let closed = Arc::new(AtomicBool::new(false));
+    sse_streams().insert(stream_id.clone(), Arc::clone(&closed));
+    let id = stream_id.clone();
+    tokio::task::spawn_blocking(move || {
+        let error = run_stream(&app, &id, easy, follow_redirects, &closed);
+        sse_streams().remove(&id);
+        let _ = app.emit(
+            SSE_CLOSED_EVENT,
+            SseClosedEvent {
+                stream_id: id,
+                error,
+            },
+        );
+    });
+    Ok(stream_id)
+}
+
+/// Close a stream opened by `sse_connect`. Returns `false` when no stream with
+/// that id is open.
+#[tauri::command]
+#[specta::specta]
+pub fn sse_close(stream_id: String) -> Result<bool, String> {
+    match sse_streams().get(&stream_id) {
+        Some(entry) => {
+            entry.value().store(true, Ordering::SeqCst);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
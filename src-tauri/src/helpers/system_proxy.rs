@@ -0,0 +1,323 @@
+//! Proxy settings from the environment and the OS, for `ApiRequest::use_system_proxy`.
+//! Environment variables win; otherwise macOS and Windows system settings are
+//! read with `scutil --proxy` and `reg query`.
+
+use std::net::IpAddr;
+
+use url::Url;
+
+#[derive(Default)]
+struct ProxySettings {
+    http: Option<String>,
+    https: Option<String>,
+    /// `ALL_PROXY`, or the SOCKS proxy from the system settings.
+    fallback: Option<String>,
+    /// `NO_PROXY` style entries: `*`, host names, `.domain` suffixes, IPs, CIDR blocks.
+    bypass: Vec<String>,
+}
+
+impl ProxySettings {
+    fn is_empty(&self) -> bool {
+        self.http.is_none() && self.https.is_none() && self.fallback.is_none()
+    }
+}
+
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+fn from_env() -> ProxySettings {
+    ProxySettings {
+        http: env_var(&["http_proxy", "HTTP_PROXY"]),
+        https: env_var(&["https_proxy", "HTTPS_PROXY"]),
+        fallback: env_var(&["all_proxy", "ALL_PROXY"]),
+        bypass: env_var(&["no_proxy", "NO_PROXY"])
+            .map(|list| split_bypass_list(&list, ','))
+            .unwrap_or_default(),
+    }
+}
+
+fn split_bypass_list(list: &str, separator: char) -> Vec<String> {
+    list.split(separator)
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+fn from_system() -> ProxySettings {
+    let Ok(output) = std::process::Command::new("scutil").arg("--proxy").output() else {
+        return ProxySettings::default();
+    };
+    parse_scutil(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the `<dictionary>` dump `scutil --proxy` prints.
+#[cfg(target_os = "macos")]
+fn parse_scutil(text: &str) -> ProxySettings {
+    let mut values = std::collections::HashMap::new();
+    let mut exceptions = Vec::new();
+    let mut in_exceptions = false;
+    for line in text.lines().map(str::trim) {
+        if line.starts_with("ExceptionsList") {
+            in_exceptions = true;
+        } else if in_exceptions && line == "}" {
+            in_exceptions = false;
+        } else if let Some((key, value)) = line.split_once(" : ") {
+            if in_exceptions {
+                exceptions.push(value.trim().to_string());
+            } else {
+                values.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+    let proxy = |prefix: &str, scheme: &str| {
+        if values.get(&format!("{prefix}Enable")).map(String::as_str) != Some("1") {
+            return None;
+        }
+        let host = values.get(&format!("{prefix}Proxy"))?;
+        Some(match values.get(&format!("{prefix}Port")) {
+            Some(port) => format!("{scheme}://{host}:{port}"),
+            None => format!("{scheme}://{host}"),
+        })
+    };
+    ProxySettings {
+        http: proxy("HTTP", "http"),
+        https: proxy("HTTPS", "http"),
+        fallback: proxy("SOCKS", "socks5h"),
+        bypass: exceptions
+            .iter()
+            .map(|entry| entry.trim_start_matches('*').to_ascii_lowercase())
+            .collect(),
+    }
+}
+
+#[cfg(windows)]
+fn from_system() -> ProxySettings {
+    use std::os::windows::process::CommandExt;
+    // CREATE_NO_WINDOW, so no console flashes up.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let output = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+    match output {
+        Ok(output) => parse_wininet(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => ProxySettings::default(),
+    }
+}
+
+/// Parses the WinINET `ProxyEnable`/`ProxyServer`/`ProxyOverride` values from `reg query`.
+#[cfg(windows)]
+fn parse_wininet(text: &str) -> ProxySettings {
+    let value = |name: &str| {
+        text.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next()? == name).then(|| parts.skip(1).collect::<Vec<_>>().join(" "))
+        })
+    };
+    if value("ProxyEnable").as_deref() != Some("0x1") {
+        return ProxySettings::default();
+    }
+    let Some(server) = value("ProxyServer") else {
+        return ProxySettings::default();
+    };
+    let with_scheme = |addr: &str, scheme: &str| {
+        if addr.contains("://") {
+            addr.to_string()
+        } else {
+            format!("{scheme}://{addr}")
+        }
+    };
+    let mut settings = ProxySettings::default();
+    // Either one `host:port` for everything or `http=host:port;https=host:port;socks=...`.
+    if server.contains('=') {
+        for entry in server.split(';') {
+            match entry.split_once('=') {
+                Some(("http", addr)) => settings.http = Some(with_scheme(addr, "http")),
+                Some(("https", addr)) => settings.https = Some(with_scheme(addr, "http")),
+                Some(("socks", addr)) => settings.fallback = Some(with_scheme(addr, "socks4")),
+                _ => {}
+            }
+        }
+    } else {
+        settings.fallback = Some(with_scheme(&server, "http"));
+    }
+    settings.bypass = value("ProxyOverride")
+        .map(|list| split_bypass_list(&list, ';'))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| entry.trim_start_matches('*').to_string())
+        .collect();
+    settings
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn from_system() -> ProxySettings {
+    ProxySettings::default()
+}
+
+/// Whether `host` falls inside an `addr/prefix` CIDR block.
+fn in_cidr(host: &str, cidr: &str) -> bool {
+    let Some((network, prefix)) = cidr.split_once('/') else {
+        return false;
+    };
+    let (Ok(host), Ok(network), Ok(prefix)) = (
+        host.parse::<IpAddr>(),
+        network.parse::<IpAddr>(),
+        prefix.parse::<u32>(),
+    ) else {
+        return false;
+    };
+    match (host, network) {
+        (IpAddr::V4(host), IpAddr::V4(network)) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(host) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(host), IpAddr::V6(network)) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(host) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+fn bypassed(host: &str, bypass: &[String]) -> bool {
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase();
+    bypass.iter().any(|entry| {
+        // Entries may carry a port, which is not compared.
+        let entry = match entry.rsplit_once(':') {
+            Some((name, port))
+                if (!name.contains(':') || name.ends_with(']'))
+                    && port.bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                name
+            }
+            _ => entry.as_str(),
+        };
+        let entry = entry.trim_start_matches('[').trim_end_matches(']');
+        match entry {
+            "*" => true,
+            // WinINET's `<local>`: any name without a dot.
+            "<local>" => !host.contains('.'),
+            _ if entry.contains('/') => in_cidr(&host, entry),
+            _ => {
+                let suffix = entry.trim_start_matches('.');
+                host == suffix || host.ends_with(&format!(".{suffix}"))
+            }
+        }
+    })
+}
+
+/// Whether `url` matches a comma-separated `ProxyConfig::no_proxy` list.
+pub fn no_proxy_matches(url: &Url, no_proxy: &str) -> bool {
+    bypassed(
+        url.host_str().unwrap_or_default(),
+        &split_bypass_list(no_proxy, ','),
+    )
+}
+
+/// The proxy the environment or OS would use for `url`, or `None` to go direct.
+pub fn system_proxy_for(url: &Url) -> Option<String> {
+    let mut settings = from_env();
+    if settings.is_empty() {
+        // NO_PROXY still applies on top of the system's own exceptions.
+        let env_bypass = std::mem::take(&mut settings.bypass);
+        settings = from_system();
+        settings.bypass.extend(env_bypass);
+    }
+    if bypassed(url.host_str().unwrap_or_default(), &settings.bypass) {
+        return None;
+    }
+    let scheme_proxy = match url.scheme() {
+        "https" | "wss" => settings.https,
+        _ => settings.http,
+    };
+    scheme_proxy.or(settings.fallback)
+}
+
+/// The proxy URL with any credentials removed, for reporting.
+pub fn redact_proxy(proxy: &str) -> String {
+    match Url::parse(proxy) {
+        Ok(mut url) if !url.username().is_empty() || url.password().is_some() => {
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            url.to_string()
+        }
+        _ => proxy.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skips_proxy(url: &str, no_proxy: &str) -> bool {
+        no_proxy_matches(&Url::parse(url).unwrap(), no_proxy)
+    }
+
+    #[test]
+    fn leading_dot_matches_subdomains_only_on_a_label_boundary() {
+        assert!(skips_proxy("https://api.example.com/", ".example.com"));
+        assert!(skips_proxy("https://a.b.example.com/", ".example.com"));
+        assert!(skips_proxy("https://example.com/", ".example.com"));
+        assert!(!skips_proxy("https://badexample.com/", ".example.com"));
+        assert!(!skips_proxy(
+            "https://example.com.evil.net/",
+            ".example.com"
+        ));
+    }
+
+    #[test]
+    fn exact_hosts_match_themselves_and_their_subdomains() {
+        assert!(skips_proxy("http://intranet/", "intranet"));
+        assert!(skips_proxy("https://Docs.Example.ORG/", "docs.example.org"));
+        assert!(skips_proxy(
+            "https://eu.docs.example.org/",
+            "docs.example.org"
+        ));
+        assert!(!skips_proxy("https://example.org/", "docs.example.org"));
+        assert!(!skips_proxy(
+            "https://mydocs.example.org/",
+            "docs.example.org"
+        ));
+    }
+
+    #[test]
+    fn localhost_and_loopback_entries_match() {
+        let no_proxy = "localhost, 127.0.0.1";
+        assert!(skips_proxy("http://localhost:8080/", no_proxy));
+        assert!(skips_proxy("http://127.0.0.1:3000/", no_proxy));
+        assert!(!skips_proxy("http://127.0.0.2/", no_proxy));
+        assert!(!skips_proxy("http://notlocalhost/", no_proxy));
+    }
+
+    #[test]
+    fn entry_ports_are_ignored() {
+        assert!(skips_proxy("http://localhost:9000/", "localhost:8080"));
+        assert!(skips_proxy("http://[::1]:9000/", "[::1]:8080"));
+    }
+
+    #[test]
+    fn cidr_entries_match_addresses_in_the_block() {
+        assert!(skips_proxy("http://10.1.2.3/", "10.0.0.0/8"));
+        assert!(!skips_proxy("http://11.0.0.1/", "10.0.0.0/8"));
+        assert!(skips_proxy("http://[fd00::1]/", "fd00::/8"));
+    }
+
+    #[test]
+    fn wildcard_skips_every_host() {
+        assert!(skips_proxy("https://anything.test/", "*"));
+        assert!(!skips_proxy("https://anything.test/", ""));
+    }
+}
@@ -0,0 +1,174 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use curl::easy::{Easy, InfoType};
+use sha2::{Digest, Sha256};
+use x509_parser::extensions::GeneralName;
+use x509_parser::pem::parse_x509_pem;
+
+use crate::types::{CertificateInfo, TlsInfo};
+
+/// A leaf certificate expiring sooner than this is flagged in `TlsInfo`.
+const EXPIRY_WARNING_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// Picks the negotiated TLS parameters out of libcurl's informational log lines,
+/// which is the only place the curl crate exposes them.
+#[derive(Default)]
+pub struct TlsHandshake {
+    version: Option<String>,
+    cipher: Option<String>,
+    alpn: Option<String>,
+}
+
+impl TlsHandshake {
+    pub fn observe(&mut self, kind: InfoType, data: &[u8]) {
+        if !matches!(kind, InfoType::Text) {
+            return;
+        }
+        let text = String::from_utf8_lossy(data);
+        let line = text.trim();
+        // "SSL connection using TLSv1.3 / TLS_AES_256_GCM_SHA384 / X25519 / RSASSA-PSS"
+        if let Some(rest) = line.strip_prefix("SSL connection using ") {
+            let mut parts = rest.split(" / ").map(str::trim);
+            self.version = parts.next().map(str::to_string);
+            self.cipher = parts.next().map(str::to_string);
+        } else if line.starts_with("ALPN: curl offers") || line.starts_with("ALPN, offering") {
+            // A new handshake; whatever the previous connection agreed no longer applies.
+            self.alpn = None;
+        } else if let Some(rest) = line
+            .strip_prefix("ALPN: server accepted ")
+            .or_else(|| line.strip_prefix("ALPN, server accepted to use "))
+        {
+            // The second form is what curl printed before 7.87.
+            self.alpn = Some(rest.trim().to_string());
+        }
+    }
+
+    pub fn into_info(self, easy: &Easy) -> TlsInfo {
+        let parsed: Vec<(CertificateInfo, i64)> = peer_certificate_pems(easy)
+            .iter()
+            .filter_map(|pem| certificate_info(pem))
+            .collect();
+        let leaf_expiring = parsed
+            .first()
+            .is_some_and(|(_, not_after)| not_after - Utc::now().timestamp() < EXPIRY_WARNING_SECS);
+        TlsInfo {
+            version: self.version,
+            cipher: self.cipher,
+            alpn: self.alpn,
+            certificates: parsed.into_iter().map(|(cert, _)| cert).collect(),
+            leaf_expiring,
+        }
+    }
+}
+
+/// The `Cert:` PEM blocks of `CURLINFO_CERTINFO`, which the curl crate doesn't wrap.
+/// Needs `certinfo(true)` before the transfer.
+fn peer_certificate_pems(easy: &Easy) -> Vec<String> {
+    let mut info: *mut curl_sys::curl_certinfo = std::ptr::null_mut();
+    // SAFETY: `easy.raw()` is a live handle and CERTINFO writes a pointer owned by
+    // the handle, valid until the next transfer; the lists are only read here.
+    unsafe {
+        let code = curl_sys::curl_easy_getinfo(easy.raw(), curl_sys::CURLINFO_CERTINFO, &mut info);
+        if code != curl_sys::CURLE_OK || info.is_null() {
+            return Vec::new();
+        }
+        let count = (*info).num_of_certs.max(0) as usize;
+        let mut pems = Vec::with_capacity(count);
+        for i in 0..count {
+            let mut node = *(*info).certinfo.add(i);
+            while !node.is_null() {
+                let entry = std::ffi::CStr::from_ptr((*node).data).to_string_lossy();
+                if let Some(pem) = entry.strip_prefix("Cert:") {
+                    pems.push(pem.to_string());
+                }
+                node = (*node).next;
+            }
+        }
+        pems
+    }
+}
+
+/// Identifies the certificate a server presented on the last handshake.
+pub struct PeerCertificate {
+    /// As in `CertificateInfo::fingerprint_sha256`.
+    pub fingerprint_sha256: String,
+    /// The leaf's public key as a `sha256//BASE64` pin for `pinned_public_key`.
+    pub public_key_pin: String,
+}
+
+pub fn leaf_certificate(easy: &Easy) -> Option<PeerCertificate> {
+    let pems = peer_certificate_pems(easy);
+    let (_, pem) = parse_x509_pem(pems.first()?.as_bytes()).ok()?;
+    let cert = pem.parse_x509().ok()?;
+    Some(PeerCertificate {
+        fingerprint_sha256: fingerprint_hex(&Sha256::digest(&pem.contents)),
+        public_key_pin: format!(
+            "sha256//{}",
+            BASE64.encode(Sha256::digest(cert.public_key().raw))
+        ),
+    })
+}
+
+fn rfc3339(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Colon-separated uppercase hex, the way browsers show fingerprints.
+pub fn fingerprint_hex(digest: &[u8]) -> String {
+    digest
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn general_name(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DNSName(dns) => Some(format!("DNS:{}", dns)),
+        GeneralName::RFC822Name(email) => Some(format!("email:{}", email)),
+        GeneralName::URI(uri) => Some(format!("URI:{}", uri)),
+        GeneralName::IPAddress(bytes) => match bytes.len() {
+            4 => <[u8; 4]>::try_from(*bytes)
+                .ok()
+                .map(|ip| format!("IP:{}", Ipv4Addr::from(ip))),
+            16 => <[u8; 16]>::try_from(*bytes)
+                .ok()
+                .map(|ip| format!("IP:{}", Ipv6Addr::from(ip))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The parsed certificate and its `notAfter` in Unix seconds.
+fn certificate_info(pem: &str) -> Option<(CertificateInfo, i64)> {
+    let (_, pem) = parse_x509_pem(pem.as_bytes()).ok()?;
+    let cert = pem.parse_x509().ok()?;
+    let subject_alt_names = cert
+        .subject_alternative_name()
+        .ok()
+        .flatten()
+        .map(|ext| {
+            ext.value
+                .general_names
+                .iter()
+                .filter_map(general_name)
+                .collect()
+        })
+        .unwrap_or_default();
+    let not_after = cert.validity().not_after.timestamp();
+    let info = CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        subject_alt_names,
+        not_before: rfc3339(cert.validity().not_before.timestamp()),
+        not_after: rfc3339(not_after),
+        serial: cert.raw_serial_as_string(),
+        fingerprint_sha256: fingerprint_hex(&Sha256::digest(&pem.contents)),
+    };
+    Some((info, not_after))
+}
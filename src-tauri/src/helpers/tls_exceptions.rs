@@ -0,0 +1,117 @@
+//! Per-host trust for self-signed certificates, kept in `tls_exceptions.json`
+//! in the workspace dir. A request that fails verification against a listed
+//! host is retried only if the server still presents the same certificate.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use tauri::AppHandle;
+
+use crate::helpers::workspaces::workspace_dir;
+use crate::types::TlsException;
+
+/// Serializes read-modify-write cycles on the exceptions file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(workspace_dir(app)?.join("tls_exceptions.json"))
+}
+
+fn load(app: &AppHandle) -> Result<Vec<TlsException>, String> {
+    match std::fs::read(store_path(app)?) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn save(app: &AppHandle, exceptions: &[TlsException]) -> Result<(), String> {
+    let path = store_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_vec_pretty(exceptions).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Accepts hex with or without colons, in either case.
+fn normalize_fingerprint(fingerprint: &str) -> Result<String, String> {
+    let hex: String = fingerprint
+        .chars()
+        .filter(|c| !matches!(c, ':' | ' '))
+        .collect::<String>()
+        .to_ascii_uppercase();
+    if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Invalid SHA-256 fingerprint '{}': expected 32 hex bytes",
+            fingerprint
+        ));
+    }
+    Ok(hex
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair).into_owned())
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// The exception stored for `host`, if any.
+pub(crate) fn tls_exception_for(app: &AppHandle, host: &str) -> Option<TlsException> {
+    let host = normalize_host(host);
+    let _guard = STORE_LOCK.lock().ok()?;
+    load(app).ok()?.into_iter().find(|e| e.host == host)
+}
+
+/// Trust `host`'s certificate with this SHA-256 fingerprint, replacing any
+/// fingerprint stored for it before.
+#[tauri::command]
+#[specta::specta]
+pub fn add_tls_exception(
+    app: AppHandle,
+    host: String,
+    fingerprint_sha256: String,
+) -> Result<TlsException, String> {
+    let host = normalize_host(&host);
+    if host.is_empty() {
+        return Err("Host is empty".to_string());
+    }
+    let exception = TlsException {
+        host,
+        fingerprint_sha256: normalize_fingerprint(&fingerprint_sha256)?,
+        added_at: Utc::now().to_rfc3339(),
+    };
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut exceptions = load(&app)?;
+    exceptions.retain(|e| e.host != exception.host);
+    exceptions.push(exception.clone());
+    save(&app, &exceptions)?;
+    Ok(exception)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_tls_exceptions(app: AppHandle) -> Result<Vec<TlsException>, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    load(&app)
+}
+
+/// Returns `false` when the host had no exception.
+#[tauri::command]
+#[specta::specta]
+pub fn remove_tls_exception(app: AppHandle, host: String) -> Result<bool, String> {
+    let host = normalize_host(&host);
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut exceptions = load(&app)?;
+    let before = exceptions.len();
+    exceptions.retain(|e| e.host != host);
+    if exceptions.len() == before {
+        return Ok(false);
+    }
+    save(&app, &exceptions)?;
+    Ok(true)
+}
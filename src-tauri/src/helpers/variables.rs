@@ -0,0 +1,455 @@
+//! `{{name}}` placeholders in a request, filled from the environment and the
+//! request's own `variables` just before sending. `\{{name}}` is sent as a
+//! literal `{{name}}`.
+//!
+//! Names starting with `$` are dynamic variables, generated on every send:
+//! `$uuid`, `$timestamp` (Unix seconds), `$isoTimestamp`, `$randomInt(min,max)`
+//! (inclusive), `$randomAlphaNumeric(n)` and `$base64(text)`. Each occurrence
+//! gets its own value; a `:key` suffix, as in `{{$uuid:order}}`, makes every
+//! occurrence with the same expression and key share one. The values used
+//! are returned in `dynamic_values`; a request carrying them gets the same
+//! ones again, which is how a send reuses what its preview showed.
+//!
+//! Secret variables are read from the keychain only when sending; previews
+//! show them as `****`.
+
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{SecondsFormat, Utc};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use tauri::AppHandle;
+
+use crate::helpers::environments::{
+    environment_variables, read_secret, VariableValue, SECRET_MASK,
+};
+use crate::types::{ApiRequest, AuthType, BodyType, MultipartValue, RequestError};
+
+/// Evaluate a dynamic variable, `name` being the placeholder after its `$`
+/// without any `:key` suffix.
+fn dynamic_value(name: &str) -> Result<String, String> {
+    let (function, args) = match name.split_once('(') {
+        Some((function, args)) => {
+            let args = args
+                .strip_suffix(')')
+                .ok_or_else(|| "missing closing parenthesis".to_string())?;
+            (function.trim(), Some(args))
+        }
+        None => (name.trim(), None),
+    };
+    let no_args = || match args {
+        Some(args) if !args.trim().is_empty() => Err(format!("${} takes no arguments", function)),
+        _ => Ok(()),
+    };
+    match function {
+        "uuid" => no_args().map(|()| uuid::Uuid::new_v4().to_string()),
+        "timestamp" => no_args().map(|()| Utc::now().timestamp().to_string()),
+        "isoTimestamp" => {
+            no_args().map(|()| Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true))
+        }
+        "randomInt" => {
+            let bounds = args
+                .and_then(|args| args.split_once(','))
+                .and_then(|(min, max)| {
+                    Some((
+                        min.trim().parse::<i64>().ok()?,
+                        max.trim().parse::<i64>().ok()?,
+                    ))
+                })
+                .ok_or_else(|| "expected $randomInt(min,max) with integer bounds".to_string())?;
+            if bounds.0 > bounds.1 {
+                return Err(format!("min {} is greater than max {}", bounds.0, bounds.1));
+            }
+            Ok(rand::thread_rng()
+                .gen_range(bounds.0..=bounds.1)
+                .to_string())
+        }
+        "randomAlphaNumeric" => {
+            let length = args
+                .and_then(|args| args.trim().parse::<usize>().ok())
+                .ok_or_else(|| "expected $randomAlphaNumeric(length)".to_string())?;
+            Ok(rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(length)
+                .map(char::from)
+                .collect())
+        }
+        "base64" => args
+            .map(|text| BASE64.encode(text))
+            .ok_or_else(|| "expected $base64(text)".to_string()),
+        _ => Err(format!("unknown function ${}", function)),
+    }
+}
+
+/// Split a dynamic variable's `:key` suffix off. The key follows the closing
+/// parenthesis when there are arguments, so `$base64(user:pass)` has none.
+fn split_reuse_key(name: &str) -> (&str, Option<&str>) {
+    let search_from = name.rfind(')').unwrap_or(0);
+    match name[search_from..].find(':') {
+        Some(colon) => {
+            let colon = search_from + colon;
+            (&name[..colon], Some(name[colon + 1..].trim()))
+        }
+        None => (name, None),
+    }
+}
+
+struct Resolver<'a> {
+    variables: &'a HashMap<String, VariableValue>,
+    mask_secrets: bool,
+    missing: Vec<String>,
+    /// Values of keyed dynamic variables, by expression and key.
+    reused: HashMap<String, String>,
+    /// Values from an earlier resolve, used in order while the placeholders match.
+    seeded: &'a [(String, String)],
+    /// Every dynamic value filled in, by placeholder.
+    generated: Vec<(String, String)>,
+    /// Secrets already read from the keychain, by name.
+    secrets: HashMap<String, String>,
+    error: Option<RequestError>,
+}
+
+impl Resolver<'_> {
+    /// A variable's value, `None` when it's missing or its secret can't be read.
+    fn variable(&mut self, name: &str) -> Option<String> {
+        let (key, account) = match self.variables.get(name)? {
+            VariableValue::Plain(value) => return Some(value.clone()),
+            VariableValue::Secret { .. } if self.mask_secrets => {
+                return Some(SECRET_MASK.to_string())
+            }
+            VariableValue::Secret { key, account } => (key, account),
+        };
+        if let Some(value) = self.secrets.get(name) {
+            return Some(value.clone());
+        }
+        match read_secret(account, key) {
+            Ok(value) => {
+                self.secrets.insert(name.to_string(), value.clone());
+                Some(value)
+            }
+            Err(detail) => {
+                self.error.get_or_insert(RequestError::SecretUnavailable {
+                    key: key.clone(),
+                    detail,
+                });
+                None
+            }
+        }
+    }
+
+    fn dynamic(&mut self, name: &str) -> Option<String> {
+        if let Some(value) = self.reused.get(name) {
+            return Some(value.clone());
+        }
+        let (expression, key) = split_reuse_key(&name[1..]);
+        let value = match self.seeded.get(self.generated.len()) {
+            Some((seeded, value)) if seeded == name => Ok(value.clone()),
+            _ => dynamic_value(expression),
+        };
+        match value {
+            Ok(value) => {
+                if key.is_some() {
+                    self.reused.insert(name.to_string(), value.clone());
+                }
+                self.generated.push((name.to_string(), value.clone()));
+                Some(value)
+            }
+            Err(detail) => {
+                self.error
+                    .get_or_insert(RequestError::InvalidDynamicVariable {
+                        expression: name.to_string(),
+                        detail,
+                    });
+                None
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            if rest[..start].ends_with('\\') {
+                out.push_str(&rest[..start - 1]);
+                out.push_str("{{");
+                rest = &rest[start + 2..];
+                continue;
+            }
+            let Some(end) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            out.push_str(&rest[..start]);
+            let placeholder = &rest[start..start + 2 + end + 2];
+            let name = placeholder[2..placeholder.len() - 2].trim();
+            if name.starts_with('$') {
+                match self.dynamic(name) {
+                    Some(value) => out.push_str(&value),
+                    None => out.push_str(placeholder),
+                }
+                rest = &rest[start + placeholder.len()..];
+                continue;
+            }
+            match self.variable(name) {
+                Some(value) => out.push_str(&value),
+                None if self.error.is_some() => out.push_str(placeholder),
+                None => {
+                    if !self.missing.iter().any(|missing| missing == name) {
+                        self.missing.push(name.to_string());
+                    }
+                    out.push_str(placeholder);
+                }
+            }
+            rest = &rest[start + placeholder.len()..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn in_place(&mut self, text: &mut String) {
+        if text.contains("{{") {
+            *text = self.text(text);
+        }
+    }
+
+    fn optional(&mut self, text: &mut Option<String>) {
+        if let Some(text) = text {
+            self.in_place(text);
+        }
+    }
+
+    fn pairs(&mut self, pairs: &mut [(String, String)]) {
+        for (name, value) in pairs {
+            self.in_place(name);
+            self.in_place(value);
+        }
+    }
+
+    fn auth(&mut self, auth: &mut AuthType) {
+        match auth {
+            AuthType::None => {}
+            AuthType::Basic { username, password } => {
+                self.in_place(username);
+                self.in_place(password);
+            }
+            AuthType::Ntlm {
+                username,
+                password,
+                domain,
+            } => {
+                self.in_place(username);
+                self.in_place(password);
+                self.optional(domain);
+            }
+            AuthType::Bearer { token }
+            | AuthType::OAuth2 {
+                access_token: token,
+            } => self.in_place(token),
+            AuthType::ApiKey { key, value, .. } => {
+                self.in_place(key);
+                self.in_place(value);
+            }
+            AuthType::Negotiate {
+                username, password, ..
+            } => {
+                self.optional(username);
+                self.optional(password);
+            }
+            AuthType::Jwt {
+                secret_or_key_pem,
+                claims_json,
+                header_overrides,
+                ..
+            } => {
+                self.in_place(secret_or_key_pem);
+                self.in_place(claims_json);
+                self.optional(header_overrides);
+            }
+        }
+    }
+
+    fn body(&mut self, body: &mut BodyType) {
+        match body {
+            BodyType::None
+            | BodyType::Binary { .. }
+            | BodyType::RawFile { .. }
+            | BodyType::BinaryFile { .. } => {}
+            BodyType::Raw { content, .. } => self.in_place(content),
+            BodyType::FormUrlEncoded { fields } => {
+                *fields = fields
+                    .drain()
+                    .map(|(name, value)| (self.text(&name), self.text(&value)))
+                    .collect();
+            }
+            BodyType::Multipart { fields } => {
+                for field in fields {
+                    self.in_place(&mut field.name);
+                    if let MultipartValue::Text(text) = &mut field.value {
+                        self.in_place(text);
+                    }
+                }
+            }
+            BodyType::GraphQL {
+                query,
+                variables,
+                operation_name,
+            } => {
+                self.in_place(query);
+                self.optional(variables);
+                self.optional(operation_name);
+            }
+            BodyType::Soap {
+                action,
+                envelope_body,
+                ..
+            } => {
+                self.in_place(action);
+                self.in_place(envelope_body);
+            }
+            BodyType::JsonRpc { method, params, id } => {
+                self.in_place(method);
+                self.optional(params);
+                self.optional(id);
+            }
+            BodyType::JsonRpcBatch { calls } => {
+                for call in calls {
+                    self.in_place(&mut call.method);
+                    self.optional(&mut call.params);
+                    self.optional(&mut call.id);
+                }
+            }
+        }
+    }
+}
+
+/// Fill the placeholders in the URL, headers, query params, cookies, auth
+/// and text bodies from `variables`. Dynamic variables come from
+/// `req.dynamic_values` where they still match, and are generated otherwise;
+/// the returned request lists the ones used. With `mask_secrets`, secrets
+/// become `****` instead of being read.
+pub(crate) fn resolve_with(
+    mut req: ApiRequest,
+    variables: &HashMap<String, VariableValue>,
+    mask_secrets: bool,
+) -> Result<ApiRequest, RequestError> {
+    let seeded = std::mem::take(&mut req.dynamic_values);
+    let mut resolver = Resolver {
+        variables,
+        mask_secrets,
+        missing: Vec::new(),
+        reused: HashMap::new(),
+        seeded: &seeded,
+        generated: Vec::new(),
+        secrets: HashMap::new(),
+        error: None,
+    };
+    resolver.in_place(&mut req.url);
+    resolver.pairs(&mut req.headers);
+    resolver.pairs(&mut req.query_params);
+    for cookie in &mut req.cookies {
+        resolver.in_place(&mut cookie.value);
+    }
+    resolver.auth(&mut req.auth);
+    resolver.body(&mut req.body);
+    if let Some(error) = resolver.error {
+        return Err(error);
+    }
+    if !resolver.missing.is_empty() {
+        return Err(RequestError::UnresolvedVariables {
+            names: resolver.missing,
+        });
+    }
+    req.dynamic_values = resolver.generated;
+    Ok(req)
+}
+
+/// `env_id`'s variables (the active environment's when `None`) under the
+/// request's own `variables`.
+fn request_variables(
+    app: &AppHandle,
+    req: &ApiRequest,
+    env_id: Option<&str>,
+) -> Result<HashMap<String, VariableValue>, String> {
+    let mut variables = environment_variables(app, env_id)?;
+    variables.extend(
+        req.variables
+            .iter()
+            .map(|(name, value)| (name.clone(), VariableValue::Plain(value.clone()))),
+    );
+    Ok(variables)
+}
+
+/// Resolve from `req.environment_id`, as `rest_request` does before sending.
+pub(crate) fn resolve_for_send(
+    app: &AppHandle,
+    req: ApiRequest,
+) -> Result<ApiRequest, RequestError> {
+    let variables = request_variables(app, &req, req.environment_id.as_deref())?;
+    resolve_with(req, &variables, false)
+}
+
+/// Like `resolve_for_send`, with secrets shown as `****`.
+pub(crate) fn resolve_for_preview(
+    app: &AppHandle,
+    req: ApiRequest,
+) -> Result<ApiRequest, RequestError> {
+    let variables = request_variables(app, &req, req.environment_id.as_deref())?;
+    resolve_with(req, &variables, true)
+}
+
+/// The request with its `{{variable}}` placeholders filled, as it would be
+/// sent but with secrets shown as `****`. Dynamic variables get new values
+/// unless `req.dynamic_values` has them; to send the ones returned, send the
+/// original request with the returned `dynamic_values`. `env_id` overrides
+/// `req.environment_id`.
+#[tauri::command]
+#[specta::specta]
+pub fn resolve_request(
+    app: AppHandle,
+    req: ApiRequest,
+    env_id: Option<String>,
+) -> Result<ApiRequest, RequestError> {
+    let env_id = env_id.or_else(|| req.environment_id.clone());
+    let variables = request_variables(&app, &req, env_id.as_deref())?;
+    resolve_with(req, &variables, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returned_dynamic_values_are_reused() {
+        let req = ApiRequest {
+            url: "https://example.com/{{$uuid}}?n={{$randomInt(1,1000000)}}".to_string(),
+            headers: vec![
+                ("Idempotency-Key".to_string(), "{{$uuid:key}}".to_string()),
+                ("X-Retry-Key".to_string(), "{{$uuid:key}}".to_string()),
+            ],
+            ..Default::default()
+        };
+        let variables = HashMap::new();
+        let first = resolve_with(req.clone(), &variables, true).unwrap();
+        assert_eq!(first.dynamic_values.len(), 3);
+        assert_eq!(first.headers[0].1, first.headers[1].1);
+
+        let again = ApiRequest {
+            dynamic_values: first.dynamic_values.clone(),
+            ..req.clone()
+        };
+        let second = resolve_with(again, &variables, false).unwrap();
+        assert_eq!(second.url, first.url);
+        assert_eq!(second.headers, first.headers);
+        assert_eq!(second.dynamic_values, first.dynamic_values);
+
+        // Values for placeholders that moved are generated afresh.
+        let changed = ApiRequest {
+            url: "https://example.com/{{$timestamp}}".to_string(),
+            dynamic_values: first.dynamic_values,
+            ..req
+        };
+        let third = resolve_with(changed, &variables, false).unwrap();
+        assert_eq!(third.dynamic_values[0].0, "$timestamp");
+        assert_ne!(third.headers[0].1, first.headers[0].1);
+    }
+}
@@ -0,0 +1,246 @@
+//! Workspaces, each with its own `workspaces/<id>/` dir under the app data
+//! dir for collections, environments, history, settings, auth profiles,
+//! proxy rules, TLS exceptions and proto registries. Which ones exist
+//! and which is active is kept in `workspaces.json`. Session cookie jars are
+//! only held in memory and are dropped on a switch.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::helpers::collections::write_atomic;
+use crate::helpers::cookie_jar::clear_all_session_jars;
+use crate::helpers::environments::delete_workspace_secrets;
+use crate::helpers::oauth::delete_workspace_auth_secrets;
+use crate::types::Workspace;
+
+pub(crate) const DEFAULT_WORKSPACE_ID: &str = "default";
+
+/// Event carrying the `Workspace` switched to. Everything read from the
+/// previous workspace should be reloaded.
+pub const WORKSPACE_SWITCHED_EVENT: &str = "workspace-switched";
+
+/// What lived directly under the app data dir before workspaces, moved into
+/// the default workspace at startup.
+const WORKSPACE_ENTRIES: [&str; 8] = [
+    "collections",
+    "environments.json",
+    "history",
+    "history_retention.json",
+    "proto_registries",
+    "proxy_rules.json",
+    "settings.json",
+    "tls_exceptions.json",
+];
+
+/// Serializes read-modify-write cycles on the workspaces file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize)]
+struct Store {
+    active_id: String,
+    workspaces: Vec<Workspace>,
+}
+
+impl Store {
+    fn get(&self, id: &str) -> Result<&Workspace, String> {
+        self.workspaces
+            .iter()
+            .find(|workspace| workspace.id == id)
+            .ok_or_else(|| format!("Workspace '{}' not found", id))
+    }
+}
+
+fn default_workspace() -> Workspace {
+    Workspace {
+        id: DEFAULT_WORKSPACE_ID.to_string(),
+        name: "Default".to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        is_default: true,
+    }
+}
+
+fn data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+fn store_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(data_dir(app)?.join("workspaces.json"))
+}
+
+/// Ids become dir names, so only the default id and UUIDs are accepted.
+fn dir_for(app: &AppHandle, id: &str) -> Result<PathBuf, String> {
+    if id != DEFAULT_WORKSPACE_ID && uuid::Uuid::parse_str(id).is_err() {
+        return Err(format!("Invalid workspace id '{}'", id));
+    }
+    Ok(data_dir(app)?.join("workspaces").join(id))
+}
+
+fn load(app: &AppHandle) -> Result<Store, String> {
+    let mut store: Store = match std::fs::read(store_path(app)?) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string())?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Store {
+            active_id: DEFAULT_WORKSPACE_ID.to_string(),
+            workspaces: Vec::new(),
+        },
+        Err(e) => return Err(e.to_string()),
+    };
+    if !store
+        .workspaces
+        .iter()
+        .any(|workspace| workspace.is_default)
+    {
+        store.workspaces.insert(0, default_workspace());
+    }
+    if store.get(&store.active_id).is_err() {
+        store.active_id = DEFAULT_WORKSPACE_ID.to_string();
+    }
+    Ok(store)
+}
+
+fn save(app: &AppHandle, store: &Store) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(store).map_err(|e| e.to_string())?;
+    write_atomic(&store_path(app)?, &json)
+}
+
+/// Where the active workspace keeps its data.
+pub(crate) fn workspace_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    dir_for(app, &load(app)?.active_id)
+}
+
+pub(crate) fn active_workspace_id(app: &AppHandle) -> Result<String, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    Ok(load(app)?.active_id)
+}
+
+/// Move data saved before workspaces existed into the default workspace.
+/// Runs at startup, before anything reads the stores. Entries are moved even
+/// when the workspaces file exists, as some stores joined workspaces later.
+pub(crate) fn migrate(app: &AppHandle) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let data_dir = data_dir(app)?;
+    let default_dir = dir_for(app, DEFAULT_WORKSPACE_ID)?;
+    std::fs::create_dir_all(&default_dir).map_err(|e| e.to_string())?;
+    for entry in WORKSPACE_ENTRIES {
+        let from = data_dir.join(entry);
+        let to = default_dir.join(entry);
+        if from.exists() && !to.exists() {
+            std::fs::rename(&from, &to).map_err(|e| e.to_string())?;
+        }
+    }
+    if store_path(app)?.exists() {
+        return Ok(());
+    }
+    save(
+        app,
+        &Store {
+            active_id: DEFAULT_WORKSPACE_ID.to_string(),
+            workspaces: vec![default_workspace()],
+        },
+    )
+}
+
+/// Make `id` active, drop the session cookie jars, and tell the frontend.
+fn activate(app: &AppHandle, store: &mut Store, id: &str) -> Result<Workspace, String> {
+    let workspace = store.get(id)?.clone();
+    std::fs::create_dir_all(dir_for(app, id)?).map_err(|e| e.to_string())?;
+    store.active_id = workspace.id.clone();
+    save(app, store)?;
+    clear_all_session_jars();
+    let _ = app.emit(WORKSPACE_SWITCHED_EVENT, workspace.clone());
+    Ok(workspace)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn create_workspace(app: AppHandle, name: String) -> Result<Workspace, String> {
+    if name.trim().is_empty() {
+        return Err("Workspace name is empty".to_string());
+    }
+    let workspace = Workspace {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        created_at: Utc::now().to_rfc3339(),
+        is_default: false,
+    };
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load(&app)?;
+    std::fs::create_dir_all(dir_for(&app, &workspace.id)?).map_err(|e| e.to_string())?;
+    store.workspaces.push(workspace.clone());
+    save(&app, &store)?;
+    Ok(workspace)
+}
+
+/// Every workspace, the default one first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_workspaces(app: AppHandle) -> Result<Vec<Workspace>, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    Ok(load(&app)?.workspaces)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_active_workspace(app: AppHandle) -> Result<Workspace, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let store = load(&app)?;
+    store.get(&store.active_id).cloned()
+}
+
+/// Make a workspace active. Emits `workspace-switched` unless it already was.
+#[tauri::command]
+#[specta::specta]
+pub fn switch_workspace(app: AppHandle, workspace_id: String) -> Result<Workspace, String> {
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load(&app)?;
+    if store.active_id == workspace_id {
+        return store.get(&workspace_id).cloned();
+    }
+    activate(&app, &mut store, &workspace_id)
+}
+
+/// Delete a workspace and everything in it, secrets included. The default
+/// workspace can't be deleted; deleting the active one switches to it.
+/// Returns `false` when there was no such workspace.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_workspace(app: AppHandle, workspace_id: String) -> Result<bool, String> {
+    if workspace_id == DEFAULT_WORKSPACE_ID {
+        return Err("The default workspace can't be deleted".to_string());
+    }
+    let dir = dir_for(&app, &workspace_id)?;
+    {
+        let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+        if load(&app)?.get(&workspace_id).is_err() {
+            return Ok(false);
+        }
+    }
+    // Not under `STORE_LOCK`: the environments and auth profile stores take
+    // their own lock before this one.
+    delete_workspace_secrets(&dir, &workspace_id)?;
+    delete_workspace_auth_secrets(&dir, &workspace_id)?;
+
+    let _guard = STORE_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut store = load(&app)?;
+    if store.active_id == workspace_id {
+        activate(&app, &mut store, DEFAULT_WORKSPACE_ID)?;
+    }
+    store
+        .workspaces
+        .retain(|workspace| workspace.id != workspace_id);
+    save(&app, &store)?;
+    remove_dir(&dir)?;
+    Ok(true)
+}
+
+fn remove_dir(dir: &Path) -> Result<(), String> {
+    match std::fs::remove_dir_all(dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -28,17 +28,53 @@ pub fn run() {
         .typ::<types::ApiResponse>()
         .typ::<types::Methods>()
         .typ::<types::AuthType>()
+        .typ::<types::JwtAlgorithm>()
         .typ::<types::BodyType>()
+        .typ::<types::SoapVersion>()
+        .typ::<types::JsonRpcCall>()
+        .typ::<types::JsonRpcError>()
         .typ::<types::Cookie>()
+        .typ::<types::CookieImport>()
         .typ::<types::ResponseRenderer>()
         .typ::<types::TimingInfo>()
         .typ::<types::RedirectEntry>()
+        .typ::<types::RequestProgressEvent>()
+        .typ::<types::UploadProgressEvent>()
+        .typ::<types::ResolveOverride>()
+        .typ::<types::IpVersion>()
+        .typ::<types::ProxyKind>()
+        .typ::<types::ProxyAuthScheme>()
+        .typ::<types::ProxyRule>()
+        .typ::<types::ProxyRuleTarget>()
+        .typ::<types::NetrcMode>()
+        .typ::<types::TlsVersion>()
+        .typ::<types::TcpKeepalive>()
+        .typ::<types::ClientCert>()
+        .typ::<types::VerboseEntry>()
+        .typ::<types::RequestPreview>()
+        .typ::<types::RetryPolicy>()
+        .typ::<types::ConnectionInfo>()
+        .typ::<types::TlsInfo>()
+        .typ::<types::TlsException>()
+        .typ::<types::RequestError>()
+        .typ::<types::OAuth2Config>()
+        .typ::<types::OAuth2Token>()
+        .typ::<types::OAuth2ClientCredentialsConfig>()
+        .typ::<types::OAuth2DeviceConfig>()
+        .typ::<types::OAuth2DeviceAuthorization>()
+        .typ::<types::OAuth2DevicePollEvent>()
+        .typ::<types::AuthProfile>()
         // ── WebSocket types ─────────────────────────────────────────────────
         .typ::<types::WsConnectRequest>()
         .typ::<types::WsConnectResponse>()
         .typ::<types::WsIncomingMessage>()
         .typ::<types::WsClosedEvent>()
         .typ::<types::WsSendRequest>()
+        // ── Server-Sent Events types ────────────────────────────────────────
+        .typ::<types::SseConnectedEvent>()
+        .typ::<types::SseMessageEvent>()
+        .typ::<types::SseKeepaliveEvent>()
+        .typ::<types::SseClosedEvent>()
         // ── Socket.IO types ─────────────────────────────────────────────────
         .typ::<types::SioConnectRequest>()
         .typ::<types::SioConnectResponse>()
@@ -59,18 +95,155 @@ pub fn run() {
         // ── GraphQL types ────────────────────────────────────────────────────
         .typ::<types::GraphQLIntrospectRequest>()
         .typ::<types::GraphQLIntrospectResponse>()
+        // ── gRPC types ───────────────────────────────────────────────────────
+        .typ::<types::GrpcTlsOptions>()
+        .typ::<types::GrpcMethodInfo>()
+        .typ::<types::GrpcServiceInfo>()
+        .typ::<types::GrpcUnaryResponse>()
+        .typ::<types::GrpcStreamMessageEvent>()
+        .typ::<types::GrpcStreamClosedEvent>()
+        .typ::<types::ProtoFieldSchema>()
+        .typ::<types::ProtoMessageSchema>()
+        .typ::<types::ProtoRegistryInfo>()
         // ── Generic fetch type ───────────────────────────────────────────────
         .typ::<types::FetchUrlResponse>()
+        // ── Body format types ────────────────────────────────────────────────
+        .typ::<types::CsvTable>()
+        .typ::<types::ProtoNode>()
+        // ── cURL types ───────────────────────────────────────────────────────
+        .typ::<types::CurlImport>()
+        .typ::<types::CurlExportOptions>()
+        // ── Collection import/export types ───────────────────────────────────
+        .typ::<types::ImportResult>()
+        .typ::<types::CollectionExport>()
+        .typ::<types::ImportedItem>()
+        .typ::<types::ExampleResponse>()
+        .typ::<types::ImportedEnvironment>()
+        .typ::<types::ImportedVariable>()
+        .typ::<types::DotenvImport>()
+        .typ::<types::HarImportProgressEvent>()
+        .typ::<types::HarEntry>()
+        .typ::<types::OpenApiExportOptions>()
+        .typ::<types::OpenApiExport>()
+        .typ::<types::Collection>()
+        .typ::<types::CollectionNode>()
+        .typ::<types::CollectionSummary>()
+        .typ::<types::CollectionDirChangedEvent>()
+        .typ::<types::EnvironmentVariable>()
+        .typ::<types::Environment>()
+        .typ::<types::HistoryEntry>()
+        .typ::<types::HistoryRetention>()
+        .typ::<types::HistoryStats>()
+        .typ::<types::HistoryPruneResult>()
+        .typ::<types::Draft>()
+        .typ::<types::RunOptions>()
+        .typ::<types::RunStatus>()
+        .typ::<types::RunItemResult>()
+        .typ::<types::RunResult>()
+        .typ::<types::RunProgressEvent>()
+        .typ::<types::Workspace>()
+        .typ::<types::AppSettings>()
         // ── Commands ─────────────────────────────────────────────────────────
         .commands(collect_commands![
             helpers::rest::rest_request,
             helpers::rest::rest_cancel_request,
+            helpers::rest::cancel_request,
+            helpers::rest::rest_discard_body_file,
+            helpers::rest::preview_request,
             helpers::rest::fetch_url,
+            helpers::cookie_jar::get_session_cookies,
+            helpers::cookie_jar::clear_session_cookies,
+            helpers::cookie_jar::delete_cookie,
+            helpers::cookie_jar::import_cookies_txt,
+            helpers::cookie_jar::export_cookies_txt,
+            helpers::tls_exceptions::add_tls_exception,
+            helpers::tls_exceptions::list_tls_exceptions,
+            helpers::tls_exceptions::remove_tls_exception,
+            helpers::proxy_rules::set_proxy_rule,
+            helpers::proxy_rules::list_proxy_rules,
+            helpers::proxy_rules::delete_proxy_rule,
+            helpers::oauth::oauth2_authorization_code,
+            helpers::oauth::oauth2_client_credentials,
+            helpers::oauth::oauth2_device_start,
+            helpers::oauth::oauth2_device_poll,
+            helpers::oauth::oauth2_device_cancel,
+            helpers::oauth::list_auth_profiles,
+            helpers::oauth::get_auth_profile,
+            helpers::oauth::delete_auth_profile,
+            helpers::body_formats::parse_csv_body,
+            helpers::body_formats::yaml_to_json,
+            helpers::body_formats::render_markdown,
+            helpers::body_formats::decode_msgpack,
+            helpers::body_formats::decode_protobuf_raw,
+            helpers::curl_command::parse_curl,
+            helpers::curl_command::to_curl,
+            helpers::postman::import_postman,
+            helpers::postman::export_postman,
+            helpers::openapi::import_openapi,
+            helpers::openapi::export_openapi,
+            helpers::har::import_har,
+            helpers::har::export_har,
+            helpers::insomnia::import_insomnia,
+            helpers::http_file::import_http_file,
+            helpers::http_file::export_http_file,
+            helpers::dotenv::import_dotenv,
+            helpers::collections::create_collection,
+            helpers::collections::list_collections,
+            helpers::collections::get_collection_tree,
+            helpers::collections::delete_collection,
+            helpers::collections::create_folder,
+            helpers::collections::save_request,
+            helpers::collections::update_request,
+            helpers::collections::move_item,
+            helpers::collections::delete_item,
+            helpers::collection_dir::export_collection_to_dir,
+            helpers::collection_dir::open_collection_from_dir,
+            helpers::environments::create_environment,
+            helpers::environments::list_environments,
+            helpers::environments::get_environment,
+            helpers::environments::delete_environment,
+            helpers::environments::duplicate_environment,
+            helpers::environments::set_variable,
+            helpers::environments::set_secret,
+            helpers::environments::reveal_secret,
+            helpers::environments::delete_variable,
+            helpers::environments::set_active_environment,
+            helpers::environments::get_active_environment,
+            helpers::environments::export_environment,
+            helpers::variables::resolve_request,
+            helpers::history::record_history,
+            helpers::history::list_history,
+            helpers::history::set_history_pinned,
+            helpers::history::delete_history_entry,
+            helpers::history::get_history_retention,
+            helpers::history::set_history_retention,
+            helpers::history::get_history_stats,
+            helpers::history::prune_history,
+            helpers::drafts::save_draft,
+            helpers::drafts::list_drafts,
+            helpers::drafts::delete_draft,
+            helpers::runner::run_collection,
+            helpers::runner::get_run_result,
+            helpers::runner::cancel_run,
+            helpers::settings::get_settings,
+            helpers::settings::update_settings,
+            helpers::workspaces::create_workspace,
+            helpers::workspaces::list_workspaces,
+            helpers::workspaces::get_active_workspace,
+            helpers::workspaces::switch_workspace,
+            helpers::workspaces::delete_workspace,
             dock_badge::set_dock_badge,
             helpers::websocket::ws_connect,
             helpers::websocket::ws_send,
             helpers::websocket::ws_disconnect,
             helpers::graphql::graphql_introspect,
+            helpers::grpc::grpc_list_services,
+            helpers::grpc::grpc_unary,
+            helpers::grpc::grpc_server_stream,
+            helpers::grpc::grpc_cancel,
+            helpers::proto_registry::import_proto_files,
+            helpers::sse::sse_connect,
+            helpers::sse::sse_close,
             helpers::socketio::sio_connect,
             helpers::socketio::sio_emit,
             helpers::socketio::sio_emit_with_ack,
@@ -94,6 +267,7 @@ pub fn run() {
     let mut tauri_builder = tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_liquid_glass::init())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -129,11 +303,26 @@ pub fn run() {
         .setup(move |app| {
             builder.mount_events(app);
             notifications::init(app.handle());
+            if let Err(e) = helpers::workspaces::migrate(app.handle()) {
+                notifications::notify_error(
+                    app.handle(),
+                    format!("Couldn't move your data into the default workspace: {e}"),
+                );
+            }
+            helpers::collection_dir::watch_linked_collections(app.handle());
+            helpers::history::prune_in_background(app.handle().clone());
+            helpers::drafts::prune_in_background(app.handle().clone());
             app_menu::set_app_menu(app.handle())?;
             macos_tahoe_icon::init_tahoe_app_icon(app.handle().clone());
             window::effects(app);
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                helpers::response_body::cleanup_spill_dir();
+                helpers::drafts::flush_drafts();
+            }
+        });
 }
@@ -19,3 +19,13 @@ use tauri_plugin_notification::NotificationExt;
 pub fn init(app: &AppHandle) {
     let _ = app.notification().request_permission();
 }
+
+/// Shows a startup failure as a notification; there's no window to report it in yet.
+pub fn notify_error(app: &AppHandle, message: String) {
+    let _ = app
+        .notification()
+        .builder()
+        .title("Mandy")
+        .body(message)
+        .show();
+}
@@ -1,7 +1,26 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
 use std::collections::HashMap;
 
+/// Deserializes ordered `[[name, value], ...]` pairs, also accepting the older
+/// `{ name: value }` map shape so previously saved requests still load.
+fn ordered_pairs<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Pairs {
+        List(Vec<(String, String)>),
+        Map(HashMap<String, String>),
+    }
+
+    Ok(match Pairs::deserialize(deserializer)? {
+        Pairs::List(list) => list,
+        Pairs::Map(map) => map.into_iter().collect(),
+    })
+}
+
 #[derive(Serialize, Deserialize, Type, Clone)]
 pub enum Methods {
     GET,
@@ -13,6 +32,8 @@ pub enum Methods {
     OPTIONS,
     TRACE,
     CONNECT,
+    /// Any other verb (e.g. `PURGE`, `PROPFIND`); must be a valid HTTP token.
+    Custom(String),
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
@@ -21,12 +42,41 @@ pub enum AuthType {
     Basic { username: String, password: String },
     Bearer { token: String },
     ApiKey { key: String, value: String, add_to: ApiKeyLocation },
+    /// A token from `oauth2_authorization_code`, sent as a Bearer header.
+    OAuth2 { access_token: String },
+    /// Windows integrated auth; the user is sent as `DOMAIN\user` when a domain is set.
+    Ntlm { username: String, password: String, domain: Option<String> },
+    /// SPNEGO/Kerberos. Without credentials the machine's ticket cache is used;
+    /// `delegate` forwards the ticket to the server. Needs the `negotiate` feature.
+    Negotiate { username: Option<String>, password: Option<String>, delegate: bool },
+    /// A token signed at send time and sent as a Bearer header. `claims_json` and
+    /// `header_overrides` are JSON objects; `iat`/`exp` are filled in when sending.
+    Jwt {
+        algorithm: JwtAlgorithm,
+        secret_or_key_pem: String,
+        claims_json: String,
+        header_overrides: Option<String>,
+        expires_in_secs: Option<u32>,
+    },
+}
+
+/// HMAC algorithms take a shared secret, the rest a PEM private key.
+#[derive(Serialize, Deserialize, Type, Clone, Debug)]
+pub enum JwtAlgorithm {
+    HS256,
+    HS384,
+    HS512,
+    RS256,
+    ES256,
+    EdDSA,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
 pub enum ApiKeyLocation {
     Header,
     Query,
+    /// Sent as `key=value` in the Cookie header, replacing a same-named cookie.
+    Cookie,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
@@ -36,6 +86,35 @@ pub enum BodyType {
     FormUrlEncoded { fields: HashMap<String, String> },
     Multipart { fields: Vec<MultipartField> },
     Binary { data: Vec<u8>, filename: Option<String> },
+    /// Streamed from disk at send time instead of crossing IPC. The path must be
+    /// inside the fs scope (e.g. picked through the file dialog).
+    RawFile { path: String, content_type: Option<String> },
+    /// Like `RawFile`, but Content-Type defaults from the file extension.
+    BinaryFile { path: String, content_type: Option<String> },
+    /// Sent as the `{"query", "variables", "operationName"}` JSON envelope.
+    /// `variables` is JSON text and is left out when empty.
+    GraphQL { query: String, variables: Option<String>, operation_name: Option<String> },
+    /// `envelope_body` (the contents of `<Body>`) wrapped in a SOAP envelope.
+    /// It must be well-formed XML; the version picks the namespace and headers.
+    Soap { action: String, envelope_body: String, soap_version: SoapVersion },
+    /// A JSON-RPC 2.0 call. `params` is JSON text (an array or object) and is
+    /// left out when empty; a missing `id` is filled with the next number.
+    JsonRpc { method: String, params: Option<String>, id: Option<String> },
+    /// Several JSON-RPC calls sent as one array.
+    JsonRpcBatch { calls: Vec<JsonRpcCall> },
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct JsonRpcCall {
+    pub method: String,
+    pub params: Option<String>,
+    pub id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, PartialEq)]
+pub enum SoapVersion {
+    Soap1_1,
+    Soap1_2,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
@@ -48,6 +127,8 @@ pub struct MultipartField {
 pub enum MultipartValue {
     Text(String),
     File { data: Vec<u8>, filename: String, content_type: Option<String> },
+    /// Read from disk while sending; `filename` defaults to the path's file name.
+    FilePath { path: String, filename: Option<String>, content_type: Option<String> },
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
@@ -56,25 +137,58 @@ pub struct Cookie {
     pub value: String,
     pub domain: Option<String>,
     pub path: Option<String>,
+    /// The raw `Expires` attribute.
     pub expires: Option<String>,
+    /// Unix seconds the cookie expires at, from `max_age` when present, else `expires`.
+    #[specta(type = Option<f64>)]
+    pub expires_at: Option<i64>,
+    /// Whether `expires_at` had passed when the cookie was parsed.
+    pub is_expired: Option<bool>,
     pub http_only: Option<bool>,
     pub secure: Option<bool>,
+    /// Seconds from receipt; zero or negative means the cookie is being deleted.
+    #[specta(type = Option<f64>)]
+    pub max_age: Option<i64>,
+    /// As sent (`Strict`, `Lax` or `None`).
+    pub same_site: Option<String>,
+}
+
+/// Result of `import_cookies_txt`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CookieImport {
+    pub cookies: Vec<Cookie>,
+    /// Lines that were neither comments nor valid cookie entries.
+    pub ignored_lines: u32,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone, Default)]
 pub enum HttpProtocol {
+    /// HTTP/2 over TLS, HTTP/1.1 for plain `http://` (the long-standing default).
     #[default]
     Tcp,
+    /// Let libcurl pick whatever it considers best.
+    Auto,
+    Http1_1,
+    /// HTTP/2, including the `Upgrade: h2c` dance on plain `http://`.
+    Http2,
+    /// HTTP/2 without negotiation; the server must already speak h2 / h2c.
+    Http2PriorKnowledge,
+    /// HTTP/3 over QUIC. Needs a libcurl built with HTTP/3 (quiche or ngtcp2).
+    Quic,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
 pub struct ApiRequest {
     pub method: Methods,
     pub url: String,
-    pub headers: HashMap<String, String>,
+    /// Sent in order; repeated names are sent as separate header lines.
+    #[serde(deserialize_with = "ordered_pairs")]
+    pub headers: Vec<(String, String)>,
     pub body: BodyType,
     pub auth: AuthType,
-    pub query_params: HashMap<String, String>,
+    /// Appended in order; repeated names are kept and an empty value is sent as a bare `?name`.
+    #[serde(deserialize_with = "ordered_pairs")]
+    pub query_params: Vec<(String, String)>,
     pub cookies: Vec<Cookie>,
     pub timeout_ms: Option<u32>,
     pub follow_redirects: Option<bool>,
@@ -88,6 +202,195 @@ pub struct ApiRequest {
     /// When set, `rest_cancel_request` can abort this transfer (libcurl progress hook).
     #[serde(default)]
     pub cancel_key: Option<String>,
+    /// Caller-chosen id for this in-flight request; `cancel_request` aborts it by id.
+    /// Falls back to `cancel_key` when unset.
+    #[serde(default)]
+    pub request_id: Option<String>,
+    /// Response bodies larger than this are written to a temp file instead of
+    /// `body_base64`. Defaults to 5 MB.
+    #[serde(default)]
+    pub spill_threshold_bytes: Option<u32>,
+    /// Stop reading the response once this many body bytes have arrived. Unlimited when unset.
+    #[serde(default)]
+    pub max_response_bytes: Option<u32>,
+    /// Connect through this Unix domain socket; `url` then only supplies the path and Host.
+    #[serde(default)]
+    pub unix_socket: Option<String>,
+    /// DNS overrides applied to every connection, including redirect hops.
+    #[serde(default)]
+    pub resolve_overrides: Vec<ResolveOverride>,
+    #[serde(default)]
+    pub ip_version: Option<IpVersion>,
+    /// Outgoing interface name (`eth0`) or source IP to bind to.
+    #[serde(default)]
+    pub local_interface: Option<String>,
+    /// Inclusive range of local ports to bind from.
+    #[serde(default)]
+    pub local_port_range: Option<(u16, u16)>,
+    /// Off unless set.
+    #[serde(default)]
+    pub tcp_keepalive: Option<TcpKeepalive>,
+    /// Limit on setting up the connection (DNS, TCP and TLS), like `curl --connect-timeout`.
+    #[serde(default)]
+    pub connect_timeout_ms: Option<u32>,
+    /// Suppress libcurl's automatic `Expect: 100-continue` on request bodies.
+    #[serde(default)]
+    pub disable_expect_continue: Option<bool>,
+    /// Download speed cap in bytes/second, like `curl --limit-rate`. `0` or unset is unlimited.
+    #[serde(default)]
+    pub max_recv_speed_bytes: Option<u32>,
+    /// Upload speed cap in bytes/second. `0` or unset is unlimited.
+    #[serde(default)]
+    pub max_send_speed_bytes: Option<u32>,
+    /// `Accept-Encoding` to advertise; defaults to every encoding libcurl can decode.
+    #[serde(default)]
+    pub accept_encoding: Option<String>,
+    /// Return the body exactly as sent on the wire instead of decompressing it.
+    #[serde(default)]
+    pub disable_decompression: Option<bool>,
+    #[serde(default)]
+    pub compress_body: Option<BodyCompression>,
+    /// Collect libcurl's `-v` style log into `ApiResponse::verbose_log`. Off by default.
+    #[serde(default)]
+    pub capture_verbose: Option<bool>,
+    #[serde(default)]
+    pub retry: Option<RetryPolicy>,
+    /// Write the body straight to this file instead of returning it; see `ApiResponse::saved_path`.
+    #[serde(default)]
+    pub download_to: Option<String>,
+    /// Requests sharing a session id share a cookie jar: cookies a response sets
+    /// are sent on later matching requests. Explicit `cookies` win by name.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Send the token stored under this auth profile as a Bearer header, refreshing
+    /// it first when it has expired or is about to. Overrides `auth`.
+    #[serde(default)]
+    pub auth_profile: Option<String>,
+    /// How close to expiry (seconds) an `auth_profile` token is refreshed, 60 by default.
+    #[serde(default)]
+    pub auth_refresh_skew_secs: Option<u32>,
+    #[serde(default)]
+    pub client_cert: Option<ClientCert>,
+    /// PEM bundle file or hashed certificate directory trusted instead of the system store.
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// Inline PEM alternative to `ca_cert_path`.
+    #[serde(default)]
+    pub ca_cert_pem: Option<String>,
+    /// Server SPKI pins as `sha256//BASE64`, several separated by `;`, like `curl --pinnedpubkey`.
+    #[serde(default)]
+    pub pinned_public_key: Option<String>,
+    /// Oldest TLS version to accept; libcurl's default when unset.
+    #[serde(default)]
+    pub tls_min_version: Option<TlsVersion>,
+    /// Newest TLS version to offer; no cap when unset.
+    #[serde(default)]
+    pub tls_max_version: Option<TlsVersion>,
+    /// OpenSSL cipher list for TLS 1.2 and below, e.g. `ECDHE-RSA-AES128-GCM-SHA256`.
+    /// Environment-specific, so share-safe exports leave it out.
+    #[serde(default)]
+    pub tls_ciphers: Option<String>,
+    /// TLS 1.3 suites, e.g. `TLS_AES_128_GCM_SHA256:TLS_CHACHA20_POLY1305_SHA256`.
+    /// Left out of share-safe exports like `tls_ciphers`.
+    #[serde(default)]
+    pub tls13_ciphers: Option<String>,
+    /// When `proxy` is unset, use the proxy from `HTTP(S)_PROXY`/`ALL_PROXY` or
+    /// the OS settings, honouring `NO_PROXY`. Off by default.
+    #[serde(default)]
+    pub use_system_proxy: Option<bool>,
+    /// Take credentials for the host from a `.netrc` file, like `curl --netrc`.
+    /// Off unless set.
+    #[serde(default)]
+    pub use_netrc: Option<NetrcMode>,
+    /// Read instead of `~/.netrc`.
+    #[serde(default)]
+    pub netrc_file: Option<String>,
+    /// Environment `{{variable}}` placeholders are resolved from; the active
+    /// one when unset.
+    #[serde(default)]
+    pub environment_id: Option<String>,
+    /// Request-scoped variables, taking precedence over the environment's.
+    #[serde(default, deserialize_with = "ordered_pairs")]
+    pub variables: Vec<(String, String)>,
+    /// Dynamic variable values to reuse instead of generating new ones, as a
+    /// preview or `resolve_request` returned them, so a send matches what was
+    /// shown. Entries that no longer line up with the request are ignored.
+    #[serde(default, deserialize_with = "ordered_pairs")]
+    pub dynamic_values: Vec<(String, String)>,
+}
+
+/// Mirrors libcurl's `CURLOPT_NETRC` levels.
+#[derive(Serialize, Deserialize, Type, Clone, PartialEq)]
+pub enum NetrcMode {
+    Ignored,
+    /// Credentials in the URL or `auth` take precedence over the file.
+    Optional,
+    /// The file's credentials win over any in the URL.
+    Required,
+}
+
+/// Address family used for name resolution, like `curl -4` / `curl -6`.
+#[derive(Serialize, Deserialize, Type, Clone, Debug, Default, PartialEq)]
+pub enum IpVersion {
+    #[default]
+    Any,
+    V4Only,
+    V6Only,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug, PartialEq, PartialOrd)]
+pub enum TlsVersion {
+    Tls1_0,
+    Tls1_1,
+    Tls1_2,
+    Tls1_3,
+}
+
+/// Compression applied to the request body before sending (`Content-Encoding`).
+#[derive(Serialize, Deserialize, Type, Clone, Default, PartialEq)]
+pub enum BodyCompression {
+    #[default]
+    None,
+    Gzip,
+    Deflate,
+}
+
+/// Automatic retries for `rest_request`. Only idempotent methods are retried
+/// unless `retry_non_idempotent` is set.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct RetryPolicy {
+    /// Total number of tries, including the first one.
+    pub max_attempts: u32,
+    /// Wait before the first retry.
+    pub backoff_ms: u32,
+    /// Factor applied to the wait after each retry (`1.0` keeps it constant).
+    pub backoff_multiplier: f64,
+    /// Response statuses that trigger a retry, e.g. `[502, 503, 504]`.
+    #[serde(default)]
+    pub retry_on_statuses: Vec<u16>,
+    /// Retry when the connection could not be made or was dropped mid-transfer.
+    #[serde(default)]
+    pub retry_on_connection_errors: bool,
+    /// Opt in to retrying POST, PATCH, CONNECT and custom verbs.
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
+}
+
+/// TCP keepalive probing for connections that sit idle waiting on a slow server.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct TcpKeepalive {
+    /// Idle time before the first probe is sent.
+    pub idle_secs: u32,
+    /// Delay between subsequent probes.
+    pub interval_secs: u32,
+}
+
+/// Pins `host:port` to a fixed address, like `curl --resolve`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ResolveOverride {
+    pub host: String,
+    pub port: u16,
+    pub address: String,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
@@ -95,6 +398,74 @@ pub struct ProxyConfig {
     pub url: String,
     pub username: Option<String>,
     pub password: Option<String>,
+    #[serde(default)]
+    pub proxy_type: ProxyKind,
+    /// Comma-separated hosts to reach directly: exact names, `.domain`
+    /// suffixes, IPs and CIDR blocks such as `10.0.0.0/8`, or `*`.
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// How to authenticate to the proxy; Basic when unset.
+    #[serde(default)]
+    pub auth_scheme: Option<ProxyAuthScheme>,
+}
+
+/// Routes requests without their own `proxy` by target host, see `set_proxy_rule`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ProxyRule {
+    pub id: String,
+    /// A host name, `*.domain` for its subdomains, or `*` for every host.
+    /// The most specific matching rule wins.
+    pub pattern: String,
+    pub target: ProxyRuleTarget,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub enum ProxyRuleTarget {
+    /// Connect directly, ignoring `use_system_proxy`.
+    Direct,
+    Proxy(ProxyConfig),
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, PartialEq)]
+pub enum ProxyAuthScheme {
+    Basic,
+    Digest,
+    /// `username` may be given as `DOMAIN\user`.
+    Ntlm,
+    /// Without a username the Kerberos ticket cache is used.
+    Negotiate,
+    /// Whichever of the above the proxy offers, strongest first.
+    Any,
+}
+
+/// How to talk to the proxy. Through a proxy, `remote_addr` is the proxy's
+/// address and `tcp_handshake_ms` includes the proxy handshake.
+#[derive(Serialize, Deserialize, Type, Clone, Default, PartialEq)]
+pub enum ProxyKind {
+    #[default]
+    Http,
+    /// TLS to the proxy itself.
+    Https,
+    Socks4,
+    /// SOCKS5 with names resolved locally.
+    Socks5,
+    /// SOCKS5 with names resolved by the proxy, e.g. for an SSH dynamic forward.
+    Socks5Hostname,
+}
+
+/// Certificate presented to servers that require mutual TLS.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub enum ClientCert {
+    /// `key_path` may be omitted when the key is in the same file as the cert.
+    Pem {
+        cert_path: String,
+        key_path: Option<String>,
+        key_password: Option<String>,
+    },
+    Pkcs12 {
+        path: String,
+        password: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Type, Clone, PartialEq)]
@@ -108,17 +479,102 @@ pub enum ResponseRenderer {
     Audio,
     Video,
     Pdf,
+    Csv,
+    Yaml,
+    Markdown,
+    MsgPack,
+    Protobuf,
+    /// Zip-based archive (also .docx, .xlsx, .jar, ...); sniffed from the body.
+    Archive,
+}
+
+/// How the transfer got its connection.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ConnectionInfo {
+    /// True when no new connection had to be opened.
+    pub reused: bool,
+    /// New connections libcurl opened for this transfer, redirects included.
+    pub num_connects: u32,
+    pub local_port: Option<u16>,
+}
+
+/// The final connection's TLS session, as a browser's lock icon would show it.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct TlsInfo {
+    /// e.g. `TLSv1.3`.
+    pub version: Option<String>,
+    pub cipher: Option<String>,
+    /// Protocol agreed via ALPN (`h2`, `http/1.1`); `None` when none was.
+    pub alpn: Option<String>,
+    /// The chain as the server sent it, leaf first. Empty when the TLS backend
+    /// doesn't report certificates or the connection was reused.
+    pub certificates: Vec<CertificateInfo>,
+    /// The leaf certificate has expired or expires within 30 days.
+    pub leaf_expiring: bool,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// DNS names, IPs, emails and URIs from the subjectAltName extension.
+    pub subject_alt_names: Vec<String>,
+    /// RFC 3339 validity window.
+    pub not_before: String,
+    pub not_after: String,
+    pub serial: String,
+    /// SHA-256 of the DER encoding as colon-separated uppercase hex.
+    pub fingerprint_sha256: String,
+}
+
+/// A host whose self-signed certificate is trusted by fingerprint, see `add_tls_exception`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct TlsException {
+    pub host: String,
+    /// Same format as `CertificateInfo::fingerprint_sha256`.
+    pub fingerprint_sha256: String,
+    /// RFC 3339.
+    pub added_at: String,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
 pub struct RedirectEntry {
     pub url: String,
     pub status: u16,
+    pub headers: HashMap<String, String>,
+    /// Cookies this hop set; they are forwarded to later hops that match them.
+    pub set_cookies: Vec<Cookie>,
+    pub duration_ms: f64,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, PartialEq)]
+pub enum VerboseDirection {
+    /// Informational text from libcurl (`*` lines in `curl -v`).
+    Info,
+    HeaderIn,
+    HeaderOut,
+    /// Body / TLS records; `text` only carries a byte count.
+    DataIn,
+    DataOut,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct VerboseEntry {
+    pub direction: VerboseDirection,
+    pub text: String,
 }
 
 #[derive(Serialize, Deserialize, Type, Clone)]
 pub struct TimingInfo {
+    /// Spans every attempt, including backoff waits, when the request was retried.
     pub total_ms: f64,
+    /// Time spent on redirect hops before the final request started. The phases
+    /// below cover only the final hop, so `redirect_ms` plus the phases add up to
+    /// `total_ms` (unless the request was retried).
+    pub redirect_ms: f64,
+    pub redirect_count: u32,
+    /// ~0 together with `tcp_handshake_ms` usually means the connection was reused
+    /// (see `ApiResponse::connection`).
     pub dns_lookup_ms: f64,
     pub tcp_handshake_ms: f64,
     pub tls_handshake_ms: f64,
@@ -132,25 +588,335 @@ pub struct SizeInfo {
     pub headers_bytes: u32,
     pub body_bytes: u32,
     pub total_bytes: u32,
+    /// Body size before `compress_body` was applied; `None` when sent uncompressed.
+    pub uncompressed_body_bytes: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Type)]
 pub struct ApiResponse {
     pub status: u16,
     pub status_text: String,
+    /// Repeated headers are comma-joined here; see `headers_list` for the raw lines.
     pub headers: HashMap<String, String>,
+    /// Every response header line in received order, duplicates intact.
+    pub headers_list: Vec<(String, String)>,
     pub cookies: Vec<Cookie>,
     pub body_base64: String,
     pub timing: TimingInfo,
     pub request_size: SizeInfo,
     pub response_size: SizeInfo,
     pub redirects: Vec<RedirectEntry>,
+    /// Number of tries made, `1` unless a `RetryPolicy` kicked in.
+    pub attempts: u32,
+    /// Status of every attempt in order; `None` where no response came back.
+    pub attempt_statuses: Vec<Option<u16>>,
     pub remote_addr: Option<String>,
+    /// The proxy the request went through (credentials removed), from `proxy`,
+    /// `use_system_proxy` or a `ProxyRule`. A rule is named after the proxy,
+    /// as in `http://gw:3128 (rule *.corp)` or `direct (rule *)`.
+    pub proxy_used: Option<String>,
+    /// Local `ip:port` the connection was made from.
+    pub local_addr: Option<String>,
+    pub connection: ConnectionInfo,
+    /// Auth scheme the server accepted, when curl negotiated one (e.g. `NTLM`).
+    pub auth_scheme: Option<String>,
+    /// For `Negotiate`, whether the server returned a token proving its identity.
+    pub mutual_auth: Option<bool>,
+    /// The login a `.netrc` entry supplied, when `use_netrc` provided the
+    /// credentials. The password is never reported.
+    pub netrc_login: Option<String>,
+    /// `None` for plain HTTP.
+    pub tls: Option<TlsInfo>,
+    /// Verification failed but the host's certificate matched its `TlsException`.
+    pub tls_exception_used: bool,
     pub http_version: String,
     pub available_renderers: Vec<ResponseRenderer>,
     pub detected_content_type: Option<String>,
+    /// Text encoding from the body's BOM or the Content-Type charset.
+    pub detected_charset: Option<String>,
+    /// Encoding named by a leading byte order mark (`UTF-8`, `UTF-16LE`, `UTF-16BE`).
+    pub bom: Option<String>,
+    /// The body transcoded to UTF-8 when `detected_charset` is not UTF-8.
+    /// `body_base64` always holds the raw bytes.
+    pub body_text_base64: Option<String>,
+    /// The request had a `BodyType::Soap` body and the response is a SOAP
+    /// Fault, whatever the HTTP status.
+    pub soap_fault: bool,
+    /// The `error` member of a JSON-RPC response to a `JsonRpc` or
+    /// `JsonRpcBatch` body; for a batch, the first call that failed.
+    pub jsonrpc_error: Option<JsonRpcError>,
     pub protocol_used: String,
+    /// True when the server actually negotiated h3 (HTTP/3 over QUIC).
+    pub negotiated_h3: bool,
+    /// Set when the transfer failed after the response started; see `partial`.
     pub error: Option<String>,
+    /// True when the connection broke mid-response: the status and headers are
+    /// real but the body is incomplete and should not be parsed.
+    pub partial: bool,
+    /// Set when the body exceeded the spill threshold; `body_base64` is then empty.
+    pub body_file_path: Option<String>,
+    /// Where a `download_to` body was written (`<path>.part` when the transfer broke off).
+    pub saved_path: Option<String>,
+    /// First 64 KB of a spilled body, for previews.
+    pub body_preview_base64: Option<String>,
+    /// True when the body was cut off at `max_response_bytes`.
+    pub truncated: bool,
+    /// Non-fatal notes about how the transfer went (extra round trips, etc.).
+    pub warnings: Vec<String>,
+    /// Present when `capture_verbose` was requested.
+    pub verbose_log: Option<Vec<VerboseEntry>>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct JsonRpcError {
+    #[specta(type = f64)]
+    pub code: i64,
+    pub message: String,
+    /// The `data` member as JSON text.
+    pub data: Option<String>,
+    /// The `id` of the failed call, as JSON text (`null` when the server
+    /// couldn't read it).
+    pub id: Option<String>,
+}
+
+/// Why `rest_request` failed, so the frontend can branch on the kind of failure
+/// instead of matching message text. `Display` gives the human-readable message.
+#[derive(Serialize, Deserialize, Type, Clone, Debug)]
+pub enum RequestError {
+    DnsResolution {
+        host: String,
+        /// Set when resolution was restricted to one address family.
+        ip_version: Option<IpVersion>,
+    },
+    ConnectionRefused,
+    /// The server accepted the connection but dropped it before the response finished.
+    ConnectionClosed {
+        detail: String,
+    },
+    /// `phase` is where the deadline hit: `dns`, `connect`, `tls`, `ttfb` or `download`.
+    Timeout {
+        phase: String,
+    },
+    TlsVerification {
+        detail: String,
+    },
+    /// The server's certificate isn't trusted. `fingerprint_sha256` is what it
+    /// presented, for offering `add_tls_exception`.
+    UntrustedCertificate {
+        host: String,
+        fingerprint_sha256: Option<String>,
+        detail: String,
+    },
+    /// The host has a `TlsException` but now presents a different certificate.
+    CertificateChanged {
+        host: String,
+        expected: String,
+        presented: String,
+    },
+    /// The server's public key matched none of the request's `pinned_public_key` pins.
+    PublicKeyPinMismatch {
+        detail: String,
+    },
+    /// The client certificate or key could not be read or unlocked.
+    ClientCertificate {
+        detail: String,
+    },
+    InvalidUrl {
+        reason: String,
+    },
+    Cancelled,
+    Io {
+        detail: String,
+    },
+    /// Any other libcurl failure; `code` is the `CURLcode`.
+    Curl {
+        code: u32,
+        detail: String,
+    },
+    /// The request's `auth_profile` token had expired and could not be refreshed.
+    /// `error` is the OAuth error code (e.g. `invalid_grant`) when the token
+    /// endpoint sent one.
+    TokenRefreshFailed {
+        profile: String,
+        error: Option<String>,
+        detail: String,
+    },
+    /// The proxy still answered 407 after the configured scheme was tried.
+    /// `proxy_authenticate` holds its `Proxy-Authenticate` challenges.
+    ProxyAuthFailed {
+        proxy_authenticate: Vec<String>,
+    },
+    /// The `.netrc` file could not be parsed; `line` is 1-based.
+    NetrcParse {
+        path: String,
+        line: u32,
+        detail: String,
+    },
+    /// The auth scheme needs a libcurl feature this build lacks.
+    AuthNotSupported {
+        scheme: String,
+        detail: String,
+    },
+    /// `{{name}}` placeholders with no value in the environment or the
+    /// request's `variables`.
+    UnresolvedVariables {
+        names: Vec<String>,
+    },
+    /// A secret variable's value couldn't be read from the OS keychain.
+    SecretUnavailable {
+        key: String,
+        detail: String,
+    },
+    /// A `{{$function}}` placeholder that isn't a known dynamic variable or
+    /// has bad arguments. `expression` is the placeholder without its braces.
+    InvalidDynamicVariable {
+        expression: String,
+        detail: String,
+    },
+    /// The request could not be built (bad method, body encoding, redirect limit, ...).
+    Other {
+        detail: String,
+    },
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestError::DnsResolution { host, ip_version } => match ip_version {
+                Some(IpVersion::V4Only) => write!(f, "No IPv4 address found for host {}", host),
+                Some(IpVersion::V6Only) => write!(f, "No IPv6 address found for host {}", host),
+                _ => write!(f, "Could not resolve host {}", host),
+            },
+            RequestError::ConnectionRefused => write!(f, "Could not connect to server"),
+            RequestError::ConnectionClosed { detail } => {
+                write!(f, "Connection closed unexpectedly. {}", detail)
+            }
+            RequestError::Timeout { phase } => write!(f, "Request timed out ({})", phase),
+            RequestError::TlsVerification { detail } => write!(f, "TLS error. {}", detail),
+            RequestError::UntrustedCertificate { detail, .. } => write!(f, "TLS error. {}", detail),
+            RequestError::CertificateChanged {
+                host,
+                expected,
+                presented,
+            } => write!(
+                f,
+                "Certificate changed for {}: expected {}, got {}",
+                host, expected, presented
+            ),
+            RequestError::PublicKeyPinMismatch { detail } if detail.is_empty() => {
+                write!(f, "Public key pin verification failed")
+            }
+            RequestError::PublicKeyPinMismatch { detail } => {
+                write!(f, "Public key pin verification failed. {}", detail)
+            }
+            RequestError::ClientCertificate { detail } => {
+                write!(f, "Client certificate error. {}", detail)
+            }
+            RequestError::InvalidUrl { reason } => write!(f, "Invalid URL: {}", reason),
+            RequestError::Cancelled => write!(f, "Request cancelled"),
+            RequestError::Io { detail } => write!(f, "{}", detail),
+            RequestError::Curl { detail, .. } => write!(f, "{}", detail),
+            RequestError::TokenRefreshFailed {
+                profile, detail, ..
+            } => write!(f, "Token refresh failed for '{}': {}", profile, detail),
+            RequestError::ProxyAuthFailed { proxy_authenticate } => {
+                if proxy_authenticate.is_empty() {
+                    write!(f, "Proxy authentication failed")
+                } else {
+                    write!(
+                        f,
+                        "Proxy authentication failed. Proxy-Authenticate: {}",
+                        proxy_authenticate.join(", ")
+                    )
+                }
+            }
+            RequestError::NetrcParse { path, line, detail } => {
+                write!(
+                    f,
+                    "Invalid netrc file {} at line {}: {}",
+                    path, line, detail
+                )
+            }
+            RequestError::AuthNotSupported { detail, .. } => write!(f, "{}", detail),
+            RequestError::UnresolvedVariables { names } => {
+                write!(f, "Unresolved variables: {}", names.join(", "))
+            }
+            RequestError::SecretUnavailable { detail, .. } => write!(f, "{}", detail),
+            RequestError::InvalidDynamicVariable { expression, detail } => {
+                write!(f, "Invalid dynamic variable '{}': {}", expression, detail)
+            }
+            RequestError::Other { detail } => write!(f, "{}", detail),
+        }
+    }
+}
+
+impl From<String> for RequestError {
+    fn from(detail: String) -> Self {
+        RequestError::Other { detail }
+    }
+}
+
+/// A `RequestError` as commands hand it to the frontend: the error to branch on
+/// plus its `Display` text, so messages are only written once.
+#[derive(Serialize, Deserialize, Type, Clone, Debug)]
+pub struct RequestFailure {
+    pub error: RequestError,
+    pub message: String,
+}
+
+impl From<RequestError> for RequestFailure {
+    fn from(error: RequestError) -> Self {
+        RequestFailure {
+            message: error.to_string(),
+            error,
+        }
+    }
+}
+
+/// Result of `preview_request`: the request as it would go on the wire.
+#[derive(Serialize, Deserialize, Type)]
+pub struct RequestPreview {
+    pub method: String,
+    /// Final URL including query params and any API key placed in the query.
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Files streamed from disk appear as `<file: path, N bytes>` rather than their contents.
+    pub body_base64: String,
+    /// Bytes that go on the wire, including streamed files.
+    pub body_size: u32,
+    /// The normalized `pinned_public_key` list the handshake will be checked against.
+    pub pinned_public_keys: Vec<String>,
+    /// The dynamic variable values shown, by placeholder in the order they
+    /// were filled. Pass them as `ApiRequest::dynamic_values` to send them.
+    pub dynamic_values: Vec<(String, String)>,
+}
+
+/// Pushed as a Tauri event (`request-progress`) while a REST response body is
+/// being received. Only emitted for requests that carry a `request_id`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct RequestProgressEvent {
+    pub request_id: String,
+    pub downloaded_bytes: f64,
+    /// From `Content-Length`; `None` when the server did not announce a size.
+    pub total_bytes: Option<f64>,
+    pub elapsed_ms: f64,
+}
+
+/// Pushed as a Tauri event (`upload-progress`) while a REST request body is
+/// being sent. Only emitted for requests that carry a `request_id`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct UploadProgressEvent {
+    pub request_id: String,
+    pub uploaded_bytes: f64,
+    pub total_bytes: f64,
+}
+
+impl ApiRequest {
+    /// Clear the settings tied to the local TLS library, for share-safe exports.
+    pub fn clear_local_tls_settings(&mut self) {
+        self.tls_ciphers = None;
+        self.tls13_ciphers = None;
+    }
 }
 
 impl Default for ApiRequest {
@@ -158,10 +924,10 @@ impl Default for ApiRequest {
         Self {
             method: Methods::GET,
             url: String::new(),
-            headers: HashMap::new(),
+            headers: Vec::new(),
             body: BodyType::None,
             auth: AuthType::None,
-            query_params: HashMap::new(),
+            query_params: Vec::new(),
             cookies: Vec::new(),
             timeout_ms: Some(30000),
             follow_redirects: Some(true),
@@ -171,6 +937,42 @@ impl Default for ApiRequest {
             protocol: None,
             request_label: None,
             cancel_key: None,
+            request_id: None,
+            spill_threshold_bytes: None,
+            max_response_bytes: None,
+            unix_socket: None,
+            resolve_overrides: Vec::new(),
+            ip_version: None,
+            local_interface: None,
+            local_port_range: None,
+            tcp_keepalive: None,
+            connect_timeout_ms: None,
+            disable_expect_continue: None,
+            max_recv_speed_bytes: None,
+            max_send_speed_bytes: None,
+            accept_encoding: None,
+            disable_decompression: None,
+            compress_body: None,
+            capture_verbose: None,
+            retry: None,
+            download_to: None,
+            session_id: None,
+            auth_profile: None,
+            auth_refresh_skew_secs: None,
+            client_cert: None,
+            ca_cert_path: None,
+            ca_cert_pem: None,
+            pinned_public_key: None,
+            tls_min_version: None,
+            tls_max_version: None,
+            tls_ciphers: None,
+            tls13_ciphers: None,
+            use_system_proxy: None,
+            use_netrc: None,
+            netrc_file: None,
+            environment_id: None,
+            variables: Vec::new(),
+            dynamic_values: Vec::new(),
         }
     }
 }
@@ -243,6 +1045,607 @@ pub struct FetchUrlResponse {
     pub body: String,
 }
 
+// ─── Body format types ──────────────────────────────────────────────────────
+
+/// One page of a CSV body parsed by `parse_csv_body`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CsvTable {
+    /// Empty when the body was parsed without a header row.
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// True when more rows follow this page.
+    pub truncated: bool,
+    /// Data rows in the whole body, for pagination.
+    pub total_rows: u32,
+}
+
+/// A protobuf message decoded without a schema by `decode_protobuf_raw`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ProtoNode {
+    pub fields: Vec<ProtoField>,
+    /// Bytes of this message that were decoded before `error`, or all of them.
+    pub decoded_bytes: u32,
+    /// Set when the wire data was malformed; `fields` holds what came before it.
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ProtoField {
+    pub number: u32,
+    /// Offset of the field's tag within its enclosing message.
+    pub offset: u32,
+    pub value: ProtoValue,
+}
+
+/// 64-bit values are strings because JavaScript numbers can't hold them exactly.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub enum ProtoValue {
+    /// `zigzag` is the value read as a `sint64`.
+    Varint {
+        value: String,
+        zigzag: String,
+    },
+    Fixed64 {
+        value: String,
+        double: f64,
+    },
+    Fixed32 {
+        value: u32,
+        float: f32,
+    },
+    /// A length-delimited field that parses as a message.
+    Message(ProtoNode),
+    /// A deprecated start/end group pair.
+    Group(ProtoNode),
+    String(String),
+    Bytes {
+        base64: String,
+    },
+}
+
+// ─── cURL types ─────────────────────────────────────────────────────────────
+
+/// Result of `parse_curl`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CurlImport {
+    pub request: ApiRequest,
+    /// Options that were skipped or only partly carried over.
+    pub warnings: Vec<String>,
+}
+
+/// How `to_curl` writes the command.
+#[derive(Serialize, Deserialize, Type, Clone, Default)]
+pub struct CurlExportOptions {
+    /// One option per line, joined with `\` continuations.
+    pub multiline: bool,
+    /// `--header` rather than `-H`, for options that have both.
+    pub long_flags: bool,
+    /// Replace passwords, tokens, API keys and cookie values with placeholders.
+    pub redact_auth: bool,
+    /// Leave out cipher lists, which depend on the local TLS library.
+    pub share_safe: bool,
+}
+
+// ─── Collection import/export types ─────────────────────────────────────────
+
+/// A collection read from another tool's format, for the frontend to save as a project.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ImportResult {
+    pub name: String,
+    pub description: Option<String>,
+    pub items: Vec<ImportedItem>,
+    /// Auth for requests with `inherit_auth` set.
+    pub auth: Option<AuthType>,
+    pub environment: Option<ImportedEnvironment>,
+    /// What couldn't be carried over, each prefixed with where it was.
+    pub report: Vec<String>,
+}
+
+/// A collection for `export_postman` and `export_openapi`, in the shape the
+/// importers return.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CollectionExport {
+    pub name: String,
+    pub description: Option<String>,
+    pub items: Vec<ImportedItem>,
+    pub auth: Option<AuthType>,
+    /// Exported as collection variables; secret values are left empty.
+    pub environment: Option<ImportedEnvironment>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub enum ImportedItem {
+    Folder {
+        name: String,
+        description: Option<String>,
+        items: Vec<ImportedItem>,
+    },
+    Request {
+        name: String,
+        description: Option<String>,
+        request: Box<ApiRequest>,
+        /// Use the collection's `auth` instead of `request.auth`.
+        inherit_auth: bool,
+        /// A saved response, used by `export_openapi` to describe the response.
+        #[serde(default)]
+        example_response: Option<ExampleResponse>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ExampleResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ImportedEnvironment {
+    pub name: String,
+    pub variables: Vec<ImportedVariable>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ImportedVariable {
+    pub key: String,
+    pub value: String,
+    pub enabled: bool,
+    pub secret: bool,
+}
+
+/// Result of `import_dotenv`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct DotenvImport {
+    /// Variables set from the file, new and overwritten.
+    pub imported: u32,
+    /// Keys the environment already had, now set from the file.
+    pub overwritten: Vec<String>,
+    /// Lines that were neither comments nor `KEY=VALUE` assignments.
+    pub ignored_lines: u32,
+}
+
+/// Pushed as a Tauri event (`har-import-progress`) while `import_har` reads
+/// an archive.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct HarImportProgressEvent {
+    pub path: String,
+    /// Entries read so far.
+    pub entries: u32,
+    pub read_bytes: f64,
+    pub total_bytes: f64,
+}
+
+/// A request and the response it got, for `export_har`.
+#[derive(Serialize, Deserialize, Type)]
+pub struct HarEntry {
+    pub request: ApiRequest,
+    pub response: Option<ApiResponse>,
+    /// When the request was sent (RFC 3339); the time of export when unset.
+    pub started_at: Option<String>,
+}
+
+/// How `export_openapi` writes the document.
+#[derive(Serialize, Deserialize, Type, Clone, Default)]
+pub struct OpenApiExportOptions {
+    /// `info.version`, `1.0.0` when unset.
+    pub version: Option<String>,
+    /// The only server listed, instead of the origins the requests use.
+    pub server_url: Option<String>,
+    /// YAML rather than JSON.
+    pub yaml: bool,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OpenApiExport {
+    pub document: String,
+    /// Requests that were merged or skipped, and auth that couldn't be described.
+    pub warnings: Vec<String>,
+}
+
+// ─── Collection types ───────────────────────────────────────────────────────
+
+/// A collection saved under the app data dir. Ids are UUIDs and don't change
+/// when items are renamed or moved.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    pub items: Vec<CollectionNode>,
+    /// RFC 3339.
+    pub created_at: String,
+    /// RFC 3339.
+    pub updated_at: String,
+    /// The directory the collection is also stored in, one file per request,
+    /// when it was opened from or exported to one. Saves are written there too.
+    #[serde(default)]
+    pub source_dir: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub enum CollectionNode {
+    Folder {
+        id: String,
+        name: String,
+        items: Vec<CollectionNode>,
+    },
+    Request {
+        id: String,
+        name: String,
+        request: Box<ApiRequest>,
+    },
+}
+
+/// Files in a collection's `source_dir` were changed outside the app.
+/// `open_collection_from_dir` reloads them.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CollectionDirChangedEvent {
+    pub collection_id: String,
+    pub path: String,
+}
+
+/// A collection as listed by `list_collections`, without its items.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct CollectionSummary {
+    pub id: String,
+    pub name: String,
+    pub request_count: u32,
+    pub updated_at: String,
+}
+
+// ─── Environment types ──────────────────────────────────────────────────────
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct EnvironmentVariable {
+    pub key: String,
+    pub value: String,
+    pub is_secret: bool,
+}
+
+/// A named set of variables saved under the app data dir. Every environment
+/// inherits the global one's variables and can override them.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct Environment {
+    pub id: String,
+    pub name: String,
+    pub variables: Vec<EnvironmentVariable>,
+    /// The global environment, whose id is always `global`.
+    pub is_global: bool,
+}
+
+// ─── History types ──────────────────────────────────────────────────────────
+
+/// A sent request kept by `record_history`, with `{{variable}}` placeholders
+/// as written rather than resolved.
+#[derive(Serialize, Deserialize, Type)]
+pub struct HistoryEntry {
+    pub id: String,
+    pub request: ApiRequest,
+    pub response: Option<ApiResponse>,
+    /// RFC 3339.
+    pub sent_at: String,
+    /// The environment variables were resolved from.
+    pub environment_id: Option<String>,
+    /// Pinned entries are never pruned.
+    pub pinned: bool,
+    /// The response body was cut to `HistoryRetention::max_body_bytes`.
+    pub body_truncated: bool,
+}
+
+/// Limits `prune_history` enforces on unpinned entries. `None` turns a limit off.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct HistoryRetention {
+    pub max_entries: Option<u32>,
+    /// Size of all entries on disk, pinned ones included.
+    pub max_total_mb: Option<u32>,
+    /// Response bodies longer than this are truncated when recorded.
+    pub max_body_bytes: Option<u32>,
+    pub max_age_days: Option<u32>,
+    /// `prune_history` runs in the background after this many recorded entries.
+    pub prune_every: u32,
+}
+
+impl Default for HistoryRetention {
+    fn default() -> Self {
+        Self {
+            max_entries: Some(5000),
+            max_total_mb: Some(500),
+            max_body_bytes: Some(1024 * 1024),
+            max_age_days: None,
+            prune_every: 50,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct HistoryStats {
+    pub entries: u32,
+    pub pinned: u32,
+    pub total_bytes: f64,
+    /// When the oldest entry was sent (RFC 3339).
+    pub oldest_sent_at: Option<String>,
+    /// What `prune_history` would remove with the current retention settings.
+    pub prunable_entries: u32,
+    pub prunable_bytes: f64,
+}
+
+/// Result of `prune_history`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct HistoryPruneResult {
+    pub removed: u32,
+    pub freed_bytes: f64,
+}
+
+// ─── Workspace types ────────────────────────────────────────────────────────
+
+/// A separate set of collections, environments, history and settings.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    /// RFC 3339.
+    pub created_at: String,
+    /// The workspace that always exists and takes over when the active one
+    /// is deleted.
+    pub is_default: bool,
+}
+
+// ─── Draft types ────────────────────────────────────────────────────────────
+
+/// The unsaved state of an editor tab, kept for restoring after a crash.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct Draft {
+    pub tab_id: String,
+    pub request: ApiRequest,
+    /// Whatever else the frontend needs to restore the tab, such as its
+    /// title or the collection item it edits.
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+    /// RFC 3339.
+    pub saved_at: String,
+}
+
+// ─── Settings types ─────────────────────────────────────────────────────────
+
+/// Request defaults for the active workspace. `rest_request` uses them for
+/// whatever the request leaves unset.
+#[derive(Serialize, Deserialize, Type, Clone)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Covers the whole transfer; `None` for no limit.
+    pub timeout_ms: Option<u32>,
+    pub connect_timeout_ms: Option<u32>,
+    pub follow_redirects: bool,
+    pub max_redirects: u32,
+    pub verify_ssl: bool,
+    /// Used for hosts without a matching `ProxyRule`.
+    pub proxy: Option<ProxyConfig>,
+    pub max_response_bytes: Option<u32>,
+    /// Sent unless the request has its own `User-Agent` header.
+    pub user_agent: Option<String>,
+    /// Unsaved drafts older than this are deleted; `None` keeps them.
+    pub draft_max_age_days: Option<u32>,
+    /// Kept by the history store; see `set_history_retention`.
+    pub history: HistoryRetention,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            timeout_ms: Some(30000),
+            connect_timeout_ms: None,
+            follow_redirects: true,
+            max_redirects: 10,
+            verify_ssl: true,
+            proxy: None,
+            max_response_bytes: None,
+            user_agent: None,
+            draft_max_age_days: Some(14),
+            history: HistoryRetention::default(),
+        }
+    }
+}
+
+// ─── Runner types ───────────────────────────────────────────────────────────
+
+/// How `run_collection` sends the requests.
+#[derive(Serialize, Deserialize, Type, Clone, Default)]
+pub struct RunOptions {
+    /// Wait between one request's response and sending the next.
+    #[serde(default)]
+    pub delay_ms: Option<u32>,
+    /// End the run after the first request that fails.
+    #[serde(default)]
+    pub stop_on_failure: bool,
+    /// Environment every request resolves variables from; each request's own
+    /// `environment_id` when unset.
+    #[serde(default)]
+    pub environment_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, PartialEq)]
+pub enum RunStatus {
+    Running,
+    Completed,
+    /// A request failed with `stop_on_failure` set.
+    Stopped,
+    Cancelled,
+    /// The run broke off for a reason other than a request failing.
+    Failed,
+}
+
+/// The outcome of one request in a run.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct RunItemResult {
+    pub item_id: String,
+    pub name: String,
+    /// `None` when no response came back.
+    pub status: Option<u16>,
+    pub status_text: Option<String>,
+    pub duration_ms: f64,
+    pub size_bytes: Option<u32>,
+    /// Got a response with a status below 400.
+    pub passed: bool,
+    pub error: Option<String>,
+}
+
+/// A run started by `run_collection`, as returned by `get_run_result`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct RunResult {
+    pub run_id: String,
+    pub collection_id: String,
+    pub folder_id: Option<String>,
+    pub status: RunStatus,
+    /// Requests the run was started with; `items` holds those sent so far.
+    pub total: u32,
+    pub items: Vec<RunItemResult>,
+    /// RFC 3339.
+    pub started_at: String,
+    /// RFC 3339; `None` while running.
+    pub finished_at: Option<String>,
+}
+
+/// Pushed as a Tauri event (`run-progress`) after each request in a run.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct RunProgressEvent {
+    pub run_id: String,
+    /// Position of `item` in the run, from 0.
+    pub index: u32,
+    pub total: u32,
+    pub item: RunItemResult,
+}
+
+// ─── OAuth types ────────────────────────────────────────────────────────────
+
+/// Settings for `oauth2_authorization_code`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OAuth2Config {
+    pub authorization_url: String,
+    pub token_url: String,
+    pub client_id: String,
+    /// Omit for public clients, which rely on PKCE alone.
+    pub client_secret: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Port for the loopback redirect listener; a free one is picked when unset.
+    /// Set it when the provider only accepts a pre-registered redirect URI.
+    #[serde(default)]
+    pub redirect_port: Option<u16>,
+    /// Path of the redirect URI, `/callback` by default.
+    #[serde(default)]
+    pub redirect_path: Option<String>,
+    /// Extra query parameters for the authorization URL (e.g. `audience`, `prompt`).
+    #[serde(default)]
+    pub extra_params: Vec<(String, String)>,
+    /// How long to wait for the browser to come back, 300 by default.
+    #[serde(default)]
+    pub timeout_secs: Option<u32>,
+    /// Save the token under this auth profile so requests can refresh it.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// How `oauth2_client_credentials` sends the client credentials.
+#[derive(Serialize, Deserialize, Type, Clone, Default)]
+pub enum OAuth2ClientAuth {
+    /// HTTP Basic `Authorization` header (`client_secret_basic`).
+    #[default]
+    BasicHeader,
+    /// `client_id` and `client_secret` form fields (`client_secret_post`).
+    Body,
+}
+
+/// Settings for `oauth2_client_credentials`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OAuth2ClientCredentialsConfig {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Space-separated scopes.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Sent as `audience`, which Auth0 and similar providers require.
+    #[serde(default)]
+    pub audience: Option<String>,
+    #[serde(default)]
+    pub auth_style: OAuth2ClientAuth,
+    /// Save the token under this auth profile so requests can use it.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// Settings for `oauth2_device_start` and `oauth2_device_poll`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OAuth2DeviceConfig {
+    pub device_authorization_url: String,
+    pub token_url: String,
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Space-separated scopes.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Save the token under this auth profile so requests can refresh it.
+    #[serde(default)]
+    pub profile: Option<String>,
+}
+
+/// What the user needs to finish a device authorization in their browser.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OAuth2DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// The verification URI with the user code already filled in, if offered.
+    pub verification_uri_complete: Option<String>,
+    /// Seconds to wait between polls.
+    pub interval: u32,
+    /// Seconds until `device_code` expires.
+    pub expires_in: u32,
+}
+
+/// Pushed as a Tauri event (`oauth-device-poll`) after each poll of the token
+/// endpoint while `oauth2_device_poll` waits for the user.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OAuth2DevicePollEvent {
+    pub user_code: String,
+    pub attempt: u32,
+    /// `authorization_pending` or `slow_down`.
+    pub status: String,
+    /// Current poll interval, raised by `slow_down` responses.
+    pub interval: u32,
+    pub remaining_secs: u32,
+}
+
+/// A token saved by the OAuth helpers under a profile name, with what is needed
+/// to refresh it.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct AuthProfile {
+    pub name: String,
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Unix seconds; `None` when the provider didn't say when the token expires.
+    #[specta(type = Option<f64>)]
+    pub expires_at: Option<i64>,
+    pub scope: Option<String>,
+}
+
+/// Token endpoint response from `oauth2_authorization_code` and
+/// `oauth2_client_credentials`. `access_token` can be sent with
+/// `AuthType::Bearer` or `AuthType::OAuth2`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct OAuth2Token {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub token_type: Option<String>,
+    pub expires_in: Option<u32>,
+    pub scope: Option<String>,
+    /// The full JSON response, for provider-specific fields like `id_token`.
+    pub raw: serde_json::Value,
+}
+
 // ─── GraphQL types ──────────────────────────────────────────────────────────
 
 /// Request to fetch (introspect) a GraphQL schema.
@@ -253,6 +1656,23 @@ pub struct GraphQLIntrospectRequest {
     /// GraphQL file name for background completion notifications.
     #[serde(default)]
     pub request_label: Option<String>,
+    /// Applied the same way as `ApiRequest::auth`.
+    #[serde(default)]
+    pub auth: Option<AuthType>,
+    // Connection settings, as on `ApiRequest`.
+    #[serde(default)]
+    pub proxy: Option<ProxyConfig>,
+    #[serde(default)]
+    pub use_system_proxy: Option<bool>,
+    #[serde(default)]
+    pub verify_ssl: Option<bool>,
+    #[serde(default)]
+    pub client_cert: Option<ClientCert>,
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// Defaults to 30 seconds.
+    #[serde(default)]
+    pub timeout_ms: Option<u32>,
 }
 
 /// Result of a GraphQL introspection fetch.
@@ -261,6 +1681,180 @@ pub struct GraphQLIntrospectResponse {
     /// Raw introspection JSON (the `data` field from the response).
     pub schema_json: Option<String>,
     pub error: Option<String>,
+    /// The server answered but refuses introspection queries.
+    pub introspection_disabled: bool,
+    /// Entries in `__schema.types`, built-in scalars and `__*` types included.
+    pub type_count: u32,
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+}
+
+// ─── gRPC types ─────────────────────────────────────────────────────────────
+
+/// Transport settings for the `grpc_*` commands. Without them, `http://`
+/// endpoints are dialled in plaintext and everything else over TLS.
+#[derive(Serialize, Deserialize, Type, Clone, Default)]
+pub struct GrpcTlsOptions {
+    /// Force plaintext HTTP/2 (`true`) or TLS (`false`) regardless of the scheme.
+    #[serde(default)]
+    pub plaintext: Option<bool>,
+    /// PEM bundle to trust instead of the system roots.
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// PEM certificate and key for mutual TLS; both must be set.
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// Name to verify the server certificate against, when it differs from
+    /// the endpoint host.
+    #[serde(default)]
+    pub server_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct GrpcMethodInfo {
+    pub name: String,
+    /// Fully qualified message names.
+    pub input_type: String,
+    pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+/// A service reported by server reflection.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct GrpcServiceInfo {
+    /// Fully qualified, e.g. `helloworld.Greeter`.
+    pub name: String,
+    pub methods: Vec<GrpcMethodInfo>,
+}
+
+/// Result of `grpc_unary`. A non-OK gRPC status is reported here rather than
+/// as an error, so its message and trailers can be shown.
+#[derive(Serialize, Deserialize, Type)]
+pub struct GrpcUnaryResponse {
+    /// The response message as pretty-printed JSON; `None` unless the status is OK.
+    pub response_json: Option<String>,
+    pub status_code: u32,
+    /// Canonical code name, e.g. `OK` or `NOT_FOUND`.
+    pub status_name: String,
+    pub status_message: String,
+    /// Response headers. `-bin` values are base64.
+    pub headers: Vec<(String, String)>,
+    /// Trailer metadata, without the `grpc-status`/`grpc-message` entries.
+    pub trailers: Vec<(String, String)>,
+    pub duration_ms: f64,
+}
+
+/// A message field, as described to the request editor.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ProtoFieldSchema {
+    pub name: String,
+    /// The key used in the JSON mapping.
+    pub json_name: String,
+    pub number: u32,
+    /// A scalar type like `int64`, or the fully qualified message or enum name.
+    pub type_name: String,
+    pub repeated: bool,
+    pub map: bool,
+    /// The `oneof` this field belongs to.
+    pub oneof: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ProtoMessageSchema {
+    /// Fully qualified message name.
+    pub name: String,
+    pub fields: Vec<ProtoFieldSchema>,
+    /// A JSON request body with every field set to its default, to start
+    /// editing from. Only the first field of each `oneof` is included.
+    pub skeleton_json: String,
+}
+
+/// Result of `import_proto_files`. Pass `registry_id` to the gRPC call
+/// commands to use these descriptors instead of server reflection.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct ProtoRegistryInfo {
+    pub registry_id: String,
+    /// Names of the compiled files, imports included.
+    pub files: Vec<String>,
+    pub services: Vec<GrpcServiceInfo>,
+    pub messages: Vec<ProtoMessageSchema>,
+}
+
+/// One message of a `grpc_server_stream` call, pushed as `grpc-stream-message`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct GrpcStreamMessageEvent {
+    pub stream_id: String,
+    /// 1-based position among the messages received, so gaps show drops.
+    pub index: u32,
+    /// The message as pretty-printed JSON.
+    pub data: String,
+    /// Messages dropped so far because the queue to the frontend was full.
+    pub dropped: u32,
+    pub timestamp_ms: f64,
+}
+
+/// Pushed as `grpc-stream-closed` when a `grpc_server_stream` call ends,
+/// after its last message event.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct GrpcStreamClosedEvent {
+    pub stream_id: String,
+    pub status_code: u32,
+    pub status_name: String,
+    pub status_message: String,
+    pub headers: Vec<(String, String)>,
+    pub trailers: Vec<(String, String)>,
+    pub received: u32,
+    pub dropped: u32,
+    pub duration_ms: f64,
+}
+
+// ─── Server-Sent Events types ───────────────────────────────────────────────
+
+/// Pushed as `sse-connected` once the response headers of an `sse_connect`
+/// stream are in.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct SseConnectedEvent {
+    pub stream_id: String,
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+}
+
+/// One dispatched event, pushed as `sse-event`.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct SseMessageEvent {
+    pub stream_id: String,
+    /// The `event` field, `message` when the server sent none.
+    pub event: String,
+    /// `data` lines joined with newlines.
+    pub data: String,
+    /// The last event id seen on the stream, which carries over between events.
+    pub id: Option<String>,
+    /// A `retry` field sent with this event.
+    pub retry_ms: Option<u32>,
+    pub timestamp_ms: f64,
+}
+
+/// A `:` comment line, pushed as `sse-keepalive`. Servers send these to keep
+/// idle connections open.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct SseKeepaliveEvent {
+    pub stream_id: String,
+    pub comment: String,
+    pub timestamp_ms: f64,
+}
+
+/// Pushed as `sse-closed` when the stream ends for any reason.
+#[derive(Serialize, Deserialize, Type, Clone)]
+pub struct SseClosedEvent {
+    pub stream_id: String,
+    /// `None` when the server ended the stream or `sse_close` was called.
+    /// For a non-2xx response, holds the start of its body.
+    pub error: Option<String>,
 }
 
 // ─── Socket.IO types ────────────────────────────────────────────────────────
@@ -462,10 +462,10 @@ function App() {
 			const resp = await sendRequest({
 				method: "POST",
 				url: resolvedUrl,
-				headers,
+				headers: Object.entries(headers),
 				body: { Raw: { content: body, content_type: "application/json" } },
 				auth: "None",
-				query_params: {},
+				query_params: [],
 				cookies: [],
 				timeout_ms: null,
 				follow_redirects: null,
@@ -868,20 +868,13 @@ function App() {
 									const resolvedHeaders: Record<string, string> = {};
 									const resolvedParams: Record<string, string> = {};
 
-									for (const [key, value] of Object.entries(
-										request.request.headers,
-									)) {
-										resolvedHeaders[key] = resolveVariables(
-											(value as string) || "",
-										);
+									for (const [key, value] of request.request.headers) {
+										resolvedHeaders[key] = resolveVariables(value || "");
 									}
 
-									for (const [key, value] of Object.entries(
-										request.request.query_params || {},
-									)) {
-										resolvedParams[key] = resolveVariables(
-											(value as string) || "",
-										);
+									for (const [key, value] of request.request.query_params ||
+										[]) {
+										resolvedParams[key] = resolveVariables(value || "");
 									}
 
 									console.log("[Workflow] Request execution:", {
@@ -980,8 +973,8 @@ function App() {
 									const resp = await sendRequest({
 										...request.request,
 										url: resolvedUrl,
-										headers: resolvedHeaders,
-										query_params: resolvedParams,
+										headers: Object.entries(resolvedHeaders),
+										query_params: Object.entries(resolvedParams),
 										body: finalBody,
 										request_label: request.name,
 									});
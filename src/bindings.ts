@@ -5,7 +5,7 @@
 
 
 export const commands = {
-async restRequest(req: ApiRequest) : Promise<Result<ApiResponse, string>> {
+async restRequest(req: ApiRequest) : Promise<Result<ApiResponse, RequestFailure>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("rest_request", { req }) };
 } catch (e) {
@@ -21,6 +21,45 @@ async restCancelRequest(cancelKey: string) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Abort an in-flight `rest_request` by its `request_id`.
+ * 
+ * Returns `false` when no request with that id is running (unknown or already
+ * finished); the cancelled request itself resolves with `RequestError::Cancelled`.
+ */
+async cancelRequest(requestId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_request", { requestId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a response body that `rest_request` spilled to `body_file_path`.
+ * Anything left over is removed when the app exits.
+ */
+async restDiscardBodyFile(path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("rest_discard_body_file", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Build the request exactly as `rest_request` would and return it without sending.
+ * Headers libcurl adds on its own (Host, Content-Length, Accept-Encoding) are not listed.
+ * Send the returned `dynamic_values` with the request to reuse the values shown.
+ */
+async previewRequest(req: ApiRequest) : Promise<Result<RequestPreview, RequestFailure>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_request", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Fetch a raw URL and return the response body as a string.
  * Used by the ImportModal to download remote OpenAPI specs via Rust
@@ -34,236 +73,2504 @@ async fetchUrl(url: string) : Promise<Result<FetchUrlResponse, string>> {
     else return { status: "error", error: e  as any };
 }
 },
-async setDockBadge(label: string | null) : Promise<Result<null, string>> {
+/**
+ * Unexpired cookies in a session's jar. Host-only cookies report the host that
+ * set them as their `domain`.
+ */
+async getSessionCookies(sessionId: string) : Promise<Result<Cookie[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("set_dock_badge", { label }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_session_cookies", { sessionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async clearSessionCookies(sessionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("clear_session_cookies", { sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Establish a new WebSocket connection.
- * 
- * - Spawns a background task that owns the socket and forwards incoming frames
- * as Tauri events (`ws://message/<connection_id>`).
- * - Returns synchronously once the HTTP upgrade handshake completes (or fails).
- * - A channel sender is stored in `registry` so `ws_send` / `ws_disconnect`
- * can reach the background task.
+ * Remove one cookie from a session's jar. `domain` is matched against the
+ * cookie's `Domain` attribute, or the setting host for host-only cookies.
+ * Returns whether a cookie was removed.
  */
-async wsConnect(req: WsConnectRequest) : Promise<Result<WsConnectResponse, string>> {
+async deleteCookie(sessionId: string, name: string, domain: string) : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("ws_connect", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_cookie", { sessionId, name, domain }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Send a text or binary frame to an existing WebSocket connection.
+ * Load a Netscape-format cookies.txt (as exported by browsers and curl) into a
+ * session's jar. Malformed lines are skipped and counted; already-expired
+ * cookies are returned but not stored.
  */
-async wsSend(req: WsSendRequest) : Promise<Result<null, string>> {
+async importCookiesTxt(path: string, sessionId: string) : Promise<Result<CookieImport, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("ws_send", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("import_cookies_txt", { path, sessionId }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Close an active WebSocket connection.
+ * Write a session's unexpired cookies as a Netscape-format cookies.txt that
+ * curl (`-b`) and browser extensions can read. Returns how many were written.
  */
-async wsDisconnect(connectionId: string) : Promise<Result<null, string>> {
+async exportCookiesTxt(sessionId: string, path: string) : Promise<Result<number, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("ws_disconnect", { connectionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("export_cookies_txt", { sessionId, path }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
 /**
- * Fetch and return a GraphQL server's introspection schema.
- * 
- * This runs the full introspection query via the Rust/curl stack so the
- * frontend never makes a direct network request.
+ * Trust `host`'s certificate with this SHA-256 fingerprint, replacing any
+ * fingerprint stored for it before.
  */
-async graphqlIntrospect(req: GraphQLIntrospectRequest) : Promise<Result<GraphQLIntrospectResponse, string>> {
+async addTlsException(host: string, fingerprintSha256: string) : Promise<Result<TlsException, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("graphql_introspect", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("add_tls_exception", { host, fingerprintSha256 }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async sioConnect(req: SioConnectRequest) : Promise<Result<SioConnectResponse, string>> {
+async listTlsExceptions() : Promise<Result<TlsException[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("sio_connect", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_tls_exceptions") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async sioEmit(req: SioEmitRequest) : Promise<Result<null, string>> {
+/**
+ * Returns `false` when the host had no exception.
+ */
+async removeTlsException(host: string) : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("sio_emit", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("remove_tls_exception", { host }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async sioEmitWithAck(req: SioEmitAckRequest) : Promise<Result<SioEmitAckResponse, string>> {
+/**
+ * Route hosts matching `pattern` to `target`. Setting an existing pattern
+ * again replaces its target and keeps its id.
+ */
+async setProxyRule(pattern: string, target: ProxyRuleTarget) : Promise<Result<ProxyRule, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("sio_emit_with_ack", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("set_proxy_rule", { pattern, target }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async sioDisconnect(connectionId: string) : Promise<Result<null, string>> {
+async listProxyRules() : Promise<Result<ProxyRule[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("sio_disconnect", { connectionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("list_proxy_rules") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mqttConnect(req: MqttConnectRequest) : Promise<Result<MqttConnectResponse, string>> {
+/**
+ * Returns `false` when no rule had this id.
+ */
+async deleteProxyRule(id: string) : Promise<Result<boolean, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mqtt_connect", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("delete_proxy_rule", { id }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mqttPublish(req: MqttPublishRequest) : Promise<Result<null, string>> {
+/**
+ * Run the authorization code flow: open the provider's consent page in the
+ * browser, wait for it to redirect back to a loopback listener, and exchange
+ * the code (with its PKCE verifier) for tokens.
+ */
+async oauth2AuthorizationCode(config: OAuth2Config) : Promise<Result<OAuth2Token, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mqtt_publish", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("oauth2_authorization_code", { config }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mqttSubscribe(req: MqttSubscribeRequest) : Promise<Result<null, string>> {
+/**
+ * Fetch a token with the client credentials grant, for service-to-service APIs.
+ */
+async oauth2ClientCredentials(config: OAuth2ClientCredentialsConfig) : Promise<Result<OAuth2Token, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mqtt_subscribe", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("oauth2_client_credentials", { config }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mqttUnsubscribe(req: MqttUnsubscribeRequest) : Promise<Result<null, string>> {
+/**
+ * Request a device and user code (RFC 8628). Show `user_code` and
+ * `verification_uri` to the user, then call `oauth2_device_poll`.
+ */
+async oauth2DeviceStart(config: OAuth2DeviceConfig) : Promise<Result<OAuth2DeviceAuthorization, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mqtt_unsubscribe", { req }) };
+    return { status: "ok", data: await TAURI_INVOKE("oauth2_device_start", { config }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async mqttDisconnect(connectionId: string) : Promise<Result<null, string>> {
+/**
+ * Poll the token endpoint until the user approves the device, the code expires,
+ * or `oauth2_device_cancel` is called. Emits `oauth-device-poll` after each
+ * pending attempt.
+ */
+async oauth2DevicePoll(config: OAuth2DeviceConfig, deviceCode: string) : Promise<Result<OAuth2Token, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("mqtt_disconnect", { connectionId }) };
+    return { status: "ok", data: await TAURI_INVOKE("oauth2_device_poll", { config, deviceCode }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop an in-progress `oauth2_device_poll`. Returns false for unknown codes.
+ */
+async oauth2DeviceCancel(deviceCode: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("oauth2_device_cancel", { deviceCode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async listAuthProfiles() : Promise<Result<AuthProfile[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_auth_profiles") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
+},
+async getAuthProfile(name: string) : Promise<Result<AuthProfile | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_auth_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
 }
-
-/** user-defined events **/
-
-
-
-/** user-defined constants **/
-
-
-
-/** user-defined types **/
-
-export type ApiKeyLocation = "Header" | "Query"
-export type ApiRequest = { method: Methods; url: string; headers: Partial<{ [key in string]: string }>; body: BodyType; auth: AuthType; query_params: Partial<{ [key in string]: string }>; cookies: Cookie[]; timeout_ms: number | null; follow_redirects: boolean | null; max_redirects: number | null; verify_ssl: boolean | null; proxy: ProxyConfig | null; protocol: HttpProtocol | null; 
+},
 /**
- * Tab / request name for background completion notifications (REST only).
+ * Returns false when no profile had that name.
  */
-request_label?: string | null; 
+async deleteAuthProfile(name: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_auth_profile", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * When set, `rest_cancel_request` can abort this transfer (libcurl progress hook).
+ * Parse a CSV response body into a table, one page of rows at a time.
+ * 
+ * `delimiter` is a single character (`,`, `;`, `\t`, ...) and is sniffed from the
+ * body when omitted. `offset`/`limit` select which data rows are returned.
  */
-cancel_key?: string | null }
-export type ApiResponse = { status: number; status_text: string; headers: Partial<{ [key in string]: string }>; cookies: Cookie[]; body_base64: string; timing: TimingInfo; request_size: SizeInfo; response_size: SizeInfo; redirects: RedirectEntry[]; remote_addr: string | null; http_version: string; available_renderers: ResponseRenderer[]; detected_content_type: string | null; protocol_used: string; error: string | null }
-export type AuthType = "None" | { Basic: { username: string; password: string } } | { Bearer: { token: string } } | { ApiKey: { key: string; value: string; add_to: ApiKeyLocation } }
-export type BodyType = "None" | { Raw: { content: string; content_type: string | null } } | { FormUrlEncoded: { fields: Partial<{ [key in string]: string }> } } | { Multipart: { fields: MultipartField[] } } | { Binary: { data: number[]; filename: string | null } }
-export type Cookie = { name: string; value: string; domain: string | null; path: string | null; expires: string | null; http_only: boolean | null; secure: boolean | null }
+async parseCsvBody(bodyBase64: string, delimiter: string | null, hasHeader: boolean, offset: number | null, limit: number | null) : Promise<Result<CsvTable, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("parse_csv_body", { bodyBase64, delimiter, hasHeader, offset, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Response from a raw URL GET fetch (used by ImportModal for OpenAPI URLs).
+ * Convert a YAML response body to JSON so the JSON tree view can show it.
+ * 
+ * Anchors, aliases and `<<` merge keys are resolved. A body with several
+ * documents becomes an array with one element per document.
  */
-export type FetchUrlResponse = { status: number; body: string }
+async yamlToJson(bodyBase64: string) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("yaml_to_json", { bodyBase64 }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Request to fetch (introspect) a GraphQL schema.
+ * Render a Markdown response body to HTML for the HtmlPreview renderer.
+ * 
+ * The output is sanitized: scripts, iframes, event handler attributes and
+ * `javascript:` links are stripped, whether they came from raw HTML in the
+ * Markdown or from link targets.
  */
-export type GraphQLIntrospectRequest = { url: string; headers: Partial<{ [key in string]: string }>; 
+async renderMarkdown(bodyBase64: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("render_markdown", { bodyBase64 }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * GraphQL file name for background completion notifications.
+ * Decode a MessagePack response body to JSON for the JSON tree view.
+ * 
+ * Map keys that aren't strings are stringified, and binary/ext values become
+ * `{"$base64", "$length"}` objects (plus `"$ext"` with the ext type).
  */
-request_label?: string | null }
+async decodeMsgpack(bodyBase64: string) : Promise<Result<JsonValue, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decode_msgpack", { bodyBase64 }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Result of a GraphQL introspection fetch.
+ * Walk a protobuf body's wire format without a schema, like
+ * `protoc --decode_raw`. Malformed input still returns the fields decoded
+ * before the error, with `error` set.
  */
-export type GraphQLIntrospectResponse = { 
+async decodeProtobufRaw(bodyBase64: string) : Promise<Result<ProtoNode, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("decode_protobuf_raw", { bodyBase64 }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Raw introspection JSON (the `data` field from the response).
+ * Read a `curl` command line into a request. Options that can't be carried
+ * over are reported in `warnings` instead of failing the import.
  */
-schema_json: string | null; error: string | null }
-export type HttpProtocol = "Tcp"
-export type Methods = "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" | "OPTIONS" | "TRACE" | "CONNECT"
-export type MqttConnectRequest = { connection_id: string; url: string; client_id: string; username: string | null; password: string | null; clean_session: boolean | null; keep_alive_secs: number | null; subscriptions: MqttSubscription[] }
-export type MqttConnectResponse = { connection_id: string; url: string; client_id: string; error: string | null }
-export type MqttDisconnectedEvent = { connection_id: string; reason: string }
-export type MqttIncomingMessage = { connection_id: string; id: string; topic: string; data: string; qos: number; retain: boolean; timestamp_ms: number }
-export type MqttPublishRequest = { connection_id: string; topic: string; data: string; qos: number; retain: boolean | null }
-export type MqttSubscribeRequest = { connection_id: string; topic: string; qos: number }
-export type MqttSubscription = { topic: string; qos: number }
-export type MqttUnsubscribeRequest = { connection_id: string; topic: string }
-export type MultipartField = { name: string; value: MultipartValue }
-export type MultipartValue = { Text: string } | { File: { data: number[]; filename: string; content_type: string | null } }
-export type ProxyConfig = { url: string; username: string | null; password: string | null }
-export type RedirectEntry = { url: string; status: number }
-export type ResponseRenderer = "Raw" | "Json" | "Xml" | "Html" | "HtmlPreview" | "Image" | "Audio" | "Video" | "Pdf"
+async parseCurl(command: string) : Promise<Result<CurlImport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("parse_curl", { command }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Sent from the frontend to open a new Socket.IO connection.
+ * Write a request as a `curl` command line. Files referenced by the body are
+ * passed as `@path`; in-memory file parts name their file instead.
  */
-export type SioConnectRequest = { 
+async toCurl(req: ApiRequest, options: CurlExportOptions) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("to_curl", { req, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Unique connection ID chosen by the caller.
+ * Read a Postman Collection v2.1 file. Folders, requests and collection
+ * variables are returned for the frontend to save; scripts, unsupported auth
+ * and other parts that can't be carried over are listed in `report`.
  */
-connection_id: string; 
+async importPostman(path: string) : Promise<Result<ImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_postman", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * The Socket.IO server URL (http:// or https://).
+ * Write a collection as a Postman Collection v2.1 file. Collections live in
+ * the frontend's store, so the caller passes the one to export. Returns what
+ * couldn't be represented in Postman's format.
  */
-url: string; 
+async exportPostman(collection: CollectionExport, path: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_postman", { collection, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Optional namespace to connect to (defaults to "/").
+ * Read an OpenAPI 3 document (YAML or JSON) from a file or an http(s) URL.
+ * Operations are grouped into folders by their first tag, with sample bodies
+ * generated from their schemas; operations that couldn't be read are listed
+ * in `report`.
  */
-namespace: string | null; 
+async importOpenapi(pathOrUrl: string) : Promise<Result<ImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_openapi", { pathOrUrl }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Optional extra headers.
+ * Describe a collection as an OpenAPI 3.0 document: one operation per path
+ * and method, with `:id` and `{{id}}` segments as path parameters. Requests
+ * sharing a path and method are merged, which is noted in `warnings`.
+ * Collections live in the frontend's store, so the caller passes the one to
+ * export.
  */
-headers: Partial<{ [key in string]: string }>; 
+async exportOpenapi(collection: CollectionExport, options: OpenApiExportOptions) : Promise<Result<OpenApiExport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_openapi", { collection, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Optional auth payload sent during the handshake.
+ * Read a HAR archive into a collection with one request per entry, in
+ * capture order and grouped into a folder per page. The file is read as a
+ * stream, with `har-import-progress` events as entries are converted.
  */
-auth: string | null; 
+async importHar(path: string) : Promise<Result<ImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_har", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Transport selection.
+ * Write requests and their responses as a HAR 1.2 archive. History lives in
+ * the frontend, so the caller passes the entries; requests from a collection
+ * can be exported without responses. Headers are written as they were sent,
+ * auth included.
  */
-transport: string | null; 
+async exportHar(entries: HarEntry[], path: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_har", { entries, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Whether automatic reconnects are enabled.
+ * Read an Insomnia v4 export (JSON or YAML). Request groups become folders,
+ * and the base environment (with its first sub-environment) becomes the
+ * collection's variables. Template tags Mandy can't evaluate are kept as
+ * written and listed in `report`.
  */
-reconnect: boolean | null; 
+async importInsomnia(path: string) : Promise<Result<ImportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_insomnia", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
- * Whether to reconnect after an explicit server disconnect.
+ * Read the requests in a REST Client `.http` or `.rest` file, in file order.
+ * `# @name` comments become each request's `request_label`, and `< file`
+ * bodies are read from disk relative to the `.http` file when sent.
  */
-reconnect_on_disconnect: boolean | null; 
+async importHttpFile(path: string) : Promise<Result<ApiRequest[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_http_file", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Write requests as a REST Client `.http` file. In-memory file bodies and parts
+ * are written to a `<name>_files` folder beside it and referenced with `< file`.
+ * Connection settings (timeouts, proxies, TLS) have no place in the format and
+ * are left out. Returns what couldn't be represented.
+ */
+async exportHttpFile(requests: ApiRequest[], path: string) : Promise<Result<string[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_http_file", { requests, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Read a `.env` file into the named environment, creating it when there is
+ * none. Keys naming a secret, token, password or key are marked secret and
+ * their values kept in the keychain; a key set twice in the file takes its
+ * last value.
+ */
+async importDotenv(path: string, environmentName: string) : Promise<Result<DotenvImport, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_dotenv", { path, environmentName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Create an empty collection.
+ */
+async createCollection(name: string) : Promise<Result<Collection, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_collection", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Every saved collection, by name. Files that can't be read are skipped.
+ */
+async listCollections() : Promise<Result<CollectionSummary[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_collections") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getCollectionTree(collectionId: string) : Promise<Result<Collection, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_collection_tree", { collectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns `false` when there was no such collection. A `source_dir` is
+ * left as it is.
+ */
+async deleteCollection(collectionId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_collection", { collectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Add an empty folder inside `parent_id`, or at the top level. Returns its id.
+ */
+async createFolder(collectionId: string, parentId: string | null, name: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_folder", { collectionId, parentId, name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Add a request at the end of `folder_id`, or at the top level. Returns its id.
+ */
+async saveRequest(collectionId: string, folderId: string | null, name: string, request: ApiRequest) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_request", { collectionId, folderId, name, request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Replace a saved request, and rename it when `name` is set.
+ */
+async updateRequest(collectionId: string, requestId: string, name: string | null, request: ApiRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_request", { collectionId, requestId, name, request }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Move a request or folder into `target_folder_id` (the top level when
+ * `None`) at `index`, or at the end. A folder can't move into itself.
+ */
+async moveItem(collectionId: string, itemId: string, targetFolderId: string | null, index: number | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("move_item", { collectionId, itemId, targetFolderId, index }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a request, or a folder with everything in it. Returns `false` when
+ * there was no such item.
+ */
+async deleteItem(collectionId: string, itemId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_item", { collectionId, itemId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Store a saved collection in `path` as a directory tree, and keep writing
+ * its changes there. `path` must be empty or hold this same collection.
+ * With `share_safe`, cipher lists are left out of the request files, now
+ * and on later saves.
+ */
+async exportCollectionToDir(collectionId: string, path: string, shareSafe: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_collection_to_dir", { collectionId, path, shareSafe }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Load a collection from a directory tree, replacing the saved copy with
+ * the same id, and keep it linked. Also used to reload after
+ * `collection-dir-changed`.
+ */
+async openCollectionFromDir(path: string) : Promise<Result<Collection, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("open_collection_from_dir", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createEnvironment(name: string) : Promise<Result<Environment, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_environment", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Every environment, the global one first. Variables are each environment's
+ * own, without the inherited ones. Secret values are empty; see
+ * `reveal_secret`.
+ */
+async listEnvironments() : Promise<Result<Environment[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_environments") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getEnvironment(envId: string) : Promise<Result<Environment, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_environment", { envId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The global environment can't be deleted. Deleting the active one leaves
+ * none active. Returns `false` when there was no such environment.
+ */
+async deleteEnvironment(envId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_environment", { envId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Copy an environment's own variables, secrets included, into a new one.
+ */
+async duplicateEnvironment(envId: string, newName: string) : Promise<Result<Environment, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("duplicate_environment", { envId, newName }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Add a variable, or replace the one with the same key. A secret's value
+ * goes to the keychain; one that stops being secret is removed from it.
+ */
+async setVariable(envId: string, key: string, value: string, isSecret: boolean) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_variable", { envId, key, value, isSecret }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Add or replace a secret variable, keeping its value in the keychain.
+ */
+async setSecret(envId: string, key: string, value: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_secret", { envId, key, value }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The value of one of the environment's own secret variables.
+ */
+async revealSecret(envId: string, key: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("reveal_secret", { envId, key }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns `false` when the environment had no such variable of its own.
+ */
+async deleteVariable(envId: string, key: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_variable", { envId, key }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Pick the environment requests resolve variables from, or none (only the
+ * global variables) with `None`.
+ */
+async setActiveEnvironment(envId: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_active_environment", { envId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getActiveEnvironment() : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_active_environment") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * An environment with the global variables it inherits, in the shape
+ * exporters take. Set it as `CollectionExport::environment` to include it in
+ * an export, or leave that `None` to leave environments out. Secret values
+ * are replaced with `****`.
+ */
+async exportEnvironment(envId: string) : Promise<Result<ImportedEnvironment, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_environment", { envId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The request with its `{{variable}}` placeholders filled, as it would be
+ * sent but with secrets shown as `****`. Dynamic variables get new values
+ * unless `req.dynamic_values` has them; to send the ones returned, send the
+ * original request with the returned `dynamic_values`. `env_id` overrides
+ * `req.environment_id`.
+ */
+async resolveRequest(req: ApiRequest, envId: string | null) : Promise<Result<ApiRequest, RequestError>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resolve_request", { req, envId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Keep a sent request and its response. `request` is the one before
+ * `{{variable}}` resolution, so secrets stay out of history; the environment
+ * it was resolved from is recorded with it. The response body is truncated
+ * to the retention's `max_body_bytes`.
+ */
+async recordHistory(request: ApiRequest, response: ApiResponse | null) : Promise<Result<HistoryEntry, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("record_history", { request, response }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Newest first, at most `limit`. Entries that can't be read are skipped.
+ */
+async listHistory(limit: number | null) : Promise<Result<HistoryEntry[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_history", { limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Returns `false` when there was no such entry.
+ */
+async setHistoryPinned(id: string, pinned: boolean) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_history_pinned", { id, pinned }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete an entry, pinned or not. Returns `false` when there was no such entry.
+ */
+async deleteHistoryEntry(id: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_history_entry", { id }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getHistoryRetention() : Promise<Result<HistoryRetention, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_retention") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Entries already recorded keep their bodies; new limits apply at the next prune.
+ */
+async setHistoryRetention(retention: HistoryRetention) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_history_retention", { retention }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getHistoryStats() : Promise<Result<HistoryStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_history_stats") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Remove unpinned entries past the retention limits, in batches so recording
+ * isn't blocked meanwhile. Returns nothing removed if a prune was already running.
+ */
+async pruneHistory() : Promise<Result<HistoryPruneResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("prune_history") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Keep the current state of a tab. Cheap enough to call on every change:
+ * the draft is written to disk shortly after, replacing the previous one.
+ */
+async saveDraft(tabId: string, request: ApiRequest, metadata: JsonValue | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("save_draft", { tabId, request, metadata }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Drafts left in the active workspace, most recent first, after deleting
+ * ones past the `draft_max_age_days` setting. Unreadable files are skipped.
+ */
+async listDrafts() : Promise<Result<Draft[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_drafts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Forget a tab's draft, as when it's saved or closed. Returns `false` when
+ * it had none.
+ */
+async deleteDraft(tabId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_draft", { tabId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Send every request in a collection, or in one of its folders, one after
+ * another. Returns the run id right away; progress comes as `run-progress`
+ * events and the outcome from `get_run_result`.
+ */
+async runCollection(collectionId: string, folderId: string | null, options: RunOptions) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("run_collection", { collectionId, folderId, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * The results so far of a running run, or all of them once it ended.
+ */
+async getRunResult(runId: string) : Promise<Result<RunResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_run_result", { runId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Stop a run, aborting the request in flight. Returns `false` when the run
+ * isn't running (unknown or already ended).
+ */
+async cancelRun(runId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("cancel_run", { runId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getSettings() : Promise<Result<AppSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_settings") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Change the settings named in `patch`, a JSON object shaped like
+ * `AppSettings`; others are left as they are. A key set to `null` clears an
+ * optional setting, and `history` may hold only the retention fields to
+ * change. Returns the settings after the change.
+ */
+async updateSettings(patch: JsonValue) : Promise<Result<AppSettings, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_settings", { patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createWorkspace(name: string) : Promise<Result<Workspace, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_workspace", { name }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Every workspace, the default one first.
+ */
+async listWorkspaces() : Promise<Result<Workspace[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_workspaces") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getActiveWorkspace() : Promise<Result<Workspace, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_active_workspace") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Make a workspace active. Emits `workspace-switched` unless it already was.
+ */
+async switchWorkspace(workspaceId: string) : Promise<Result<Workspace, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("switch_workspace", { workspaceId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Delete a workspace and everything in it, secrets included. The default
+ * workspace can't be deleted; deleting the active one switches to it.
+ * Returns `false` when there was no such workspace.
+ */
+async deleteWorkspace(workspaceId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_workspace", { workspaceId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setDockBadge(label: string | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_dock_badge", { label }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Establish a new WebSocket connection.
+ * 
+ * - Spawns a background task that owns the socket and forwards incoming frames
+ * as Tauri events (`ws://message/<connection_id>`).
+ * - Returns synchronously once the HTTP upgrade handshake completes (or fails).
+ * - A channel sender is stored in `registry` so `ws_send` / `ws_disconnect`
+ * can reach the background task.
+ */
+async wsConnect(req: WsConnectRequest) : Promise<Result<WsConnectResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("ws_connect", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Send a text or binary frame to an existing WebSocket connection.
+ */
+async wsSend(req: WsSendRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("ws_send", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Close an active WebSocket connection.
+ */
+async wsDisconnect(connectionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("ws_disconnect", { connectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch and return a GraphQL server's introspection schema.
+ * 
+ * This runs the full introspection query via the Rust/curl stack so the
+ * frontend never makes a direct network request.
+ */
+async graphqlIntrospect(req: GraphQLIntrospectRequest) : Promise<Result<GraphQLIntrospectResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("graphql_introspect", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * List the services a server exposes through reflection, with their methods.
+ */
+async grpcListServices(endpoint: string, tlsOptions: GrpcTlsOptions | null) : Promise<Result<GrpcServiceInfo[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("grpc_list_services", { endpoint, tlsOptions }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Call a unary method. `request_json` uses the protobuf JSON mapping, and
+ * `metadata` is sent as request headers. `timeout_ms` (30 s by default) is
+ * sent to the server as the call's deadline.
+ */
+async grpcUnary(endpoint: string, service: string, method: string, requestJson: string, metadata: ([string, string])[], tlsOptions: GrpcTlsOptions | null, registryId: string | null, timeoutMs: number | null) : Promise<Result<GrpcUnaryResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("grpc_unary", { endpoint, service, method, requestJson, metadata, tlsOptions, registryId, timeoutMs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Start a server-streaming call and return its stream id. `stream_id` is
+ * used as the id when set, so listeners can be in place before any event
+ * arrives.
+ * 
+ * Each response message is emitted as `grpc-stream-message`; the final
+ * status and trailers follow as `grpc-stream-closed`. The call has no
+ * deadline and runs until the server ends it or `grpc_cancel`.
+ */
+async grpcServerStream(endpoint: string, service: string, method: string, requestJson: string, metadata: ([string, string])[], tlsOptions: GrpcTlsOptions | null, registryId: string | null, streamId: string | null) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("grpc_server_stream", { endpoint, service, method, requestJson, metadata, tlsOptions, registryId, streamId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Cancel a call started by `grpc_server_stream`. Returns `false` when no
+ * stream with that id is running.
+ */
+async grpcCancel(streamId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("grpc_cancel", { streamId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Compile `.proto` files and store the descriptors for gRPC calls to servers
+ * without reflection. Imports are resolved against `include_dirs`, or each
+ * file's own directory when none are given.
+ */
+async importProtoFiles(paths: string[], includeDirs: string[]) : Promise<Result<ProtoRegistryInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("import_proto_files", { paths, includeDirs }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Open an event stream and return its id. `req.request_id` is used as the
+ * id when set, so listeners can be in place before any event arrives.
+ * 
+ * Events are emitted as `sse-connected`, `sse-event`, `sse-keepalive` and
+ * finally `sse-closed`, each carrying the stream id.
+ */
+async sseConnect(req: ApiRequest) : Promise<Result<string, RequestFailure>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sse_connect", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Close a stream opened by `sse_connect`. Returns `false` when no stream with
+ * that id is open.
+ */
+async sseClose(streamId: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sse_close", { streamId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sioConnect(req: SioConnectRequest) : Promise<Result<SioConnectResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sio_connect", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sioEmit(req: SioEmitRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sio_emit", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sioEmitWithAck(req: SioEmitAckRequest) : Promise<Result<SioEmitAckResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sio_emit_with_ack", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sioDisconnect(connectionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sio_disconnect", { connectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mqttConnect(req: MqttConnectRequest) : Promise<Result<MqttConnectResponse, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_connect", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mqttPublish(req: MqttPublishRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_publish", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mqttSubscribe(req: MqttSubscribeRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_subscribe", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mqttUnsubscribe(req: MqttUnsubscribeRequest) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_unsubscribe", { req }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async mqttDisconnect(connectionId: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mqtt_disconnect", { connectionId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type ApiKeyLocation = "Header" | "Query" | 
+/**
+ * Sent as `key=value` in the Cookie header, replacing a same-named cookie.
+ */
+"Cookie"
+export type ApiRequest = { method: Methods; url: string; 
+/**
+ * Sent in order; repeated names are sent as separate header lines.
+ */
+headers: ([string, string])[]; body: BodyType; auth: AuthType; 
+/**
+ * Appended in order; repeated names are kept and an empty value is sent as a bare `?name`.
+ */
+query_params: ([string, string])[]; cookies: Cookie[]; timeout_ms: number | null; follow_redirects: boolean | null; max_redirects: number | null; verify_ssl: boolean | null; proxy: ProxyConfig | null; protocol: HttpProtocol | null; 
+/**
+ * Tab / request name for background completion notifications (REST only).
+ */
+request_label?: string | null; 
+/**
+ * When set, `rest_cancel_request` can abort this transfer (libcurl progress hook).
+ */
+cancel_key?: string | null; 
+/**
+ * Caller-chosen id for this in-flight request; `cancel_request` aborts it by id.
+ * Falls back to `cancel_key` when unset.
+ */
+request_id?: string | null; 
+/**
+ * Response bodies larger than this are written to a temp file instead of
+ * `body_base64`. Defaults to 5 MB.
+ */
+spill_threshold_bytes?: number | null; 
+/**
+ * Stop reading the response once this many body bytes have arrived. Unlimited when unset.
+ */
+max_response_bytes?: number | null; 
+/**
+ * Connect through this Unix domain socket; `url` then only supplies the path and Host.
+ */
+unix_socket?: string | null; 
+/**
+ * DNS overrides applied to every connection, including redirect hops.
+ */
+resolve_overrides?: ResolveOverride[]; ip_version?: IpVersion | null; 
+/**
+ * Outgoing interface name (`eth0`) or source IP to bind to.
+ */
+local_interface?: string | null; 
+/**
+ * Inclusive range of local ports to bind from.
+ */
+local_port_range?: [number, number] | null; 
+/**
+ * Off unless set.
+ */
+tcp_keepalive?: TcpKeepalive | null; 
+/**
+ * Limit on setting up the connection (DNS, TCP and TLS), like `curl --connect-timeout`.
+ */
+connect_timeout_ms?: number | null; 
+/**
+ * Suppress libcurl's automatic `Expect: 100-continue` on request bodies.
+ */
+disable_expect_continue?: boolean | null; 
+/**
+ * Download speed cap in bytes/second, like `curl --limit-rate`. `0` or unset is unlimited.
+ */
+max_recv_speed_bytes?: number | null; 
+/**
+ * Upload speed cap in bytes/second. `0` or unset is unlimited.
+ */
+max_send_speed_bytes?: number | null; 
+/**
+ * `Accept-Encoding` to advertise; defaults to every encoding libcurl can decode.
+ */
+accept_encoding?: string | null; 
+/**
+ * Return the body exactly as sent on the wire instead of decompressing it.
+ */
+disable_decompression?: boolean | null; compress_body?: BodyCompression | null; 
+/**
+ * Collect libcurl's `-v` style log into `ApiResponse::verbose_log`. Off by default.
+ */
+capture_verbose?: boolean | null; retry?: RetryPolicy | null; 
+/**
+ * Write the body straight to this file instead of returning it; see `ApiResponse::saved_path`.
+ */
+download_to?: string | null; 
+/**
+ * Requests sharing a session id share a cookie jar: cookies a response sets
+ * are sent on later matching requests. Explicit `cookies` win by name.
+ */
+session_id?: string | null; 
+/**
+ * Send the token stored under this auth profile as a Bearer header, refreshing
+ * it first when it has expired or is about to. Overrides `auth`.
+ */
+auth_profile?: string | null; 
+/**
+ * How close to expiry (seconds) an `auth_profile` token is refreshed, 60 by default.
+ */
+auth_refresh_skew_secs?: number | null; client_cert?: ClientCert | null; 
+/**
+ * PEM bundle file or hashed certificate directory trusted instead of the system store.
+ */
+ca_cert_path?: string | null; 
+/**
+ * Inline PEM alternative to `ca_cert_path`.
+ */
+ca_cert_pem?: string | null; 
+/**
+ * Server SPKI pins as `sha256//BASE64`, several separated by `;`, like `curl --pinnedpubkey`.
+ */
+pinned_public_key?: string | null; 
+/**
+ * Oldest TLS version to accept; libcurl's default when unset.
+ */
+tls_min_version?: TlsVersion | null; 
+/**
+ * Newest TLS version to offer; no cap when unset.
+ */
+tls_max_version?: TlsVersion | null; 
+/**
+ * OpenSSL cipher list for TLS 1.2 and below, e.g. `ECDHE-RSA-AES128-GCM-SHA256`.
+ * Environment-specific, so share-safe exports leave it out.
+ */
+tls_ciphers?: string | null; 
+/**
+ * TLS 1.3 suites, e.g. `TLS_AES_128_GCM_SHA256:TLS_CHACHA20_POLY1305_SHA256`.
+ * Left out of share-safe exports like `tls_ciphers`.
+ */
+tls13_ciphers?: string | null; 
+/**
+ * When `proxy` is unset, use the proxy from `HTTP(S)_PROXY`/`ALL_PROXY` or
+ * the OS settings, honouring `NO_PROXY`. Off by default.
+ */
+use_system_proxy?: boolean | null; 
+/**
+ * Take credentials for the host from a `.netrc` file, like `curl --netrc`.
+ * Off unless set.
+ */
+use_netrc?: NetrcMode | null; 
+/**
+ * Read instead of `~/.netrc`.
+ */
+netrc_file?: string | null; 
+/**
+ * Environment `{{variable}}` placeholders are resolved from; the active
+ * one when unset.
+ */
+environment_id?: string | null; 
+/**
+ * Request-scoped variables, taking precedence over the environment's.
+ */
+variables?: ([string, string])[]; 
+/**
+ * Dynamic variable values to reuse instead of generating new ones, as a
+ * preview or `resolve_request` returned them, so a send matches what was
+ * shown. Entries that no longer line up with the request are ignored.
+ */
+dynamic_values?: ([string, string])[] }
+export type ApiResponse = { status: number; status_text: string; 
+/**
+ * Repeated headers are comma-joined here; see `headers_list` for the raw lines.
+ */
+headers: Partial<{ [key in string]: string }>; 
+/**
+ * Every response header line in received order, duplicates intact.
+ */
+headers_list: ([string, string])[]; cookies: Cookie[]; body_base64: string; timing: TimingInfo; request_size: SizeInfo; response_size: SizeInfo; redirects: RedirectEntry[]; 
+/**
+ * Number of tries made, `1` unless a `RetryPolicy` kicked in.
+ */
+attempts: number; 
+/**
+ * Status of every attempt in order; `None` where no response came back.
+ */
+attempt_statuses: (number | null)[]; remote_addr: string | null; 
+/**
+ * The proxy the request went through (credentials removed), from `proxy`,
+ * `use_system_proxy` or a `ProxyRule`. A rule is named after the proxy,
+ * as in `http://gw:3128 (rule *.corp)` or `direct (rule *)`.
+ */
+proxy_used: string | null; 
+/**
+ * Local `ip:port` the connection was made from.
+ */
+local_addr: string | null; connection: ConnectionInfo; 
+/**
+ * Auth scheme the server accepted, when curl negotiated one (e.g. `NTLM`).
+ */
+auth_scheme: string | null; 
+/**
+ * For `Negotiate`, whether the server returned a token proving its identity.
+ */
+mutual_auth: boolean | null; 
+/**
+ * The login a `.netrc` entry supplied, when `use_netrc` provided the
+ * credentials. The password is never reported.
+ */
+netrc_login: string | null; 
+/**
+ * `None` for plain HTTP.
+ */
+tls: TlsInfo | null; 
+/**
+ * Verification failed but the host's certificate matched its `TlsException`.
+ */
+tls_exception_used: boolean; http_version: string; available_renderers: ResponseRenderer[]; detected_content_type: string | null; 
+/**
+ * Text encoding from the body's BOM or the Content-Type charset.
+ */
+detected_charset: string | null; 
+/**
+ * Encoding named by a leading byte order mark (`UTF-8`, `UTF-16LE`, `UTF-16BE`).
+ */
+bom: string | null; 
+/**
+ * The body transcoded to UTF-8 when `detected_charset` is not UTF-8.
+ * `body_base64` always holds the raw bytes.
+ */
+body_text_base64: string | null; 
+/**
+ * The request had a `BodyType::Soap` body and the response is a SOAP
+ * Fault, whatever the HTTP status.
+ */
+soap_fault: boolean; 
+/**
+ * The `error` member of a JSON-RPC response to a `JsonRpc` or
+ * `JsonRpcBatch` body; for a batch, the first call that failed.
+ */
+jsonrpc_error: JsonRpcError | null; protocol_used: string; 
+/**
+ * True when the server actually negotiated h3 (HTTP/3 over QUIC).
+ */
+negotiated_h3: boolean; 
+/**
+ * Set when the transfer failed after the response started; see `partial`.
+ */
+error: string | null; 
+/**
+ * True when the connection broke mid-response: the status and headers are
+ * real but the body is incomplete and should not be parsed.
+ */
+partial: boolean; 
+/**
+ * Set when the body exceeded the spill threshold; `body_base64` is then empty.
+ */
+body_file_path: string | null; 
+/**
+ * Where a `download_to` body was written (`<path>.part` when the transfer broke off).
+ */
+saved_path: string | null; 
+/**
+ * First 64 KB of a spilled body, for previews.
+ */
+body_preview_base64: string | null; 
+/**
+ * True when the body was cut off at `max_response_bytes`.
+ */
+truncated: boolean; 
+/**
+ * Non-fatal notes about how the transfer went (extra round trips, etc.).
+ */
+warnings: string[]; 
+/**
+ * Present when `capture_verbose` was requested.
+ */
+verbose_log: VerboseEntry[] | null }
+/**
+ * Request defaults for the active workspace. `rest_request` uses them for
+ * whatever the request leaves unset.
+ */
+export type AppSettings = { 
+/**
+ * Covers the whole transfer; `None` for no limit.
+ */
+timeout_ms: number | null; connect_timeout_ms: number | null; follow_redirects: boolean; max_redirects: number; verify_ssl: boolean; 
+/**
+ * Used for hosts without a matching `ProxyRule`.
+ */
+proxy: ProxyConfig | null; max_response_bytes: number | null; 
+/**
+ * Sent unless the request has its own `User-Agent` header.
+ */
+user_agent: string | null; 
+/**
+ * Unsaved drafts older than this are deleted; `None` keeps them.
+ */
+draft_max_age_days: number | null; 
+/**
+ * Kept by the history store; see `set_history_retention`.
+ */
+history: HistoryRetention }
+/**
+ * A token saved by the OAuth helpers under a profile name, with what is needed
+ * to refresh it.
+ */
+export type AuthProfile = { name: string; token_url: string; client_id: string; client_secret: string | null; access_token: string; refresh_token: string | null; 
+/**
+ * Unix seconds; `None` when the provider didn't say when the token expires.
+ */
+expires_at: number | null; scope: string | null }
+export type AuthType = "None" | { Basic: { username: string; password: string } } | { Bearer: { token: string } } | { ApiKey: { key: string; value: string; add_to: ApiKeyLocation } } | 
+/**
+ * A token from `oauth2_authorization_code`, sent as a Bearer header.
+ */
+{ OAuth2: { access_token: string } } | 
+/**
+ * Windows integrated auth; the user is sent as `DOMAIN\user` when a domain is set.
+ */
+{ Ntlm: { username: string; password: string; domain: string | null } } | 
+/**
+ * SPNEGO/Kerberos. Without credentials the machine's ticket cache is used;
+ * `delegate` forwards the ticket to the server. Needs the `negotiate` feature.
+ */
+{ Negotiate: { username: string | null; password: string | null; delegate: boolean } } | 
+/**
+ * A token signed at send time and sent as a Bearer header. `claims_json` and
+ * `header_overrides` are JSON objects; `iat`/`exp` are filled in when sending.
+ */
+{ Jwt: { algorithm: JwtAlgorithm; secret_or_key_pem: string; claims_json: string; header_overrides: string | null; expires_in_secs: number | null } }
+/**
+ * Compression applied to the request body before sending (`Content-Encoding`).
+ */
+export type BodyCompression = "None" | "Gzip" | "Deflate"
+export type BodyType = "None" | { Raw: { content: string; content_type: string | null } } | { FormUrlEncoded: { fields: Partial<{ [key in string]: string }> } } | { Multipart: { fields: MultipartField[] } } | { Binary: { data: number[]; filename: string | null } } | 
+/**
+ * Streamed from disk at send time instead of crossing IPC. The path must be
+ * inside the fs scope (e.g. picked through the file dialog).
+ */
+{ RawFile: { path: string; content_type: string | null } } | 
+/**
+ * Like `RawFile`, but Content-Type defaults from the file extension.
+ */
+{ BinaryFile: { path: string; content_type: string | null } } | 
+/**
+ * Sent as the `{"query", "variables", "operationName"}` JSON envelope.
+ * `variables` is JSON text and is left out when empty.
+ */
+{ GraphQL: { query: string; variables: string | null; operation_name: string | null } } | 
+/**
+ * `envelope_body` (the contents of `<Body>`) wrapped in a SOAP envelope.
+ * It must be well-formed XML; the version picks the namespace and headers.
+ */
+{ Soap: { action: string; envelope_body: string; soap_version: SoapVersion } } | 
+/**
+ * A JSON-RPC 2.0 call. `params` is JSON text (an array or object) and is
+ * left out when empty; a missing `id` is filled with the next number.
+ */
+{ JsonRpc: { method: string; params: string | null; id: string | null } } | 
+/**
+ * Several JSON-RPC calls sent as one array.
+ */
+{ JsonRpcBatch: { calls: JsonRpcCall[] } }
+export type CertificateInfo = { subject: string; issuer: string; 
+/**
+ * DNS names, IPs, emails and URIs from the subjectAltName extension.
+ */
+subject_alt_names: string[]; 
+/**
+ * RFC 3339 validity window.
+ */
+not_before: string; not_after: string; serial: string; 
+/**
+ * SHA-256 of the DER encoding as colon-separated uppercase hex.
+ */
+fingerprint_sha256: string }
+/**
+ * Certificate presented to servers that require mutual TLS.
+ */
+export type ClientCert = 
+/**
+ * `key_path` may be omitted when the key is in the same file as the cert.
+ */
+{ Pem: { cert_path: string; key_path: string | null; key_password: string | null } } | { Pkcs12: { path: string; password: string | null } }
+/**
+ * A collection saved under the app data dir. Ids are UUIDs and don't change
+ * when items are renamed or moved.
+ */
+export type Collection = { id: string; name: string; items: CollectionNode[]; 
+/**
+ * RFC 3339.
+ */
+created_at: string; 
+/**
+ * RFC 3339.
+ */
+updated_at: string; 
+/**
+ * The directory the collection is also stored in, one file per request,
+ * when it was opened from or exported to one. Saves are written there too.
+ */
+source_dir?: string | null }
+/**
+ * Files in a collection's `source_dir` were changed outside the app.
+ * `open_collection_from_dir` reloads them.
+ */
+export type CollectionDirChangedEvent = { collection_id: string; path: string }
+/**
+ * A collection for `export_postman` and `export_openapi`, in the shape the
+ * importers return.
+ */
+export type CollectionExport = { name: string; description: string | null; items: ImportedItem[]; auth: AuthType | null; 
+/**
+ * Exported as collection variables; secret values are left empty.
+ */
+environment: ImportedEnvironment | null }
+export type CollectionNode = { Folder: { id: string; name: string; items: CollectionNode[] } } | { Request: { id: string; name: string; request: ApiRequest } }
+/**
+ * A collection as listed by `list_collections`, without its items.
+ */
+export type CollectionSummary = { id: string; name: string; request_count: number; updated_at: string }
+/**
+ * How the transfer got its connection.
+ */
+export type ConnectionInfo = { 
+/**
+ * True when no new connection had to be opened.
+ */
+reused: boolean; 
+/**
+ * New connections libcurl opened for this transfer, redirects included.
+ */
+num_connects: number; local_port: number | null }
+export type Cookie = { name: string; value: string; domain: string | null; path: string | null; 
+/**
+ * The raw `Expires` attribute.
+ */
+expires: string | null; 
+/**
+ * Unix seconds the cookie expires at, from `max_age` when present, else `expires`.
+ */
+expires_at: number | null; 
+/**
+ * Whether `expires_at` had passed when the cookie was parsed.
+ */
+is_expired: boolean | null; http_only: boolean | null; secure: boolean | null; 
+/**
+ * Seconds from receipt; zero or negative means the cookie is being deleted.
+ */
+max_age: number | null; 
+/**
+ * As sent (`Strict`, `Lax` or `None`).
+ */
+same_site: string | null }
+/**
+ * Result of `import_cookies_txt`.
+ */
+export type CookieImport = { cookies: Cookie[]; 
+/**
+ * Lines that were neither comments nor valid cookie entries.
+ */
+ignored_lines: number }
+/**
+ * One page of a CSV body parsed by `parse_csv_body`.
+ */
+export type CsvTable = { 
+/**
+ * Empty when the body was parsed without a header row.
+ */
+headers: string[]; rows: string[][]; 
+/**
+ * True when more rows follow this page.
+ */
+truncated: boolean; 
+/**
+ * Data rows in the whole body, for pagination.
+ */
+total_rows: number }
+/**
+ * How `to_curl` writes the command.
+ */
+export type CurlExportOptions = { 
+/**
+ * One option per line, joined with `\` continuations.
+ */
+multiline: boolean; 
+/**
+ * `--header` rather than `-H`, for options that have both.
+ */
+long_flags: boolean; 
+/**
+ * Replace passwords, tokens, API keys and cookie values with placeholders.
+ */
+redact_auth: boolean; 
+/**
+ * Leave out cipher lists, which depend on the local TLS library.
+ */
+share_safe: boolean }
+/**
+ * Result of `parse_curl`.
+ */
+export type CurlImport = { request: ApiRequest; 
+/**
+ * Options that were skipped or only partly carried over.
+ */
+warnings: string[] }
+/**
+ * Result of `import_dotenv`.
+ */
+export type DotenvImport = { 
+/**
+ * Variables set from the file, new and overwritten.
+ */
+imported: number; 
+/**
+ * Keys the environment already had, now set from the file.
+ */
+overwritten: string[]; 
+/**
+ * Lines that were neither comments nor `KEY=VALUE` assignments.
+ */
+ignored_lines: number }
+/**
+ * The unsaved state of an editor tab, kept for restoring after a crash.
+ */
+export type Draft = { tab_id: string; request: ApiRequest; 
+/**
+ * Whatever else the frontend needs to restore the tab, such as its
+ * title or the collection item it edits.
+ */
+metadata?: JsonValue; 
+/**
+ * RFC 3339.
+ */
+saved_at: string }
+/**
+ * A named set of variables saved under the app data dir. Every environment
+ * inherits the global one's variables and can override them.
+ */
+export type Environment = { id: string; name: string; variables: EnvironmentVariable[]; 
+/**
+ * The global environment, whose id is always `global`.
+ */
+is_global: boolean }
+export type EnvironmentVariable = { key: string; value: string; is_secret: boolean }
+export type ExampleResponse = { status: number; content_type: string | null; body: string }
+/**
+ * Response from a raw URL GET fetch (used by ImportModal for OpenAPI URLs).
+ */
+export type FetchUrlResponse = { status: number; body: string }
+/**
+ * Request to fetch (introspect) a GraphQL schema.
+ */
+export type GraphQLIntrospectRequest = { url: string; headers: Partial<{ [key in string]: string }>; 
+/**
+ * GraphQL file name for background completion notifications.
+ */
+request_label?: string | null; 
+/**
+ * Applied the same way as `ApiRequest::auth`.
+ */
+auth?: AuthType | null; proxy?: ProxyConfig | null; use_system_proxy?: boolean | null; verify_ssl?: boolean | null; client_cert?: ClientCert | null; ca_cert_path?: string | null; 
+/**
+ * Defaults to 30 seconds.
+ */
+timeout_ms?: number | null }
+/**
+ * Result of a GraphQL introspection fetch.
+ */
+export type GraphQLIntrospectResponse = { 
+/**
+ * Raw introspection JSON (the `data` field from the response).
+ */
+schema_json: string | null; error: string | null; 
+/**
+ * The server answered but refuses introspection queries.
+ */
+introspection_disabled: boolean; 
+/**
+ * Entries in `__schema.types`, built-in scalars and `__*` types included.
+ */
+type_count: number; query_type: string | null; mutation_type: string | null; subscription_type: string | null }
+export type GrpcMethodInfo = { name: string; 
+/**
+ * Fully qualified message names.
+ */
+input_type: string; output_type: string; client_streaming: boolean; server_streaming: boolean }
+/**
+ * A service reported by server reflection.
+ */
+export type GrpcServiceInfo = { 
+/**
+ * Fully qualified, e.g. `helloworld.Greeter`.
+ */
+name: string; methods: GrpcMethodInfo[] }
+/**
+ * Pushed as `grpc-stream-closed` when a `grpc_server_stream` call ends,
+ * after its last message event.
+ */
+export type GrpcStreamClosedEvent = { stream_id: string; status_code: number; status_name: string; status_message: string; headers: ([string, string])[]; trailers: ([string, string])[]; received: number; dropped: number; duration_ms: number }
+/**
+ * One message of a `grpc_server_stream` call, pushed as `grpc-stream-message`.
+ */
+export type GrpcStreamMessageEvent = { stream_id: string; 
+/**
+ * 1-based position among the messages received, so gaps show drops.
+ */
+index: number; 
+/**
+ * The message as pretty-printed JSON.
+ */
+data: string; 
+/**
+ * Messages dropped so far because the queue to the frontend was full.
+ */
+dropped: number; timestamp_ms: number }
+/**
+ * Transport settings for the `grpc_*` commands. Without them, `http://`
+ * endpoints are dialled in plaintext and everything else over TLS.
+ */
+export type GrpcTlsOptions = { 
+/**
+ * Force plaintext HTTP/2 (`true`) or TLS (`false`) regardless of the scheme.
+ */
+plaintext?: boolean | null; 
+/**
+ * PEM bundle to trust instead of the system roots.
+ */
+ca_cert_path?: string | null; 
+/**
+ * PEM certificate and key for mutual TLS; both must be set.
+ */
+client_cert_path?: string | null; client_key_path?: string | null; 
+/**
+ * Name to verify the server certificate against, when it differs from
+ * the endpoint host.
+ */
+server_name?: string | null }
+/**
+ * Result of `grpc_unary`. A non-OK gRPC status is reported here rather than
+ * as an error, so its message and trailers can be shown.
+ */
+export type GrpcUnaryResponse = { 
+/**
+ * The response message as pretty-printed JSON; `None` unless the status is OK.
+ */
+response_json: string | null; status_code: number; 
+/**
+ * Canonical code name, e.g. `OK` or `NOT_FOUND`.
+ */
+status_name: string; status_message: string; 
+/**
+ * Response headers. `-bin` values are base64.
+ */
+headers: ([string, string])[]; 
+/**
+ * Trailer metadata, without the `grpc-status`/`grpc-message` entries.
+ */
+trailers: ([string, string])[]; duration_ms: number }
+/**
+ * A request and the response it got, for `export_har`.
+ */
+export type HarEntry = { request: ApiRequest; response: ApiResponse | null; 
+/**
+ * When the request was sent (RFC 3339); the time of export when unset.
+ */
+started_at: string | null }
+/**
+ * Pushed as a Tauri event (`har-import-progress`) while `import_har` reads
+ * an archive.
+ */
+export type HarImportProgressEvent = { path: string; 
+/**
+ * Entries read so far.
+ */
+entries: number; read_bytes: number; total_bytes: number }
+/**
+ * A sent request kept by `record_history`, with `{{variable}}` placeholders
+ * as written rather than resolved.
+ */
+export type HistoryEntry = { id: string; request: ApiRequest; response: ApiResponse | null; 
+/**
+ * RFC 3339.
+ */
+sent_at: string; 
+/**
+ * The environment variables were resolved from.
+ */
+environment_id: string | null; 
+/**
+ * Pinned entries are never pruned.
+ */
+pinned: boolean; 
+/**
+ * The response body was cut to `HistoryRetention::max_body_bytes`.
+ */
+body_truncated: boolean }
+/**
+ * Result of `prune_history`.
+ */
+export type HistoryPruneResult = { removed: number; freed_bytes: number }
+/**
+ * Limits `prune_history` enforces on unpinned entries. `None` turns a limit off.
+ */
+export type HistoryRetention = { max_entries: number | null; 
+/**
+ * Size of all entries on disk, pinned ones included.
+ */
+max_total_mb: number | null; 
+/**
+ * Response bodies longer than this are truncated when recorded.
+ */
+max_body_bytes: number | null; max_age_days: number | null; 
+/**
+ * `prune_history` runs in the background after this many recorded entries.
+ */
+prune_every: number }
+export type HistoryStats = { entries: number; pinned: number; total_bytes: number; 
+/**
+ * When the oldest entry was sent (RFC 3339).
+ */
+oldest_sent_at: string | null; 
+/**
+ * What `prune_history` would remove with the current retention settings.
+ */
+prunable_entries: number; prunable_bytes: number }
+export type HttpProtocol = 
+/**
+ * HTTP/2 over TLS, HTTP/1.1 for plain `http://` (the long-standing default).
+ */
+"Tcp" | 
+/**
+ * Let libcurl pick whatever it considers best.
+ */
+"Auto" | "Http1_1" | 
+/**
+ * HTTP/2, including the `Upgrade: h2c` dance on plain `http://`.
+ */
+"Http2" | 
+/**
+ * HTTP/2 without negotiation; the server must already speak h2 / h2c.
+ */
+"Http2PriorKnowledge" | 
+/**
+ * HTTP/3 over QUIC. Needs a libcurl built with HTTP/3 (quiche or ngtcp2).
+ */
+"Quic"
+/**
+ * A collection read from another tool's format, for the frontend to save as a project.
+ */
+export type ImportResult = { name: string; description: string | null; items: ImportedItem[]; 
+/**
+ * Auth for requests with `inherit_auth` set.
+ */
+auth: AuthType | null; environment: ImportedEnvironment | null; 
+/**
+ * What couldn't be carried over, each prefixed with where it was.
+ */
+report: string[] }
+export type ImportedEnvironment = { name: string; variables: ImportedVariable[] }
+export type ImportedItem = { Folder: { name: string; description: string | null; items: ImportedItem[] } } | { Request: { name: string; description: string | null; request: ApiRequest; 
+/**
+ * Use the collection's `auth` instead of `request.auth`.
+ */
+inherit_auth: boolean; 
+/**
+ * A saved response, used by `export_openapi` to describe the response.
+ */
+example_response?: ExampleResponse | null } }
+export type ImportedVariable = { key: string; value: string; enabled: boolean; secret: boolean }
+/**
+ * Address family used for name resolution, like `curl -4` / `curl -6`.
+ */
+export type IpVersion = "Any" | "V4Only" | "V6Only"
+export type JsonRpcCall = { method: string; params: string | null; id: string | null }
+export type JsonRpcError = { code: number; message: string; 
+/**
+ * The `data` member as JSON text.
+ */
+data: string | null; 
+/**
+ * The `id` of the failed call, as JSON text (`null` when the server
+ * couldn't read it).
+ */
+id: string | null }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * HMAC algorithms take a shared secret, the rest a PEM private key.
+ */
+export type JwtAlgorithm = "HS256" | "HS384" | "HS512" | "RS256" | "ES256" | "EdDSA"
+export type Methods = "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" | "OPTIONS" | "TRACE" | "CONNECT" | 
+/**
+ * Any other verb (e.g. `PURGE`, `PROPFIND`); must be a valid HTTP token.
+ */
+{ Custom: string }
+export type MqttConnectRequest = { connection_id: string; url: string; client_id: string; username: string | null; password: string | null; clean_session: boolean | null; keep_alive_secs: number | null; subscriptions: MqttSubscription[] }
+export type MqttConnectResponse = { connection_id: string; url: string; client_id: string; error: string | null }
+export type MqttDisconnectedEvent = { connection_id: string; reason: string }
+export type MqttIncomingMessage = { connection_id: string; id: string; topic: string; data: string; qos: number; retain: boolean; timestamp_ms: number }
+export type MqttPublishRequest = { connection_id: string; topic: string; data: string; qos: number; retain: boolean | null }
+export type MqttSubscribeRequest = { connection_id: string; topic: string; qos: number }
+export type MqttSubscription = { topic: string; qos: number }
+export type MqttUnsubscribeRequest = { connection_id: string; topic: string }
+export type MultipartField = { name: string; value: MultipartValue }
+export type MultipartValue = { Text: string } | { File: { data: number[]; filename: string; content_type: string | null } } | 
+/**
+ * Read from disk while sending; `filename` defaults to the path's file name.
+ */
+{ FilePath: { path: string; filename: string | null; content_type: string | null } }
+/**
+ * Mirrors libcurl's `CURLOPT_NETRC` levels.
+ */
+export type NetrcMode = "Ignored" | 
+/**
+ * Credentials in the URL or `auth` take precedence over the file.
+ */
+"Optional" | 
+/**
+ * The file's credentials win over any in the URL.
+ */
+"Required"
+/**
+ * How `oauth2_client_credentials` sends the client credentials.
+ */
+export type OAuth2ClientAuth = 
+/**
+ * HTTP Basic `Authorization` header (`client_secret_basic`).
+ */
+"BasicHeader" | 
+/**
+ * `client_id` and `client_secret` form fields (`client_secret_post`).
+ */
+"Body"
+/**
+ * Settings for `oauth2_client_credentials`.
+ */
+export type OAuth2ClientCredentialsConfig = { token_url: string; client_id: string; client_secret: string; 
+/**
+ * Space-separated scopes.
+ */
+scope?: string | null; 
+/**
+ * Sent as `audience`, which Auth0 and similar providers require.
+ */
+audience?: string | null; auth_style?: OAuth2ClientAuth; 
+/**
+ * Save the token under this auth profile so requests can use it.
+ */
+profile?: string | null }
+/**
+ * Settings for `oauth2_authorization_code`.
+ */
+export type OAuth2Config = { authorization_url: string; token_url: string; client_id: string; 
+/**
+ * Omit for public clients, which rely on PKCE alone.
+ */
+client_secret: string | null; scopes?: string[]; 
+/**
+ * Port for the loopback redirect listener; a free one is picked when unset.
+ * Set it when the provider only accepts a pre-registered redirect URI.
+ */
+redirect_port?: number | null; 
+/**
+ * Path of the redirect URI, `/callback` by default.
+ */
+redirect_path?: string | null; 
+/**
+ * Extra query parameters for the authorization URL (e.g. `audience`, `prompt`).
+ */
+extra_params?: ([string, string])[]; 
+/**
+ * How long to wait for the browser to come back, 300 by default.
+ */
+timeout_secs?: number | null; 
+/**
+ * Save the token under this auth profile so requests can refresh it.
+ */
+profile?: string | null }
+/**
+ * What the user needs to finish a device authorization in their browser.
+ */
+export type OAuth2DeviceAuthorization = { device_code: string; user_code: string; verification_uri: string; 
+/**
+ * The verification URI with the user code already filled in, if offered.
+ */
+verification_uri_complete: string | null; 
+/**
+ * Seconds to wait between polls.
+ */
+interval: number; 
+/**
+ * Seconds until `device_code` expires.
+ */
+expires_in: number }
+/**
+ * Settings for `oauth2_device_start` and `oauth2_device_poll`.
+ */
+export type OAuth2DeviceConfig = { device_authorization_url: string; token_url: string; client_id: string; client_secret?: string | null; 
+/**
+ * Space-separated scopes.
+ */
+scope?: string | null; 
+/**
+ * Save the token under this auth profile so requests can refresh it.
+ */
+profile?: string | null }
+/**
+ * Pushed as a Tauri event (`oauth-device-poll`) after each poll of the token
+ * endpoint while `oauth2_device_poll` waits for the user.
+ */
+export type OAuth2DevicePollEvent = { user_code: string; attempt: number; 
+/**
+ * `authorization_pending` or `slow_down`.
+ */
+status: string; 
+/**
+ * Current poll interval, raised by `slow_down` responses.
+ */
+interval: number; remaining_secs: number }
+/**
+ * Token endpoint response from `oauth2_authorization_code` and
+ * `oauth2_client_credentials`. `access_token` can be sent with
+ * `AuthType::Bearer` or `AuthType::OAuth2`.
+ */
+export type OAuth2Token = { access_token: string; refresh_token: string | null; token_type: string | null; expires_in: number | null; scope: string | null; 
+/**
+ * The full JSON response, for provider-specific fields like `id_token`.
+ */
+raw: JsonValue }
+export type OpenApiExport = { document: string; 
+/**
+ * Requests that were merged or skipped, and auth that couldn't be described.
+ */
+warnings: string[] }
+/**
+ * How `export_openapi` writes the document.
+ */
+export type OpenApiExportOptions = { 
+/**
+ * `info.version`, `1.0.0` when unset.
+ */
+version: string | null; 
+/**
+ * The only server listed, instead of the origins the requests use.
+ */
+server_url: string | null; 
+/**
+ * YAML rather than JSON.
+ */
+yaml: boolean }
+export type ProtoField = { number: number; 
+/**
+ * Offset of the field's tag within its enclosing message.
+ */
+offset: number; value: ProtoValue }
+/**
+ * A message field, as described to the request editor.
+ */
+export type ProtoFieldSchema = { name: string; 
+/**
+ * The key used in the JSON mapping.
+ */
+json_name: string; number: number; 
+/**
+ * A scalar type like `int64`, or the fully qualified message or enum name.
+ */
+type_name: string; repeated: boolean; map: boolean; 
+/**
+ * The `oneof` this field belongs to.
+ */
+oneof: string | null }
+export type ProtoMessageSchema = { 
+/**
+ * Fully qualified message name.
+ */
+name: string; fields: ProtoFieldSchema[]; 
+/**
+ * A JSON request body with every field set to its default, to start
+ * editing from. Only the first field of each `oneof` is included.
+ */
+skeleton_json: string }
+/**
+ * A protobuf message decoded without a schema by `decode_protobuf_raw`.
+ */
+export type ProtoNode = { fields: ProtoField[]; 
+/**
+ * Bytes of this message that were decoded before `error`, or all of them.
+ */
+decoded_bytes: number; 
+/**
+ * Set when the wire data was malformed; `fields` holds what came before it.
+ */
+error: string | null }
+/**
+ * Result of `import_proto_files`. Pass `registry_id` to the gRPC call
+ * commands to use these descriptors instead of server reflection.
+ */
+export type ProtoRegistryInfo = { registry_id: string; 
+/**
+ * Names of the compiled files, imports included.
+ */
+files: string[]; services: GrpcServiceInfo[]; messages: ProtoMessageSchema[] }
+/**
+ * 64-bit values are strings because JavaScript numbers can't hold them exactly.
+ */
+export type ProtoValue = 
+/**
+ * `zigzag` is the value read as a `sint64`.
+ */
+{ Varint: { value: string; zigzag: string } } | { Fixed64: { value: string; double: number } } | { Fixed32: { value: number; float: number } } | 
+/**
+ * A length-delimited field that parses as a message.
+ */
+{ Message: ProtoNode } | 
+/**
+ * A deprecated start/end group pair.
+ */
+{ Group: ProtoNode } | { String: string } | { Bytes: { base64: string } }
+export type ProxyAuthScheme = "Basic" | "Digest" | 
+/**
+ * `username` may be given as `DOMAIN\user`.
+ */
+"Ntlm" | 
+/**
+ * Without a username the Kerberos ticket cache is used.
+ */
+"Negotiate" | 
+/**
+ * Whichever of the above the proxy offers, strongest first.
+ */
+"Any"
+export type ProxyConfig = { url: string; username: string | null; password: string | null; proxy_type?: ProxyKind; 
+/**
+ * Comma-separated hosts to reach directly: exact names, `.domain`
+ * suffixes, IPs and CIDR blocks such as `10.0.0.0/8`, or `*`.
+ */
+no_proxy?: string | null; 
+/**
+ * How to authenticate to the proxy; Basic when unset.
+ */
+auth_scheme?: ProxyAuthScheme | null }
+/**
+ * How to talk to the proxy. Through a proxy, `remote_addr` is the proxy's
+ * address and `tcp_handshake_ms` includes the proxy handshake.
+ */
+export type ProxyKind = "Http" | 
+/**
+ * TLS to the proxy itself.
+ */
+"Https" | "Socks4" | 
+/**
+ * SOCKS5 with names resolved locally.
+ */
+"Socks5" | 
+/**
+ * SOCKS5 with names resolved by the proxy, e.g. for an SSH dynamic forward.
+ */
+"Socks5Hostname"
+/**
+ * Routes requests without their own `proxy` by target host, see `set_proxy_rule`.
+ */
+export type ProxyRule = { id: string; 
+/**
+ * A host name, `*.domain` for its subdomains, or `*` for every host.
+ * The most specific matching rule wins.
+ */
+pattern: string; target: ProxyRuleTarget }
+export type ProxyRuleTarget = 
+/**
+ * Connect directly, ignoring `use_system_proxy`.
+ */
+"Direct" | { Proxy: ProxyConfig }
+export type RedirectEntry = { url: string; status: number; headers: Partial<{ [key in string]: string }>; 
+/**
+ * Cookies this hop set; they are forwarded to later hops that match them.
+ */
+set_cookies: Cookie[]; duration_ms: number }
+/**
+ * Why `rest_request` failed, so the frontend can branch on the kind of failure
+ * instead of matching message text. `Display` gives the human-readable message.
+ */
+export type RequestError = { DnsResolution: { host: string; 
+/**
+ * Set when resolution was restricted to one address family.
+ */
+ip_version: IpVersion | null } } | "ConnectionRefused" | 
+/**
+ * The server accepted the connection but dropped it before the response finished.
+ */
+{ ConnectionClosed: { detail: string } } | 
+/**
+ * `phase` is where the deadline hit: `dns`, `connect`, `tls`, `ttfb` or `download`.
+ */
+{ Timeout: { phase: string } } | { TlsVerification: { detail: string } } | 
+/**
+ * The server's certificate isn't trusted. `fingerprint_sha256` is what it
+ * presented, for offering `add_tls_exception`.
+ */
+{ UntrustedCertificate: { host: string; fingerprint_sha256: string | null; detail: string } } | 
+/**
+ * The host has a `TlsException` but now presents a different certificate.
+ */
+{ CertificateChanged: { host: string; expected: string; presented: string } } | 
+/**
+ * The server's public key matched none of the request's `pinned_public_key` pins.
+ */
+{ PublicKeyPinMismatch: { detail: string } } | 
+/**
+ * The client certificate or key could not be read or unlocked.
+ */
+{ ClientCertificate: { detail: string } } | { InvalidUrl: { reason: string } } | "Cancelled" | { Io: { detail: string } } | 
+/**
+ * Any other libcurl failure; `code` is the `CURLcode`.
+ */
+{ Curl: { code: number; detail: string } } | 
+/**
+ * The request's `auth_profile` token had expired and could not be refreshed.
+ * `error` is the OAuth error code (e.g. `invalid_grant`) when the token
+ * endpoint sent one.
+ */
+{ TokenRefreshFailed: { profile: string; error: string | null; detail: string } } | 
+/**
+ * The proxy still answered 407 after the configured scheme was tried.
+ * `proxy_authenticate` holds its `Proxy-Authenticate` challenges.
+ */
+{ ProxyAuthFailed: { proxy_authenticate: string[] } } | 
+/**
+ * The `.netrc` file could not be parsed; `line` is 1-based.
+ */
+{ NetrcParse: { path: string; line: number; detail: string } } | 
+/**
+ * The auth scheme needs a libcurl feature this build lacks.
+ */
+{ AuthNotSupported: { scheme: string; detail: string } } | 
+/**
+ * `{{name}}` placeholders with no value in the environment or the
+ * request's `variables`.
+ */
+{ UnresolvedVariables: { names: string[] } } | 
+/**
+ * A secret variable's value couldn't be read from the OS keychain.
+ */
+{ SecretUnavailable: { key: string; detail: string } } | 
+/**
+ * A `{{$function}}` placeholder that isn't a known dynamic variable or
+ * has bad arguments. `expression` is the placeholder without its braces.
+ */
+{ InvalidDynamicVariable: { expression: string; detail: string } } | 
+/**
+ * The request could not be built (bad method, body encoding, redirect limit, ...).
+ */
+{ Other: { detail: string } }
+/**
+ * A `RequestError` as commands hand it to the frontend: the error to branch on
+ * plus its `Display` text, so messages are only written once.
+ */
+export type RequestFailure = { error: RequestError; message: string }
+/**
+ * Result of `preview_request`: the request as it would go on the wire.
+ */
+export type RequestPreview = { method: string; 
+/**
+ * Final URL including query params and any API key placed in the query.
+ */
+url: string; headers: ([string, string])[]; 
+/**
+ * Files streamed from disk appear as `<file: path, N bytes>` rather than their contents.
+ */
+body_base64: string; 
+/**
+ * Bytes that go on the wire, including streamed files.
+ */
+body_size: number; 
+/**
+ * The normalized `pinned_public_key` list the handshake will be checked against.
+ */
+pinned_public_keys: string[]; 
+/**
+ * The dynamic variable values shown, by placeholder in the order they
+ * were filled. Pass them as `ApiRequest::dynamic_values` to send them.
+ */
+dynamic_values: ([string, string])[] }
+/**
+ * Pushed as a Tauri event (`request-progress`) while a REST response body is
+ * being received. Only emitted for requests that carry a `request_id`.
+ */
+export type RequestProgressEvent = { request_id: string; downloaded_bytes: number; 
+/**
+ * From `Content-Length`; `None` when the server did not announce a size.
+ */
+total_bytes: number | null; elapsed_ms: number }
+/**
+ * Pins `host:port` to a fixed address, like `curl --resolve`.
+ */
+export type ResolveOverride = { host: string; port: number; address: string }
+export type ResponseRenderer = "Raw" | "Json" | "Xml" | "Html" | "HtmlPreview" | "Image" | "Audio" | "Video" | "Pdf" | "Csv" | "Yaml" | "Markdown" | "MsgPack" | "Protobuf" | 
+/**
+ * Zip-based archive (also .docx, .xlsx, .jar, ...); sniffed from the body.
+ */
+"Archive"
+/**
+ * Automatic retries for `rest_request`. Only idempotent methods are retried
+ * unless `retry_non_idempotent` is set.
+ */
+export type RetryPolicy = { 
+/**
+ * Total number of tries, including the first one.
+ */
+max_attempts: number; 
+/**
+ * Wait before the first retry.
+ */
+backoff_ms: number; 
+/**
+ * Factor applied to the wait after each retry (`1.0` keeps it constant).
+ */
+backoff_multiplier: number; 
+/**
+ * Response statuses that trigger a retry, e.g. `[502, 503, 504]`.
+ */
+retry_on_statuses?: number[]; 
+/**
+ * Retry when the connection could not be made or was dropped mid-transfer.
+ */
+retry_on_connection_errors?: boolean; 
+/**
+ * Opt in to retrying POST, PATCH, CONNECT and custom verbs.
+ */
+retry_non_idempotent?: boolean }
+/**
+ * The outcome of one request in a run.
+ */
+export type RunItemResult = { item_id: string; name: string; 
+/**
+ * `None` when no response came back.
+ */
+status: number | null; status_text: string | null; duration_ms: number; size_bytes: number | null; 
+/**
+ * Got a response with a status below 400.
+ */
+passed: boolean; error: string | null }
+/**
+ * How `run_collection` sends the requests.
+ */
+export type RunOptions = { 
+/**
+ * Wait between one request's response and sending the next.
+ */
+delay_ms?: number | null; 
+/**
+ * End the run after the first request that fails.
+ */
+stop_on_failure?: boolean; 
+/**
+ * Environment every request resolves variables from; each request's own
+ * `environment_id` when unset.
+ */
+environment_id?: string | null }
+/**
+ * Pushed as a Tauri event (`run-progress`) after each request in a run.
+ */
+export type RunProgressEvent = { run_id: string; 
+/**
+ * Position of `item` in the run, from 0.
+ */
+index: number; total: number; item: RunItemResult }
+/**
+ * A run started by `run_collection`, as returned by `get_run_result`.
+ */
+export type RunResult = { run_id: string; collection_id: string; folder_id: string | null; status: RunStatus; 
+/**
+ * Requests the run was started with; `items` holds those sent so far.
+ */
+total: number; items: RunItemResult[]; 
+/**
+ * RFC 3339.
+ */
+started_at: string; 
+/**
+ * RFC 3339; `None` while running.
+ */
+finished_at: string | null }
+export type RunStatus = "Running" | "Completed" | 
+/**
+ * A request failed with `stop_on_failure` set.
+ */
+"Stopped" | "Cancelled" | 
+/**
+ * The run broke off for a reason other than a request failing.
+ */
+"Failed"
+/**
+ * Sent from the frontend to open a new Socket.IO connection.
+ */
+export type SioConnectRequest = { 
+/**
+ * Unique connection ID chosen by the caller.
+ */
+connection_id: string; 
+/**
+ * The Socket.IO server URL (http:// or https://).
+ */
+url: string; 
+/**
+ * Optional namespace to connect to (defaults to "/").
+ */
+namespace: string | null; 
+/**
+ * Optional extra headers.
+ */
+headers: Partial<{ [key in string]: string }>; 
+/**
+ * Optional auth payload sent during the handshake.
+ */
+auth: string | null; 
+/**
+ * Transport selection.
+ */
+transport: string | null; 
+/**
+ * Whether automatic reconnects are enabled.
+ */
+reconnect: boolean | null; 
+/**
+ * Whether to reconnect after an explicit server disconnect.
+ */
+reconnect_on_disconnect: boolean | null; 
 /**
  * Minimum reconnect delay in milliseconds.
  */
@@ -300,8 +2607,141 @@ export type SioEmitRequest = { connection_id: string; event: string; data: strin
  * Pushed as a Tauri event for every message received.
  */
 export type SioIncomingMessage = { connection_id: string; id: string; event: string; data: string; timestamp_ms: number }
-export type SizeInfo = { headers_bytes: number; body_bytes: number; total_bytes: number }
-export type TimingInfo = { total_ms: number; dns_lookup_ms: number; tcp_handshake_ms: number; tls_handshake_ms: number; transfer_start_ms: number; ttfb_ms: number; content_download_ms: number }
+export type SizeInfo = { headers_bytes: number; body_bytes: number; total_bytes: number; 
+/**
+ * Body size before `compress_body` was applied; `None` when sent uncompressed.
+ */
+uncompressed_body_bytes: number | null }
+export type SoapVersion = "Soap1_1" | "Soap1_2"
+/**
+ * Pushed as `sse-closed` when the stream ends for any reason.
+ */
+export type SseClosedEvent = { stream_id: string; 
+/**
+ * `None` when the server ended the stream or `sse_close` was called.
+ * For a non-2xx response, holds the start of its body.
+ */
+error: string | null }
+/**
+ * Pushed as `sse-connected` once the response headers of an `sse_connect`
+ * stream are in.
+ */
+export type SseConnectedEvent = { stream_id: string; status: number; status_text: string; headers: ([string, string])[] }
+/**
+ * A `:` comment line, pushed as `sse-keepalive`. Servers send these to keep
+ * idle connections open.
+ */
+export type SseKeepaliveEvent = { stream_id: string; comment: string; timestamp_ms: number }
+/**
+ * One dispatched event, pushed as `sse-event`.
+ */
+export type SseMessageEvent = { stream_id: string; 
+/**
+ * The `event` field, `message` when the server sent none.
+ */
+event: string; 
+/**
+ * `data` lines joined with newlines.
+ */
+data: string; 
+/**
+ * The last event id seen on the stream, which carries over between events.
+ */
+id: string | null; 
+/**
+ * A `retry` field sent with this event.
+ */
+retry_ms: number | null; timestamp_ms: number }
+/**
+ * TCP keepalive probing for connections that sit idle waiting on a slow server.
+ */
+export type TcpKeepalive = { 
+/**
+ * Idle time before the first probe is sent.
+ */
+idle_secs: number; 
+/**
+ * Delay between subsequent probes.
+ */
+interval_secs: number }
+export type TimingInfo = { 
+/**
+ * Spans every attempt, including backoff waits, when the request was retried.
+ */
+total_ms: number; 
+/**
+ * Time spent on redirect hops before the final request started. The phases
+ * below cover only the final hop, so `redirect_ms` plus the phases add up to
+ * `total_ms` (unless the request was retried).
+ */
+redirect_ms: number; redirect_count: number; 
+/**
+ * ~0 together with `tcp_handshake_ms` usually means the connection was reused
+ * (see `ApiResponse::connection`).
+ */
+dns_lookup_ms: number; tcp_handshake_ms: number; tls_handshake_ms: number; transfer_start_ms: number; ttfb_ms: number; content_download_ms: number }
+/**
+ * A host whose self-signed certificate is trusted by fingerprint, see `add_tls_exception`.
+ */
+export type TlsException = { host: string; 
+/**
+ * Same format as `CertificateInfo::fingerprint_sha256`.
+ */
+fingerprint_sha256: string; 
+/**
+ * RFC 3339.
+ */
+added_at: string }
+/**
+ * The final connection's TLS session, as a browser's lock icon would show it.
+ */
+export type TlsInfo = { 
+/**
+ * e.g. `TLSv1.3`.
+ */
+version: string | null; cipher: string | null; 
+/**
+ * Protocol agreed via ALPN (`h2`, `http/1.1`); `None` when none was.
+ */
+alpn: string | null; 
+/**
+ * The chain as the server sent it, leaf first. Empty when the TLS backend
+ * doesn't report certificates or the connection was reused.
+ */
+certificates: CertificateInfo[]; 
+/**
+ * The leaf certificate has expired or expires within 30 days.
+ */
+leaf_expiring: boolean }
+export type TlsVersion = "Tls1_0" | "Tls1_1" | "Tls1_2" | "Tls1_3"
+/**
+ * Pushed as a Tauri event (`upload-progress`) while a REST request body is
+ * being sent. Only emitted for requests that carry a `request_id`.
+ */
+export type UploadProgressEvent = { request_id: string; uploaded_bytes: number; total_bytes: number }
+export type VerboseDirection = 
+/**
+ * Informational text from libcurl (`*` lines in `curl -v`).
+ */
+"Info" | "HeaderIn" | "HeaderOut" | 
+/**
+ * Body / TLS records; `text` only carries a byte count.
+ */
+"DataIn" | "DataOut"
+export type VerboseEntry = { direction: VerboseDirection; text: string }
+/**
+ * A separate set of collections, environments, history and settings.
+ */
+export type Workspace = { id: string; name: string; 
+/**
+ * RFC 3339.
+ */
+created_at: string; 
+/**
+ * The workspace that always exists and takes over when the active one
+ * is deleted.
+ */
+is_default: boolean }
 /**
  * Pushed as a Tauri event (`ws://closed/<connection_id>`) when the connection
  * is terminated (either side).
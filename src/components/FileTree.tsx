@@ -62,7 +62,7 @@ interface FileTreeProps {
 import {
 	getMethodColor,
 	getShortMethod,
-	METHOD_COLORS,
+	methodName,
 } from "../utils/methodConstants";
 
 const LAZY_BATCH_SIZE = 100;
@@ -362,10 +362,10 @@ const DragOverlayItem = memo(function DragOverlayItem({
 						className="mr-1 shrink-0 font-bold font-mono text-[10px]"
 						style={{
 							color:
-								METHOD_COLORS[(item as RequestFile).request.method] || "#888",
+								getMethodColor((item as RequestFile).request.method),
 						}}
 					>
-						{(item as RequestFile).request.method}
+						{methodName((item as RequestFile).request.method)}
 					</span>
 					<span className="truncate text-white/90">{item.name}</span>
 				</>
@@ -1,9 +1,9 @@
 import { useEffect, useRef, useState } from "react";
 import { createPortal } from "react-dom";
 import type { Methods } from "../bindings";
-import { getMethodColor, METHOD_COLORS } from "../utils/methodConstants";
+import { getMethodColor, methodName } from "../utils/methodConstants";
 
-const METHODS: Methods[] = [
+const METHODS: Exclude<Methods, { Custom: string }>[] = [
 	"GET",
 	"POST",
 	"PUT",
@@ -64,9 +64,9 @@ export function MethodSelector({ value, onChange }: MethodSelectorProps) {
 				type="button"
 				onClick={() => setIsOpen(!isOpen)}
 				className="no-drag flex w-[80px] items-center gap-1 px-4 py-2.5 font-bold font-mono text-sm focus:outline-none"
-				style={{ color: METHOD_COLORS[value] }}
+				style={{ color: getMethodColor(value) }}
 			>
-				{value}
+				{methodName(value)}
 			</button>
 
 			{isOpen &&
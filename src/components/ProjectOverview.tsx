@@ -14,7 +14,7 @@ import type {
 } from "../types/project";
 import type { WorkflowFile } from "../types/workflow";
 import { hexToRgba } from "../utils/format";
-import { getMethodColorTailwind } from "../utils/methodConstants";
+import { getMethodColorTailwind, methodName } from "../utils/methodConstants";
 import { generateSnippet } from "../utils/snippets";
 import { CodeViewer } from "./CodeMirror";
 import { AuthEditor } from "./editors/AuthEditor";
@@ -122,7 +122,7 @@ const RequestDetails = React.memo(function RequestDetails({
 
 	const colors = getMethodColorTailwind(request.request.method);
 
-	const queryParams = Object.entries(request.request.query_params);
+	const queryParams = request.request.query_params;
 	const body = request.request.body;
 	const hasBody = body !== "None";
 
@@ -184,7 +184,7 @@ const RequestDetails = React.memo(function RequestDetails({
 					className={`flex items-center justify-start px-4 ${colors.bg} ${colors.text} min-w-[69px] shrink-0 transition-colors`}
 				>
 					<span className="text-left font-mono font-semibold text-xs">
-						{request.request.method}
+						{methodName(request.request.method)}
 					</span>
 				</div>
 				<div className="flex min-w-0 flex-1 items-center justify-between px-4">
@@ -3,6 +3,7 @@ import { useMemo, useState } from "react";
 import { decodeBody } from "../reqhelpers/rest";
 import type { ObjectDefinition } from "../types/overview";
 import type { RequestFile } from "../types/project";
+import { methodName } from "../utils/methodConstants";
 import {
 	extractDefinitions,
 	getTypeColor,
@@ -259,7 +260,7 @@ export const RequestOverview: React.FC<RequestOverviewProps> = ({
 		);
 	};
 
-	const method = activeRequest.request.method;
+	const method = methodName(activeRequest.request.method);
 	const methodBadgeClassName =
 		method === "GET"
 			? "bg-green/20 text-green"
@@ -281,13 +282,13 @@ export const RequestOverview: React.FC<RequestOverviewProps> = ({
 
 	const leftFooter = (
 		<>
-			{Object.keys(activeRequest.request.query_params).length > 0 && (
+			{activeRequest.request.query_params.length > 0 && (
 				<div className="mt-4">
 					<h3 className="mb-2 font-semibold text-sm text-white/70">
 						Query Parameters
 					</h3>
 					<div className="space-y-1">
-						{Object.entries(activeRequest.request.query_params).map(
+						{activeRequest.request.query_params.map(
 							([key, value]) =>
 								renderProperty(key, value, "params", true, false),
 						)}
@@ -369,7 +370,7 @@ export const RequestOverview: React.FC<RequestOverviewProps> = ({
 			onCommitName={onUpdateName}
 			onDescriptionChange={onUpdateDescription}
 			leftFooter={leftFooter}
-			panelBadge={activeRequest.request.method}
+			panelBadge={method}
 			panelBadgeClassName={methodBadgeClassName}
 			panelSubtitle={activeRequest.name || "/"}
 			snippetDropdownLabel={snippetLang}
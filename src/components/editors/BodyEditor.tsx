@@ -273,8 +273,10 @@ export function BodyEditor({
 								value:
 									"Text" in f.value
 										? f.value.Text
-										: f.value.File.filename || "file",
-								description: "File" in f.value ? "File" : "Text",
+										: "File" in f.value
+											? f.value.File.filename || "file"
+											: f.value.FilePath.filename || "file",
+								description: "Text" in f.value ? "Text" : "File",
 								enabled: true,
 							}))}
 							onChange={(items) => {
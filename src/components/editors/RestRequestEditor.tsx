@@ -50,13 +50,22 @@ interface RestRequestEditorProps {
 	onOpenProjectSettings: () => void;
 }
 
+function mergeHeaders(
+	current: [string, string][],
+	incoming: [string, string][] = [],
+): [string, string][] {
+	const replaced = new Set(incoming.map(([key]) => key.toLowerCase()));
+	return [
+		...current.filter(([key]) => !replaced.has(key.toLowerCase())),
+		...incoming,
+	];
+}
+
 function buildQueryString(
-	params: Record<string, string | undefined>,
+	params: [string, string][],
 	disabledKeys: Set<string> = new Set(),
 ): string {
-	const enabledParams = Object.entries(params).filter(
-		([key, value]) => !disabledKeys.has(key) && value !== undefined,
-	) as [string, string][];
+	const enabledParams = params.filter(([key]) => !disabledKeys.has(key));
 
 	if (enabledParams.length === 0) return "";
 
@@ -359,7 +368,7 @@ export const RestRequestEditor = forwardRef<
 
 		if (hasInvalid(activeRequest.request.url)) return true;
 
-		for (const [key, value] of Object.entries(activeRequest.request.headers)) {
+		for (const [key, value] of activeRequest.request.headers) {
 			if (isItemEnabled("header", key)) {
 				if (hasInvalid(value || "")) return true;
 			}
@@ -374,14 +383,9 @@ export const RestRequestEditor = forwardRef<
 		try {
 			const resolvedUrl = resolveVariables(activeRequest.request.url);
 
-			const resolvedHeaders: Record<string, string> = {};
-			for (const [key, value] of Object.entries(
-				activeRequest.request.headers,
-			)) {
-				if (isItemEnabled("header", key)) {
-					resolvedHeaders[key] = resolveVariables(value || "");
-				}
-			}
+			const resolvedHeaders: [string, string][] = activeRequest.request.headers
+				.filter(([key]) => isItemEnabled("header", key))
+				.map(([key, value]) => [key, resolveVariables(value || "")]);
 
 			const resolvedCookies = activeRequest.request.cookies
 				.filter((_, idx) => isItemEnabled("cookie", `${idx}`))
@@ -405,7 +409,7 @@ export const RestRequestEditor = forwardRef<
 				url: resolvedUrl,
 				headers: resolvedHeaders,
 				cookies: resolvedCookies,
-				query_params: {},
+				query_params: [],
 				body: isGet ? "None" : activeRequest.request.body,
 				auth: effectiveAuth,
 				request_label: activeRequest.name,
@@ -455,10 +459,13 @@ export const RestRequestEditor = forwardRef<
 				status: 0,
 				status_text: "Error",
 				headers: {},
+				headers_list: [],
 				cookies: [],
 				body_base64: btoa(errorMessage),
 				timing: {
 					total_ms: 0,
+					redirect_ms: 0,
+					redirect_count: 0,
 					dns_lookup_ms: 0,
 					tcp_handshake_ms: 0,
 					tls_handshake_ms: 0,
@@ -466,15 +473,48 @@ export const RestRequestEditor = forwardRef<
 					ttfb_ms: 0,
 					content_download_ms: 0,
 				},
-				request_size: { headers_bytes: 0, body_bytes: 0, total_bytes: 0 },
-				response_size: { headers_bytes: 0, body_bytes: 0, total_bytes: 0 },
+				request_size: {
+					headers_bytes: 0,
+					body_bytes: 0,
+					total_bytes: 0,
+					uncompressed_body_bytes: null,
+				},
+				response_size: {
+					headers_bytes: 0,
+					body_bytes: 0,
+					total_bytes: 0,
+					uncompressed_body_bytes: null,
+				},
 				redirects: [],
+				attempts: 0,
+				attempt_statuses: [],
 				remote_addr: null,
+				proxy_used: null,
+				local_addr: null,
+				connection: { reused: false, num_connects: 0, local_port: null },
+				auth_scheme: null,
+				mutual_auth: null,
+				netrc_login: null,
+				tls: null,
+				tls_exception_used: false,
 				http_version: "",
 				available_renderers: ["Raw"],
 				detected_content_type: "text/plain",
+				detected_charset: null,
+				bom: null,
+				body_text_base64: null,
+				soap_fault: false,
+				jsonrpc_error: null,
 				protocol_used: "",
+				negotiated_h3: false,
 				error: errorMessage,
+				partial: false,
+				body_file_path: null,
+				saved_path: null,
+				body_preview_base64: null,
+				truncated: false,
+				warnings: [],
+				verbose_log: null,
 			};
 			setRequestResponse(requestId, errorResponse);
 			setResponseTab("Raw");
@@ -513,7 +553,7 @@ export const RestRequestEditor = forwardRef<
 				request: {
 					...r.request,
 					...parsed,
-					headers: { ...r.request.headers, ...parsed.headers },
+					headers: mergeHeaders(r.request.headers, parsed.headers),
 				},
 			}));
 			addToast("Imported from cURL", "success");
@@ -529,7 +569,7 @@ export const RestRequestEditor = forwardRef<
 			request: {
 				...r.request,
 				...parsed,
-				headers: { ...r.request.headers, ...parsed.headers },
+				headers: mergeHeaders(r.request.headers, parsed.headers),
 			},
 		}));
 		setShowCurlImport(false);
@@ -540,8 +580,8 @@ export const RestRequestEditor = forwardRef<
 		const isEmpty =
 			!activeRequest.request.url &&
 			activeRequest.request.method === "GET" &&
-			Object.keys(activeRequest.request.headers).length === 0 &&
-			Object.keys(activeRequest.request.query_params || {}).length === 0 &&
+			activeRequest.request.headers.length === 0 &&
+			(activeRequest.request.query_params || []).length === 0 &&
 			activeRequest.request.body === "None" &&
 			activeRequest.request.auth === "None";
 
@@ -555,7 +595,7 @@ export const RestRequestEditor = forwardRef<
 	}
 
 	function updateUrl(url: string) {
-		const params: Record<string, string> = {};
+		const params: [string, string][] = [];
 		const hashIndex = url.indexOf("#");
 		const urlWithoutHash = hashIndex !== -1 ? url.slice(0, hashIndex) : url;
 		const fragment = hashIndex !== -1 ? url.slice(hashIndex) : "";
@@ -566,17 +606,17 @@ export const RestRequestEditor = forwardRef<
 				if (!part) return;
 				const eqIndex = part.indexOf("=");
 				if (eqIndex === -1) {
-					params[decodeURIComponent(part)] = "";
+					params.push([decodeURIComponent(part), ""]);
 				} else {
 					const key = decodeURIComponent(part.slice(0, eqIndex));
 					const rawValue = part.slice(eqIndex + 1);
 					if (rawValue.includes("{{")) {
-						params[key] = rawValue;
+						params.push([key, rawValue]);
 					} else {
 						try {
-							params[key] = decodeURIComponent(rawValue);
+							params.push([key, decodeURIComponent(rawValue)]);
 						} catch {
-							params[key] = rawValue;
+							params.push([key, rawValue]);
 						}
 					}
 				}
@@ -897,9 +937,9 @@ export const RestRequestEditor = forwardRef<
 							<div className="flex min-h-0 flex-1 flex-col">
 								<KeyValueTable
 									title="Query Params"
-									items={Object.entries(activeRequest.request.query_params).map(
-										([key, value]) => ({
-											id: key,
+									items={activeRequest.request.query_params.map(
+										([key, value], index) => ({
+											id: `${index}`,
 											key: key,
 											value: value || "",
 											description: "",
@@ -907,13 +947,13 @@ export const RestRequestEditor = forwardRef<
 										}),
 									)}
 									onChange={(items) => {
-										const newQueryParams: Record<string, string> = {};
+										const newQueryParams: [string, string][] = [];
 										const newDisabledItems = new Set(disabledItems);
 										const activeId = activeRequest.id;
 
 										items.forEach((item) => {
 											if (item.key.trim() || item.value.trim()) {
-												newQueryParams[item.key] = item.value;
+												newQueryParams.push([item.key, item.value]);
 												const disabledKey = `${activeId}:param:${item.key}`;
 												if (item.enabled) {
 													newDisabledItems.delete(disabledKey);
@@ -923,9 +963,9 @@ export const RestRequestEditor = forwardRef<
 											}
 										});
 
-										Object.keys(activeRequest.request.query_params).forEach(
-											(oldKey) => {
-												if (!newQueryParams[oldKey]) {
+										activeRequest.request.query_params.forEach(
+											([oldKey]) => {
+												if (!newQueryParams.some(([key]) => key === oldKey)) {
 													newDisabledItems.delete(
 														`${activeId}:param:${oldKey}`,
 													);
@@ -980,9 +1020,9 @@ export const RestRequestEditor = forwardRef<
 								<KeyValueTable
 									items={[
 										...getComputedHeaders(),
-										...Object.entries(activeRequest.request.headers).map(
-											([key, value]) => ({
-												id: key,
+										...activeRequest.request.headers.map(
+											([key, value], index) => ({
+												id: `${index}`,
 												key: key,
 												value: value || "",
 												description: "",
@@ -994,13 +1034,13 @@ export const RestRequestEditor = forwardRef<
 										const userItems = items.filter(
 											(i) => !i.id.startsWith("computed:"),
 										);
-										const newHeaders: Record<string, string> = {};
+										const newHeaders: [string, string][] = [];
 										const newDisabledItems = new Set(disabledItems);
 										const activeId = activeRequest.id;
 
 										userItems.forEach((item) => {
 											if (item.key.trim() || item.value.trim()) {
-												newHeaders[item.key] = item.value;
+												newHeaders.push([item.key, item.value]);
 												const disabledKey = `${activeId}:header:${item.key}`;
 												if (item.enabled) {
 													newDisabledItems.delete(disabledKey);
@@ -1010,9 +1050,9 @@ export const RestRequestEditor = forwardRef<
 											}
 										});
 
-										Object.keys(activeRequest.request.headers).forEach(
-											(oldKey) => {
-												if (!newHeaders[oldKey]) {
+										activeRequest.request.headers.forEach(
+											([oldKey]) => {
+												if (!newHeaders.some(([key]) => key === oldKey)) {
 													newDisabledItems.delete(
 														`${activeId}:header:${oldKey}`,
 													);
@@ -1064,8 +1104,12 @@ export const RestRequestEditor = forwardRef<
 												domain: null,
 												path: null,
 												expires: null,
+												expires_at: null,
+												is_expired: null,
 												http_only: null,
 												secure: null,
+												max_age: null,
+												same_site: null,
 											};
 										});
 
@@ -208,9 +208,10 @@ function ParamsTab({
 }) {
 	const existingParams = useMemo(() => {
 		if (!requestFile?.request?.query_params) return [];
-		return Object.entries(requestFile.request.query_params)
-			.filter(([_, v]) => v !== undefined && v !== null)
-			.map(([key, value]) => ({ key, value: String(value || "") }));
+		return requestFile.request.query_params.map(([key, value]) => ({
+			key,
+			value: value || "",
+		}));
 	}, [requestFile]);
 
 	const getOverride = (key: string) =>
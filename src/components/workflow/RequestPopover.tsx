@@ -2,7 +2,11 @@ import { memo, useCallback, useEffect, useRef, useState } from "react";
 import { FaFolder, FaFolderOpen } from "react-icons/fa6";
 import { VscChevronDown, VscChevronRight } from "react-icons/vsc";
 import type { Folder, RequestFile, TreeItem } from "../../types/project";
-import { getMethodColor, getShortMethod } from "../../utils/methodConstants";
+import {
+	getMethodColor,
+	getShortMethod,
+	methodName,
+} from "../../utils/methodConstants";
 
 interface RequestPopoverProps {
 	root: Folder;
@@ -181,7 +185,11 @@ export function RequestPopover({
 								item={item}
 								depth={depth}
 								onClick={() => {
-									onAddRequest?.(item.id, item.name, item.request.method);
+									onAddRequest?.(
+										item.id,
+										item.name,
+										methodName(item.request.method),
+									);
 									onClose();
 								}}
 							/>
@@ -5,6 +5,7 @@ import { VscTypeHierarchySub } from "react-icons/vsc";
 import { createDefaultRequest } from "../reqhelpers/rest";
 import type { RequestType } from "../types/project";
 import type { WorkflowNodeData } from "../types/workflow";
+import { methodName } from "../utils/methodConstants";
 import { defineItemType, type ItemTypeConfig } from "./itemTypes";
 
 export type { ItemTypeConfig } from "./itemTypes";
@@ -50,15 +51,18 @@ const requestConfig = defineItemType({
 			name: `${item.name} (copy)`,
 			request: {
 				...item.request,
-				headers: { ...item.request.headers },
-				query_params: { ...item.request.query_params },
+				headers: [...item.request.headers],
+				query_params: [...item.request.query_params],
 			},
 			response: null,
 		};
 	},
 
 	getRecentMeta(item) {
-		return { methodLabel: item.request.method, url: item.request.url };
+		return {
+			methodLabel: methodName(item.request.method),
+			url: item.request.url,
+		};
 	},
 });
 
@@ -32,10 +32,10 @@ export function createDefaultRequest(
 	return {
 		method,
 		url,
-		headers: {},
+		headers: [],
 		body: "None",
 		auth: "None",
-		query_params: {},
+		query_params: [],
 		cookies: [],
 		timeout_ms: 30000,
 		follow_redirects: true,
@@ -76,7 +76,7 @@ export function setFormBody(fields: Record<string, string>): BodyType {
 export async function sendRequest(request: ApiRequest): Promise<ApiResponse> {
 	const result = await commands.restRequest(request);
 	if (result.status === "error") {
-		throw new Error(result.error);
+		throw new Error(result.error.message);
 	}
 	return result.data;
 }
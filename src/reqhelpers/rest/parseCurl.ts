@@ -3,8 +3,8 @@ import type { ApiRequest, Methods } from "../../bindings";
 export function parseCurlCommand(curl: string): Partial<ApiRequest> {
 	const request: Partial<ApiRequest> = {
 		method: "GET",
-		headers: {},
-		query_params: {},
+		headers: [],
+		query_params: [],
 		cookies: [],
 	};
 
@@ -33,11 +33,12 @@ export function parseCurlCommand(curl: string): Partial<ApiRequest> {
 					const [k, v] = part.split("=");
 					if (k) {
 						try {
-							request.query_params![decodeURIComponent(k)] = v
-								? decodeURIComponent(v)
-								: "";
+							request.query_params!.push([
+								decodeURIComponent(k),
+								v ? decodeURIComponent(v) : "",
+							]);
 						} catch {
-							request.query_params![k] = v || "";
+							request.query_params!.push([k, v || ""]);
 						}
 					}
 				});
@@ -60,7 +61,7 @@ export function parseCurlCommand(curl: string): Partial<ApiRequest> {
 					if (colonIndex > 0) {
 						const key = header.slice(0, colonIndex).trim();
 						const value = header.slice(colonIndex + 1).trim();
-						request.headers![key] = value;
+						request.headers!.push([key, value]);
 					}
 				}
 				break;
@@ -86,8 +87,12 @@ export function parseCurlCommand(curl: string): Partial<ApiRequest> {
 								domain: "",
 								path: "/",
 								expires: null,
+								expires_at: null,
+								is_expired: null,
 								http_only: false,
 								secure: false,
+								max_age: null,
+								same_site: null,
 							});
 						}
 					});
@@ -130,7 +135,7 @@ export function parseCurlCommand(curl: string): Partial<ApiRequest> {
 		}
 	}
 
-	const contentTypeHeader = Object.entries(request.headers!).find(
+	const contentTypeHeader = request.headers!.find(
 		([k]) => k.toLowerCase() === "content-type",
 	);
 	const contentType = contentTypeHeader?.[1]?.toLowerCase() || "";
@@ -23,6 +23,7 @@ import type {
 	SortMode,
 	TreeItem,
 } from "../types/project";
+import { methodName } from "../utils/methodConstants";
 import { findSecrets } from "../utils/secretDetection";
 
 function scheduleRealtimeFullTeardown() {
@@ -137,8 +138,8 @@ function sortChildren(children: TreeItem[], mode: SortMode): TreeItem[] {
 				"HEAD",
 				"OPTIONS",
 			];
-			const aMethod = a.type === "request" ? a.request.method : "ZZZZ";
-			const bMethod = b.type === "request" ? b.request.method : "ZZZZ";
+			const aMethod = a.type === "request" ? methodName(a.request.method) : "ZZZZ";
+			const bMethod = b.type === "request" ? methodName(b.request.method) : "ZZZZ";
 			const diff = order.indexOf(aMethod) - order.indexOf(bMethod);
 			return diff !== 0 ? diff : a.name.localeCompare(b.name);
 		}
@@ -710,17 +711,15 @@ export const useProjectStore = create<ProjectState>()(
 					req.name = processString(req.name);
 					req.request.url = processString(req.request.url);
 
-					const newHeaders: Record<string, string> = {};
-					for (const [k, v] of Object.entries(req.request.headers)) {
-						newHeaders[k] = processString(v);
-					}
-					req.request.headers = newHeaders;
+					req.request.headers = req.request.headers.map(([k, v]) => [
+						k,
+						processString(v),
+					]);
 
-					const newParams: Record<string, string> = {};
-					for (const [k, v] of Object.entries(req.request.query_params)) {
-						newParams[k] = processString(v);
-					}
-					req.request.query_params = newParams;
+					req.request.query_params = req.request.query_params.map(([k, v]) => [
+						k,
+						processString(v),
+					]);
 
 					if (req.request.auth && req.request.auth !== "None") {
 						const auth = req.request.auth;
@@ -1,3 +1,5 @@
+import type { Methods } from "../bindings";
+
 export const METHOD_COLORS: Record<string, string> = {
 	GET: "#22c55e",
 	POST: "#f97316",
@@ -59,16 +61,22 @@ export const SHORT_METHODS: Record<string, string> = {
 	OPTIONS: "OPT",
 };
 
-export function getShortMethod(method: string): string {
-	return SHORT_METHODS[method.toUpperCase()] || method;
+export function methodName(method: Methods | string): string {
+	return typeof method === "string" ? method : method.Custom;
+}
+
+export function getShortMethod(method: Methods | string): string {
+	const name = methodName(method);
+	return SHORT_METHODS[name.toUpperCase()] || name;
 }
 
-export function getMethodColor(method: string): string {
-	return METHOD_COLORS[method.toUpperCase()] || "#888";
+export function getMethodColor(method: Methods | string): string {
+	return METHOD_COLORS[methodName(method).toUpperCase()] || "#888";
 }
 
-export function getMethodColorTailwind(method: string) {
+export function getMethodColorTailwind(method: Methods | string) {
 	return (
-		METHOD_COLORS_TAILWIND[method.toUpperCase()] || METHOD_COLORS_TAILWIND.GET
+		METHOD_COLORS_TAILWIND[methodName(method).toUpperCase()] ||
+		METHOD_COLORS_TAILWIND.GET
 	);
 }
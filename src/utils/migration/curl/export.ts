@@ -1,12 +1,13 @@
 import type { ApiRequest } from "../../../bindings";
+import { methodName } from "../../methodConstants";
 
 export function generateCurl(request: ApiRequest): string {
 	const parts = ["curl"];
 
-	parts.push("--request", request.method);
+	parts.push("--request", methodName(request.method));
 	parts.push("--url", `'${request.url}'`);
 
-	Object.entries(request.headers).forEach(([key, value]) => {
+	request.headers.forEach(([key, value]) => {
 		if (value) {
 			parts.push("--header", `'${key}: ${value}'`);
 		}
@@ -38,7 +39,7 @@ export function generateCurl(request: ApiRequest): string {
 				} else {
 					parts.push(
 						"--form",
-						`'${field.name}=@${field.value.File.filename || "file"}'`,
+						`'${field.name}=@${"File" in field.value ? field.value.File.filename || "file" : field.value.FilePath.path}'`,
 					);
 				}
 			}
@@ -5,6 +5,7 @@ import type {
 	RequestFile,
 	WebSocketFile,
 } from "../../../types/project";
+import { methodName } from "../../methodConstants";
 import { generatePrefixedId } from "../shared";
 import type {
 	InsomniaAuthentication,
@@ -98,7 +99,10 @@ function convertBodyToInsomnia(body: BodyType): InsomniaBody {
 						name: field.name,
 						value: "",
 						type: "file",
-						fileName: field.value.File.filename,
+						fileName:
+							"File" in field.value
+								? field.value.File.filename
+								: field.value.FilePath.path,
 					};
 				}
 			}),
@@ -151,19 +155,17 @@ function convertRequestToInsomnia(
 ): InsomniaRequest {
 	const apiReq = req.request;
 
-	const parameters: InsomniaParameter[] = Object.entries(apiReq.query_params)
-		.filter(([, value]) => value !== undefined)
-		.map(([name, value]) => ({
+	const parameters: InsomniaParameter[] = apiReq.query_params.map(
+		([name, value]) => ({
 			name,
 			value: value || "",
-		}));
+		}),
+	);
 
-	const headers: InsomniaHeader[] = Object.entries(apiReq.headers)
-		.filter(([, value]) => value !== undefined)
-		.map(([name, value]) => ({
-			name,
-			value: value || "",
-		}));
+	const headers: InsomniaHeader[] = apiReq.headers.map(([name, value]) => ({
+		name,
+		value: value || "",
+	}));
 
 	if (apiReq.cookies.length > 0) {
 		const cookieValue = apiReq.cookies
@@ -179,7 +181,7 @@ function convertRequestToInsomnia(
 		name: req.name,
 		description: req.description,
 		url: apiReq.url,
-		method: apiReq.method,
+		method: methodName(apiReq.method),
 		headers,
 		body: convertBodyToInsomnia(apiReq.body),
 		parameters,
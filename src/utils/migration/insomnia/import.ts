@@ -116,10 +116,10 @@ function parseInsomniaBody(body: InsomniaBody | undefined): BodyType {
 }
 
 function parseInsomniaHeaders(headers: InsomniaHeader[] | undefined): {
-	headers: Record<string, string>;
+	headers: [string, string][];
 	cookies: Cookie[];
 } {
-	const result: Record<string, string> = {};
+	const result: [string, string][] = [];
 	const cookies: Cookie[] = [];
 
 	if (!headers) {
@@ -140,13 +140,17 @@ function parseInsomniaHeaders(headers: InsomniaHeader[] | undefined): {
 						domain: null,
 						path: null,
 						expires: null,
+						expires_at: null,
+						is_expired: null,
 						http_only: null,
 						secure: null,
+						max_age: null,
+						same_site: null,
 					});
 				}
 			}
 		} else {
-			result[h.name] = h.value;
+			result.push([h.name, h.value]);
 		}
 	}
 
@@ -156,11 +160,11 @@ function parseInsomniaHeaders(headers: InsomniaHeader[] | undefined): {
 function parseInsomniaRequest(resource: InsomniaRequest): RequestFile {
 	const { headers, cookies } = parseInsomniaHeaders(resource.headers);
 
-	const queryParams: Record<string, string> = {};
+	const queryParams: [string, string][] = [];
 	if (resource.parameters) {
 		for (const p of resource.parameters) {
 			if (!p.disabled) {
-				queryParams[p.name] = p.value;
+				queryParams.push([p.name, p.value]);
 			}
 		}
 	}
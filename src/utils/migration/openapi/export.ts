@@ -1,4 +1,5 @@
 import type { Folder, Project, RequestFile } from "../../../types/project";
+import { methodName } from "../../methodConstants";
 import type { OpenAPIOperation, OpenAPISpec } from "./types";
 
 export function generateOpenAPISpec(
@@ -32,7 +33,7 @@ export function generateOpenAPISpec(
 
 	for (const { request: req, folders } of requestsWithContext) {
 		const url = req.request.url || "/";
-		const method = req.request.method.toLowerCase();
+		const method = methodName(req.request.method).toLowerCase();
 		let pathKey = url;
 		let operationServers: { url: string }[] | undefined;
 
@@ -96,7 +97,7 @@ export function generateOpenAPISpec(
 
 		const parameters: OpenAPIOperation["parameters"] = [];
 
-		for (const [key, value] of Object.entries(req.request.query_params)) {
+		for (const [key, value] of req.request.query_params) {
 			parameters.push({
 				name: key,
 				in: "query",
@@ -107,7 +108,7 @@ export function generateOpenAPISpec(
 			});
 		}
 
-		for (const [key, value] of Object.entries(req.request.headers)) {
+		for (const [key, value] of req.request.headers) {
 			parameters.push({
 				name: key,
 				in: "header",
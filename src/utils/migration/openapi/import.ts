@@ -42,15 +42,15 @@ export function parseOpenAPISpec(spec: any): Partial<Project> {
 			) {
 				const op = operation as OpenAPIOperation;
 
-				const queryParams: Record<string, string> = {};
-				const headers: Record<string, string> = {};
+				const queryParams: [string, string][] = [];
+				const headers: [string, string][] = [];
 
 				if (op.parameters) {
 					for (const param of op.parameters) {
 						if (param.in === "query") {
-							queryParams[param.name] = "";
+							queryParams.push([param.name, ""]);
 						} else if (param.in === "header") {
-							headers[param.name] = "";
+							headers.push([param.name, ""]);
 						}
 					}
 				}
@@ -1,5 +1,6 @@
 import type { AuthType, BodyType, Cookie } from "../../../bindings";
 import type { Folder, Project, RequestFile } from "../../../types/project";
+import { methodName } from "../../methodConstants";
 import { generateId } from "../shared";
 import type {
 	PostmanAuth,
@@ -14,9 +15,9 @@ import type {
 
 function parseUrlForPostman(
 	urlString: string,
-	queryParams: Record<string, string>,
+	queryParams: [string, string][],
 ): PostmanUrl {
-	const extraQueryParams = Object.entries(queryParams).map(([key, value]) => ({
+	const extraQueryParams = queryParams.map(([key, value]) => ({
 		key,
 		value: value || "",
 	}));
@@ -184,7 +185,10 @@ function convertBodyToPostman(body: BodyType): PostmanBody | null {
 					return {
 						key: field.name,
 						type: "file" as const,
-						src: field.value.File.filename || null,
+						src:
+							"File" in field.value
+								? field.value.File.filename || null
+								: field.value.FilePath.path,
 					};
 				}
 			}),
@@ -207,23 +211,17 @@ function convertCookiesToHeaders(cookies: Cookie[]): PostmanHeader[] {
 
 function convertRequestToPostman(req: RequestFile): PostmanItem {
 	const apiReq = req.request;
-	const queryParams: Record<string, string> = {};
-	for (const [k, v] of Object.entries(apiReq.query_params)) {
-		if (v !== undefined) queryParams[k] = v;
-	}
-	const postmanUrl = parseUrlForPostman(apiReq.url, queryParams);
+	const postmanUrl = parseUrlForPostman(apiReq.url, apiReq.query_params);
 
-	const headers: PostmanHeader[] = Object.entries(apiReq.headers).map(
-		([key, value]) => ({
-			key,
-			value: value || "",
-		}),
-	);
+	const headers: PostmanHeader[] = apiReq.headers.map(([key, value]) => ({
+		key,
+		value: value || "",
+	}));
 
 	headers.push(...convertCookiesToHeaders(apiReq.cookies));
 
 	const postmanRequest: any = {
-		method: apiReq.method,
+		method: methodName(apiReq.method),
 		header: headers,
 		url: postmanUrl,
 		description: req.description,
@@ -121,9 +121,9 @@ function parsePostmanBody(body: PostmanBody | null | undefined): BodyType {
 
 function parsePostmanUrl(url: PostmanUrl | string | undefined | null): {
 	url: string;
-	queryParams: Record<string, string>;
+	queryParams: [string, string][];
 } {
-	const queryParams: Record<string, string> = {};
+	const queryParams: [string, string][] = [];
 
 	if (!url) {
 		return { url: "", queryParams };
@@ -134,7 +134,7 @@ function parsePostmanUrl(url: PostmanUrl | string | undefined | null): {
 		if (queryString) {
 			const params = new URLSearchParams(queryString);
 			params.forEach((value, key) => {
-				queryParams[key] = value;
+				queryParams.push([key, value]);
 			});
 		}
 		return { url: baseUrl || "", queryParams };
@@ -143,7 +143,7 @@ function parsePostmanUrl(url: PostmanUrl | string | undefined | null): {
 	if (url.query && Array.isArray(url.query)) {
 		for (const q of url.query) {
 			if (q && !q.disabled && q.key) {
-				queryParams[q.key] = q.value || "";
+				queryParams.push([q.key, q.value || ""]);
 			}
 		}
 	}
@@ -152,10 +152,10 @@ function parsePostmanUrl(url: PostmanUrl | string | undefined | null): {
 }
 
 function parsePostmanHeaders(headers: PostmanHeader[] | string | undefined): {
-	headers: Record<string, string>;
+	headers: [string, string][];
 	cookies: Cookie[];
 } {
-	const result: Record<string, string> = {};
+	const result: [string, string][] = [];
 	const cookies: Cookie[] = [];
 
 	if (!headers || typeof headers === "string") {
@@ -176,13 +176,17 @@ function parsePostmanHeaders(headers: PostmanHeader[] | string | undefined): {
 						domain: null,
 						path: null,
 						expires: null,
+						expires_at: null,
+						is_expired: null,
 						http_only: null,
 						secure: null,
+						max_age: null,
+						same_site: null,
 					});
 				}
 			}
 		} else {
-			result[h.key] = h.value;
+			result.push([h.key, h.value]);
 		}
 	}
 
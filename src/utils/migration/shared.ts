@@ -21,8 +21,12 @@ export function parseCookieHeader(value: string): Cookie[] {
 				domain: null,
 				path: null,
 				expires: null,
+				expires_at: null,
+				is_expired: null,
 				http_only: null,
 				secure: null,
+				max_age: null,
+				same_site: null,
 			});
 		}
 	}
@@ -1,11 +1,12 @@
 import type { ApiRequest } from "../bindings";
+import { methodName } from "./methodConstants";
 
 export function generateCurl(request: ApiRequest): string {
 	const parts = ["curl"];
-	parts.push(`--request ${request.method}`);
+	parts.push(`--request ${methodName(request.method)}`);
 	parts.push(`--url ${request.url}`);
 
-	Object.entries(request.headers).forEach(([key, value]) => {
+	request.headers.forEach(([key, value]) => {
 		if (value) {
 			parts.push(`--header '${key}: ${value}'`);
 		}
@@ -14,7 +15,10 @@ export function generateCurl(request: ApiRequest): string {
 	if (request.body !== "None") {
 		if ("Raw" in request.body) {
 			const { content, content_type } = request.body.Raw;
-			if (content_type && !request.headers["Content-Type"]) {
+			if (
+				content_type &&
+				!request.headers.some(([key]) => key.toLowerCase() === "content-type")
+			) {
 				parts.push(`--header 'Content-Type: ${content_type}'`);
 			}
 			if (content_type?.includes("json")) {
@@ -44,7 +48,7 @@ export function generateCurl(request: ApiRequest): string {
 
 export function generateFetch(request: ApiRequest): string {
 	let bodyCode = "";
-	const headers = { ...request.headers };
+	const headers = Object.fromEntries(request.headers);
 
 	if (request.body !== "None") {
 		if ("Raw" in request.body) {
@@ -75,7 +79,7 @@ export function generateFetch(request: ApiRequest): string {
 	}
 
 	const options = {
-		method: request.method,
+		method: methodName(request.method),
 		headers: headers,
 	};
 
@@ -91,8 +95,8 @@ export function generatePythonRequests(request: ApiRequest): string {
 	let code = `import requests\n\n`;
 	code += `url = "${request.url}"\n`;
 
-	if (Object.keys(request.headers).length > 0) {
-		code += `headers = ${JSON.stringify(request.headers, null, 4)}\n`;
+	if (request.headers.length > 0) {
+		code += `headers = ${JSON.stringify(Object.fromEntries(request.headers), null, 4)}\n`;
 	} else {
 		code += `headers = {}\n`;
 	}
@@ -120,7 +124,7 @@ export function generatePythonRequests(request: ApiRequest): string {
 		}
 	}
 
-	code += `response = requests.request("${request.method}", url, headers=headers${dataArg})\n`;
+	code += `response = requests.request("${methodName(request.method)}", url, headers=headers${dataArg})\n`;
 	code += `print(response.text)`;
 
 	return code;
@@ -129,9 +133,9 @@ export function generatePythonRequests(request: ApiRequest): string {
 export function generateGo(request: ApiRequest): string {
 	let code = `package main\n\nimport (\n\t"fmt"\n\t"net/http"\n\t"io/ioutil"\n)\n\nfunc main() {\n`;
 	code += `\turl := "${request.url}"\n`;
-	code += `\treq, _ := http.NewRequest("${request.method}", url, nil)\n`;
+	code += `\treq, _ := http.NewRequest("${methodName(request.method)}", url, nil)\n`;
 
-	Object.entries(request.headers).forEach(([key, value]) => {
+	request.headers.forEach(([key, value]) => {
 		code += `\treq.Header.Add("${key}", "${value}")\n`;
 	});
 
@@ -146,9 +150,9 @@ export function generateGo(request: ApiRequest): string {
 export function generateRust(request: ApiRequest): string {
 	let code = `use reqwest;\n\n#[tokio::main]\nasync func main() -> Result<(), reqwest::Error> {\n`;
 	code += `\tlet client = reqwest::Client::new();\n`;
-	code += `\tlet res = client.${request.method.toLowerCase()}("${request.url}")\n`;
+	code += `\tlet res = client.${methodName(request.method).toLowerCase()}("${request.url}")\n`;
 
-	Object.entries(request.headers).forEach(([key, value]) => {
+	request.headers.forEach(([key, value]) => {
 		code += `\t\t.header("${key}", "${value}")\n`;
 	});
 
@@ -163,9 +167,9 @@ export function generateJava(request: ApiRequest): string {
 	code += `\t\tHttpClient client = HttpClient.newHttpClient();\n`;
 	code += `\t\tHttpRequest request = HttpRequest.newBuilder()\n`;
 	code += `\t\t\t.uri(URI.create("${request.url}"))\n`;
-	code += `\t\t\t.method("${request.method}", HttpRequest.BodyPublishers.noBody())\n`;
+	code += `\t\t\t.method("${methodName(request.method)}", HttpRequest.BodyPublishers.noBody())\n`;
 
-	Object.entries(request.headers).forEach(([key, value]) => {
+	request.headers.forEach(([key, value]) => {
 		code += `\t\t\t.header("${key}", "${value}")\n`;
 	});
 
@@ -177,10 +181,10 @@ export function generateJava(request: ApiRequest): string {
 
 export function generatePHP(request: ApiRequest): string {
 	let code = `<?php\n\n$client = new \\GuzzleHttp\\Client();\n\n`;
-	code += `$response = $client->request('${request.method}', '${request.url}', [\n`;
+	code += `$response = $client->request('${methodName(request.method)}', '${request.url}', [\n`;
 
-	if (Object.keys(request.headers).length > 0) {
-		code += `    'headers' => ${JSON.stringify(request.headers, null, 8).replace(/{/g, "[").replace(/}/g, "]")},\n`;
+	if (request.headers.length > 0) {
+		code += `    'headers' => ${JSON.stringify(Object.fromEntries(request.headers), null, 8).replace(/{/g, "[").replace(/}/g, "]")},\n`;
 	}
 
 	code += `]);\n\necho $response->getBody();`;